@@ -0,0 +1,153 @@
+// Copyright 2019 Octavian Oncescu
+
+//! Applies arbitrary sequences of mutations to a graph and checks
+//! that its internal tables stay consistent after each of them.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use graphlib::{Graph, VertexId};
+use libfuzzer_sys::fuzz_target;
+use std::collections::HashSet;
+
+#[derive(Arbitrary, Debug)]
+enum Operation {
+    AddVertex,
+    Remove(u8),
+    AddEdge(u8, u8),
+    AddEdgeWithWeight(u8, u8, i8),
+    AddEdgeCheckCycle(u8, u8),
+    RemoveEdge(u8, u8),
+    SetWeight(u8, u8, i8),
+    KeepTopKOutEdges(u8),
+    Undo,
+    Redo,
+}
+
+/// Maps an arbitrary byte to a weight between `-1.0` and `1.0`.
+fn weight(w: i8) -> f32 {
+    (f32::from(w) / 127.0).max(-1.0)
+}
+
+fn pick(ids: &[VertexId], i: u8) -> Option<VertexId> {
+    if ids.is_empty() {
+        None
+    } else {
+        Some(ids[i as usize % ids.len()])
+    }
+}
+
+/// Returns the ids of the vertices in a stable order,
+/// since undone and redone vertices keep their ids.
+fn vertex_ids(graph: &Graph<usize>) -> Vec<VertexId> {
+    let mut ids: Vec<VertexId> = graph.vertices().cloned().collect();
+    ids.sort();
+    ids
+}
+
+fn check(graph: &Graph<usize>) {
+    let mut edges = 0;
+
+    for v in graph.vertices() {
+        let outbound = graph.out_neighbors_count(v);
+        let inbound = graph.in_neighbors_count(v);
+
+        edges += outbound;
+
+        assert_eq!(graph.roots().any(|r| r == v), inbound == 0);
+        assert_eq!(graph.tips().any(|t| t == v), outbound == 0);
+
+        for n in graph.out_neighbors(v) {
+            assert!(graph.in_neighbors(n).any(|i| i == v));
+
+            let w = graph.weight(v, n).unwrap();
+            assert!((-1.0..=1.0).contains(&w));
+        }
+    }
+
+    assert_eq!(edges, graph.edge_count());
+    assert_eq!(graph.dfs().count(), graph.vertex_count());
+
+    // Bfs starts from the roots only, so it skips vertices on cycles
+    let visited: HashSet<&VertexId> = graph.bfs().collect();
+    assert_eq!(visited.len(), graph.bfs().count());
+
+    if !graph.is_cyclic() {
+        assert_eq!(graph.topo().count(), graph.vertex_count());
+    }
+}
+
+fuzz_target!(|operations: Vec<Operation>| {
+    let mut graph: Graph<usize> = Graph::new();
+    let mut ids: Vec<VertexId> = Vec::new();
+
+    graph.track_history();
+
+    for operation in operations {
+        let was_cyclic = graph.is_cyclic();
+
+        match operation {
+            Operation::AddVertex => ids.push(graph.add_vertex(ids.len())),
+            Operation::Remove(i) => {
+                if let Some(id) = pick(&ids, i) {
+                    graph.remove(&id);
+                    ids.retain(|v| *v != id);
+                }
+            }
+            Operation::AddEdge(a, b) => {
+                if let (Some(a), Some(b)) = (pick(&ids, a), pick(&ids, b)) {
+                    graph.add_edge(&a, &b).unwrap();
+                }
+            }
+            Operation::AddEdgeWithWeight(a, b, w) => {
+                if let (Some(a), Some(b)) = (pick(&ids, a), pick(&ids, b)) {
+                    graph.add_edge_with_weight(&a, &b, weight(w)).unwrap();
+                }
+            }
+            Operation::AddEdgeCheckCycle(a, b) => {
+                if let (Some(a), Some(b)) = (pick(&ids, a), pick(&ids, b)) {
+                    let _ = graph.add_edge_check_cycle(&a, &b);
+
+                    // Edges which would create a cycle are rolled back
+                    assert!(was_cyclic || !graph.is_cyclic());
+                }
+            }
+            Operation::RemoveEdge(a, b) => {
+                if let (Some(a), Some(b)) = (pick(&ids, a), pick(&ids, b)) {
+                    graph.remove_edge(&a, &b);
+                    assert!(!graph.has_edge(&a, &b));
+                }
+            }
+            Operation::SetWeight(a, b, w) => {
+                if let (Some(a), Some(b)) = (pick(&ids, a), pick(&ids, b)) {
+                    let _ = graph.set_weight(&a, &b, weight(w));
+                }
+            }
+            Operation::KeepTopKOutEdges(k) => {
+                let k = k as usize % 4;
+                graph.keep_top_k_out_edges(k);
+                assert!(graph.vertices().all(|v| graph.out_neighbors_count(v) <= k));
+            }
+            Operation::Undo => {
+                let before = graph.clone();
+
+                if !graph.undo() {
+                    assert_eq!(graph, before);
+                }
+
+                ids = vertex_ids(&graph);
+            }
+            Operation::Redo => {
+                let before = graph.clone();
+
+                if !graph.redo() {
+                    assert_eq!(graph, before);
+                }
+
+                ids = vertex_ids(&graph);
+            }
+        }
+
+        check(&graph);
+    }
+});
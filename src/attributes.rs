@@ -0,0 +1,120 @@
+// Copyright 2019 Octavian Oncescu
+
+use hashbrown::HashMap;
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::string::String;
+
+/// Attributes of a vertex or an edge, mapped by their keys.
+pub(crate) type Attributes = HashMap<String, AttrValue>;
+
+#[derive(Clone, Debug, PartialEq)]
+/// Value of an attribute of a vertex, as set with `Graph::set_attr()`.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::{AttrValue, Graph};
+///
+/// let mut graph: Graph<usize> = Graph::new();
+/// let v1 = graph.add_vertex(1);
+///
+/// graph.set_attr(&v1, "x", 1.5).unwrap();
+/// graph.set_attr(&v1, "color", "red").unwrap();
+///
+/// assert_eq!(graph.attr(&v1, "x").and_then(AttrValue::as_float), Some(1.5));
+/// assert_eq!(graph.attr(&v1, "color").and_then(AttrValue::as_str), Some("red"));
+/// assert_eq!(graph.attr(&v1, "color").and_then(AttrValue::as_float), None);
+/// ```
+pub enum AttrValue {
+    /// Boolean value, such as a flag
+    Bool(bool),
+
+    /// Integer value, such as a timestamp
+    Int(i64),
+
+    /// Floating point value, such as a coordinate
+    Float(f64),
+
+    /// String value, such as a color
+    Text(String),
+}
+
+impl AttrValue {
+    /// Returns the value if it is a boolean.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            AttrValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is an integer.
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            AttrValue::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is a number. Integers
+    /// are converted to floating point numbers.
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            AttrValue::Float(value) => Some(*value),
+            AttrValue::Int(value) => Some(*value as f64),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AttrValue::Text(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for AttrValue {
+    fn from(value: bool) -> AttrValue {
+        AttrValue::Bool(value)
+    }
+}
+
+impl From<i32> for AttrValue {
+    fn from(value: i32) -> AttrValue {
+        AttrValue::Int(i64::from(value))
+    }
+}
+
+impl From<i64> for AttrValue {
+    fn from(value: i64) -> AttrValue {
+        AttrValue::Int(value)
+    }
+}
+
+impl From<f32> for AttrValue {
+    fn from(value: f32) -> AttrValue {
+        AttrValue::Float(f64::from(value))
+    }
+}
+
+impl From<f64> for AttrValue {
+    fn from(value: f64) -> AttrValue {
+        AttrValue::Float(value)
+    }
+}
+
+impl From<&str> for AttrValue {
+    fn from(value: &str) -> AttrValue {
+        AttrValue::Text(value.into())
+    }
+}
+
+impl From<String> for AttrValue {
+    fn from(value: String) -> AttrValue {
+        AttrValue::Text(value)
+    }
+}
@@ -0,0 +1,220 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::{
+    DefaultHashBuilder, DuplicateEdgePolicy, EdgeRules, Graph, GraphErr, WeightPolicy,
+};
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+#[derive(Clone, Debug, Default)]
+/// Builder of graphs whose edges must satisfy the given invariants,
+/// which are enforced on every mutation of the built graph.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::{DuplicateEdgePolicy, Graph, GraphBuilder, GraphErr};
+///
+/// let mut graph: Graph<usize> = GraphBuilder::new()
+///     .acyclic(true)
+///     .self_loops(false)
+///     .duplicate_edges(DuplicateEdgePolicy::Reject)
+///     .weight_bounds(0.0, 1.0)
+///     .capacity(3)
+///     .build()
+///     .unwrap();
+///
+/// let v1 = graph.add_vertex(1);
+/// let v2 = graph.add_vertex(2);
+///
+/// graph.add_edge_with_weight(&v1, &v2, 0.5).unwrap();
+///
+/// assert_eq!(graph.add_edge(&v1, &v2), Err(GraphErr::CannotAddEdge));
+/// assert_eq!(graph.add_edge(&v2, &v1), Err(GraphErr::CycleError));
+/// assert_eq!(graph.add_edge(&v1, &v1), Err(GraphErr::CannotAddEdge));
+/// assert_eq!(graph.set_weight(&v1, &v2, -1.0), Err(GraphErr::InvalidWeight));
+/// ```
+pub struct GraphBuilder {
+    /// Number of vertices to allocate space for
+    capacity: Option<usize>,
+
+    /// Number of edges to allocate space for
+    edge_capacity: Option<usize>,
+
+    /// Invariants enforced on the edges of the graph
+    rules: EdgeRules,
+
+    /// Policy for the edges added without a weight
+    weight_policy: WeightPolicy,
+}
+
+impl GraphBuilder {
+    /// Creates a new builder of graphs with the same
+    /// invariants as the ones returned by `Graph::new()`.
+    pub fn new() -> GraphBuilder {
+        GraphBuilder::default()
+    }
+
+    /// Sets whether adding an edge which creates a cycle fails with
+    /// `GraphErr::CycleError`, as with `Graph::add_edge_check_cycle()`.
+    pub fn acyclic(mut self, acyclic: bool) -> GraphBuilder {
+        self.rules.acyclic = acyclic;
+        self
+    }
+
+    /// Sets the policy for the edges added between vertices
+    /// which are already connected. The default policy
+    /// is `DuplicateEdgePolicy::Ignore`.
+    pub fn duplicate_edges(mut self, policy: DuplicateEdgePolicy) -> GraphBuilder {
+        self.rules.duplicate_edges = policy;
+        self
+    }
+
+    /// Sets whether edges from a vertex to itself can be added. Adding
+    /// one when they can't fails with `GraphErr::CannotAddEdge`.
+    pub fn self_loops(mut self, allowed: bool) -> GraphBuilder {
+        self.rules.self_loops = allowed;
+        self
+    }
+
+    /// Sets the number of vertices the graph allocates space for.
+    pub fn capacity(mut self, capacity: usize) -> GraphBuilder {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    /// Sets the number of edges the graph allocates space
+    /// for, which is otherwise derived from its capacity.
+    pub fn edge_capacity(mut self, capacity: usize) -> GraphBuilder {
+        self.edge_capacity = Some(capacity);
+        self
+    }
+
+    /// Sets the lowest and highest weights, inclusive, which the edges
+    /// of the graph can have. The default bounds are `-1.0` and `1.0`,
+    /// and the given ones must lie between them, as the algorithms of
+    /// the graph assume that weights are in that range.
+    pub fn weight_bounds(mut self, min: f32, max: f32) -> GraphBuilder {
+        self.rules.min_weight = min;
+        self.rules.max_weight = max;
+        self
+    }
+
+    /// Sets the policy deciding the weight of the edges which
+    /// are added without one, as with `Graph::set_weight_policy()`.
+    pub fn weight_policy(mut self, policy: WeightPolicy) -> GraphBuilder {
+        self.weight_policy = policy;
+        self
+    }
+
+    /// Builds a graph with the configured invariants.
+    ///
+    /// Returns `GraphErr::InvalidWeight` if the weight bounds are not
+    /// between `-1.0` and `1.0`, if the lowest one is greater than the
+    /// highest one, or if the weight of a `WeightPolicy::Fixed` policy
+    /// is out of them.
+    pub fn build<T>(&self) -> Result<Graph<T>, GraphErr> {
+        self.build_with_hasher(DefaultHashBuilder::default())
+    }
+
+    /// Builds a graph with the configured invariants
+    /// which uses the given hash builder.
+    pub fn build_with_hasher<T, S: BuildHasher + Clone>(
+        &self,
+        hash_builder: S,
+    ) -> Result<Graph<T, S>, GraphErr> {
+        let (min, max) = (self.rules.min_weight, self.rules.max_weight);
+
+        // Also rejects NaN bounds, which no range contains
+        if !(-1.0..=1.0).contains(&min) || !(-1.0..=1.0).contains(&max) || min > max {
+            return Err(GraphErr::InvalidWeight);
+        }
+
+        let mut graph = match self.capacity {
+            Some(capacity) => Graph::with_capacity_and_hasher(capacity, hash_builder),
+            None => Graph::with_hasher(hash_builder),
+        };
+
+        if let Some(capacity) = self.edge_capacity {
+            graph.reserve_edges(capacity);
+        }
+
+        graph.set_rules(self.rules);
+        graph.set_weight_policy(self.weight_policy)?;
+
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_duplicate_edges() {
+        let mut graph: Graph<usize> = GraphBuilder::new()
+            .duplicate_edges(DuplicateEdgePolicy::Replace)
+            .weight_policy(WeightPolicy::Fixed(0.5))
+            .build()
+            .unwrap();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+
+        graph.add_edge_with_weight(&v1, &v2, -0.5).unwrap();
+        assert_eq!(graph.weight(&v1, &v2), Some(-0.5));
+
+        graph.add_edge(&v1, &v2).unwrap();
+        assert_eq!(graph.weight(&v1, &v2), Some(0.5));
+        assert_eq!(graph.edge_count(), 1);
+    }
+
+    #[test]
+    fn test_rules_survive_subgraphs() {
+        let mut graph: Graph<usize> = GraphBuilder::new().acyclic(true).build().unwrap();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+
+        graph.add_edge(&v1, &v2).unwrap();
+
+        let mut sub = graph.subgraph(&graph.vertex_set()).unwrap();
+
+        assert_eq!(sub.add_edge(&v2, &v1), Err(GraphErr::CycleError));
+        assert_eq!(sub.edge_count(), 1);
+    }
+
+    #[test]
+    fn test_invalid_bounds() {
+        assert!(GraphBuilder::new()
+            .weight_bounds(1.0, 0.0)
+            .build::<usize>()
+            .is_err());
+        assert!(GraphBuilder::new()
+            .weight_bounds(0.0, 1.0)
+            .weight_policy(WeightPolicy::Fixed(-1.0))
+            .build::<usize>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_bounds_outside_unit_range() {
+        assert_eq!(
+            GraphBuilder::new()
+                .weight_bounds(0.0, 10.0)
+                .build::<usize>()
+                .unwrap_err(),
+            GraphErr::InvalidWeight
+        );
+        assert!(GraphBuilder::new()
+            .weight_bounds(-2.0, 0.0)
+            .build::<usize>()
+            .is_err());
+        assert!(GraphBuilder::new()
+            .weight_bounds(f32::NAN, 1.0)
+            .build::<usize>()
+            .is_err());
+    }
+}
@@ -0,0 +1,182 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::Graph;
+use crate::vertex_id::VertexId;
+
+use hashbrown::HashMap;
+
+#[cfg(not(feature = "no_std"))]
+use std::collections::VecDeque;
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::collections::vec_deque::VecDeque;
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+/// Returns the number of edges on the shortest paths from the given
+/// vertex to each vertex that is reachable from it. The centrality
+/// measures of this module are all based on these hop counts and
+/// ignore the weights of the edges.
+fn distances<T, S: BuildHasher>(
+    graph: &Graph<T, S>,
+    source: &VertexId,
+) -> HashMap<VertexId, usize> {
+    let mut distances = HashMap::with_capacity(graph.vertex_count());
+    let mut queue = VecDeque::new();
+
+    distances.insert(*source, 0);
+    queue.push_back(*source);
+
+    while let Some(current) = queue.pop_front() {
+        let distance = distances[&current] + 1;
+
+        for n in graph.out_neighbors(&current) {
+            if !distances.contains_key(n) {
+                distances.insert(*n, distance);
+                queue.push_back(*n);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Lazily computes the hop counts of the shortest paths from every vertex
+/// of the graph, so that only the distances from one vertex are kept in
+/// memory.
+fn all_distances<T, S: BuildHasher>(
+    graph: &Graph<T, S>,
+) -> impl Iterator<Item = (VertexId, HashMap<VertexId, usize>)> + '_ {
+    graph.vertices().map(move |v| (*v, distances(graph, v)))
+}
+
+/// Returns the largest distance in the given distance table or `None`
+/// if there are vertices of the graph which are missing from it.
+fn max_distance<T, S: BuildHasher>(
+    graph: &Graph<T, S>,
+    distances: &HashMap<VertexId, usize>,
+) -> Option<usize> {
+    if distances.len() == graph.vertex_count() {
+        distances.values().cloned().max()
+    } else {
+        None
+    }
+}
+
+pub(crate) fn eccentricity<T, S: BuildHasher>(graph: &Graph<T, S>, id: &VertexId) -> Option<usize> {
+    graph.fetch(id)?;
+
+    max_distance(graph, &distances(graph, id))
+}
+
+pub(crate) fn diameter<T, S: BuildHasher>(graph: &Graph<T, S>) -> Option<usize> {
+    all_distances(graph)
+        .map(|(_, d)| max_distance(graph, &d))
+        .try_fold(None, |diameter: Option<usize>, e| {
+            // Stopping at the first vertex which cannot reach all others
+            Some(diameter.max(Some(e?)))
+        })?
+}
+
+pub(crate) fn radius<T, S: BuildHasher>(graph: &Graph<T, S>) -> Option<usize> {
+    all_distances(graph)
+        .filter_map(|(_, d)| max_distance(graph, &d))
+        .min()
+}
+
+/// Computes the closeness centrality of every vertex, scaled by the
+/// fraction of the graph that can be reached from it so that vertices
+/// of small components do not score higher than central ones.
+pub(crate) fn closeness_centrality<T, S: BuildHasher>(
+    graph: &Graph<T, S>,
+) -> HashMap<VertexId, f32> {
+    let others = graph.vertex_count().saturating_sub(1) as f32;
+
+    all_distances(graph)
+        .map(|(v, d)| {
+            let reached = (d.len() - 1) as f32;
+            let total: usize = d.values().sum();
+
+            let closeness = if total == 0 {
+                0.0
+            } else {
+                (reached / total as f32) * (reached / others)
+            };
+
+            (v, closeness)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_distances() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+        let v4 = graph.add_vertex(4);
+
+        // Directed path v1 -> v2 -> v3 -> v4
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v2, &v3).unwrap();
+        graph.add_edge(&v3, &v4).unwrap();
+
+        assert_eq!(eccentricity(&graph, &v1), Some(3));
+        assert_eq!(eccentricity(&graph, &v2), None);
+        assert_eq!(diameter(&graph), None);
+        assert_eq!(radius(&graph), Some(3));
+
+        // Closing the path into a cycle
+        graph.add_edge(&v4, &v1).unwrap();
+
+        assert_eq!(eccentricity(&graph, &v2), Some(3));
+        assert_eq!(diameter(&graph), Some(3));
+        assert_eq!(radius(&graph), Some(3));
+
+        // A shortcut makes v1 the center
+        graph.add_edge(&v1, &v3).unwrap();
+
+        assert_eq!(eccentricity(&graph, &v1), Some(2));
+        assert_eq!(radius(&graph), Some(2));
+        assert_eq!(diameter(&graph), Some(3));
+
+        // Weights do not change the number of edges on a path
+        graph.set_weight(&v1, &v3, 1.0).unwrap();
+        graph.set_weight(&v1, &v2, -1.0).unwrap();
+
+        assert_eq!(eccentricity(&graph, &v1), Some(2));
+    }
+
+    #[test]
+    fn test_closeness_centrality() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+        let v4 = graph.add_vertex(4);
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v1, &v3).unwrap();
+        graph.add_edge(&v3, &v4).unwrap();
+
+        let closeness = closeness_centrality(&graph);
+
+        // v1 reaches all 3 others with a total distance of 4
+        assert_eq!(closeness[&v1], 0.75);
+        // v3 only reaches v4, which is a third of the graph
+        assert_eq!(closeness[&v3], 1.0 / 3.0);
+        assert_eq!(closeness[&v2], 0.0);
+        assert_eq!(closeness[&v4], 0.0);
+    }
+}
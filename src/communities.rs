@@ -0,0 +1,168 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::Graph;
+use crate::vertex_id::VertexId;
+
+use hashbrown::HashMap;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_isaac::IsaacRng;
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+/// Upper bound of the number of passes over the vertices,
+/// in case the labels keep oscillating between passes.
+const MAX_PASSES: usize = 100;
+
+/// Detects communities by label propagation. Each vertex starts in a
+/// community of its own and then repeatedly joins the community which
+/// is the most common among its neighbors, ignoring edge directions,
+/// until no vertex changes its community.
+///
+/// Vertices are visited in a random order and ties are broken randomly,
+/// unless `deterministic` is set, in which case vertices are visited in
+/// the order of their ids and ties favor the smallest community id.
+pub(crate) fn label_propagation<T, S: BuildHasher>(
+    graph: &Graph<T, S>,
+    deterministic: bool,
+) -> HashMap<VertexId, usize> {
+    let mut vertices: Vec<VertexId> = graph.vertices().cloned().collect();
+    vertices.sort();
+
+    let mut labels: HashMap<VertexId, usize> =
+        vertices.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+
+    let mut rng = IsaacRng::seed_from_u64(u64::from_le_bytes({
+        let mut seed = [0; 8];
+        seed.copy_from_slice(&crate::gen_bytes()[..8]);
+        seed
+    }));
+
+    let mut counts: HashMap<usize, usize> = HashMap::new();
+    let mut candidates: Vec<usize> = Vec::new();
+
+    for _ in 0..MAX_PASSES {
+        if !deterministic {
+            vertices.shuffle(&mut rng);
+        }
+
+        let mut changed = false;
+
+        for v in vertices.iter() {
+            counts.clear();
+
+            for n in graph.neighbors(v).filter(|n| *n != v) {
+                *counts.entry(labels[n]).or_insert(0) += 1;
+            }
+
+            let max = match counts.values().max() {
+                Some(max) => *max,
+                None => continue,
+            };
+
+            let current = labels[v];
+
+            // Staying in the current community on ties
+            // guarantees that the propagation settles.
+            if counts.get(&current) == Some(&max) {
+                continue;
+            }
+
+            candidates.clear();
+            candidates.extend(counts.iter().filter(|(_, c)| **c == max).map(|(l, _)| *l));
+
+            let label = if deterministic {
+                *candidates.iter().min().unwrap()
+            } else {
+                *candidates.choose(&mut rng).unwrap()
+            };
+
+            labels.insert(*v, label);
+            changed = true;
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    // Number the communities in the order of the ids of their vertices
+    vertices.sort();
+
+    let mut communities: HashMap<usize, usize> = HashMap::new();
+
+    vertices
+        .iter()
+        .map(|v| {
+            let next = communities.len();
+            let community = *communities.entry(labels[v]).or_insert(next);
+
+            (*v, community)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds two separate triangles.
+    fn triangles() -> (Graph<usize>, Vec<VertexId>) {
+        let mut graph: Graph<usize> = Graph::new();
+        let v: Vec<VertexId> = (0..6).map(|i| graph.add_vertex(i)).collect();
+
+        for (a, b) in &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)] {
+            graph.add_edge(&v[*a], &v[*b]).unwrap();
+        }
+
+        (graph, v)
+    }
+
+    #[test]
+    fn test_two_triangles() {
+        let (graph, v) = triangles();
+
+        for deterministic in &[true, false] {
+            let communities = label_propagation(&graph, *deterministic);
+
+            assert_eq!(communities[&v[0]], communities[&v[1]]);
+            assert_eq!(communities[&v[1]], communities[&v[2]]);
+            assert_eq!(communities[&v[3]], communities[&v[4]]);
+            assert_eq!(communities[&v[4]], communities[&v[5]]);
+            assert_ne!(communities[&v[0]], communities[&v[5]]);
+        }
+    }
+
+    #[test]
+    fn test_deterministic_is_repeatable() {
+        let (graph, _) = triangles();
+
+        assert_eq!(
+            label_propagation(&graph, true),
+            label_propagation(&graph, true)
+        );
+    }
+
+    #[test]
+    fn test_isolated_vertices() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+
+        let communities = label_propagation(&graph, false);
+        let mut ids: Vec<usize> = communities.values().cloned().collect();
+        ids.sort();
+
+        assert_eq!(ids, vec![0, 1]);
+        assert_ne!(communities[&v1], communities[&v2]);
+    }
+}
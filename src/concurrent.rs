@@ -0,0 +1,265 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::{DefaultHashBuilder, Graph, GraphErr};
+use crate::vertex_id::VertexId;
+
+use std::hash::BuildHasher;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+const POISONED_MSG: &str = "graph lock was poisoned by a panicking writer";
+
+#[derive(Debug)]
+/// Graph which can be shared between threads, allowing any number of
+/// concurrent readers or a single writer at a time.
+///
+/// This is a coarse wrapper placing the whole graph behind a single
+/// `RwLock`, not a sharded one. Unlike a graph behind a `Mutex`,
+/// readers do not block each other, but every write blocks all
+/// readers for its duration, so it suits workloads with many reads
+/// and occasional writes.
+///
+/// Reads go through `ConcurrentGraph::read()`, which returns a guard
+/// dereferencing to the underlying `Graph`, so every traversal iterator
+/// of `Graph` can be used while the guard is held. Writes either go
+/// through the mutating methods of this type, which only hold the lock
+/// for the duration of the call, or through `ConcurrentGraph::write()`
+/// for batches of mutations.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::ConcurrentGraph;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let graph: Arc<ConcurrentGraph<usize>> = Arc::new(ConcurrentGraph::new());
+///
+/// let v1 = graph.add_vertex(1);
+/// let v2 = graph.add_vertex(2);
+///
+/// graph.add_edge(&v1, &v2).unwrap();
+///
+/// let readers: Vec<_> = (0..4)
+///     .map(|_| {
+///         let graph = graph.clone();
+///
+///         thread::spawn(move || graph.read().dfs().count())
+///     })
+///     .collect();
+///
+/// for reader in readers {
+///     assert_eq!(reader.join().unwrap(), 2);
+/// }
+/// ```
+pub struct ConcurrentGraph<T, S = DefaultHashBuilder> {
+    inner: RwLock<Graph<T, S>>,
+}
+
+impl<T> ConcurrentGraph<T> {
+    /// Creates a new concurrent graph.
+    pub fn new() -> ConcurrentGraph<T> {
+        ConcurrentGraph::from(Graph::new())
+    }
+}
+
+impl<T, S: BuildHasher> ConcurrentGraph<T, S> {
+    /// Locks the graph for reading, blocking the current thread until
+    /// there are no writers. Any number of threads can read at once.
+    ///
+    /// Panics if a writer panicked while holding the lock.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::ConcurrentGraph;
+    ///
+    /// let graph: ConcurrentGraph<usize> = ConcurrentGraph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    ///
+    /// let guard = graph.read();
+    ///
+    /// assert_eq!(guard.bfs().collect::<Vec<_>>(), vec![&v1, &v2]);
+    /// assert_eq!(guard.fetch(&v2), Some(&2));
+    /// ```
+    pub fn read(&self) -> RwLockReadGuard<'_, Graph<T, S>> {
+        self.inner.read().expect(POISONED_MSG)
+    }
+
+    /// Locks the graph for writing, blocking the current thread
+    /// until there are no other readers or writers.
+    ///
+    /// Panics if a writer panicked while holding the lock.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::ConcurrentGraph;
+    ///
+    /// let graph: ConcurrentGraph<usize> = ConcurrentGraph::new();
+    ///
+    /// {
+    ///     let mut guard = graph.write();
+    ///
+    ///     let v1 = guard.add_vertex(1);
+    ///     let v2 = guard.add_vertex(2);
+    ///
+    ///     guard.add_edge(&v1, &v2).unwrap();
+    /// }
+    ///
+    /// assert_eq!(graph.edge_count(), 1);
+    /// ```
+    pub fn write(&self) -> RwLockWriteGuard<'_, Graph<T, S>> {
+        self.inner.write().expect(POISONED_MSG)
+    }
+
+    /// Consumes the concurrent graph, returning the underlying graph.
+    pub fn into_inner(self) -> Graph<T, S> {
+        self.inner.into_inner().expect(POISONED_MSG)
+    }
+
+    /// Adds a new vertex to the graph and returns its id.
+    pub fn add_vertex(&self, item: T) -> VertexId {
+        self.write().add_vertex(item)
+    }
+
+    /// Removes the vertex with the given id along with its edges.
+    pub fn remove(&self, id: &VertexId) {
+        self.write().remove(id)
+    }
+
+    /// Adds an edge from `a` to `b`.
+    pub fn add_edge(&self, a: &VertexId, b: &VertexId) -> Result<(), GraphErr> {
+        self.write().add_edge(a, b)
+    }
+
+    /// Adds an edge from `a` to `b` with the given weight.
+    pub fn add_edge_with_weight(
+        &self,
+        a: &VertexId,
+        b: &VertexId,
+        weight: f32,
+    ) -> Result<(), GraphErr> {
+        self.write().add_edge_with_weight(a, b, weight)
+    }
+
+    /// Removes the edge from `a` to `b`, returning its weight
+    /// or `None` if there is no such edge.
+    pub fn remove_edge(&self, a: &VertexId, b: &VertexId) -> Option<f32> {
+        self.write().remove_edge(a, b)
+    }
+
+    /// Sets the weight of the edge from `a` to `b`.
+    pub fn set_weight(&self, a: &VertexId, b: &VertexId, weight: f32) -> Result<(), GraphErr> {
+        self.write().set_weight(a, b, weight)
+    }
+
+    /// Returns the number of vertices in the graph.
+    pub fn vertex_count(&self) -> usize {
+        self.read().vertex_count()
+    }
+
+    /// Returns the number of edges in the graph.
+    pub fn edge_count(&self) -> usize {
+        self.read().edge_count()
+    }
+
+    /// Returns true if there is an edge from `a` to `b`.
+    pub fn has_edge(&self, a: &VertexId, b: &VertexId) -> bool {
+        self.read().has_edge(a, b)
+    }
+
+    /// Returns the weight of the edge from `a` to `b`.
+    pub fn weight(&self, a: &VertexId, b: &VertexId) -> Option<f32> {
+        self.read().weight(a, b)
+    }
+}
+
+impl<T, S: BuildHasher + Clone + Default> Default for ConcurrentGraph<T, S> {
+    fn default() -> ConcurrentGraph<T, S> {
+        ConcurrentGraph::from(Graph::default())
+    }
+}
+
+impl<T, S> From<Graph<T, S>> for ConcurrentGraph<T, S> {
+    fn from(graph: Graph<T, S>) -> ConcurrentGraph<T, S> {
+        ConcurrentGraph {
+            inner: RwLock::new(graph),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_concurrent_readers_and_writers() {
+        let graph: Arc<ConcurrentGraph<usize>> = Arc::new(ConcurrentGraph::new());
+        let root = graph.add_vertex(0);
+
+        let writers: Vec<_> = (0..4)
+            .map(|i| {
+                let graph = graph.clone();
+
+                thread::spawn(move || {
+                    for j in 0..50 {
+                        // Adds each vertex along with its edge
+                        let mut guard = graph.write();
+                        let v = guard.add_vertex(i * 50 + j);
+
+                        guard.add_edge(&root, &v).unwrap();
+                    }
+                })
+            })
+            .collect();
+
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let graph = graph.clone();
+
+                thread::spawn(move || {
+                    for _ in 0..50 {
+                        let guard = graph.read();
+
+                        // Every vertex but the root is reached from
+                        // it, however many vertices were added so far
+                        assert_eq!(guard.bfs().count(), guard.vertex_count());
+                        assert_eq!(guard.edge_count() + 1, guard.vertex_count());
+                    }
+                })
+            })
+            .collect();
+
+        for handle in writers.into_iter().chain(readers) {
+            handle.join().unwrap();
+        }
+
+        let graph = Arc::try_unwrap(graph).unwrap().into_inner();
+
+        assert_eq!(graph.vertex_count(), 201);
+        assert_eq!(graph.out_neighbors_count(&root), 200);
+    }
+
+    #[test]
+    fn test_custom_hasher() {
+        use std::collections::hash_map::RandomState;
+
+        let graph: ConcurrentGraph<usize, RandomState> =
+            ConcurrentGraph::from(Graph::with_hasher(RandomState::new()));
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+
+        graph.add_edge_with_weight(&v1, &v2, 0.5).unwrap();
+
+        assert_eq!(graph.weight(&v1, &v2), Some(0.5));
+        assert_eq!(
+            graph.read().out_neighbors(&v1).collect::<Vec<_>>(),
+            vec![&v2]
+        );
+        assert_eq!(graph.into_inner().vertex_count(), 2);
+    }
+}
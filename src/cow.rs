@@ -0,0 +1,138 @@
+// Copyright 2019 Octavian Oncescu
+
+#[cfg(feature = "no_std")]
+use core::fmt::{self, Debug, Formatter};
+#[cfg(not(feature = "no_std"))]
+use std::fmt::{self, Debug, Formatter};
+
+#[cfg(feature = "no_std")]
+use core::mem;
+#[cfg(not(feature = "no_std"))]
+use std::mem;
+
+#[cfg(feature = "no_std")]
+use core::ops::{Deref, DerefMut};
+#[cfg(not(feature = "no_std"))]
+use std::ops::{Deref, DerefMut};
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::sync::Arc;
+#[cfg(not(feature = "no_std"))]
+use std::sync::Arc;
+
+/// Table of a graph which is either owned or shared with the
+/// clones of the graph. Cloning a shared table only clones the
+/// pointer to it, while mutably dereferencing it copies the table
+/// if it is still shared with another graph.
+pub(crate) enum CowTable<X> {
+    Owned(X),
+
+    /// Shared table along with the function copying it, which
+    /// lets graphs copy tables of values that are not known
+    /// to implement `Clone`.
+    Shared(Arc<X>, fn(&X) -> X),
+}
+
+impl<X: Clone + Default> CowTable<X> {
+    /// Makes clones of the table share it.
+    pub(crate) fn share(&mut self) {
+        if let CowTable::Owned(table) = self {
+            let table = mem::take(table);
+            *self = CowTable::Shared(Arc::new(table), X::clone);
+        }
+    }
+}
+
+impl<X> CowTable<X> {
+    /// Returns the table, copying it if it is still shared.
+    pub(crate) fn into_inner(self) -> X {
+        match self {
+            CowTable::Owned(table) => table,
+            CowTable::Shared(table, copy) => {
+                Arc::try_unwrap(table).unwrap_or_else(|table| copy(&table))
+            }
+        }
+    }
+
+    /// Replaces the table with the given one, which stays shared if the
+    /// table was, and returns the old table. The old table is only copied
+    /// if it is still shared with another graph.
+    pub(crate) fn replace(&mut self, table: X) -> X {
+        match self {
+            CowTable::Owned(old) => mem::replace(old, table),
+            CowTable::Shared(old, copy) => {
+                let old = mem::replace(old, Arc::new(table));
+                Arc::try_unwrap(old).unwrap_or_else(|old| copy(&old))
+            }
+        }
+    }
+
+    /// Returns true if clones of the table share it.
+    pub(crate) fn is_shared(&self) -> bool {
+        match self {
+            CowTable::Owned(_) => false,
+            CowTable::Shared(_, _) => true,
+        }
+    }
+}
+
+impl<X> From<X> for CowTable<X> {
+    fn from(table: X) -> CowTable<X> {
+        CowTable::Owned(table)
+    }
+}
+
+impl<X: Default> Default for CowTable<X> {
+    fn default() -> CowTable<X> {
+        CowTable::Owned(X::default())
+    }
+}
+
+impl<X: Clone> Clone for CowTable<X> {
+    fn clone(&self) -> CowTable<X> {
+        match self {
+            CowTable::Owned(table) => CowTable::Owned(table.clone()),
+            CowTable::Shared(table, copy) => CowTable::Shared(table.clone(), *copy),
+        }
+    }
+}
+
+impl<X: Debug> Debug for CowTable<X> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<X: PartialEq> PartialEq for CowTable<X> {
+    fn eq(&self, other: &CowTable<X>) -> bool {
+        self.deref() == other.deref()
+    }
+}
+
+impl<X> Deref for CowTable<X> {
+    type Target = X;
+
+    fn deref(&self) -> &X {
+        match self {
+            CowTable::Owned(table) => table,
+            CowTable::Shared(table, _) => table,
+        }
+    }
+}
+
+impl<X> DerefMut for CowTable<X> {
+    fn deref_mut(&mut self) -> &mut X {
+        match self {
+            CowTable::Owned(table) => table,
+            CowTable::Shared(table, copy) => {
+                if Arc::get_mut(table).is_none() {
+                    *table = Arc::new(copy(table));
+                }
+
+                Arc::get_mut(table).unwrap()
+            }
+        }
+    }
+}
@@ -0,0 +1,661 @@
+// Copyright 2019 Octavian Oncescu
+
+//! Loaders and writers for common benchmark graph formats.
+//!
+//! Vertices are created in the order of their ids in the input and
+//! each vertex holds its original id from the file as its value. Since
+//! edge weights in a `Graph` must lie between `-1.0` and `1.0`, weighted
+//! formats are normalized by dividing every weight by the largest absolute
+//! weight found in the input.
+
+use crate::graph::{Graph, GraphErr};
+use crate::vertex_id::VertexId;
+
+use hashbrown::HashMap;
+use std::io::{self, BufRead, Write};
+
+/// Iterates over the non-empty lines of the given reader
+/// which do not start with any of the comment prefixes.
+fn lines<R: BufRead>(
+    reader: R,
+    comments: &'static [&'static str],
+) -> impl Iterator<Item = Result<String, GraphErr>> {
+    reader
+        .lines()
+        .map(|line| line.map_err(|_| GraphErr::CouldNotRead))
+        .filter(move |line| match line {
+            Ok(line) => {
+                let line = line.trim();
+                !line.is_empty() && !comments.iter().any(|c| line.starts_with(c))
+            }
+            Err(_) => true,
+        })
+}
+
+fn parse<N: core::str::FromStr>(token: Option<&str>) -> Result<N, GraphErr> {
+    token
+        .and_then(|t| t.parse().ok())
+        .ok_or(GraphErr::CouldNotParse)
+}
+
+/// Creates vertices with the ids `1..=count` and
+/// returns a lookup table from ids to vertices.
+fn numbered_vertices(graph: &mut Graph<usize>, count: usize) -> Vec<VertexId> {
+    (1..=count).map(|id| graph.add_vertex(id)).collect()
+}
+
+/// Returns the vertex with the given one-based id.
+fn vertex(vertices: &[VertexId], id: usize) -> Result<&VertexId, GraphErr> {
+    id.checked_sub(1)
+        .and_then(|i| vertices.get(i))
+        .ok_or(GraphErr::NoSuchVertex)
+}
+
+/// Adds the given edges to the graph, dividing
+/// their weights by the largest absolute weight.
+fn add_normalized(
+    graph: &mut Graph<usize>,
+    edges: Vec<(VertexId, VertexId, f64)>,
+) -> Result<(), GraphErr> {
+    let max = edges.iter().map(|(_, _, w)| w.abs()).fold(0.0, f64::max);
+
+    for (a, b, w) in edges {
+        let weight = if max == 0.0 { 0.0 } else { (w / max) as f32 };
+
+        graph.add_edge_with_weight(&a, &b, weight)?;
+    }
+
+    Ok(())
+}
+
+/// Reads a SNAP edge list, which contains one edge per line as a
+/// pair of whitespace separated vertex ids. Lines starting with `#`
+/// are ignored. Vertices are created when their ids are first seen.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::datasets;
+///
+/// let input = "# Directed graph\n# FromNodeId\tToNodeId\n0\t1\n0\t2\n2\t1\n";
+/// let graph = datasets::read_snap(input.as_bytes()).unwrap();
+///
+/// assert_eq!(graph.vertex_count(), 3);
+/// assert_eq!(graph.edge_count(), 3);
+/// ```
+pub fn read_snap<R: BufRead>(reader: R) -> Result<Graph<usize>, GraphErr> {
+    let mut graph = Graph::new();
+    let mut vertices: HashMap<usize, VertexId> = HashMap::new();
+
+    for line in lines(reader, &["#"]) {
+        let line = line?;
+        let mut tokens = line.split_whitespace();
+        let mut ids = [VertexId::random(); 2];
+
+        for id in ids.iter_mut() {
+            let value: usize = parse(tokens.next())?;
+
+            *id = *vertices
+                .entry(value)
+                .or_insert_with(|| graph.add_vertex(value));
+        }
+
+        graph.add_edge(&ids[0], &ids[1])?;
+    }
+
+    Ok(graph)
+}
+
+/// Reads an adjacency list, which contains one line of the form
+/// `u: v1 v2 v3` for each vertex `u` with edges to the vertices `v1`,
+/// `v2` and `v3`. Lines starting with `#` are ignored and vertices are
+/// created when their ids are first seen, so vertices without outbound
+/// edges can be listed as `u:`.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::datasets;
+///
+/// let input = "# Course prerequisites\n1: 2 3\n2: 3\n3:\n4:\n";
+/// let graph = datasets::read_adjacency_list(input.as_bytes()).unwrap();
+///
+/// assert_eq!(graph.vertex_count(), 4);
+/// assert_eq!(graph.edge_count(), 3);
+/// ```
+pub fn read_adjacency_list<R: BufRead>(reader: R) -> Result<Graph<usize>, GraphErr> {
+    let mut graph = Graph::new();
+    let mut vertices: HashMap<usize, VertexId> = HashMap::new();
+
+    for line in lines(reader, &["#"]) {
+        let line = line?;
+        let (vertex, neighbors) = line.split_once(':').ok_or(GraphErr::CouldNotParse)?;
+        let mut ids = vec![vertex.trim()];
+        ids.extend(neighbors.split_whitespace());
+
+        let ids = ids
+            .into_iter()
+            .map(|token| {
+                let value: usize = parse(Some(token))?;
+
+                Ok(*vertices
+                    .entry(value)
+                    .or_insert_with(|| graph.add_vertex(value)))
+            })
+            .collect::<Result<Vec<VertexId>, GraphErr>>()?;
+
+        for b in ids[1..].iter() {
+            graph.add_edge(&ids[0], b)?;
+        }
+    }
+
+    Ok(graph)
+}
+
+/// Writes the given graph as an adjacency list, with one line for
+/// each vertex, including those without outbound edges. The values
+/// of the vertices are used as their ids and the lines are sorted by
+/// them, which makes this the inverse of `read_adjacency_list()`.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::datasets;
+///
+/// let input = "1: 2 3\n2: 3\n3:\n4:\n";
+/// let graph = datasets::read_adjacency_list(input.as_bytes()).unwrap();
+///
+/// let mut output = Vec::new();
+/// datasets::write_adjacency_list(&graph, &mut output).unwrap();
+///
+/// assert_eq!(String::from_utf8(output).unwrap(), input);
+/// ```
+pub fn write_adjacency_list<W: Write>(graph: &Graph<usize>, mut writer: W) -> Result<(), GraphErr> {
+    let mut vertices: Vec<(usize, &VertexId)> = graph
+        .vertices()
+        .map(|v| (*graph.fetch(v).unwrap(), v))
+        .collect();
+
+    vertices.sort_by_key(|(value, _)| *value);
+
+    for (value, v) in vertices {
+        let mut neighbors: Vec<usize> = graph
+            .out_neighbors(v)
+            .map(|n| *graph.fetch(n).unwrap())
+            .collect();
+
+        neighbors.sort_unstable();
+
+        let line = neighbors
+            .iter()
+            .fold(format!("{}:", value), |line, n| format!("{} {}", line, n));
+
+        writeln!(writer, "{}", line).map_err(|_| GraphErr::CouldNotWrite)?;
+    }
+
+    Ok(())
+}
+
+/// Graph of a DIMACS problem along with its source and sink, if any.
+type DimacsProblem = (Graph<usize>, Option<VertexId>, Option<VertexId>);
+
+/// Parses a DIMACS problem of the given kind, returning the graph along
+/// with the vertices marked as the source and the sink of the problem.
+fn read_dimacs_problem<R: BufRead>(reader: R, kind: &str) -> Result<DimacsProblem, GraphErr> {
+    let mut graph = Graph::new();
+    let mut vertices = None;
+    let mut edges = Vec::new();
+    let mut source = None;
+    let mut sink = None;
+
+    for line in lines(reader, &["c"]) {
+        let line = line?;
+        let mut tokens = line.split_whitespace();
+
+        match (tokens.next(), vertices.as_ref()) {
+            (Some("p"), None) => {
+                if tokens.next() != Some(kind) {
+                    return Err(GraphErr::CouldNotParse);
+                }
+
+                let count = parse(tokens.next())?;
+                let arcs = parse(tokens.next())?;
+
+                vertices = Some(numbered_vertices(&mut graph, count));
+                edges.reserve(arcs);
+            }
+            (Some("n"), Some(vertices)) if kind == "max" => {
+                let v = *vertex(vertices, parse(tokens.next())?)?;
+
+                match tokens.next() {
+                    Some("s") => source = Some(v),
+                    Some("t") => sink = Some(v),
+                    _ => return Err(GraphErr::CouldNotParse),
+                }
+            }
+            (Some("a"), Some(vertices)) => {
+                let a = vertex(vertices, parse(tokens.next())?)?;
+                let b = vertex(vertices, parse(tokens.next())?)?;
+                let weight = parse(tokens.next())?;
+
+                edges.push((*a, *b, weight));
+            }
+            _ => return Err(GraphErr::CouldNotParse),
+        }
+    }
+
+    add_normalized(&mut graph, edges)?;
+
+    Ok((graph, source, sink))
+}
+
+/// Reads a graph in the DIMACS shortest path format (`.gr`). The problem
+/// line `p sp <vertices> <arcs>` must come before any `a <from> <to> <weight>`
+/// arc line and lines starting with `c` are ignored.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::datasets;
+///
+/// let input = "c Example\np sp 3 2\na 1 2 10\na 2 3 5\n";
+/// let graph = datasets::read_dimacs(input.as_bytes()).unwrap();
+///
+/// assert_eq!(graph.vertex_count(), 3);
+/// assert_eq!(graph.edge_count(), 2);
+///
+/// let mut weights: Vec<f32> = graph
+///     .edges()
+///     .map(|(b, a)| graph.weight(a, b).unwrap())
+///     .collect();
+///
+/// weights.sort_by(|a, b| a.partial_cmp(b).unwrap());
+///
+/// assert_eq!(weights, vec![0.5, 1.0]);
+/// ```
+pub fn read_dimacs<R: BufRead>(reader: R) -> Result<Graph<usize>, GraphErr> {
+    read_dimacs_problem(reader, "sp").map(|(graph, _, _)| graph)
+}
+
+/// Reads a graph in the DIMACS maximum flow format (`.max`) and returns
+/// it along with its source and sink. Besides the problem and arc lines
+/// of the shortest path format, the input must contain the `n <id> s`
+/// and `n <id> t` lines which mark the source and the sink.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::datasets;
+///
+/// let input = "p max 3 2\nn 1 s\nn 3 t\na 1 2 4\na 2 3 2\n";
+/// let (graph, source, sink) = datasets::read_dimacs_max(input.as_bytes()).unwrap();
+///
+/// assert_eq!(*graph.fetch(&source).unwrap(), 1);
+/// assert_eq!(*graph.fetch(&sink).unwrap(), 3);
+///
+/// // Capacities are divided by the largest one
+/// assert_eq!(graph.max_flow(&source, &sink).unwrap().value() * 4.0, 2.0);
+/// ```
+pub fn read_dimacs_max<R: BufRead>(
+    reader: R,
+) -> Result<(Graph<usize>, VertexId, VertexId), GraphErr> {
+    match read_dimacs_problem(reader, "max")? {
+        (graph, Some(source), Some(sink)) => Ok((graph, source, sink)),
+        _ => Err(GraphErr::CouldNotParse),
+    }
+}
+
+/// Returns the edges of the given graph as the values of their
+/// vertices along with their weights, sorted by the values.
+fn sorted_edges(graph: &Graph<usize>) -> Vec<(usize, usize, f32)> {
+    let mut edges: Vec<(usize, usize, f32)> = graph
+        .edges()
+        .map(|(b, a)| {
+            (
+                *graph.fetch(a).unwrap(),
+                *graph.fetch(b).unwrap(),
+                graph.weight(a, b).unwrap(),
+            )
+        })
+        .collect();
+
+    edges.sort_by_key(|(a, b, _)| (*a, *b));
+    edges
+}
+
+/// Writes the arcs of the given graph in the DIMACS format, sorted
+/// by the ids of their vertices.
+fn write_arcs<W: Write>(graph: &Graph<usize>, scale: f32, writer: &mut W) -> io::Result<()> {
+    for (a, b, weight) in sorted_edges(graph) {
+        writeln!(writer, "a {} {} {}", a, b, (weight * scale).round() as i64)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the number of vertices of the given graph, failing with
+/// `GraphErr::CouldNotWrite` unless their values are the numbers from
+/// `1` to that number, as DIMACS ids are 1-based and dense.
+fn dimacs_count(graph: &Graph<usize>) -> Result<usize, GraphErr> {
+    let count = graph.vertex_count();
+    let mut used = vec![false; count];
+
+    for value in graph.values() {
+        match value.checked_sub(1).and_then(|i| used.get_mut(i)) {
+            Some(used) if !*used => *used = true,
+            _ => return Err(GraphErr::CouldNotWrite),
+        }
+    }
+
+    Ok(count)
+}
+
+/// Writes the given graph in the DIMACS shortest path format. The values
+/// of the vertices are used as their ids, which makes this the inverse of
+/// `read_dimacs()`, and the weights are multiplied by `scale` and rounded
+/// to integers.
+///
+/// Fails with `GraphErr::CouldNotWrite` unless the values of the vertices
+/// are the numbers from `1` to the number of vertices, each used once.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::datasets;
+///
+/// let input = "p sp 3 2\na 1 2 10\na 2 3 5\n";
+/// let graph = datasets::read_dimacs(input.as_bytes()).unwrap();
+///
+/// let mut output = Vec::new();
+/// datasets::write_dimacs(&graph, 10.0, &mut output).unwrap();
+///
+/// assert_eq!(String::from_utf8(output).unwrap(), input);
+/// ```
+pub fn write_dimacs<W: Write>(
+    graph: &Graph<usize>,
+    scale: f32,
+    mut writer: W,
+) -> Result<(), GraphErr> {
+    let count = dimacs_count(graph)?;
+
+    writeln!(writer, "p sp {} {}", count, graph.edge_count())
+        .and_then(|_| write_arcs(graph, scale, &mut writer))
+        .map_err(|_| GraphErr::CouldNotWrite)
+}
+
+/// Writes the given graph in the DIMACS maximum flow format, marking the
+/// given vertices as the source and the sink. As with `write_dimacs()`, the
+/// values of the vertices are used as their ids and the capacities are
+/// multiplied by `scale` and rounded to integers.
+///
+/// Fails with `GraphErr::CouldNotWrite` unless the values of the vertices
+/// are the numbers from `1` to the number of vertices, each used once.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::datasets;
+///
+/// let input = "p max 3 2\nn 1 s\nn 3 t\na 1 2 4\na 2 3 2\n";
+/// let (graph, source, sink) = datasets::read_dimacs_max(input.as_bytes()).unwrap();
+///
+/// let mut output = Vec::new();
+/// datasets::write_dimacs_max(&graph, &source, &sink, 4.0, &mut output).unwrap();
+///
+/// assert_eq!(String::from_utf8(output).unwrap(), input);
+/// ```
+pub fn write_dimacs_max<W: Write>(
+    graph: &Graph<usize>,
+    source: &VertexId,
+    sink: &VertexId,
+    scale: f32,
+    mut writer: W,
+) -> Result<(), GraphErr> {
+    let source = graph.fetch(source).ok_or(GraphErr::NoSuchVertex)?;
+    let sink = graph.fetch(sink).ok_or(GraphErr::NoSuchVertex)?;
+    let count = dimacs_count(graph)?;
+
+    writeln!(writer, "p max {} {}", count, graph.edge_count())
+        .and_then(|_| writeln!(writer, "n {} s", source))
+        .and_then(|_| writeln!(writer, "n {} t", sink))
+        .and_then(|_| write_arcs(graph, scale, &mut writer))
+        .map_err(|_| GraphErr::CouldNotWrite)
+}
+
+/// Reads a sparse matrix in the Matrix Market coordinate format as an
+/// adjacency matrix, where each entry `i j [value]` is an edge from `i`
+/// to `j`. Entries of symmetric matrices are mirrored. Only the `real`,
+/// `integer` and `pattern` fields are supported.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::datasets;
+///
+/// let input = "%%MatrixMarket matrix coordinate pattern symmetric\n\
+///              % Path of three vertices\n\
+///              3 3 2\n\
+///              2 1\n\
+///              3 2\n";
+///
+/// let graph = datasets::read_matrix_market(input.as_bytes()).unwrap();
+///
+/// assert_eq!(graph.vertex_count(), 3);
+/// assert_eq!(graph.edge_count(), 4);
+/// ```
+pub fn read_matrix_market<R: BufRead>(reader: R) -> Result<Graph<usize>, GraphErr> {
+    let mut lines = lines(reader, &[]);
+    let header = lines.next().ok_or(GraphErr::CouldNotParse)??;
+    let header: Vec<String> = header
+        .split_whitespace()
+        .map(|t| t.to_lowercase())
+        .collect();
+
+    let (pattern, mirror) = match header.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["%%matrixmarket", "matrix", "coordinate", field, symmetry] => {
+            let pattern = match field {
+                "real" | "integer" => false,
+                "pattern" => true,
+                _ => return Err(GraphErr::CouldNotParse),
+            };
+
+            let mirror = match symmetry {
+                "general" => None,
+                "symmetric" => Some(1.0),
+                "skew-symmetric" => Some(-1.0),
+                _ => return Err(GraphErr::CouldNotParse),
+            };
+
+            (pattern, mirror)
+        }
+        _ => return Err(GraphErr::CouldNotParse),
+    };
+
+    let mut lines = lines.filter(|line| match line {
+        Ok(line) => !line.trim_start().starts_with('%'),
+        Err(_) => true,
+    });
+
+    let size = lines.next().ok_or(GraphErr::CouldNotParse)??;
+    let mut tokens = size.split_whitespace();
+    let rows: usize = parse(tokens.next())?;
+    let columns: usize = parse(tokens.next())?;
+    let entries: usize = parse(tokens.next())?;
+
+    let mut graph = Graph::new();
+    let vertices = numbered_vertices(&mut graph, rows.max(columns));
+    let mut edges = Vec::with_capacity(entries);
+
+    for line in lines {
+        let line = line?;
+        let mut tokens = line.split_whitespace();
+        let a = *vertex(&vertices, parse(tokens.next())?)?;
+        let b = *vertex(&vertices, parse(tokens.next())?)?;
+        let weight = if pattern { 0.0 } else { parse(tokens.next())? };
+
+        edges.push((a, b, weight));
+
+        if let Some(sign) = mirror {
+            if a != b {
+                edges.push((b, a, sign * weight));
+            }
+        }
+    }
+
+    add_normalized(&mut graph, edges)?;
+
+    Ok(graph)
+}
+
+/// Writes the given graph in the Matrix Market coordinate format as a
+/// general real adjacency matrix, with one `i j weight` entry for each
+/// edge. As with `write_dimacs()`, the values of the vertices are used
+/// as their ids, which makes this the inverse of `read_matrix_market()`
+/// for graphs whose largest absolute weight is `1.0`.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::datasets;
+///
+/// let input = "%%MatrixMarket matrix coordinate real general\n\
+///              3 3 2\n\
+///              1 2 -0.5\n\
+///              3 1 1\n";
+///
+/// let graph = datasets::read_matrix_market(input.as_bytes()).unwrap();
+///
+/// let mut output = Vec::new();
+/// datasets::write_matrix_market(&graph, &mut output).unwrap();
+///
+/// assert_eq!(String::from_utf8(output).unwrap(), input);
+/// ```
+pub fn write_matrix_market<W: Write>(graph: &Graph<usize>, mut writer: W) -> Result<(), GraphErr> {
+    let count = graph.values().cloned().max().unwrap_or(0);
+
+    writeln!(writer, "%%MatrixMarket matrix coordinate real general")
+        .and_then(|_| writeln!(writer, "{} {} {}", count, count, graph.edge_count()))
+        .and_then(|_| {
+            for (a, b, weight) in sorted_edges(graph) {
+                writeln!(writer, "{} {} {}", a, b, weight)?;
+            }
+
+            Ok(())
+        })
+        .map_err(|_| GraphErr::CouldNotWrite)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_snap_reuses_vertices() {
+        let input = "# Comment\n7 3\n3 7\n\n7 3\n";
+        let graph = read_snap(input.as_bytes()).unwrap();
+
+        assert_eq!(graph.vertex_count(), 2);
+        assert_eq!(graph.edge_count(), 2);
+
+        let mut values: Vec<usize> = graph.values().cloned().collect();
+        values.sort();
+
+        assert_eq!(values, vec![3, 7]);
+        assert_eq!(
+            read_snap("1 x\n".as_bytes()).unwrap_err(),
+            GraphErr::CouldNotParse
+        );
+    }
+
+    #[test]
+    fn test_read_dimacs_errors() {
+        // Arc before the problem line
+        assert_eq!(
+            read_dimacs("a 1 2 3\np sp 2 1\n".as_bytes()).unwrap_err(),
+            GraphErr::CouldNotParse
+        );
+
+        // Arc to a vertex outside of the problem size
+        assert_eq!(
+            read_dimacs("p sp 2 1\na 1 3 1\n".as_bytes()).unwrap_err(),
+            GraphErr::NoSuchVertex
+        );
+    }
+
+    #[test]
+    fn test_read_dimacs_max_without_sink() {
+        assert_eq!(
+            read_dimacs_max("p max 2 1\nn 1 s\na 1 2 1\n".as_bytes()).unwrap_err(),
+            GraphErr::CouldNotParse
+        );
+
+        // Terminals are only valid in flow problems
+        assert_eq!(
+            read_dimacs("p sp 2 1\nn 1 s\na 1 2 1\n".as_bytes()).unwrap_err(),
+            GraphErr::CouldNotParse
+        );
+    }
+
+    #[test]
+    fn test_write_dimacs_requires_dense_ids() {
+        // SNAP ids are 0-based
+        let graph = read_snap("0 1\n1 2\n".as_bytes()).unwrap();
+
+        assert_eq!(
+            write_dimacs(&graph, 1.0, Vec::new()).unwrap_err(),
+            GraphErr::CouldNotWrite
+        );
+
+        let mut graph: Graph<usize> = Graph::new();
+        let v1 = graph.add_vertex(1);
+        let v3 = graph.add_vertex(3);
+
+        graph.add_edge(&v1, &v3).unwrap();
+
+        assert_eq!(
+            write_dimacs_max(&graph, &v1, &v3, 1.0, Vec::new()).unwrap_err(),
+            GraphErr::CouldNotWrite
+        );
+
+        graph.add_vertex(2);
+        assert!(write_dimacs_max(&graph, &v1, &v3, 1.0, Vec::new()).is_ok());
+    }
+
+    #[test]
+    fn test_read_matrix_market_skew_symmetric() {
+        let input = "%%MatrixMarket matrix coordinate real skew-symmetric\n\
+                     2 2 1\n\
+                     2 1 -4.0\n";
+
+        let graph = read_matrix_market(input.as_bytes()).unwrap();
+        let one = graph
+            .vertices()
+            .find(|v| *graph.fetch(v).unwrap() == 1)
+            .unwrap();
+        let two = graph
+            .vertices()
+            .find(|v| *graph.fetch(v).unwrap() == 2)
+            .unwrap();
+
+        assert_eq!(graph.weight(two, one), Some(-1.0));
+        assert_eq!(graph.weight(one, two), Some(1.0));
+
+        let input = "%%MatrixMarket matrix array real general\n2 2\n1.0\n";
+
+        assert_eq!(
+            read_matrix_market(input.as_bytes()).unwrap_err(),
+            GraphErr::CouldNotParse
+        );
+    }
+
+    #[test]
+    fn test_read_adjacency_list_errors() {
+        // Missing separator
+        assert_eq!(
+            read_adjacency_list("1 2 3\n".as_bytes()).unwrap_err(),
+            GraphErr::CouldNotParse
+        );
+
+        assert_eq!(
+            read_adjacency_list("1: 2 x\n".as_bytes()).unwrap_err(),
+            GraphErr::CouldNotParse
+        );
+
+        // Vertices may be listed on several lines
+        let graph = read_adjacency_list("1: 2\n\n1: 3\n".as_bytes()).unwrap();
+
+        assert_eq!(graph.vertex_count(), 3);
+        assert_eq!(graph.edge_count(), 2);
+    }
+}
@@ -0,0 +1,244 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::Graph;
+use crate::vertex_id::VertexId;
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "no_std")]
+use core::fmt::{self, Debug, Display};
+#[cfg(not(feature = "no_std"))]
+use std::fmt::{self, Debug, Display};
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+#[derive(Clone, Debug, PartialEq)]
+/// Structural differences between two graphs, where vertices are
+/// matched by their ids. Created by `Graph::diff()`.
+///
+/// All lists are sorted by vertex ids.
+pub struct GraphDiff<'a, T> {
+    /// Vertices which are only in the left graph, along with their values
+    pub left_vertices: Vec<(VertexId, &'a T)>,
+
+    /// Vertices which are only in the right graph, along with their values
+    pub right_vertices: Vec<(VertexId, &'a T)>,
+
+    /// Vertices which have different values in the two graphs
+    pub values: Vec<(VertexId, &'a T, &'a T)>,
+
+    /// Edges which are only in the left graph, along with their weights
+    pub left_edges: Vec<(VertexId, VertexId, f32)>,
+
+    /// Edges which are only in the right graph, along with their weights
+    pub right_edges: Vec<(VertexId, VertexId, f32)>,
+
+    /// Edges which have different weights in the two graphs
+    pub weights: Vec<(VertexId, VertexId, f32, f32)>,
+}
+
+impl<'a, T> GraphDiff<'a, T> {
+    /// Returns true if the graphs have no differences.
+    pub fn is_empty(&self) -> bool {
+        self.left_vertices.is_empty()
+            && self.right_vertices.is_empty()
+            && self.values.is_empty()
+            && self.left_edges.is_empty()
+            && self.right_edges.is_empty()
+            && self.weights.is_empty()
+    }
+}
+
+/// Returns the edges of the graph along with their weights,
+/// which are not in the other graph or have a different weight.
+fn edges_not_in<T, U, S: BuildHasher>(
+    graph: &Graph<T, S>,
+    other: &Graph<U, S>,
+) -> Vec<(VertexId, VertexId, f32)> {
+    let mut edges: Vec<(VertexId, VertexId, f32)> = graph
+        .edges_with_weights()
+        .map(|(a, b, w)| (*a, *b, w))
+        .filter(|(a, b, _)| !other.has_edge(a, b))
+        .collect();
+
+    edges.sort_by_key(|(a, b, _)| (*a, *b));
+    edges
+}
+
+pub(crate) fn diff<'a, T: PartialEq, S: BuildHasher>(
+    left: &'a Graph<T, S>,
+    right: &'a Graph<T, S>,
+) -> GraphDiff<'a, T> {
+    let mut left_vertices = Vec::new();
+    let mut values = Vec::new();
+
+    for v in left.vertices() {
+        let value = left.fetch(v).unwrap();
+
+        match right.fetch(v) {
+            Some(other) if other != value => values.push((*v, value, other)),
+            Some(_) => {}
+            None => left_vertices.push((*v, value)),
+        }
+    }
+
+    let mut right_vertices: Vec<(VertexId, &T)> = right
+        .vertices()
+        .filter(|v| left.fetch(v).is_none())
+        .map(|v| (*v, right.fetch(v).unwrap()))
+        .collect();
+
+    let mut weights: Vec<(VertexId, VertexId, f32, f32)> = left
+        .edges()
+        .filter_map(|(b, a)| {
+            let weight = left.weight(a, b).unwrap();
+            let other = right.weight(a, b)?;
+
+            if weight != other {
+                Some((*a, *b, weight, other))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    left_vertices.sort_by_key(|(v, _)| *v);
+    right_vertices.sort_by_key(|(v, _)| *v);
+    values.sort_by_key(|(v, _, _)| *v);
+    weights.sort_by_key(|(a, b, _, _)| (*a, *b));
+
+    GraphDiff {
+        left_vertices,
+        right_vertices,
+        values,
+        left_edges: edges_not_in(left, right),
+        right_edges: edges_not_in(right, left),
+        weights,
+    }
+}
+
+impl<'a, T: Debug> Display for GraphDiff<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "graphs are equal");
+        }
+
+        write!(f, "graphs differ:")?;
+
+        for (v, value) in self.left_vertices.iter() {
+            write!(f, "\n  vertex {:?} only in left, value: {:?}", v, value)?;
+        }
+
+        for (v, value) in self.right_vertices.iter() {
+            write!(f, "\n  vertex {:?} only in right, value: {:?}", v, value)?;
+        }
+
+        for (v, left, right) in self.values.iter() {
+            write!(
+                f,
+                "\n  vertex {:?} values differ, left: {:?}, right: {:?}",
+                v, left, right
+            )?;
+        }
+
+        for (a, b, weight) in self.left_edges.iter() {
+            write!(
+                f,
+                "\n  edge {:?} -> {:?} only in left, weight: {}",
+                a, b, weight
+            )?;
+        }
+
+        for (a, b, weight) in self.right_edges.iter() {
+            write!(
+                f,
+                "\n  edge {:?} -> {:?} only in right, weight: {}",
+                a, b, weight
+            )?;
+        }
+
+        for (a, b, left, right) in self.weights.iter() {
+            write!(
+                f,
+                "\n  edge {:?} -> {:?} weights differ, left: {}, right: {}",
+                a, b, left, right
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let mut left: Graph<usize> = Graph::new();
+
+        let v1 = left.add_vertex(1);
+        let v2 = left.add_vertex(2);
+        let v3 = left.add_vertex(3);
+
+        left.add_edge(&v1, &v2).unwrap();
+        left.add_edge(&v2, &v3).unwrap();
+
+        let mut right = left.clone();
+
+        assert!(diff(&left, &right).is_empty());
+        assert_eq!(diff(&left, &right).to_string(), "graphs are equal");
+
+        right.remove(&v3);
+        right.set_weight(&v1, &v2, 0.5).unwrap();
+        *right.fetch_mut(&v1).unwrap() = 4;
+
+        let v4 = right.add_vertex(4);
+        right.add_edge(&v4, &v1).unwrap();
+
+        let d = diff(&left, &right);
+
+        assert_eq!(d.left_vertices, vec![(v3, &3)]);
+        assert_eq!(d.right_vertices, vec![(v4, &4)]);
+        assert_eq!(d.values, vec![(v1, &1, &4)]);
+        assert_eq!(d.left_edges, vec![(v2, v3, 0.0)]);
+        assert_eq!(d.right_edges, vec![(v4, v1, 0.0)]);
+        assert_eq!(d.weights, vec![(v1, v2, 0.0, 0.5)]);
+
+        let lines: Vec<String> = d.to_string().lines().map(String::from).collect();
+
+        assert_eq!(lines.len(), 7);
+        assert_eq!(
+            lines[3],
+            format!("  vertex {:?} values differ, left: 1, right: 4", v1)
+        );
+        assert_eq!(
+            lines[6],
+            format!(
+                "  edge {:?} -> {:?} weights differ, left: 0, right: 0.5",
+                v1, v2
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "only in right, value: 2")]
+    fn test_assert_graph_eq() {
+        let mut left: Graph<usize> = Graph::new();
+        left.add_vertex(1);
+
+        let mut right = left.clone();
+
+        crate::assert_graph_eq!(left, right);
+
+        right.add_vertex(2);
+
+        crate::assert_graph_eq!(left, right, "after adding {}", 2);
+    }
+}
@@ -0,0 +1,221 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::vertex_id::VertexId;
+
+use hashbrown::HashMap;
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, Default)]
+/// Disjoint sets of vertices, merged by union by size
+/// and queried with path compression.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::{DisjointSet, Graph};
+///
+/// let mut graph: Graph<usize> = Graph::new();
+///
+/// let v1 = graph.add_vertex(1);
+/// let v2 = graph.add_vertex(2);
+/// let v3 = graph.add_vertex(3);
+///
+/// let mut sets = DisjointSet::new();
+///
+/// for v in graph.vertices() {
+///     sets.make_set(*v);
+/// }
+///
+/// assert!(sets.union(&v1, &v2));
+/// assert!(!sets.union(&v2, &v1));
+///
+/// assert!(sets.same_set(&v1, &v2));
+/// assert!(!sets.same_set(&v1, &v3));
+/// assert_eq!(sets.set_count(), 2);
+/// ```
+pub struct DisjointSet {
+    /// Mapping between vertex ids and their parents
+    parents: HashMap<VertexId, VertexId>,
+
+    /// Mapping between the representatives of the sets and their sizes
+    sizes: HashMap<VertexId, usize>,
+}
+
+impl DisjointSet {
+    /// Creates an empty collection of sets.
+    pub fn new() -> DisjointSet {
+        DisjointSet::default()
+    }
+
+    /// Creates an empty collection of sets with
+    /// space for the given number of vertices.
+    pub fn with_capacity(capacity: usize) -> DisjointSet {
+        DisjointSet {
+            parents: HashMap::with_capacity(capacity),
+            sizes: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Places the given vertex in a set of its own if it
+    /// is not already placed in a set.
+    pub fn make_set(&mut self, id: VertexId) {
+        if !self.parents.contains_key(&id) {
+            self.parents.insert(id, id);
+            self.sizes.insert(id, 1);
+        }
+    }
+
+    /// Returns true if the given vertex is placed in a set.
+    pub fn contains(&self, id: &VertexId) -> bool {
+        self.parents.contains_key(id)
+    }
+
+    /// Returns the representative of the set containing the given
+    /// vertex or `None` if the vertex is not placed in a set.
+    pub fn find(&mut self, id: &VertexId) -> Option<VertexId> {
+        let mut root = *self.parents.get(id)?;
+
+        while self.parents[&root] != root {
+            root = self.parents[&root];
+        }
+
+        // Point every vertex on the way directly to the root.
+        let mut current = *id;
+
+        while current != root {
+            let next = self.parents[&current];
+            self.parents.insert(current, root);
+            current = next;
+        }
+
+        Some(root)
+    }
+
+    /// Returns true if both vertices are placed in the same set.
+    pub fn same_set(&mut self, a: &VertexId, b: &VertexId) -> bool {
+        match (self.find(a), self.find(b)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Merges the sets containing the given vertices. Returns
+    /// true if the vertices were placed in different sets.
+    pub fn union(&mut self, a: &VertexId, b: &VertexId) -> bool {
+        let (a, b) = match (self.find(a), self.find(b)) {
+            (Some(a), Some(b)) if a != b => (a, b),
+            _ => return false,
+        };
+
+        let (small, large) = if self.sizes[&a] < self.sizes[&b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+
+        let small_size = self.sizes.remove(&small).unwrap();
+
+        self.parents.insert(small, large);
+        *self.sizes.get_mut(&large).unwrap() += small_size;
+
+        true
+    }
+
+    /// Returns the number of vertices placed in the sets.
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Returns true if no vertex is placed in a set.
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Returns the number of sets.
+    pub fn set_count(&self) -> usize {
+        self.sizes.len()
+    }
+
+    /// Returns the size of the set containing the given vertex.
+    pub fn set_size(&mut self, id: &VertexId) -> Option<usize> {
+        let root = self.find(id)?;
+
+        Some(self.sizes[&root])
+    }
+
+    /// Returns an iterator over the sizes of all sets.
+    pub fn set_sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.sizes.values().cloned()
+    }
+
+    /// Returns the vertices of each set.
+    pub fn sets(&mut self) -> Vec<Vec<VertexId>> {
+        let ids: Vec<VertexId> = self.parents.keys().cloned().collect();
+        let mut sets: HashMap<VertexId, Vec<VertexId>> = HashMap::with_capacity(self.set_count());
+
+        for id in ids {
+            let root = self.find(&id).unwrap();
+            sets.entry(root).or_default().push(id);
+        }
+
+        sets.into_iter().map(|(_, set)| set).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_vertices() {
+        let mut sets = DisjointSet::new();
+
+        let v1 = VertexId::random();
+        let v2 = VertexId::random();
+
+        sets.make_set(v1);
+
+        assert!(sets.contains(&v1));
+        assert!(!sets.contains(&v2));
+        assert_eq!(sets.find(&v2), None);
+        assert_eq!(sets.set_size(&v2), None);
+        assert!(!sets.union(&v1, &v2));
+        assert!(!sets.same_set(&v1, &v2));
+        assert!(!sets.same_set(&v2, &v2));
+    }
+
+    #[test]
+    fn test_union_chain() {
+        let mut sets = DisjointSet::with_capacity(100);
+        let ids: Vec<VertexId> = (0..100).map(|_| VertexId::random()).collect();
+
+        for id in ids.iter() {
+            sets.make_set(*id);
+        }
+
+        // Merging all even vertices and all odd vertices
+        for pair in ids.windows(3) {
+            sets.union(&pair[0], &pair[2]);
+        }
+
+        assert_eq!(sets.len(), 100);
+        assert_eq!(sets.set_count(), 2);
+        assert_eq!(sets.set_size(&ids[0]), Some(50));
+        assert!(sets.same_set(&ids[0], &ids[98]));
+        assert!(!sets.same_set(&ids[0], &ids[99]));
+
+        // Making an existing vertex a set does not split it off
+        sets.make_set(ids[0]);
+        assert_eq!(sets.set_count(), 2);
+
+        let mut sizes: Vec<usize> = sets.sets().iter().map(Vec::len).collect();
+        sizes.sort();
+
+        assert_eq!(sizes, vec![50, 50]);
+        assert!(sets.union(&ids[0], &ids[1]));
+        assert_eq!(sets.set_sizes().collect::<Vec<_>>(), vec![100]);
+    }
+}
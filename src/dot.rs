@@ -1,82 +1,1016 @@
-use crate::{Graph, GraphErr, VertexId};
+use crate::graph::DEFAULT_LABEL;
+use crate::{AttrValue, Graph, GraphErr, VertexId};
+use hashbrown::HashMap;
 
 #[cfg(feature = "no_std")]
-use core::io::Write;
+use alloc::borrow::ToOwned;
+#[cfg(feature = "no_std")]
+use alloc::string::{String, ToString};
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
 
 #[cfg(not(feature = "no_std"))]
 use std::io::Write;
 
 #[cfg(feature = "no_std")]
-use core::borrow::Cow;
-
+use core::fmt;
 #[cfg(not(feature = "no_std"))]
-use std::borrow::Cow;
+use std::fmt;
 
 #[cfg(feature = "no_std")]
-use core::fmt::Debug;
-
+use core::hash::BuildHasher;
 #[cfg(not(feature = "no_std"))]
-use std::fmt::Debug;
+use std::hash::BuildHasher;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Direction in which the ranks of a rendered graph are laid out.
+pub enum RankDir {
+    /// Ranks are laid out from top to bottom. This is the Graphviz default.
+    TopToBottom,
+
+    /// Ranks are laid out from left to right.
+    LeftToRight,
+
+    /// Ranks are laid out from bottom to top.
+    BottomToTop,
+
+    /// Ranks are laid out from right to left.
+    RightToLeft,
+}
+
+impl RankDir {
+    fn as_str(self) -> &'static str {
+        match self {
+            RankDir::TopToBottom => "TB",
+            RankDir::LeftToRight => "LR",
+            RankDir::BottomToTop => "BT",
+            RankDir::RightToLeft => "RL",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Rank constraint placed on a group of vertices.
+pub enum Rank {
+    /// All vertices of the group are placed on the same rank.
+    Same,
+
+    /// All vertices of the group are placed on the minimum rank.
+    Min,
+
+    /// All vertices of the group are placed on the maximum rank.
+    Max,
+
+    /// The vertices of the group are the only ones on the minimum rank.
+    Source,
+
+    /// The vertices of the group are the only ones on the maximum rank.
+    Sink,
+}
+
+impl Rank {
+    fn as_str(self) -> &'static str {
+        match self {
+            Rank::Same => "same",
+            Rank::Min => "min",
+            Rank::Max => "max",
+            Rank::Source => "source",
+            Rank::Sink => "sink",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+/// Options used when rendering a graph to the dot format.
+pub struct DotOptions {
+    /// Direction of the rendered graph. Graphviz lays out
+    /// graphs from top to bottom if this is not set.
+    pub rank_dir: Option<RankDir>,
+
+    /// Whether to place the `VertexId` of each vertex in the `id`
+    /// attribute of its node. Graphs rendered with this option can
+    /// be loaded with `Graph::from_dot()` without losing the ids.
+    pub embed_ids: bool,
+
+    /// Whether to render the weight of each edge as its label. The
+    /// weights of labeled edges are appended to their labels.
+    pub show_weights: bool,
+
+    /// Whether to scale the pen width of each edge with its weight,
+    /// from 1 for the lightest edges to 5 for the heaviest ones.
+    pub weighted_pen: bool,
+
+    /// Label of the whole graph, which Graphviz places below it.
+    pub label: Option<String>,
+
+    /// Shape of the nodes, such as `box` or `circle`. Graphviz
+    /// draws nodes as ellipses if this is not set.
+    pub node_shape: Option<String>,
 
-type Nd = VertexId;
-type Ed<'a> = (&'a VertexId, &'a VertexId);
+    /// Font used for the labels of the graph, nodes and edges.
+    pub font_name: Option<String>,
 
+    /// Size in points of the font used for the labels
+    /// of the graph, nodes and edges.
+    pub font_size: Option<f32>,
 
-pub(crate) struct DotGraph<'a, T> {
-    name: dot::Id<'a>,
-    graph: &'a Graph<T>,
+    /// Style of the arrow heads of the edges, such as `vee`
+    /// or `none`. Graphviz draws filled triangles if this is not set.
+    pub arrow_head: Option<String>,
 }
 
+#[derive(Clone, Debug, Default, PartialEq)]
+/// Style of a single node or edge, as returned by the
+/// callbacks given to `Graph::to_dot_styled()`. Each
+/// attribute which is not set is left to Graphviz.
+pub struct Style {
+    /// Color of the outline of a node or of an edge, such as `red` or `#ff0000`.
+    pub color: Option<String>,
 
-impl<'a, T> DotGraph<'a, T> {
-    pub fn new(graph: &'a Graph<T>, name: &'a str) -> Result<DotGraph<'a, T>, GraphErr> {
-        let name = dot::Id::new(name)
-            .map_err(|_| GraphErr::InvalidGraphName)?;
-        Ok(DotGraph { name, graph })
+    /// Color used to fill a node. Nodes are only filled
+    /// if their style is set to `filled`.
+    pub fill_color: Option<String>,
+
+    /// Shape of a node, such as `box` or `circle`. Ignored for edges.
+    pub shape: Option<String>,
+
+    /// Style of a node or an edge, such as `filled`, `dashed` or `bold`.
+    pub style: Option<String>,
+}
+
+impl Style {
+    /// Renders the attributes which are set as a dot attribute list.
+    fn attr_list(&self) -> String {
+        let attrs = [
+            ("color", &self.color),
+            ("fillcolor", &self.fill_color),
+            ("shape", &self.shape),
+            ("style", &self.style),
+        ];
+
+        attrs
+            .iter()
+            .filter_map(|(key, value)| Some(format!("[{}={}]", key, quote(value.as_ref()?))))
+            .collect()
     }
 }
 
+/// Callback returning the style of the node of a vertex.
+type NodeStyle<'a, T> = &'a dyn Fn(&VertexId, &T) -> Style;
+
+/// Callback returning the style of an edge from its vertices and weight.
+type EdgeStyle<'a> = &'a dyn Fn(&VertexId, &VertexId, f32) -> Style;
+
+pub(crate) struct DotGraph<'a, T, S> {
+    name: &'a str,
+    graph: &'a Graph<T, S>,
+    options: &'a DotOptions,
+    node_style: Option<NodeStyle<'a, T>>,
+    edge_style: Option<EdgeStyle<'a>>,
+}
+
+impl<'a, T, S: BuildHasher> DotGraph<'a, T, S> {
+    pub fn new(
+        graph: &'a Graph<T, S>,
+        name: &'a str,
+        options: &'a DotOptions,
+    ) -> Result<DotGraph<'a, T, S>, GraphErr> {
+        if !is_id(name) {
+            return Err(GraphErr::InvalidGraphName);
+        }
+
+        Ok(DotGraph {
+            name,
+            graph,
+            options,
+            node_style: None,
+            edge_style: None,
+        })
+    }
+
+    #[cfg(not(feature = "no_std"))]
+    /// Renders the nodes and edges with the styles returned by the given callbacks.
+    pub fn with_styles(
+        mut self,
+        node_style: NodeStyle<'a, T>,
+        edge_style: EdgeStyle<'a>,
+    ) -> DotGraph<'a, T, S> {
+        self.node_style = Some(node_style);
+        self.edge_style = Some(edge_style);
+        self
+    }
+
+    #[cfg(not(feature = "no_std"))]
+    /// Renders the graph to the given writer.
+    pub fn render(&self, output: &mut impl Write) -> Result<(), GraphErr> {
+        output
+            .write_all(self.render_string()?.as_bytes())
+            .map_err(|_| GraphErr::CouldNotRender)
+    }
+
+    /// Renders the graph to a new string.
+    pub fn render_string(&self) -> Result<String, GraphErr> {
+        let mut result = String::new();
+        self.render_fmt(&mut result)?;
+        Ok(result)
+    }
+
+    /// Renders the graph to the given formatter.
+    pub fn render_fmt(&self, output: &mut impl fmt::Write) -> Result<(), GraphErr> {
+        self.write_graph(output)
+            .map_err(|_| GraphErr::CouldNotRender)
+    }
+
+    fn write_graph(&self, output: &mut impl fmt::Write) -> fmt::Result {
+        writeln!(output, "digraph {} {{", self.name)?;
+
+        if let Some(rank_dir) = self.options.rank_dir {
+            writeln!(output, "    rankdir={};", rank_dir.as_str())?;
+        }
+
+        output.write_str(&self.default_attrs())?;
+
+        for v in self.graph.vertices() {
+            let label = self.graph.vertex_label(v).unwrap();
+            writeln!(output, "    {}[label=\"{}\"];", node_name(v), escape(label))?;
+        }
+
+        let pen = self.pen_widths();
+
+        for (b, a) in self.graph.edges() {
+            let weight = self.graph.weight(a, b).unwrap();
+            let mut attrs = attr_list(self.graph.edge_attrs(a, b));
+
+            if let Some(edge_style) = self.edge_style {
+                attrs.insert_str(0, &edge_style(a, b, weight).attr_list());
+            }
 
-impl<'a, T> dot::Labeller<'a, Nd, Ed<'a>> for DotGraph<'a, T> {
-    fn graph_id(&'a self) -> dot::Id<'a> {
-        dot::Id::new(self.name.as_slice()).unwrap()
+            if let Some(pen) = &pen {
+                attrs.insert_str(0, &format!("[penwidth={:.2}]", pen(weight)));
+            }
+
+            writeln!(
+                output,
+                "    {} -> {}[label=\"{}\"]{};",
+                node_name(a),
+                node_name(b),
+                escape(&self.edge_label(a, b)),
+                attrs
+            )?;
+        }
+
+        for v in self.graph.vertices() {
+            let mut attrs = attr_list(self.graph.attrs(v));
+
+            if let Some(node_style) = self.node_style {
+                let value = self.graph.fetch(v).unwrap();
+                attrs.insert_str(0, &node_style(v, value).attr_list());
+            }
+
+            if !attrs.is_empty() {
+                writeln!(output, "    {}{};", node_name(v), attrs)?;
+            }
+        }
+
+        if self.options.embed_ids {
+            for v in self.graph.vertices() {
+                writeln!(output, "    {}[id=\"{}\"];", node_name(v), hex_id(v))?;
+            }
+        }
+
+        for (rank, vertices) in self.graph.rank_groups() {
+            write!(output, "    {{ rank={};", rank.as_str())?;
+
+            for v in vertices {
+                write!(output, " {};", node_name(v))?;
+            }
+
+            writeln!(output, " }}")?;
+        }
+
+        for (cluster, mut vertices) in self.graph.clusters() {
+            vertices.sort();
+
+            write!(
+                output,
+                "    subgraph {} {{\n        label={};\n       ",
+                quote(&format!("cluster_{}", cluster)),
+                quote(cluster)
+            )?;
+
+            for v in vertices {
+                write!(output, " {};", node_name(v))?;
+            }
+
+            output.write_str("\n    }\n")?;
+        }
+
+        output.write_str("}\n")
     }
 
-    fn node_id(&'a self, n: &Nd) -> dot::Id<'a> {
-        let hex = format!("N{}", hex::encode(n.bytes()));
-        dot::Id::new(hex).unwrap()
+    /// Returns the label of the given edge, followed by
+    /// its weight if the `show_weights` option is set.
+    fn edge_label(&self, a: &VertexId, b: &VertexId) -> String {
+        let label = self.graph.edge_label(a, b).unwrap();
+
+        if !self.options.show_weights {
+            return label.to_owned();
+        }
+
+        let weight = self.graph.weight(a, b).unwrap();
+
+        match label {
+            DEFAULT_LABEL => weight.to_string(),
+            label => format!("{} ({})", label, weight),
+        }
+    }
+
+    /// Renders the statements setting the attributes of the
+    /// graph and the default attributes of its nodes and edges.
+    fn default_attrs(&self) -> String {
+        let options = self.options;
+        let mut result = String::new();
+
+        if let Some(label) = &options.label {
+            result.push_str(&format!("    label={};\n", quote(label)));
+        }
+
+        let mut fonts = Vec::new();
+
+        if let Some(name) = &options.font_name {
+            fonts.push(format!("fontname={}", quote(name)));
+        }
+
+        if let Some(size) = options.font_size {
+            fonts.push(format!("fontsize={}", size));
+        }
+
+        for font in fonts.iter() {
+            result.push_str(&format!("    {};\n", font));
+        }
+
+        let mut node_attrs = fonts.clone();
+        let mut edge_attrs = fonts;
+
+        if let Some(shape) = &options.node_shape {
+            node_attrs.push(format!("shape={}", quote(shape)));
+        }
+
+        if let Some(arrow_head) = &options.arrow_head {
+            edge_attrs.push(format!("arrowhead={}", quote(arrow_head)));
+        }
+
+        if !node_attrs.is_empty() {
+            result.push_str(&format!("    node [{}];\n", node_attrs.join(", ")));
+        }
+
+        if !edge_attrs.is_empty() {
+            result.push_str(&format!("    edge [{}];\n", edge_attrs.join(", ")));
+        }
+
+        result
     }
 
-    fn node_label<'b>(&'b self, n: &Nd) -> dot::LabelText<'b> {
-        let label = self.graph.vertex_label(n).unwrap();
-        dot::LabelText::label(Cow::Borrowed(label))
+    /// Returns the function mapping weights to pen widths
+    /// if the `weighted_pen` option is set.
+    fn pen_widths(&self) -> Option<impl Fn(f32) -> f32> {
+        if !self.options.weighted_pen {
+            return None;
+        }
+
+        let (min, max) = self
+            .graph
+            .edges_with_weights()
+            .fold((f32::MAX, f32::MIN), |(min, max), (_, _, w)| {
+                (min.min(w), max.max(w))
+            });
+
+        Some(move |w: f32| {
+            if max > min {
+                1.0 + 4.0 * (w - min) / (max - min)
+            } else {
+                1.0
+            }
+        })
     }
+}
+
+/// Returns the name of the node representing
+/// the vertex with the given id.
+fn node_name(id: &VertexId) -> String {
+    format!("N{}", hex_id(id))
+}
 
-    fn edge_label<'b>(&'b self, e: &Ed) -> dot::LabelText<'b> {
-        let label = self.graph.edge_label(e.0, e.1).unwrap();
-        dot::LabelText::LabelStr(Cow::Borrowed(label))
+/// Renders the given attributes as dot attribute
+/// lists, sorted by their keys.
+fn attr_list<'b>(attrs: impl Iterator<Item = (&'b str, &'b AttrValue)>) -> String {
+    let mut attrs: Vec<(&str, &AttrValue)> = attrs.collect();
+    attrs.sort_by_key(|(key, _)| *key);
+
+    attrs
+        .into_iter()
+        .map(|(key, value)| {
+            let value = match value {
+                AttrValue::Bool(value) => value.to_string(),
+                AttrValue::Int(value) => value.to_string(),
+                AttrValue::Float(value) => value.to_string(),
+                AttrValue::Text(value) => value.clone(),
+            };
+
+            format!("[{}={}]", quote(key), quote(&value))
+        })
+        .collect()
+}
+
+/// Returns true if the given name is a valid unquoted dot id,
+/// which starts with a letter or an underscore and only contains
+/// letters, digits and underscores.
+fn is_id(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return false,
     }
+
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
 }
 
+/// Escapes the given label so it can be placed between double quotes.
+fn escape(label: &str) -> String {
+    label.chars().flat_map(char::escape_default).collect()
+}
 
-impl<'a, T> dot::GraphWalk<'a, Nd, Ed<'a>> for DotGraph<'a, T> {
-    fn nodes(&self) -> dot::Nodes<'a, Nd> {
-        let nodes = self.graph.vertices().cloned().collect();
-        Cow::Owned(nodes)
+/// Returns the given string as a quoted dot id.
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Returns the hex encoding of the given id.
+fn hex_id(id: &VertexId) -> String {
+    let mut buff: [u8; 32] = [0; 32];
+    let _ = hex::encode_to_slice(id.bytes(), &mut buff);
+    String::from(core::str::from_utf8(&buff).unwrap())
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Id(String),
+    OpenBrace,
+    CloseBrace,
+    OpenBracket,
+    CloseBracket,
+    Equals,
+    Separator,
+    Arrow,
+}
+
+/// Splits the given dot source into tokens, skipping comments.
+fn tokenize(input: &str) -> Result<Vec<Token>, GraphErr> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '{' => tokens.push(Token::OpenBrace),
+            '}' => tokens.push(Token::CloseBrace),
+            '[' => tokens.push(Token::OpenBracket),
+            ']' => tokens.push(Token::CloseBracket),
+            '=' => tokens.push(Token::Equals),
+            ';' | ',' => tokens.push(Token::Separator),
+            '#' => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut last = ' ';
+
+                loop {
+                    match chars.next() {
+                        Some('/') if last == '*' => break,
+                        Some(c) => last = c,
+                        None => return Err(GraphErr::CouldNotParse),
+                    }
+                }
+            }
+            '-' if chars.peek() == Some(&'>') => {
+                chars.next();
+                tokens.push(Token::Arrow);
+            }
+            '"' => tokens.push(Token::Id(unescape(&mut chars)?)),
+            c if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' => {
+                let mut id = c.to_string();
+
+                while let Some(c) = chars.peek() {
+                    if c.is_alphanumeric() || *c == '_' || *c == '.' {
+                        id.push(*c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+
+                tokens.push(Token::Id(id));
+            }
+            _ => return Err(GraphErr::CouldNotParse),
+        }
     }
 
-    fn edges(&'a self) -> dot::Edges<'a, Ed<'a>> {
-        self.graph.edges()
-            .map(|e| (e.1, e.0))
-            .collect()
+    Ok(tokens)
+}
+
+/// Reads a quoted string up to its closing quote, reversing
+/// the escaping that is applied to rendered labels.
+fn unescape(chars: &mut impl Iterator<Item = char>) -> Result<String, GraphErr> {
+    let mut result = String::new();
+
+    loop {
+        match chars.next() {
+            Some('"') => return Ok(result),
+            Some('\\') => match chars.next() {
+                Some('n') => result.push('\n'),
+                Some('r') => result.push('\r'),
+                Some('t') => result.push('\t'),
+                Some('u') => {
+                    if chars.next() != Some('{') {
+                        return Err(GraphErr::CouldNotParse);
+                    }
+
+                    let code: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                    let c = u32::from_str_radix(&code, 16)
+                        .ok()
+                        .and_then(core::char::from_u32)
+                        .ok_or(GraphErr::CouldNotParse)?;
+
+                    result.push(c);
+                }
+                Some(c) => result.push(c),
+                None => return Err(GraphErr::CouldNotParse),
+            },
+            Some(c) => result.push(c),
+            None => return Err(GraphErr::CouldNotParse),
+        }
+    }
+}
+
+/// Parses the attribute lists following a statement, if any.
+fn parse_attributes(tokens: &[Token], pos: &mut usize) -> Result<Vec<(String, String)>, GraphErr> {
+    let mut attributes = Vec::new();
+
+    while tokens.get(*pos) == Some(&Token::OpenBracket) {
+        *pos += 1;
+
+        loop {
+            match (tokens.get(*pos), tokens.get(*pos + 1), tokens.get(*pos + 2)) {
+                (Some(Token::CloseBracket), _, _) => {
+                    *pos += 1;
+                    break;
+                }
+                (Some(Token::Separator), _, _) => *pos += 1,
+                (Some(Token::Id(key)), Some(Token::Equals), Some(Token::Id(value))) => {
+                    attributes.push((key.clone(), value.clone()));
+                    *pos += 3;
+                }
+                _ => return Err(GraphErr::CouldNotParse),
+            }
+        }
+    }
+
+    Ok(attributes)
+}
+
+/// Skips the block starting at the given position,
+/// including all of its nested blocks.
+fn skip_block(tokens: &[Token], pos: &mut usize) -> Result<(), GraphErr> {
+    let mut depth = 0;
+
+    loop {
+        match tokens.get(*pos) {
+            Some(Token::OpenBrace) => depth += 1,
+            Some(Token::CloseBrace) => depth -= 1,
+            Some(_) => {}
+            None => return Err(GraphErr::CouldNotParse),
+        }
+
+        *pos += 1;
+
+        if depth == 0 {
+            return Ok(());
+        }
+    }
+}
+
+/// Parses a directed graph in the dot format. Vertex values
+/// are taken from node labels, falling back to node names.
+pub(crate) fn parse(input: &str) -> Result<Graph<String>, GraphErr> {
+    let tokens = tokenize(input)?;
+    let mut pos = 0;
+
+    if tokens.get(pos) == Some(&Token::Id("strict".to_owned())) {
+        pos += 1;
+    }
+
+    if tokens.get(pos) != Some(&Token::Id("digraph".to_owned())) {
+        return Err(GraphErr::CouldNotParse);
+    }
+
+    pos += 1;
+
+    if let Some(Token::Id(_)) = tokens.get(pos) {
+        pos += 1;
+    }
+
+    if tokens.get(pos) != Some(&Token::OpenBrace) {
+        return Err(GraphErr::CouldNotParse);
+    }
+
+    pos += 1;
+
+    // Nodes in order of appearance along with their attributes
+    let mut nodes: Vec<(String, HashMap<String, String>)> = Vec::new();
+    let mut node_indexes: HashMap<String, usize> = HashMap::new();
+    let mut edges: Vec<(String, String, HashMap<String, String>)> = Vec::new();
+
+    let mut declare = |name: &str, attributes: Vec<(String, String)>| {
+        let idx = *node_indexes.entry(name.to_owned()).or_insert_with(|| {
+            nodes.push((name.to_owned(), HashMap::new()));
+            nodes.len() - 1
+        });
+
+        nodes[idx].1.extend(attributes);
+    };
+
+    loop {
+        match tokens.get(pos) {
+            Some(Token::CloseBrace) => {
+                pos += 1;
+                break;
+            }
+            Some(Token::Separator) => pos += 1,
+            // Subgraphs only carry layout hints
+            Some(Token::OpenBrace) => skip_block(&tokens, &mut pos)?,
+            Some(Token::Id(id)) if id == "subgraph" => {
+                pos += 1;
+
+                if let Some(Token::Id(_)) = tokens.get(pos) {
+                    pos += 1;
+                }
+
+                skip_block(&tokens, &mut pos)?;
+            }
+            Some(Token::Id(id)) => {
+                pos += 1;
+
+                match tokens.get(pos) {
+                    // Graph attribute
+                    Some(Token::Equals) => match tokens.get(pos + 1) {
+                        Some(Token::Id(_)) => pos += 2,
+                        _ => return Err(GraphErr::CouldNotParse),
+                    },
+                    // Default attributes
+                    Some(Token::OpenBracket) if id == "graph" || id == "node" || id == "edge" => {
+                        parse_attributes(&tokens, &mut pos)?;
+                    }
+                    Some(Token::Arrow) => {
+                        let mut chain = vec![id.clone()];
+
+                        while tokens.get(pos) == Some(&Token::Arrow) {
+                            match tokens.get(pos + 1) {
+                                Some(Token::Id(id)) => chain.push(id.clone()),
+                                _ => return Err(GraphErr::CouldNotParse),
+                            }
+
+                            pos += 2;
+                        }
+
+                        let attributes: HashMap<String, String> =
+                            parse_attributes(&tokens, &mut pos)?.into_iter().collect();
+
+                        for id in chain.iter() {
+                            declare(id, Vec::new());
+                        }
+
+                        for pair in chain.windows(2) {
+                            edges.push((pair[0].clone(), pair[1].clone(), attributes.clone()));
+                        }
+                    }
+                    _ => {
+                        let attributes = parse_attributes(&tokens, &mut pos)?;
+                        declare(id, attributes);
+                    }
+                }
+            }
+            _ => return Err(GraphErr::CouldNotParse),
+        }
     }
 
-    fn source(&self, e: &Ed) -> Nd {
-        *e.0
+    if pos != tokens.len() {
+        return Err(GraphErr::CouldNotParse);
     }
 
-    fn target(&self, e: &Ed) -> Nd {
-        *e.1
+    let mut graph = Graph::with_capacity(nodes.len());
+    let mut ids: HashMap<String, VertexId> = HashMap::with_capacity(nodes.len());
+
+    for (name, attributes) in nodes {
+        let id = match attributes.get("id") {
+            Some(hex_id) => {
+                let mut bytes = [0; 16];
+                hex::decode_to_slice(hex_id, &mut bytes).map_err(|_| GraphErr::CouldNotParse)?;
+                VertexId::from_bytes(bytes)
+            }
+            None => VertexId::random(),
+        };
+
+        let value = attributes
+            .get("label")
+            .cloned()
+            .unwrap_or_else(|| name.clone());
+
+        if graph.fetch(&id).is_some() {
+            return Err(GraphErr::CouldNotParse);
+        }
+
+        graph.insert_vertex(id, value);
+
+        match attributes.get("label") {
+            Some(label) if label != DEFAULT_LABEL => {
+                graph.add_vertex_label(&id, label)?;
+            }
+            _ => {}
+        }
+
+        ids.insert(name, id);
     }
+
+    for (a, b, attributes) in edges {
+        let (a, b) = (ids[&a], ids[&b]);
+        graph.add_edge(&a, &b)?;
+
+        match attributes.get("label") {
+            Some(label) if label != DEFAULT_LABEL => {
+                graph.label_edge(&a, &b, label)?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(graph)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(graph: &Graph<String>, options: &DotOptions) -> String {
+        let mut output = String::new();
+        graph.to_dot_fmt("test", &mut output, options).unwrap();
+        output
+    }
+
+    #[test]
+    fn round_trip_with_embedded_ids() {
+        let mut graph: Graph<String> = Graph::new();
+
+        let v1 = graph.add_vertex("a".to_owned());
+        let v2 = graph.add_vertex("b".to_owned());
+        let v3 = graph.add_vertex("c".to_owned());
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v2, &v3).unwrap();
+        graph.add_edge(&v1, &v3).unwrap();
+
+        graph.add_vertex_label(&v1, "first \"vertex\"").unwrap();
+        graph.add_vertex_label(&v2, "second\nvertex").unwrap();
+        graph.label_edge(&v1, &v2, "V1->V2").unwrap();
+
+        let options = DotOptions {
+            embed_ids: true,
+            ..DotOptions::default()
+        };
+
+        let rendered = render(&graph, &options);
+        let parsed = Graph::from_dot(&rendered).unwrap();
+
+        assert_eq!(parsed.vertex_count(), 3);
+        assert_eq!(parsed.edge_count(), 3);
+        assert!(parsed.has_edge(&v1, &v2));
+        assert!(parsed.has_edge(&v2, &v3));
+        assert!(parsed.has_edge(&v1, &v3));
+        assert_eq!(parsed.vertex_label(&v1), Some("first \"vertex\""));
+        assert_eq!(parsed.vertex_label(&v2), Some("second\nvertex"));
+        assert_eq!(parsed.vertex_label(&v3), Some(DEFAULT_LABEL));
+        assert_eq!(parsed.edge_label(&v1, &v2), Some("V1->V2"));
+        assert_eq!(parsed.edge_label(&v2, &v3), Some(DEFAULT_LABEL));
+
+        // Rendering the parsed graph again yields the same statements
+        let mut expected: Vec<&str> = rendered.lines().collect();
+        let rendered = render(&parsed, &options);
+        let mut actual: Vec<&str> = rendered.lines().collect();
+
+        expected.sort();
+        actual.sort();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn parse_without_ids() {
+        let input = "
+            // Comments are skipped
+            digraph example {
+                rankdir=LR;
+                node [shape=box];
+                a -> b -> c [label=\"chain\"];
+                d [label=\"D\"];
+                { rank=same; b; d; }
+            }
+        ";
+
+        let graph = Graph::from_dot(input).unwrap();
+
+        assert_eq!(graph.vertex_count(), 4);
+        assert_eq!(graph.edge_count(), 2);
+
+        let mut values: Vec<&String> = graph.values().collect();
+        values.sort();
+
+        assert_eq!(values, vec!["D", "a", "b", "c"]);
+    }
+
+    #[test]
+    fn parse_invalid_input() {
+        assert_eq!(
+            Graph::from_dot("graph { a -- b }").err(),
+            Some(GraphErr::CouldNotParse)
+        );
+        assert_eq!(
+            Graph::from_dot("digraph { a -> }").err(),
+            Some(GraphErr::CouldNotParse)
+        );
+        assert_eq!(
+            Graph::from_dot("digraph { a [label=]; }").err(),
+            Some(GraphErr::CouldNotParse)
+        );
+        assert_eq!(
+            Graph::from_dot("digraph { a [id=\"xyz\"]; }").err(),
+            Some(GraphErr::CouldNotParse)
+        );
+        assert_eq!(
+            Graph::from_dot("digraph { a").err(),
+            Some(GraphErr::CouldNotParse)
+        );
+    }
+
+    #[test]
+    fn render_attributes() {
+        let mut graph: Graph<String> = Graph::new();
+
+        let v1 = graph.add_vertex("a".to_owned());
+        let v2 = graph.add_vertex("b".to_owned());
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v2, &v1).unwrap();
+        graph.label_edge(&v1, &v2, "a -> b").unwrap();
+        graph.set_edge_attr(&v1, &v2, "kind", "say \"hi\"").unwrap();
+        graph.set_edge_attr(&v1, &v2, "count", 2).unwrap();
+        graph.set_attr(&v2, "x", 0.5).unwrap();
+
+        let rendered = render(&graph, &DotOptions::default());
+        let edge = format!("{} -> {}", node_name(&v1), node_name(&v2));
+        let line = rendered.lines().find(|l| l.contains(&edge)).unwrap();
+
+        assert!(line.ends_with("[\"count\"=\"2\"][\"kind\"=\"say \\\"hi\\\"\"];"));
+        assert!(rendered.contains(&format!("    {}[\"x\"=\"0.5\"];", node_name(&v2))));
+        assert_eq!(rendered.matches("[\"kind\"").count(), 1);
+        assert_eq!(Graph::from_dot(&rendered).unwrap().edge_count(), 2);
+    }
+
+    #[test]
+    fn render_edge_labels() {
+        let mut graph: Graph<String> = Graph::new();
+
+        let v1 = graph.add_vertex("a".to_owned());
+        let v2 = graph.add_vertex("b".to_owned());
+        let v3 = graph.add_vertex("c".to_owned());
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v2, &v3).unwrap();
+        graph.add_edge_label(&v1, &v2, "V1->V2").unwrap();
+
+        let rendered = render(&graph, &DotOptions::default());
+        let line = |a, b| {
+            let edge = format!("{} -> {}", node_name(a), node_name(b));
+            rendered.lines().find(|l| l.contains(&edge)).unwrap()
+        };
+
+        assert!(line(&v1, &v2).contains("[label=\"V1->V2\"]"));
+        assert!(line(&v2, &v3).contains("[label=\"\"]"));
+    }
+
+    #[test]
+    fn render_weights() {
+        let mut graph: Graph<String> = Graph::new();
+
+        let v1 = graph.add_vertex("a".to_owned());
+        let v2 = graph.add_vertex("b".to_owned());
+        let v3 = graph.add_vertex("c".to_owned());
+
+        graph.add_edge_with_weight(&v1, &v2, 0.5).unwrap();
+        graph.add_edge_with_weight(&v2, &v3, -0.5).unwrap();
+        graph.add_edge_label(&v1, &v2, "V1->V2").unwrap();
+
+        let options = DotOptions {
+            show_weights: true,
+            weighted_pen: true,
+            ..DotOptions::default()
+        };
+
+        let rendered = render(&graph, &options);
+        let line = |a, b| {
+            let edge = format!("{} -> {}", node_name(a), node_name(b));
+            rendered.lines().find(|l| l.contains(&edge)).unwrap()
+        };
+
+        assert!(line(&v1, &v2).ends_with("[label=\"V1->V2 (0.5)\"][penwidth=5.00];"));
+        assert!(line(&v2, &v3).ends_with("[label=\"-0.5\"][penwidth=1.00];"));
+
+        // Weights are not rendered by default
+        assert!(!render(&graph, &DotOptions::default()).contains("0.5"));
+    }
+
+    #[test]
+    fn render_default_attributes() {
+        let mut graph: Graph<String> = Graph::new();
+
+        let v1 = graph.add_vertex("a".to_owned());
+        let v2 = graph.add_vertex("b".to_owned());
+
+        graph.add_edge(&v1, &v2).unwrap();
+
+        let options = DotOptions {
+            label: Some("Report \"1\"".to_owned()),
+            node_shape: Some("box".to_owned()),
+            font_name: Some("Helvetica".to_owned()),
+            font_size: Some(10.5),
+            arrow_head: Some("vee".to_owned()),
+            ..DotOptions::default()
+        };
+
+        let rendered = render(&graph, &options);
+
+        assert!(rendered.contains("    label=\"Report \\\"1\\\"\";\n"));
+        assert!(rendered.contains("    fontname=\"Helvetica\";\n    fontsize=10.5;\n"));
+        assert!(
+            rendered.contains("    node [fontname=\"Helvetica\", fontsize=10.5, shape=\"box\"];\n")
+        );
+        assert!(rendered
+            .contains("    edge [fontname=\"Helvetica\", fontsize=10.5, arrowhead=\"vee\"];\n"));
+        assert_eq!(Graph::from_dot(&rendered).unwrap().edge_count(), 1);
+
+        // Nothing is added without options
+        let rendered = render(&graph, &DotOptions::default());
+
+        assert!(!rendered.contains("node ["));
+        assert!(!rendered.contains("edge ["));
+    }
+
+    #[test]
+    fn render_clusters() {
+        let mut graph: Graph<String> = Graph::new();
+
+        let v1 = graph.add_vertex("a".to_owned());
+        let v2 = graph.add_vertex("b".to_owned());
+        let v3 = graph.add_vertex("c".to_owned());
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v2, &v3).unwrap();
+        graph.assign_cluster(&v1, "stage 1").unwrap();
+        graph.assign_cluster(&v2, "stage 1").unwrap();
+        graph.assign_cluster(&v3, "stage 2").unwrap();
+
+        graph.remove(&v3);
+
+        let rendered = render(&graph, &DotOptions::default());
+        let mut members = [node_name(&v1), node_name(&v2)];
+        members.sort();
+
+        assert!(rendered.contains(&format!(
+            "    subgraph \"cluster_stage 1\" {{\n        label=\"stage 1\";\n        {}; {};\n    }}\n",
+            members[0], members[1]
+        )));
+        assert!(!rendered.contains("stage 2"));
+        assert_eq!(Graph::from_dot(&rendered).unwrap().edge_count(), 1);
+    }
+}
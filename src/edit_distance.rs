@@ -0,0 +1,236 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::Graph;
+use crate::vertex_id::VertexId;
+
+use hashbrown::{HashMap, HashSet};
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+/// Cost of an assignment which is not allowed.
+const FORBIDDEN: f64 = 1e9;
+
+/// Solves the assignment problem for the given square cost matrix
+/// using the Hungarian algorithm. Returns the column assigned to
+/// each row of the matrix.
+fn assign(costs: &[Vec<f64>]) -> Vec<usize> {
+    let n = costs.len();
+    let mut u = vec![0.0; n + 1];
+    let mut v = vec![0.0; n + 1];
+    let mut rows = vec![0; n + 1];
+    let mut way = vec![0; n + 1];
+
+    for i in 1..=n {
+        let mut min_v = vec![f64::MAX; n + 1];
+        let mut used = vec![false; n + 1];
+        let mut j0 = 0;
+
+        rows[0] = i;
+
+        loop {
+            used[j0] = true;
+
+            let i0 = rows[j0];
+            let mut delta = f64::MAX;
+            let mut j1 = 0;
+
+            for j in 1..=n {
+                if !used[j] {
+                    let cur = costs[i0 - 1][j - 1] - u[i0] - v[j];
+
+                    if cur < min_v[j] {
+                        min_v[j] = cur;
+                        way[j] = j0;
+                    }
+
+                    if min_v[j] < delta {
+                        delta = min_v[j];
+                        j1 = j;
+                    }
+                }
+            }
+
+            for j in 0..=n {
+                if used[j] {
+                    u[rows[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min_v[j] -= delta;
+                }
+            }
+
+            j0 = j1;
+
+            if rows[j0] == 0 {
+                break;
+            }
+        }
+
+        loop {
+            let j1 = way[j0];
+            rows[j0] = rows[j1];
+            j0 = j1;
+
+            if j0 == 0 {
+                break;
+            }
+        }
+    }
+
+    let mut result = vec![0; n];
+
+    for j in 1..=n {
+        result[rows[j] - 1] = j - 1;
+    }
+
+    result
+}
+
+/// Approximates the edit distance between the given graphs. Vertices
+/// are matched by solving an assignment problem over their substitution
+/// costs and degrees, then the cost of the edit path which follows from
+/// that matching is returned.
+///
+/// Inserting or deleting a vertex or an edge costs `1.0`, while
+/// substitutions cost as much as the given comparators return.
+pub(crate) fn edit_distance<T, U, S: BuildHasher>(
+    a: &Graph<T, S>,
+    b: &Graph<U, S>,
+    value_cost: impl Fn(&T, &U) -> f32,
+    weight_cost: impl Fn(f32, f32) -> f32,
+) -> f32 {
+    let a_vertices: Vec<&VertexId> = a.vertices().collect();
+    let b_vertices: Vec<&VertexId> = b.vertices().collect();
+    let (n, m) = (a_vertices.len(), b_vertices.len());
+
+    let mut costs = vec![vec![0.0; n + m]; n + m];
+
+    for (i, x) in a_vertices.iter().enumerate() {
+        let x_value = a.fetch(x).unwrap();
+        let x_in = a.in_neighbors_count(x) as f64;
+        let x_out = a.out_neighbors_count(x) as f64;
+
+        for (j, y) in b_vertices.iter().enumerate() {
+            let y_in = b.in_neighbors_count(y) as f64;
+            let y_out = b.out_neighbors_count(y) as f64;
+
+            // Edges which cannot be matched
+            // have to be inserted or deleted.
+            costs[i][j] = f64::from(value_cost(x_value, b.fetch(y).unwrap()))
+                + (x_in - y_in).abs()
+                + (x_out - y_out).abs();
+        }
+
+        for j in 0..n {
+            costs[i][m + j] = if i == j {
+                1.0 + x_in + x_out
+            } else {
+                FORBIDDEN
+            };
+        }
+    }
+
+    for (i, y) in b_vertices.iter().enumerate() {
+        let y_degree = b.neighbors_count(y) as f64;
+
+        for (j, cost) in costs[n + i].iter_mut().enumerate().take(m) {
+            *cost = if i == j { 1.0 + y_degree } else { FORBIDDEN };
+        }
+    }
+
+    let mut mapping: HashMap<VertexId, VertexId> = HashMap::with_capacity(n);
+
+    for (i, j) in assign(&costs).into_iter().enumerate().take(n) {
+        if j < m {
+            mapping.insert(*a_vertices[i], *b_vertices[j]);
+        }
+    }
+
+    // Vertex substitutions, deletions and insertions
+    let mut cost = ((n - mapping.len()) + (m - mapping.len())) as f32;
+
+    for (x, y) in mapping.iter() {
+        cost += value_cost(a.fetch(x).unwrap(), b.fetch(y).unwrap());
+    }
+
+    // Edge substitutions and deletions
+    let mut substituted: HashSet<(VertexId, VertexId)> = HashSet::new();
+
+    for (x2, x1) in a.edges() {
+        match (mapping.get(x1), mapping.get(x2)) {
+            (Some(y1), Some(y2)) if b.has_edge(y1, y2) => {
+                cost += weight_cost(a.weight(x1, x2).unwrap(), b.weight(y1, y2).unwrap());
+                substituted.insert((*y1, *y2));
+            }
+            _ => cost += 1.0,
+        }
+    }
+
+    // Edge insertions
+    cost += (b.edge_count() - substituted.len()) as f32;
+
+    cost
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assign() {
+        let costs = vec![
+            vec![4.0, 1.0, 3.0],
+            vec![2.0, 0.0, 5.0],
+            vec![3.0, 2.0, 2.0],
+        ];
+
+        assert_eq!(assign(&costs), vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        let mut a: Graph<usize> = Graph::new();
+
+        let v1 = a.add_vertex(1);
+        let v2 = a.add_vertex(2);
+        let v3 = a.add_vertex(3);
+
+        a.add_edge_with_weight(&v1, &v2, 0.5).unwrap();
+        a.add_edge(&v2, &v3).unwrap();
+
+        let value_cost = |x: &usize, y: &usize| if x == y { 0.0 } else { 1.0 };
+        let weight_cost = |x: f32, y: f32| (x - y).abs();
+
+        // Equal graphs
+        assert_eq!(edit_distance(&a, &a.clone(), value_cost, weight_cost), 0.0);
+
+        // Changing a weight and a value
+        let mut b = a.clone();
+
+        b.set_weight(&v1, &v2, 0.25).unwrap();
+        *b.fetch_mut(&v3).unwrap() = 4;
+
+        assert_eq!(edit_distance(&a, &b, value_cost, weight_cost), 1.25);
+
+        // Removing a vertex along with its edge
+        let mut c = a.clone();
+        c.remove(&v3);
+
+        assert_eq!(edit_distance(&a, &c, value_cost, weight_cost), 2.0);
+        assert_eq!(edit_distance(&c, &a, value_cost, weight_cost), 2.0);
+
+        // Empty graphs
+        let empty: Graph<usize> = Graph::new();
+
+        assert_eq!(edit_distance(&a, &empty, value_cost, weight_cost), 5.0);
+        assert_eq!(edit_distance(&empty, &empty, value_cost, weight_cost), 0.0);
+    }
+}
@@ -0,0 +1,84 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::{DefaultHashBuilder, Graph};
+use crate::vertex_id::VertexId;
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+/// Entry of a vertex with a given id in a graph, which may
+/// be vacant, as returned by `Graph::entry()`.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::{Graph, VertexId};
+///
+/// let mut graph: Graph<usize> = Graph::new();
+/// let id = VertexId::random();
+///
+/// // Count the visits of the vertex, adding it on the first one
+/// for _ in 0..3 {
+///     *graph.entry(id).or_insert(0) += 1;
+/// }
+///
+/// assert_eq!(graph.fetch(&id), Some(&3));
+/// ```
+#[derive(Debug)]
+pub struct VertexEntry<'a, T, S = DefaultHashBuilder> {
+    graph: &'a mut Graph<T, S>,
+    id: VertexId,
+}
+
+impl<'a, T, S: BuildHasher> VertexEntry<'a, T, S> {
+    pub(crate) fn new(graph: &'a mut Graph<T, S>, id: VertexId) -> VertexEntry<'a, T, S> {
+        VertexEntry { graph, id }
+    }
+
+    /// Returns the id of the vertex of the entry.
+    pub fn id(&self) -> &VertexId {
+        &self.id
+    }
+
+    /// Returns true if there is a vertex in the entry.
+    pub fn is_occupied(&self) -> bool {
+        self.graph.fetch(&self.id).is_some()
+    }
+
+    /// Returns a mutable reference to the value of the vertex,
+    /// adding it with the given value if the entry is vacant.
+    pub fn or_insert(self, item: T) -> &'a mut T {
+        self.or_insert_with(|| item)
+    }
+
+    /// Returns a mutable reference to the value of the vertex, adding
+    /// it with the value returned by the given function if the entry
+    /// is vacant.
+    pub fn or_insert_with(self, fun: impl FnOnce() -> T) -> &'a mut T {
+        if !self.is_occupied() {
+            self.graph.add_vertex_with_id(self.id, fun()).unwrap();
+        }
+
+        self.graph.fetch_mut(&self.id).unwrap()
+    }
+
+    /// Returns a mutable reference to the value of the vertex,
+    /// adding it with the default value if the entry is vacant.
+    pub fn or_default(self) -> &'a mut T
+    where
+        T: Default,
+    {
+        self.or_insert_with(T::default)
+    }
+
+    /// Calls the given function with the value of the
+    /// vertex if the entry is occupied.
+    pub fn and_modify(self, fun: impl FnOnce(&mut T)) -> VertexEntry<'a, T, S> {
+        if let Some(value) = self.graph.fetch_mut(&self.id) {
+            fun(value);
+        }
+
+        self
+    }
+}
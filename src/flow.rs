@@ -0,0 +1,241 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::{Graph, GraphErr};
+use crate::vertex_id::VertexId;
+
+use hashbrown::HashMap;
+
+#[cfg(not(feature = "no_std"))]
+use std::collections::VecDeque;
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::collections::vec_deque::VecDeque;
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+#[derive(Clone, Debug, PartialEq)]
+/// Result of a maximum flow computation.
+pub struct MaxFlow {
+    /// Total amount of flow going from the source to the sink
+    value: f32,
+
+    /// Mapping between edges and the flow passing through them
+    flows: HashMap<(VertexId, VertexId), f32>,
+}
+
+impl MaxFlow {
+    /// Returns the total amount of flow going from
+    /// the source to the sink.
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Returns the flow passing through the edge between
+    /// the given vertices or `None` if there is no such edge.
+    pub fn flow(&self, a: &VertexId, b: &VertexId) -> Option<f32> {
+        self.flows.get(&(*a, *b)).cloned()
+    }
+
+    /// Returns an iterator over all edges of the graph
+    /// and the flow passing through each of them.
+    pub fn flows(&self) -> impl Iterator<Item = (&VertexId, &VertexId, f32)> {
+        self.flows.iter().map(|((a, b), f)| (a, b, *f))
+    }
+}
+
+/// Computes the maximum flow between `source` and `sink` using the
+/// Edmonds-Karp algorithm, treating edge weights as capacities.
+pub(crate) fn edmonds_karp<T, S: BuildHasher>(
+    graph: &Graph<T, S>,
+    source: &VertexId,
+    sink: &VertexId,
+) -> Result<MaxFlow, GraphErr> {
+    if graph.fetch(source).is_none() || graph.fetch(sink).is_none() {
+        return Err(GraphErr::NoSuchVertex);
+    }
+
+    let mut capacities: HashMap<(VertexId, VertexId), f32> =
+        HashMap::with_capacity(graph.edge_count());
+
+    for (a, b, capacity) in graph.edges_with_weights() {
+        if capacity < 0.0 {
+            return Err(GraphErr::InvalidWeight);
+        }
+
+        capacities.insert((*a, *b), capacity);
+    }
+
+    // Residual capacities, including the ones of the reverse edges.
+    let mut residual = capacities.clone();
+
+    for (a, b) in capacities.keys() {
+        residual.entry((*b, *a)).or_insert(0.0);
+    }
+
+    let mut value = 0.0;
+
+    if source != sink {
+        while let Some(path) = augmenting_path(graph, &residual, source, sink) {
+            let bottleneck = path
+                .iter()
+                .map(|edge| residual[edge])
+                .fold(f32::MAX, f32::min);
+
+            for (a, b) in path {
+                *residual.get_mut(&(a, b)).unwrap() -= bottleneck;
+                *residual.get_mut(&(b, a)).unwrap() += bottleneck;
+            }
+
+            value += bottleneck;
+        }
+    }
+
+    let flows = capacities
+        .iter()
+        .map(|(edge, capacity)| (*edge, (capacity - residual[edge]).max(0.0)))
+        .collect();
+
+    Ok(MaxFlow { value, flows })
+}
+
+/// Finds the shortest path from `source` to `sink` in the
+/// residual graph and returns its edges in order.
+fn augmenting_path<T, S: BuildHasher>(
+    graph: &Graph<T, S>,
+    residual: &HashMap<(VertexId, VertexId), f32>,
+    source: &VertexId,
+    sink: &VertexId,
+) -> Option<Vec<(VertexId, VertexId)>> {
+    let mut previous: HashMap<VertexId, VertexId> = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    queue.push_back(*source);
+
+    while let Some(current) = queue.pop_front() {
+        if current == *sink {
+            break;
+        }
+
+        // Residual edges may go both ways of an edge in the graph.
+        let neighbors = graph
+            .out_neighbors(&current)
+            .chain(graph.in_neighbors(&current));
+
+        for n in neighbors {
+            if n == source || previous.contains_key(n) {
+                continue;
+            }
+
+            match residual.get(&(current, *n)) {
+                Some(capacity) if *capacity > 0.0 => {
+                    previous.insert(*n, current);
+                    queue.push_back(*n);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if !previous.contains_key(sink) {
+        return None;
+    }
+
+    let mut path = Vec::new();
+    let mut current = *sink;
+
+    while current != *source {
+        let prev = previous[&current];
+        path.push((prev, current));
+        current = prev;
+    }
+
+    path.reverse();
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_flow_with_invalid_vertex() {
+        let random_vertex = VertexId::random();
+
+        let mut graph: Graph<usize> = Graph::new();
+        let v1 = graph.add_vertex(1);
+
+        assert_eq!(
+            graph.max_flow(&v1, &random_vertex),
+            Err(GraphErr::NoSuchVertex)
+        );
+        assert_eq!(
+            graph.max_flow(&random_vertex, &v1),
+            Err(GraphErr::NoSuchVertex)
+        );
+    }
+
+    #[test]
+    fn test_max_flow_with_negative_weight_edge() {
+        let mut graph: Graph<usize> = Graph::new();
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+
+        graph.add_edge_with_weight(&v1, &v2, -0.1).unwrap();
+
+        assert_eq!(graph.max_flow(&v1, &v2), Err(GraphErr::InvalidWeight));
+    }
+
+    #[test]
+    fn test_max_flow_with_reverse_edge() {
+        // s -> a -> b -> t and s -> b -> a -> t where the
+        // optimal solution must cancel flow along a -> b.
+        let mut graph: Graph<usize> = Graph::new();
+
+        let s = graph.add_vertex(0);
+        let a = graph.add_vertex(1);
+        let b = graph.add_vertex(2);
+        let t = graph.add_vertex(3);
+
+        graph.add_edge_with_weight(&s, &a, 0.5).unwrap();
+        graph.add_edge_with_weight(&s, &b, 0.5).unwrap();
+        graph.add_edge_with_weight(&a, &b, 0.5).unwrap();
+        graph.add_edge_with_weight(&a, &t, 0.25).unwrap();
+        graph.add_edge_with_weight(&b, &t, 0.75).unwrap();
+
+        let flow = graph.max_flow(&s, &t).unwrap();
+
+        assert_eq!(flow.value(), 1.0);
+        assert_eq!(flow.flow(&s, &a), Some(0.5));
+        assert_eq!(flow.flow(&s, &b), Some(0.5));
+        assert_eq!(flow.flow(&a, &b), Some(0.25));
+        assert_eq!(flow.flow(&a, &t), Some(0.25));
+        assert_eq!(flow.flow(&b, &t), Some(0.75));
+        assert_eq!(flow.flow(&t, &s), None);
+        assert_eq!(flow.flows().count(), 5);
+    }
+
+    #[test]
+    fn test_max_flow_without_path() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+
+        graph.add_edge_with_weight(&v1, &v2, 0.5).unwrap();
+        graph.add_edge_with_weight(&v3, &v2, 0.5).unwrap();
+
+        let flow = graph.max_flow(&v1, &v3).unwrap();
+
+        assert_eq!(flow.value(), 0.0);
+        assert!(flow.flows().all(|(_, _, f)| f == 0.0));
+        assert_eq!(graph.max_flow(&v1, &v1).unwrap().value(), 0.0);
+    }
+}
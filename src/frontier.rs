@@ -0,0 +1,119 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::vertex_id::VertexId;
+
+use hashbrown::{HashMap, HashSet};
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+/// Vertices which became or stopped being roots or tips of a graph.
+/// Created by `Graph::take_frontier_changes()`.
+///
+/// All lists are sorted by vertex ids.
+pub struct FrontierChanges {
+    /// Vertices which became roots
+    pub new_roots: Vec<VertexId>,
+
+    /// Vertices which stopped being roots
+    pub lost_roots: Vec<VertexId>,
+
+    /// Vertices which became tips
+    pub new_tips: Vec<VertexId>,
+
+    /// Vertices which stopped being tips
+    pub lost_tips: Vec<VertexId>,
+}
+
+impl FrontierChanges {
+    /// Returns true if no vertex became or stopped being a root or a tip.
+    pub fn is_empty(&self) -> bool {
+        self.new_roots.is_empty()
+            && self.lost_roots.is_empty()
+            && self.new_tips.is_empty()
+            && self.lost_tips.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+/// Records whether the vertices whose root or tip status changed
+/// were roots or tips before their first change.
+pub(crate) struct FrontierTracker {
+    /// Mapping between vertices and whether they were roots
+    roots: HashMap<VertexId, bool>,
+
+    /// Mapping between vertices and whether they were tips
+    tips: HashMap<VertexId, bool>,
+}
+
+impl FrontierTracker {
+    pub(crate) fn root_changed(&mut self, id: VertexId, was_root: bool) {
+        self.roots.entry(id).or_insert(was_root);
+    }
+
+    pub(crate) fn tip_changed(&mut self, id: VertexId, was_tip: bool) {
+        self.tips.entry(id).or_insert(was_tip);
+    }
+
+    /// Renames the recorded vertices with the given mapping,
+    /// dropping the ones which are not in it.
+    pub(crate) fn rename(&mut self, mapping: &HashMap<VertexId, VertexId>) {
+        for statuses in [&mut self.roots, &mut self.tips].iter_mut() {
+            **statuses = statuses
+                .iter()
+                .filter_map(|(id, status)| Some((*mapping.get(id)?, *status)))
+                .collect();
+        }
+    }
+
+    /// Compares the recorded statuses with the current roots and tips,
+    /// skipping the vertices which are not in the graph anymore.
+    pub(crate) fn changes<S: BuildHasher>(
+        &self,
+        roots: &HashSet<VertexId, S>,
+        tips: &HashSet<VertexId, S>,
+        exists: impl Fn(&VertexId) -> bool,
+    ) -> FrontierChanges {
+        let (new_roots, lost_roots) = split(&self.roots, roots, &exists);
+        let (new_tips, lost_tips) = split(&self.tips, tips, &exists);
+
+        FrontierChanges {
+            new_roots,
+            lost_roots,
+            new_tips,
+            lost_tips,
+        }
+    }
+}
+
+/// Splits the vertices whose membership in the given set
+/// changed into the ones which joined it and the ones which left it.
+fn split<S: BuildHasher>(
+    before: &HashMap<VertexId, bool>,
+    now: &HashSet<VertexId, S>,
+    exists: impl Fn(&VertexId) -> bool,
+) -> (Vec<VertexId>, Vec<VertexId>) {
+    let mut joined = Vec::new();
+    let mut left = Vec::new();
+
+    for (id, was_member) in before.iter().filter(|(id, _)| exists(id)) {
+        match (*was_member, now.contains(id)) {
+            (false, true) => joined.push(*id),
+            (true, false) => left.push(*id),
+            _ => {}
+        }
+    }
+
+    joined.sort();
+    left.sort();
+
+    (joined, left)
+}
@@ -0,0 +1,282 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::{Graph, GraphErr};
+use crate::vertex_id::VertexId;
+
+use hashbrown::HashSet;
+use rand::Rng;
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
+
+/// Adds `n` vertices holding the numbers `0..n` to the graph.
+fn numbered_vertices(graph: &mut Graph<usize>, n: usize) -> Vec<VertexId> {
+    (0..n).map(|i| graph.add_vertex(i)).collect()
+}
+
+/// Returns `count` distinct edges between the `n` vertices, excluding
+/// self-loops, chosen uniformly at random.
+fn random_edges<R: Rng + ?Sized>(n: usize, count: usize, rng: &mut R) -> HashSet<(usize, usize)> {
+    let mut edges = HashSet::with_capacity(count);
+
+    while edges.len() < count {
+        let a = rng.gen_range(0, n);
+        let b = rng.gen_range(0, n);
+
+        if a != b {
+            edges.insert((a, b));
+        }
+    }
+
+    edges
+}
+
+pub(crate) fn complete(n: usize) -> (Graph<usize>, Vec<VertexId>) {
+    let mut graph = Graph::with_capacity(n);
+    let ids = numbered_vertices(&mut graph, n);
+
+    for a in ids.iter() {
+        for b in ids.iter().filter(|b| *b != a) {
+            graph.add_edge(a, b).unwrap();
+        }
+    }
+
+    (graph, ids)
+}
+
+pub(crate) fn path(n: usize) -> (Graph<usize>, Vec<VertexId>) {
+    let mut graph = Graph::with_capacity(n);
+    let ids = numbered_vertices(&mut graph, n);
+
+    for pair in ids.windows(2) {
+        graph.add_edge(&pair[0], &pair[1]).unwrap();
+    }
+
+    (graph, ids)
+}
+
+pub(crate) fn cycle(n: usize) -> (Graph<usize>, Vec<VertexId>) {
+    let (mut graph, ids) = path(n);
+
+    if n > 1 {
+        graph.add_edge(&ids[n - 1], &ids[0]).unwrap();
+    }
+
+    (graph, ids)
+}
+
+pub(crate) fn star(n: usize) -> (Graph<usize>, Vec<VertexId>) {
+    let mut graph = Graph::with_capacity(n);
+    let ids = numbered_vertices(&mut graph, n);
+
+    for leaf in ids.iter().skip(1) {
+        graph.add_edge(&ids[0], leaf).unwrap();
+    }
+
+    (graph, ids)
+}
+
+pub(crate) fn grid(width: usize, height: usize) -> (Graph<usize>, Vec<VertexId>) {
+    let mut graph = Graph::with_capacity(width * height);
+    let ids = numbered_vertices(&mut graph, width * height);
+
+    for y in 0..height {
+        for x in 0..width {
+            let id = &ids[y * width + x];
+
+            if x + 1 < width {
+                graph.add_edge(id, &ids[y * width + x + 1]).unwrap();
+            }
+
+            if y + 1 < height {
+                graph.add_edge(id, &ids[(y + 1) * width + x]).unwrap();
+            }
+        }
+    }
+
+    (graph, ids)
+}
+
+pub(crate) fn gnp<R: Rng + ?Sized>(n: usize, p: f64, rng: &mut R) -> Graph<usize> {
+    let mut graph = Graph::with_capacity(n);
+    let ids = numbered_vertices(&mut graph, n);
+
+    for a in ids.iter() {
+        for b in ids.iter().filter(|b| *b != a) {
+            if rng.gen::<f64>() < p {
+                graph.add_edge(a, b).unwrap();
+            }
+        }
+    }
+
+    graph
+}
+
+pub(crate) fn gnm<R: Rng + ?Sized>(
+    n: usize,
+    m: usize,
+    rng: &mut R,
+) -> Result<Graph<usize>, GraphErr> {
+    let max = n * n.saturating_sub(1);
+
+    if m > max {
+        return Err(GraphErr::CannotAddEdge);
+    }
+
+    let mut graph = Graph::with_capacity(n);
+    let ids = numbered_vertices(&mut graph, n);
+
+    // For dense graphs, it is faster to pick the edges to leave out.
+    if m * 2 > max {
+        let excluded = random_edges(n, max - m, rng);
+
+        for a in 0..n {
+            for b in (0..n).filter(|b| *b != a && !excluded.contains(&(a, *b))) {
+                graph.add_edge(&ids[a], &ids[b]).unwrap();
+            }
+        }
+    } else {
+        for (a, b) in random_edges(n, m, rng) {
+            graph.add_edge(&ids[a], &ids[b]).unwrap();
+        }
+    }
+
+    Ok(graph)
+}
+
+pub(crate) fn barabasi_albert<R: Rng + ?Sized>(
+    n: usize,
+    m: usize,
+    rng: &mut R,
+) -> Result<Graph<usize>, GraphErr> {
+    if m == 0 || m >= n {
+        return Err(GraphErr::CannotAddEdge);
+    }
+
+    let mut graph = Graph::with_capacity(n);
+    let ids = numbered_vertices(&mut graph, n);
+
+    // Each vertex appears in this list once for every edge it is
+    // part of, so sampling from it is proportional to the degree.
+    let mut endpoints: Vec<usize> = Vec::with_capacity(2 * m * (n - m));
+    let mut targets: Vec<usize> = (0..m).collect();
+
+    for source in m..n {
+        if source > m {
+            targets.clear();
+
+            while targets.len() < m {
+                let target = endpoints[rng.gen_range(0, endpoints.len())];
+
+                if !targets.contains(&target) {
+                    targets.push(target);
+                }
+            }
+        }
+
+        for target in targets.iter() {
+            graph.add_edge(&ids[source], &ids[*target]).unwrap();
+            endpoints.push(source);
+            endpoints.push(*target);
+        }
+    }
+
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_isaac::IsaacRng;
+
+    #[test]
+    fn test_gnp_extremes() {
+        let mut rng = IsaacRng::seed_from_u64(7);
+
+        let empty = gnp(10, 0.0, &mut rng);
+        let complete = gnp(10, 1.0, &mut rng);
+
+        assert_eq!(empty.vertex_count(), 10);
+        assert_eq!(empty.edge_count(), 0);
+        assert_eq!(complete.edge_count(), 90);
+        assert!(!complete.vertices().any(|v| complete.has_edge(v, v)));
+    }
+
+    #[test]
+    fn test_gnm_edge_counts() {
+        let mut rng = IsaacRng::seed_from_u64(7);
+
+        for m in &[0, 10, 45, 80, 90] {
+            let graph = gnm(10, *m, &mut rng).unwrap();
+
+            assert_eq!(graph.edge_count(), *m);
+            assert!(!graph.vertices().any(|v| graph.has_edge(v, v)));
+        }
+
+        assert_eq!(gnm(10, 91, &mut rng).unwrap_err(), GraphErr::CannotAddEdge);
+        assert_eq!(gnm(1, 0, &mut rng).unwrap().vertex_count(), 1);
+    }
+
+    #[test]
+    fn test_barabasi_albert() {
+        let mut rng = IsaacRng::seed_from_u64(7);
+        let graph = barabasi_albert(200, 3, &mut rng).unwrap();
+
+        assert_eq!(graph.vertex_count(), 200);
+        assert_eq!(graph.edge_count(), (200 - 3) * 3);
+        assert!(!graph.vertices().any(|v| graph.has_edge(v, v)));
+
+        // All vertices added after the initial ones have `m` out edges
+        assert!(graph
+            .vertices()
+            .filter(|v| *graph.fetch(v).unwrap() >= 3)
+            .all(|v| graph.out_neighbors_count(v) == 3));
+
+        // Early vertices become hubs
+        let max_in = graph
+            .vertices()
+            .map(|v| graph.in_neighbors_count(v))
+            .max()
+            .unwrap();
+        assert!(max_in > 3 * 3);
+
+        assert_eq!(
+            barabasi_albert(3, 3, &mut rng).unwrap_err(),
+            GraphErr::CannotAddEdge
+        );
+        assert_eq!(
+            barabasi_albert(3, 0, &mut rng).unwrap_err(),
+            GraphErr::CannotAddEdge
+        );
+    }
+
+    #[test]
+    fn test_structures() {
+        let (graph, ids) = complete(4);
+        assert_eq!(graph.edge_count(), 12);
+        assert_eq!(ids.len(), 4);
+
+        let (graph, ids) = cycle(4);
+        assert_eq!(graph.edge_count(), 4);
+        assert!(graph.has_edge(&ids[3], &ids[0]));
+        assert_eq!(cycle(1).0.edge_count(), 0);
+
+        let (graph, ids) = path(4);
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(graph.fetch(&ids[2]), Some(&2));
+        assert_eq!(path(0).0.vertex_count(), 0);
+
+        let (graph, ids) = star(4);
+        assert_eq!(graph.out_neighbors_count(&ids[0]), 3);
+        assert_eq!(graph.edge_count(), 3);
+
+        let (graph, ids) = grid(3, 2);
+        assert_eq!(graph.vertex_count(), 6);
+        assert_eq!(graph.edge_count(), 7);
+        assert!(graph.has_edge(&ids[1], &ids[4]));
+        assert!(!graph.has_edge(&ids[2], &ids[3]));
+    }
+}
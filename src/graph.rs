@@ -1,31 +1,76 @@
 // Copyright 2019 Octavian Oncescu
 
+use crate::attributes::{AttrValue, Attributes};
+use crate::cow::CowTable;
+use crate::diff::GraphDiff;
 use crate::edge::Edge;
+use crate::entry::VertexEntry;
+use crate::flow::MaxFlow;
+use crate::frontier::{FrontierChanges, FrontierTracker};
+use crate::history::{History, Mutation, Step, VertexData};
+use crate::id_provider::IdProvider;
+use crate::isomorphism::{self, MatchMode, Matcher, SubgraphMatches};
 use crate::iterators::*;
+use crate::priority::PriorityIndex;
+use crate::reachability::ReachabilityIndex;
+use crate::scheduler::Scheduler;
+use crate::simulation::TokenSimulation;
 use crate::vertex_id::VertexId;
+use crate::vertex_set::VertexSet;
+use crate::weight_mut::{WeightMut, WeightsMut};
 use hashbrown::{HashMap, HashSet};
+use rand::Rng;
+
+#[cfg(feature = "dot")]
+use crate::dot::{DotOptions, Rank};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 #[cfg(feature = "no_std")]
 use core::iter;
 #[cfg(not(feature = "no_std"))]
 use std::iter;
 
+#[cfg(not(feature = "no_std"))]
+use std::collections::VecDeque;
+
+#[cfg(feature = "no_std")]
+use core::fmt::{self, Debug, Display};
+#[cfg(not(feature = "no_std"))]
+use std::fmt::{self, Debug, Display};
+
 #[cfg(feature = "no_std")]
-use core::fmt::Debug;
+use core::hash::{BuildHasher, Hash};
 #[cfg(not(feature = "no_std"))]
-use std::fmt::Debug;
+use std::hash::{BuildHasher, Hash};
+
+#[cfg(feature = "no_std")]
+use core::ops::{Index, IndexMut};
+#[cfg(not(feature = "no_std"))]
+use std::ops::{Index, IndexMut};
 
 #[cfg(feature = "no_std")]
 extern crate alloc;
 #[cfg(feature = "no_std")]
+use alloc::borrow::ToOwned;
+#[cfg(feature = "no_std")]
 use alloc::boxed::Box;
 #[cfg(feature = "no_std")]
+use alloc::collections::vec_deque::VecDeque;
+#[cfg(feature = "no_std")]
+use alloc::string::String;
+#[cfg(feature = "no_std")]
 use alloc::vec;
 #[cfg(feature = "no_std")]
 use alloc::vec::Vec;
 
-#[cfg(feature = "dot")]
-use super::SEED;
+/// Label used when rendering vertices and edges that were not labeled
+pub(crate) const DEFAULT_LABEL: &str = "";
+
+const FOREIGN_ID_MSG: &str = "vertex id belongs to a different graph";
+const MISSING_VERTEX_MSG: &str = "no vertex with the given id in the graph";
+const USED_ID_MSG: &str = "id provider returned the id of a vertex in the graph";
 
 #[derive(Clone, Debug, PartialEq)]
 /// Graph operation error
@@ -33,6 +78,9 @@ pub enum GraphErr {
     /// There is no vertex with the given id in the graph
     NoSuchVertex,
 
+    /// There is already a vertex with the given id in the graph
+    VertexExists,
+
     /// There is no such edge in the graph
     NoSuchEdge,
 
@@ -46,44 +94,404 @@ pub enum GraphErr {
     /// create a cycle in the graph.
     CycleError,
 
+    /// The given vertex sets belong to different graphs
+    GraphMismatch,
+
+    /// The graph checks the ids it is given, which
+    /// the operation would stop it from doing
+    CheckedIds,
+
+    /// The given vertex is not ready or running
+    NotReady,
+
     #[cfg(feature = "dot")]
     /// Could not render .dot file
     CouldNotRender,
 
+    /// Could not parse the given input
+    CouldNotParse,
+
+    #[cfg(feature = "datasets")]
+    /// Could not read from the given input
+    CouldNotRead,
+
+    #[cfg(feature = "datasets")]
+    /// Could not write to the given output
+    CouldNotWrite,
+
     #[cfg(feature = "dot")]
     /// The name of the graph is invalid. Check [this](https://docs.rs/dot/0.1.1/dot/struct.Id.html#method.new)
     /// out for more information.
     InvalidGraphName,
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Inconsistency between the internal tables of a
+/// graph, as reported by `Graph::validate()`.
+pub enum InvariantViolation {
+    /// An edge or one of the tables refers to a vertex which is not in the graph
+    DanglingId(VertexId),
+
+    /// The edge is missing from the outbound table of its first vertex
+    MissingOutbound(VertexId, VertexId),
+
+    /// The edge is missing from the inbound table of its second vertex
+    MissingInbound(VertexId, VertexId),
+
+    /// The outbound table lists an edge which is not in the
+    /// graph or lists the same edge more than once
+    StrayOutbound(VertexId, VertexId),
+
+    /// The inbound table lists an edge which is not in the
+    /// graph or lists the same edge more than once
+    StrayInbound(VertexId, VertexId),
+
+    /// The outbound edges of the vertex are not sorted by their weights
+    UnsortedOutbound(VertexId),
+
+    /// The inbound edges of the vertex are not sorted by their weights
+    UnsortedInbound(VertexId),
+
+    /// The vertex is marked as a root while having inbound
+    /// edges, or is not marked as one without having any
+    WrongRoot(VertexId),
+
+    /// The vertex is marked as a tip while having outbound
+    /// edges, or is not marked as one without having any
+    WrongTip(VertexId),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Policy deciding the weight of the edges which are
+/// added to a graph without specifying one.
+pub enum WeightPolicy {
+    /// Edges get the given weight
+    Fixed(f32),
+
+    /// Edges get the mean weight of the edges already in the graph
+    /// or `0.0` if there are none.
+    Mean,
+
+    /// Edges are marked as unweighted and shortest path algorithms
+    /// count them as one hop each. Their stored weight is `0.0`.
+    Unweighted,
+}
+
+impl Default for WeightPolicy {
+    fn default() -> WeightPolicy {
+        WeightPolicy::Fixed(0.0)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+/// Policy deciding what happens when an edge is added
+/// between two vertices which already have one.
+pub enum DuplicateEdgePolicy {
+    /// The existing edge is kept as is
+    #[default]
+    Ignore,
+
+    /// Adding the edge fails with `GraphErr::CannotAddEdge`
+    Reject,
+
+    /// The weight of the existing edge is replaced
+    /// with the weight of the added edge
+    Replace,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Order in which the vertices of a graph are visited,
+/// as given to `Graph::fold_in()`.
+pub enum Traversal {
+    /// Depth-First Order, as visited by `Graph::dfs()`
+    Dfs,
+
+    /// Breadth-First Order, as visited by `Graph::bfs()`
+    Bfs,
+
+    /// Topological Order, as visited by `Graph::topo()`
+    Topo,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Number of inbound and outbound edges of a vertex,
+/// as given to the condition of `Graph::retain_with()`.
+pub struct Degree {
+    /// Number of edges going into the vertex
+    pub inbound: usize,
+
+    /// Number of edges going out of the vertex
+    pub outbound: usize,
+}
+
+#[derive(Clone, Copy, Debug)]
+/// Invariants enforced on the edges of a graph,
+/// which are configured with a `GraphBuilder`.
+pub(crate) struct EdgeRules {
+    /// Whether adding an edge which creates a cycle fails
+    pub(crate) acyclic: bool,
+
+    /// Policy for the edges added between connected vertices
+    pub(crate) duplicate_edges: DuplicateEdgePolicy,
+
+    /// Whether edges from a vertex to itself can be added
+    pub(crate) self_loops: bool,
+
+    /// Lowest weight an edge can have
+    pub(crate) min_weight: f32,
+
+    /// Highest weight an edge can have
+    pub(crate) max_weight: f32,
+}
+
+impl EdgeRules {
+    /// Returns true if an edge can have the given weight.
+    pub(crate) fn allows_weight(&self, weight: f32) -> bool {
+        (self.min_weight..=self.max_weight).contains(&weight)
+    }
+}
+
+impl Default for EdgeRules {
+    fn default() -> EdgeRules {
+        EdgeRules {
+            acyclic: false,
+            duplicate_edges: DuplicateEdgePolicy::default(),
+            self_loops: true,
+            min_weight: -1.0,
+            max_weight: 1.0,
+        }
+    }
+}
+
+/// Edge between two vertex values, which is either a pair of values
+/// or a triple of values and a weight, as accepted by `Graph::from_edges()`.
+pub trait IntoWeightedEdge<T> {
+    /// Returns the values of the outbound and the inbound
+    /// vertex along with the weight of the edge, if given.
+    fn into_weighted_edge(self) -> (T, T, Option<f32>);
+}
+
+impl<T> IntoWeightedEdge<T> for (T, T) {
+    fn into_weighted_edge(self) -> (T, T, Option<f32>) {
+        (self.0, self.1, None)
+    }
+}
+
+impl<T> IntoWeightedEdge<T> for (T, T, f32) {
+    fn into_weighted_edge(self) -> (T, T, Option<f32>) {
+        (self.0, self.1, Some(self.2))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+/// Estimates of the bytes allocated by the tables of a graph, as
+/// returned by `Graph::memory_usage()`. Memory allocated by the
+/// values of the vertices themselves is not included.
+pub struct MemoryUsage {
+    /// Bytes of the table of vertices and their values
+    pub vertices: usize,
+
+    /// Bytes of the table of edges and their weights
+    pub edges: usize,
+
+    /// Bytes of the sets of roots and tips
+    pub frontier: usize,
+
+    /// Bytes of the tables of inbound edges
+    pub inbound: usize,
+
+    /// Bytes of the tables of outbound edges
+    pub outbound: usize,
+
+    /// Bytes of the vertex and edge labels
+    pub labels: usize,
+
+    /// Bytes of the tables of attributes, not including
+    /// the strings of the attribute values
+    pub attributes: usize,
+}
+
+impl MemoryUsage {
+    /// Returns the bytes allocated by all tables.
+    pub fn total(&self) -> usize {
+        self.vertices
+            + self.edges
+            + self.frontier
+            + self.inbound
+            + self.outbound
+            + self.labels
+            + self.attributes
+    }
+}
+
+/// Returns the bytes allocated by the given table of attributes
+/// and by the keys of the attributes.
+fn attribute_bytes<K>(table: &HashMap<K, Attributes>) -> usize {
+    let attributes: usize = table
+        .values()
+        .map(|attrs| {
+            let keys: usize = attrs.keys().map(|key| key.capacity()).sum();
+            keys + table_bytes::<(String, AttrValue)>(attrs.capacity())
+        })
+        .sum();
+
+    attributes + table_bytes::<(K, Attributes)>(table.capacity())
+}
+
+/// Returns the index at which a vertex whose edge has the given weight
+/// is inserted in an adjacency list, which is sorted by the weights of
+/// the edges that `edge` returns for its vertices. Among the vertices
+/// whose edges weigh as much, it is placed after the ones with an index
+/// lower than `before`.
+fn adjacency_index<S: BuildHasher>(
+    edges: &HashMap<Edge, f32, S>,
+    list: &[VertexId],
+    edge: impl Fn(VertexId) -> Edge,
+    weight: f32,
+    before: usize,
+) -> usize {
+    let mut low = 0;
+    let mut high = list.len();
+
+    while low < high {
+        let mid = (low + high) / 2;
+        let mid_weight = edges[&edge(list[mid])];
+
+        if mid_weight < weight || (mid_weight == weight && mid < before) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    low
+}
+
+/// Returns the index of the given vertex in an adjacency list sorted as
+/// in `adjacency_index()`, given the weight of its edge. Only the vertices
+/// whose edges weigh as much are compared to it.
+fn adjacency_position<S: BuildHasher>(
+    edges: &HashMap<Edge, f32, S>,
+    list: &[VertexId],
+    edge: impl Fn(VertexId) -> Edge + Copy,
+    target: &VertexId,
+    weight: f32,
+) -> Option<usize> {
+    let start = adjacency_index(edges, list, edge, weight, 0);
+
+    list[start..]
+        .iter()
+        .take_while(|v| edges[&edge(**v)] == weight)
+        .position(|v| v == target)
+        .map(|i| start + i)
+}
+
+/// Estimates the bytes allocated by a hash table with the given
+/// capacity, which keeps one control byte for each entry.
+fn table_bytes<E>(capacity: usize) -> usize {
+    capacity * (core::mem::size_of::<E>() + 1)
+}
+
+/// Estimates the bytes allocated by a table of adjacency lists.
+fn adjacency_bytes<S>(table: &HashMap<VertexId, Vec<VertexId>, S>) -> usize {
+    let lists: usize = table.values().map(|list| list.capacity()).sum();
+
+    table_bytes::<(VertexId, Vec<VertexId>)>(table.capacity())
+        + lists * core::mem::size_of::<VertexId>()
+}
+
+#[derive(Clone, Default)]
+/// Hasher used by graphs unless they are created with another one.
+pub struct DefaultHashBuilder(hashbrown::hash_map::DefaultHashBuilder);
+
+impl BuildHasher for DefaultHashBuilder {
+    type Hasher = <hashbrown::hash_map::DefaultHashBuilder as BuildHasher>::Hasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        self.0.build_hasher()
+    }
+}
+
+impl Debug for DefaultHashBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DefaultHashBuilder")
+    }
+}
+
+#[derive(Clone)]
 /// Graph data-structure
-pub struct Graph<T> {
+pub struct Graph<T, S = DefaultHashBuilder> {
     /// Mapping of vertex ids and vertex values
-    vertices: HashMap<VertexId, (T, VertexId)>,
+    vertices: CowTable<HashMap<VertexId, (T, VertexId), S>>,
 
     /// Mapping between edges and weights
-    edges: HashMap<Edge, f32>,
+    edges: CowTable<HashMap<Edge, f32, S>>,
 
     /// Set containing the roots of the graph
-    roots: HashSet<VertexId>,
+    roots: CowTable<HashSet<VertexId, S>>,
 
     /// Set containing the tips of the graph
-    tips: HashSet<VertexId>,
+    tips: CowTable<HashSet<VertexId, S>>,
 
-    /// Mapping between vertex ids and inbound edges
-    inbound_table: HashMap<VertexId, Vec<VertexId>>,
+    /// Mapping between vertex ids and inbound edges,
+    /// sorted by their weights
+    inbound_table: CowTable<HashMap<VertexId, Vec<VertexId>, S>>,
 
-    /// Mapping between vertex ids and outbound edges
-    outbound_table: HashMap<VertexId, Vec<VertexId>>,
+    /// Mapping between vertex ids and outbound edges,
+    /// sorted by their weights
+    outbound_table: CowTable<HashMap<VertexId, Vec<VertexId>, S>>,
+
+    /// Tag of the ids of the vertices if the graph checks
+    /// that the ids it is given were created by it.
+    id_tag: Option<[u8; 4]>,
+
+    /// Sequence number of the next vertex id if the
+    /// graph allocates its ids sequentially.
+    next_id: Option<u64>,
+
+    /// Source of the vertex ids given by the user, if any
+    id_provider: Option<Box<dyn IdProvider<T>>>,
+
+    /// Policy for the edges added without a weight
+    weight_policy: WeightPolicy,
+
+    /// Invariants enforced on the edges of the graph
+    rules: EdgeRules,
+
+    /// Sum of the weights of all edges
+    weight_sum: f64,
+
+    /// Set containing the edges marked as unweighted
+    unweighted_edges: CowTable<HashSet<Edge>>,
+
+    /// Changes of the roots and tips since they were last taken, if tracked
+    frontier: Option<FrontierTracker>,
+
+    /// Journal of the mutations of the graph, if tracked
+    history: Option<History<T>>,
+
+    /// Index of the vertices which were given a priority
+    priorities: CowTable<PriorityIndex>,
+
+    /// Mapping between vertices and their attributes
+    vertex_attrs: CowTable<HashMap<VertexId, Attributes>>,
+
+    /// Mapping between edges and their attributes
+    edge_attrs: CowTable<HashMap<Edge, Attributes>>,
 
-    #[cfg(feature = "dot")]
     /// Mapping between vertices and labels
-    vertex_labels: HashMap<VertexId, String>,
+    vertex_labels: CowTable<HashMap<VertexId, String>>,
 
-    #[cfg(feature = "dot")]
     /// Mapping between edges and labels
-    edge_labels: HashMap<Edge, String>,
+    edge_labels: CowTable<HashMap<Edge, String>>,
+
+    #[cfg(feature = "dot")]
+    /// Groups of vertices with rank constraints
+    rank_groups: CowTable<Vec<(Rank, Vec<VertexId>)>>,
+
+    #[cfg(feature = "dot")]
+    /// Mapping between vertices and the names of their clusters
+    clusters: CowTable<HashMap<VertexId, String>>,
 }
 
 impl<T> Graph<T> {
@@ -99,483 +507,422 @@ impl<T> Graph<T> {
     /// assert_eq!(graph.vertex_count(), 1);
     /// ```
     pub fn new() -> Graph<T> {
-        Graph {
-            vertices: HashMap::new(),
-            edges: HashMap::new(),
-            roots: HashSet::new(),
-            tips: HashSet::new(),
-            inbound_table: HashMap::new(),
-            outbound_table: HashMap::new(),
-
-            #[cfg(feature = "dot")]
-            vertex_labels: HashMap::new(),
-            #[cfg(feature = "dot")]
-            edge_labels: HashMap::new(),
-        }
+        Graph::with_hasher(DefaultHashBuilder::default())
     }
 
-    /// Creates a new graph with the given capacity.
+    /// Creates a new graph which checks that the vertex ids it is given
+    /// were created by it. Clones of the graph and graphs derived from it
+    /// with `Graph::map()` or `Graph::subgraph()` share its ids. Passing an
+    /// id of a vertex from another graph to a checked graph panics, even if
+    /// a vertex with the same id happens to exist in the graph.
     ///
     /// ## Example
     /// ```rust
     /// use graphlib::Graph;
     ///
-    /// let mut graph: Graph<usize> = Graph::with_capacity(5);
+    /// let mut graph1: Graph<usize> = Graph::new_checked();
+    /// let v1 = graph1.add_vertex(1);
+    ///
+    /// let graph2 = graph1.map(|v| v * 2);
+    ///
+    /// assert_eq!(graph1.fetch(&v1), Some(&1));
+    /// assert_eq!(graph2.fetch(&v1), Some(&2));
     /// ```
-    pub fn with_capacity(capacity: usize) -> Graph<T> {
-        let edges_capacity = if capacity < 100 {
-            usize::pow(capacity, 2)
-        } else {
-            capacity
-        };
+    ///
+    /// ```rust,should_panic
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph1: Graph<usize> = Graph::new_checked();
+    /// let mut graph2: Graph<usize> = Graph::new_checked();
+    ///
+    /// let v1 = graph1.add_vertex(1);
+    ///
+    /// // Panics as `v1` does not belong to `graph2`
+    /// graph2.fetch(&v1);
+    /// ```
+    pub fn new_checked() -> Graph<T> {
+        let mut tag = [0; 4];
+        tag.copy_from_slice(&crate::gen_bytes()[..4]);
 
-        Graph {
-            vertices: HashMap::with_capacity(capacity),
-            edges: HashMap::with_capacity(edges_capacity),
-            roots: HashSet::with_capacity(capacity),
-            tips: HashSet::with_capacity(capacity),
-            inbound_table: HashMap::with_capacity(capacity),
-            outbound_table: HashMap::with_capacity(capacity),
+        let mut graph = Graph::new();
+        graph.id_tag = Some(tag);
 
-            #[cfg(feature = "dot")]
-            vertex_labels: HashMap::with_capacity(capacity),
-            #[cfg(feature = "dot")]
-            edge_labels: HashMap::with_capacity(capacity),
-        }
+        graph
     }
 
-    /// Returns the current capacity of the graph.
+    /// Creates a new graph which allocates the ids of its vertices
+    /// sequentially instead of randomly, making them the same on
+    /// every run.
+    ///
     /// ## Example
     /// ```rust
     /// use graphlib::Graph;
     ///
-    /// let mut graph: Graph<usize> = Graph::with_capacity(5);
+    /// let mut graph1: Graph<usize> = Graph::new_sequential();
+    /// let mut graph2: Graph<usize> = Graph::new_sequential();
     ///
-    /// assert!(graph.capacity() >= 5);
+    /// let v1 = graph1.add_vertex(1);
+    /// let v2 = graph1.add_vertex(2);
+    ///
+    /// assert_ne!(v1, v2);
+    /// assert_eq!(graph2.add_vertex(1), v1);
+    /// assert_eq!(graph2.add_vertex(2), v2);
     /// ```
-    pub fn capacity(&self) -> usize {
-        min!(
-            self.vertices.capacity(),
-            self.edges.capacity(),
-            self.roots.capacity(),
-            self.tips.capacity(),
-            self.inbound_table.capacity(),
-            self.outbound_table.capacity()
-        )
+    pub fn new_sequential() -> Graph<T> {
+        let mut graph = Graph::new();
+        graph.next_id = Some(0);
+
+        graph
     }
 
-    /// Reserves capacity for at least additional more elements to be inserted in the given
-    /// graph. After calling reserve, capacity will be greater than or equal to `self.vertex_count() + additional`.
+    /// Creates a new graph with the given capacity.
     ///
     /// ## Example
     /// ```rust
     /// use graphlib::Graph;
     ///
-    /// let mut graph: Graph<usize> = Graph::with_capacity(3);
-    ///
-    /// assert_eq!(graph.capacity(), 3);
-    ///
-    /// graph.add_vertex(0);
-    /// graph.add_vertex(1);
-    /// graph.add_vertex(2);
-    ///
-    /// graph.reserve(10);
-    /// assert!(graph.capacity() >= 13);
+    /// let mut graph: Graph<usize> = Graph::with_capacity(5);
     /// ```
-    pub fn reserve(&mut self, additional: usize) {
-        // Calculate additional value for edges vector
-        // such that it is always n^2 where n is the
-        // number of vertices that are currently placed
-        // in the graph.
-        let new_capacity = self.vertices.len() + additional;
-        let edges_capacity = if new_capacity < 100 {
-            usize::pow(new_capacity, 2)
-        } else {
-            new_capacity
-        };
-        let edges_count = self.edges.len();
-        let edges_additional = edges_capacity - edges_count;
-
-        self.edges.reserve(edges_additional);
-        self.roots.reserve(additional);
-        self.tips.reserve(additional);
-        self.vertices.reserve(additional);
-        self.outbound_table.reserve(additional);
-        self.inbound_table.reserve(additional);
-
-        #[cfg(feature = "dot")]
-        self.vertex_labels.reserve(additional);
-        #[cfg(feature = "dot")]
-        self.edge_labels.reserve(additional);
+    pub fn with_capacity(capacity: usize) -> Graph<T> {
+        Graph::with_capacity_and_hasher(capacity, DefaultHashBuilder::default())
     }
 
-    /// Shrinks the capacity of the graph as much as possible.
+    /// Creates a graph from a list of edges between vertex values, given
+    /// either as pairs of values or as triples of values and a weight.
+    /// A vertex is added for each distinct value the first time it is
+    /// seen. Returns the graph along with the mapping between the values
+    /// and the ids of their vertices.
+    ///
+    /// Fails with `GraphErr::InvalidWeight` if a weight is out of range.
     ///
-    /// It will drop down as close as possible to the length but the allocator may still inform the
-    /// vector that there is space for a few more elements.
     /// ## Example
     /// ```rust
     /// use graphlib::Graph;
     ///
-    /// let mut graph: Graph<usize> = Graph::with_capacity(5);
+    /// let (graph, ids) = Graph::from_edges(vec![("a", "b"), ("b", "c"), ("a", "c")]).unwrap();
     ///
-    /// assert!(graph.capacity() >= 5);
+    /// assert_eq!(graph.vertex_count(), 3);
+    /// assert_eq!(graph.edge_count(), 3);
+    /// assert!(graph.has_edge(&ids["b"], &ids["c"]));
     ///
-    /// graph.shrink_to_fit();
-    /// assert!(graph.capacity() < 5);
+    /// let (graph, ids) = Graph::from_edges(vec![(1, 2, 0.5), (2, 3, -0.5)]).unwrap();
+    ///
+    /// assert_eq!(graph.weight(&ids[&1], &ids[&2]), Some(0.5));
+    /// assert_eq!(graph[&ids[&3]], 3);
     /// ```
-    pub fn shrink_to_fit(&mut self) {
-        self.edges.shrink_to_fit();
-        self.roots.shrink_to_fit();
-        self.tips.shrink_to_fit();
-        self.vertices.shrink_to_fit();
-        self.outbound_table.shrink_to_fit();
-        self.inbound_table.shrink_to_fit();
+    pub fn from_edges<E: IntoWeightedEdge<T>>(
+        edges: impl IntoIterator<Item = E>,
+    ) -> Result<(Graph<T>, HashMap<T, VertexId>), GraphErr>
+    where
+        T: Hash + Eq + Clone,
+    {
+        let mut graph = Graph::new();
+        let mut ids: HashMap<T, VertexId> = HashMap::new();
 
-        #[cfg(feature = "dot")]
-        self.vertex_labels.shrink_to_fit();
-        #[cfg(feature = "dot")]
-        self.edge_labels.shrink_to_fit();
+        for edge in edges {
+            let (a, b, weight) = edge.into_weighted_edge();
 
-        // Calculate additional value for edges vector
-        // such that it is always n^2 where n is the
-        // number of vertices that are currently placed
-        // in the graph.
-        let edges_capacity = usize::pow(self.vertices.len(), 2);
-        let edges_count = self.edges.len();
-        let edges_additional = edges_capacity - edges_count;
+            let mut id = |value: T| match ids.get(&value) {
+                Some(id) => *id,
+                None => {
+                    let id = graph.add_vertex(value.clone());
+                    ids.insert(value, id);
+                    id
+                }
+            };
 
-        self.edges.reserve(edges_additional);
+            let (a, b) = (id(a), id(b));
+
+            match weight {
+                Some(weight) => graph.add_edge_with_weight(&a, &b, weight)?,
+                None => graph.add_edge(&a, &b)?,
+            }
+        }
+
+        Ok((graph, ids))
     }
+}
 
-    /// Adds a new vertex to the graph and returns the id
-    /// of the added vertex.
+impl<T, S: BuildHasher + Clone> Graph<T, S> {
+    /// Creates a new graph which uses the given hasher for
+    /// the tables of its vertices and edges.
     ///
     /// ## Example
     /// ```rust
     /// use graphlib::Graph;
+    /// use std::collections::hash_map::RandomState;
     ///
-    /// let mut graph: Graph<usize> = Graph::new();
-    /// let id = graph.add_vertex(1);
+    /// let mut graph: Graph<usize, RandomState> = Graph::with_hasher(RandomState::new());
     ///
-    /// assert_eq!(graph.fetch(&id).unwrap(), &1);
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    ///
+    /// assert_eq!(graph.bfs().count(), 2);
     /// ```
-    pub fn add_vertex(&mut self, item: T) -> VertexId {
-        let id = VertexId::random();
-
-        self.vertices.insert(id, (item, id));
-        self.roots.insert(id);
-        self.tips.insert(id);
-
-        id
+    pub fn with_hasher(hash_builder: S) -> Graph<T, S> {
+        Graph::with_capacity_and_hasher(0, hash_builder)
     }
 
-    /// Attempts to place a new edge in the graph.
+    /// Creates a new graph with the given capacity which uses
+    /// the given hasher for the tables of its vertices and edges.
     ///
     /// ## Example
     /// ```rust
-    /// use graphlib::{Graph, GraphErr, VertexId};
-    ///
-    /// let mut graph: Graph<usize> = Graph::new();
-    ///
-    /// // Id of vertex that is not place in the graph
-    /// let id = VertexId::random();
-    ///
-    /// let v1 = graph.add_vertex(1);
-    /// let v2 = graph.add_vertex(2);
+    /// use graphlib::Graph;
+    /// use std::collections::hash_map::RandomState;
     ///
-    /// // Adding an edge is idempotent
-    /// graph.add_edge(&v1, &v2);
-    /// graph.add_edge(&v1, &v2);
-    /// graph.add_edge(&v1, &v2);
+    /// let graph: Graph<usize, RandomState> =
+    ///     Graph::with_capacity_and_hasher(5, RandomState::new());
     ///
-    /// // Fails on adding an edge between an
-    /// // existing vertex and a non-existing one.
-    /// assert_eq!(graph.add_edge(&v1, &id), Err(GraphErr::NoSuchVertex));
+    /// assert!(graph.capacity() >= 5);
     /// ```
-    pub fn add_edge(&mut self, a: &VertexId, b: &VertexId) -> Result<(), GraphErr> {
-        if self.has_edge(a, b) {
-            return Ok(());
-        }
-
-        self.do_add_edge(a, b, 0.0, false)
-    }
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Graph<T, S> {
+        let edges_capacity = if capacity < 100 {
+            usize::pow(capacity, 2)
+        } else {
+            capacity
+        };
 
-    /// Attempts to place a new edge in the graph, checking if the specified
-    /// edge will create a cycle in the graph. If it does, this operation will fail.
-    ///
-    /// Note that this operation has a bigger performance hit than `Graph::add_edge()`.
-    ///
-    /// ## Example
-    /// ```rust
-    /// use graphlib::{Graph, GraphErr, VertexId};
-    ///
-    /// let mut graph: Graph<usize> = Graph::new();
-    ///
-    /// // Id of vertex that is not place in the graph
-    /// let id = VertexId::random();
-    ///
-    /// let v1 = graph.add_vertex(1);
-    /// let v2 = graph.add_vertex(2);
-    ///
-    /// // Adding an edge is idempotent
-    /// graph.add_edge_check_cycle(&v1, &v2);
-    /// graph.add_edge_check_cycle(&v1, &v2);
-    /// graph.add_edge_check_cycle(&v1, &v2);
-    ///
-    /// // Fails on adding an edge which creates
-    /// // a cycle in the graph.
-    /// assert_eq!(graph.add_edge_check_cycle(&v2, &v1), Err(GraphErr::CycleError));
-    /// ```
-    pub fn add_edge_check_cycle(&mut self, a: &VertexId, b: &VertexId) -> Result<(), GraphErr> {
-        if self.has_edge(a, b) {
-            return Ok(());
+        Graph {
+            vertices: HashMap::with_capacity_and_hasher(capacity, hash_builder.clone()).into(),
+            edges: HashMap::with_capacity_and_hasher(edges_capacity, hash_builder.clone()).into(),
+            roots: HashSet::with_capacity_and_hasher(capacity, hash_builder.clone()).into(),
+            tips: HashSet::with_capacity_and_hasher(capacity, hash_builder.clone()).into(),
+            inbound_table: HashMap::with_capacity_and_hasher(capacity, hash_builder.clone()).into(),
+            outbound_table: HashMap::with_capacity_and_hasher(capacity, hash_builder).into(),
+            id_tag: None,
+            next_id: None,
+            id_provider: None,
+            weight_policy: WeightPolicy::default(),
+            rules: EdgeRules::default(),
+            weight_sum: 0.0,
+            unweighted_edges: HashSet::new().into(),
+            frontier: None,
+            history: None,
+            priorities: PriorityIndex::default().into(),
+            vertex_attrs: HashMap::new().into(),
+            edge_attrs: HashMap::new().into(),
+
+            vertex_labels: HashMap::with_capacity(capacity).into(),
+            edge_labels: HashMap::with_capacity(capacity).into(),
+            #[cfg(feature = "dot")]
+            rank_groups: Vec::new().into(),
+            #[cfg(feature = "dot")]
+            clusters: HashMap::new().into(),
         }
-
-        self.do_add_edge(a, b, 0.0, true)
     }
+}
 
-    /// Attempts to place a new edge in the graph.
+impl<T, S: BuildHasher> Graph<T, S> {
+    /// Returns true if the graph checks that the
+    /// vertex ids it is given were created by it.
     ///
     /// ## Example
     /// ```rust
-    /// use graphlib::{Graph, GraphErr, VertexId};
-    ///
-    /// let mut graph: Graph<usize> = Graph::new();
-    ///
-    /// // Id of vertex that is not place in the graph
-    /// let id = VertexId::random();
-    ///
-    /// let v1 = graph.add_vertex(1);
-    /// let v2 = graph.add_vertex(2);
-    ///
-    /// // Adding an edge is idempotent
-    /// graph.add_edge_with_weight(&v1, &v2, 0.3);
+    /// use graphlib::Graph;
     ///
-    /// // Fails on adding an edge between an
-    /// // existing vertex and a non-existing one.
-    /// assert_eq!(graph.weight(&v1, &v2), Some(0.3));
+    /// assert!(!Graph::<usize>::new().is_checked());
+    /// assert!(Graph::<usize>::new_checked().is_checked());
     /// ```
-    pub fn add_edge_with_weight(
-        &mut self,
-        a: &VertexId,
-        b: &VertexId,
-        weight: f32,
-    ) -> Result<(), GraphErr> {
-        if self.has_edge(a, b) {
-            return Ok(());
-        }
+    pub fn is_checked(&self) -> bool {
+        self.id_tag.is_some()
+    }
 
-        if weight > 1.0 || weight < -1.0 {
-            return Err(GraphErr::InvalidWeight);
-        }
+    /// Sets the invariants enforced on the edges of the graph.
+    pub(crate) fn set_rules(&mut self, rules: EdgeRules) {
+        self.rules = rules;
+    }
 
-        self.do_add_edge(a, b, weight, false)
+    /// Reserves capacity for at least `additional` more edges.
+    pub(crate) fn reserve_edges(&mut self, additional: usize) {
+        self.edges.reserve(additional);
     }
 
-    /// Returns the weight of the specified edge
-    /// if it is listed.
+    /// Returns true if the graph allocates the ids
+    /// of its vertices sequentially.
     ///
+    /// ## Example
     /// ```rust
-    /// use graphlib::{Graph, GraphErr, VertexId};
-    ///
-    /// let mut graph: Graph<usize> = Graph::new();
-    ///
-    /// // Id of vertex that is not place in the graph
-    /// let id = VertexId::random();
-    ///
-    /// let v1 = graph.add_vertex(1);
-    /// let v2 = graph.add_vertex(2);
-    /// let v3 = graph.add_vertex(3);
-    ///
-    /// // Adding an edge is idempotent
-    /// graph.add_edge_with_weight(&v1, &v2, 0.54543);
+    /// use graphlib::Graph;
     ///
-    /// assert_eq!(graph.weight(&v1, &v2), Some(0.54543));
-    /// assert_eq!(graph.weight(&v1, &v3), None);
+    /// assert!(!Graph::<usize>::new().is_sequential());
+    /// assert!(Graph::<usize>::new_sequential().is_sequential());
     /// ```
-    pub fn weight(&self, a: &VertexId, b: &VertexId) -> Option<f32> {
-        if !self.has_edge(a, b) {
-            return None;
-        }
-
-        if let Some(result) = self.edges.get(&Edge::new(*a, *b)) {
-            Some(*result)
-        } else {
-            None
-        }
+    pub fn is_sequential(&self) -> bool {
+        self.next_id.is_some()
     }
 
-    /// Sets the weight of the edge to the new value
-    /// if the edge exists in the graph. Note that
-    /// the given weight must be a number between
-    /// (and including) `-1.0` and `1.0`.
+    /// Makes the graph take the ids of the vertices added to it
+    /// from the given provider. The graph no longer allocates ids
+    /// sequentially.
     ///
-    /// ```rust
-    /// use graphlib::{Graph, GraphErr, VertexId};
+    /// Fails with `GraphErr::CheckedIds` if the graph is checked, as
+    /// the ids of the provider are not tagged. Adding a vertex with
+    /// `Graph::try_add_vertex()` fails if the provider returns the id
+    /// of a vertex which is already in the graph.
     ///
-    /// let mut graph: Graph<usize> = Graph::new();
+    /// ## Example
+    /// ```rust
+    /// use graphlib::{Graph, GraphErr, SeededIds};
     ///
-    /// // Id of vertex that is not place in the graph
-    /// let id = VertexId::random();
+    /// let mut graph: Graph<usize> = Graph::new_sequential();
+    /// graph.set_id_provider(SeededIds::new(1)).unwrap();
     ///
     /// let v1 = graph.add_vertex(1);
     /// let v2 = graph.add_vertex(2);
-    /// let v3 = graph.add_vertex(3);
     ///
-    /// graph.add_edge_with_weight(&v1, &v2, 0.54543);
-    /// assert_eq!(graph.weight(&v1, &v2), Some(0.54543));
+    /// assert!(!graph.is_sequential());
+    /// assert_ne!(v1, v2);
     ///
-    /// // Set new weight
-    /// graph.set_weight(&v1, &v2, 0.123).unwrap();
-    /// assert_eq!(graph.weight(&v1, &v2), Some(0.123));
+    /// let mut checked: Graph<usize> = Graph::new_checked();
+    ///
+    /// assert_eq!(
+    ///     checked.set_id_provider(SeededIds::new(1)),
+    ///     Err(GraphErr::CheckedIds)
+    /// );
     /// ```
-    pub fn set_weight(
+    pub fn set_id_provider(
         &mut self,
-        a: &VertexId,
-        b: &VertexId,
-        new_weight: f32,
+        provider: impl IdProvider<T> + 'static,
     ) -> Result<(), GraphErr> {
-        if !self.has_edge(a, b) {
-            return Err(GraphErr::NoSuchEdge);
-        }
-
-        if new_weight > 1.0 || new_weight < -1.0 {
-            return Err(GraphErr::InvalidWeight);
+        if self.is_checked() {
+            return Err(GraphErr::CheckedIds);
         }
 
-        self.edges.insert(Edge::new(*a, *b), new_weight);
-
-        // Sort outbound vertices after setting a new weight
-        let mut outbounds = self.outbound_table.get(a).unwrap().clone();
-
-        self.sort_outbounds(a.clone(), &mut outbounds);
-
-        // Update outbounds
-        self.outbound_table.insert(a.clone(), outbounds);
+        self.next_id = None;
+        self.id_provider = Some(Box::new(provider));
 
         Ok(())
     }
 
-    /// Checks whether or not exists an edge between
-    /// the vertices with the given ids.
+    /// Returns a reference to the hasher of the graph.
     ///
     /// ## Example
     /// ```rust
     /// use graphlib::Graph;
+    /// use std::collections::hash_map::RandomState;
+    /// use std::hash::BuildHasher;
     ///
-    /// let mut graph: Graph<usize> = Graph::new();
-    ///
-    /// let v1 = graph.add_vertex(1);
-    /// let v2 = graph.add_vertex(2);
-    /// let v3 = graph.add_vertex(3);
-    ///
-    /// graph.add_edge(&v1, &v2).unwrap();
-    ///
-    /// assert!(graph.has_edge(&v1, &v2));
-    /// assert!(!graph.has_edge(&v2, &v3));
+    /// let graph: Graph<usize, RandomState> = Graph::with_hasher(RandomState::new());
+    /// let mut hasher = graph.hasher().build_hasher();
     /// ```
-    pub fn has_edge(&self, a: &VertexId, b: &VertexId) -> bool {
-        match self.outbound_table.get(a) {
-            Some(outbounds) => outbounds.contains(b),
-            None => false,
-        }
+    pub fn hasher(&self) -> &S {
+        self.vertices.hasher()
     }
 
-    /// Returns the total number of edges that are listed
-    /// in the graph.
-    ///
+    /// Returns the current capacity of the graph.
     /// ## Example
     /// ```rust
     /// use graphlib::Graph;
     ///
-    /// let mut graph: Graph<usize> = Graph::new();
-    ///
-    /// let v1 = graph.add_vertex(0);
-    /// let v2 = graph.add_vertex(1);
-    /// let v3 = graph.add_vertex(2);
-    /// let v4 = graph.add_vertex(3);
-    ///
-    /// graph.add_edge(&v1, &v2).unwrap();
-    /// graph.add_edge(&v2, &v3).unwrap();
-    /// graph.add_edge(&v3, &v4).unwrap();
+    /// let mut graph: Graph<usize> = Graph::with_capacity(5);
     ///
-    /// assert_eq!(graph.edge_count(), 3);
+    /// assert!(graph.capacity() >= 5);
     /// ```
-    pub fn edge_count(&self) -> usize {
-        self.edges.len()
+    pub fn capacity(&self) -> usize {
+        min!(
+            self.vertices.capacity(),
+            self.edges.capacity(),
+            self.roots.capacity(),
+            self.tips.capacity(),
+            self.inbound_table.capacity(),
+            self.outbound_table.capacity()
+        )
     }
 
-    /// Returns the number of vertices that are placed in
-    /// the graph.
+    /// Reserves capacity for at least additional more elements to be inserted in the given
+    /// graph. After calling reserve, capacity will be greater than or equal to `self.vertex_count() + additional`.
     ///
     /// ## Example
     /// ```rust
     /// use graphlib::Graph;
     ///
-    /// let mut graph: Graph<usize> = Graph::new();
+    /// let mut graph: Graph<usize> = Graph::with_capacity(3);
+    ///
+    /// assert_eq!(graph.capacity(), 3);
     ///
+    /// graph.add_vertex(0);
     /// graph.add_vertex(1);
     /// graph.add_vertex(2);
-    /// graph.add_vertex(3);
     ///
-    /// assert_eq!(graph.vertex_count(), 3);
+    /// graph.reserve(10);
+    /// assert!(graph.capacity() >= 13);
     /// ```
-    pub fn vertex_count(&self) -> usize {
-        self.vertices.len()
+    pub fn reserve(&mut self, additional: usize) {
+        // Calculate additional value for edges vector
+        // such that it is always n^2 where n is the
+        // number of vertices that are currently placed
+        // in the graph.
+        let new_capacity = self.vertices.len() + additional;
+        let edges_capacity = if new_capacity < 100 {
+            usize::pow(new_capacity, 2)
+        } else {
+            new_capacity
+        };
+        let edges_count = self.edges.len();
+        let edges_additional = edges_capacity - edges_count;
+
+        self.edges.reserve(edges_additional);
+        self.roots.reserve(additional);
+        self.tips.reserve(additional);
+        self.vertices.reserve(additional);
+        self.outbound_table.reserve(additional);
+        self.inbound_table.reserve(additional);
+
+        self.vertex_labels.reserve(additional);
+        self.edge_labels.reserve(additional);
     }
 
-    /// Attempts to fetch a reference to an item placed
-    /// in the graph using the provided `VertexId`.
+    /// Shrinks the capacity of the graph as much as possible.
     ///
+    /// It will drop down as close as possible to the length but the allocator may still inform the
+    /// vector that there is space for a few more elements.
     /// ## Example
     /// ```rust
     /// use graphlib::Graph;
     ///
-    /// let mut graph: Graph<usize> = Graph::new();
-    /// let id = graph.add_vertex(1);
+    /// let mut graph: Graph<usize> = Graph::with_capacity(5);
     ///
-    /// assert_eq!(*graph.fetch(&id).unwrap(), 1);
+    /// assert!(graph.capacity() >= 5);
+    ///
+    /// graph.shrink_to_fit();
+    /// assert!(graph.capacity() < 5);
     /// ```
-    pub fn fetch(&self, id: &VertexId) -> Option<&T> {
-        let result = self.vertices.get(id);
-
-        match result {
-            Some((result, _)) => Some(result),
-            None => None,
-        }
-    }
+    pub fn shrink_to_fit(&mut self) {
+        self.edges.shrink_to_fit();
+        self.roots.shrink_to_fit();
+        self.tips.shrink_to_fit();
+        self.vertices.shrink_to_fit();
+        self.outbound_table.shrink_to_fit();
+        self.inbound_table.shrink_to_fit();
+        self.vertex_attrs.shrink_to_fit();
+        self.edge_attrs.shrink_to_fit();
 
-    /// Attempts to fetch a mutable reference to an item placed
-    /// in the graph using the provided `VertexId`.
-    ///
-    /// ## Example
-    /// ```rust
-    /// use graphlib::Graph;
-    ///
-    /// let mut graph: Graph<usize> = Graph::new();
-    /// let id = graph.add_vertex(1);
-    ///
-    /// assert_eq!(*graph.fetch(&id).unwrap(), 1);
-    ///
-    /// // Fetch a mutable reference
-    /// let v = graph.fetch_mut(&id).unwrap();
-    ///
-    /// // Mutate vertex value
-    /// *v = 2;
-    ///
-    /// assert_eq!(*graph.fetch(&id).unwrap(), 2);
-    /// ```
-    pub fn fetch_mut(&mut self, id: &VertexId) -> Option<&mut T> {
-        let result = self.vertices.get_mut(id);
+        self.vertex_labels.shrink_to_fit();
+        self.edge_labels.shrink_to_fit();
 
-        match result {
-            Some((result, _)) => Some(result),
-            None => None,
-        }
+        // Calculate additional value for edges vector
+        // such that it is always n^2 where n is the
+        // number of vertices that are currently placed
+        // in the graph.
+        let edges_capacity = usize::pow(self.vertices.len(), 2);
+        let edges_count = self.edges.len();
+        let edges_additional = edges_capacity - edges_count;
+
+        self.edges.reserve(edges_additional);
     }
 
-    /// Removes a vertex that matches the given `VertexId`.
+    /// Cross-checks the internal tables of the graph, returning all of the
+    /// inconsistencies found between them. The edges must match the inbound
+    /// and outbound tables, the outbound edges of each vertex must be sorted
+    /// by their weights, the roots and tips must be exactly the vertices
+    /// without inbound and outbound edges, and no table may refer to
+    /// a vertex which is not in the graph.
+    ///
+    /// This takes `O(|V| + |E|)` time and is meant for asserting the
+    /// consistency of graphs in tests, which holds unless there is a bug.
     ///
     /// ## Example
     /// ```rust
@@ -587,103 +934,106 @@ impl<T> Graph<T> {
     /// let v2 = graph.add_vertex(2);
     /// let v3 = graph.add_vertex(3);
     ///
-    /// // The remove operation is idempotent
-    /// graph.remove(&v2);
-    /// graph.remove(&v2);
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
     /// graph.remove(&v2);
     ///
-    /// assert_eq!(graph.vertex_count(), 2);
+    /// assert_eq!(graph.validate(), Ok(()));
     /// ```
-    pub fn remove(&mut self, id: &VertexId) {
-        self.vertices.remove(id);
+    pub fn validate(&self) -> Result<(), Vec<InvariantViolation>> {
+        let mut violations = Vec::new();
+        let mut dangling = HashSet::new();
+        let mut check_id = |id: &VertexId, violations: &mut Vec<InvariantViolation>| {
+            if !self.vertices.contains_key(id) && dangling.insert(*id) {
+                violations.push(InvariantViolation::DanglingId(*id));
+            }
+        };
 
-        // Remove each inbound edge
-        if let Some(inbounds) = self.inbound_table.remove(id) {
-            for vertex in inbounds {
-                self.remove_edge(&vertex, id);
+        for edge in self.edges.keys() {
+            let (a, b) = (edge.outbound(), edge.inbound());
 
-                // Add to tips if inbound vertex doesn't
-                // have other outbound vertices.
-                if self.out_neighbors_count(&vertex) == 0 {
-                    self.tips.insert(vertex);
-                }
+            check_id(a, &mut violations);
+            check_id(b, &mut violations);
+
+            if !self.outbound_table.get(a).is_some_and(|o| o.contains(b)) {
+                violations.push(InvariantViolation::MissingOutbound(*a, *b));
+            }
+
+            if !self.inbound_table.get(b).is_some_and(|i| i.contains(a)) {
+                violations.push(InvariantViolation::MissingInbound(*a, *b));
             }
         }
 
-        // Remove each outbound edge
-        if let Some(outbounds) = self.outbound_table.remove(id) {
-            for vertex in outbounds {
-                self.remove_edge(id, &vertex);
+        for (a, outbounds) in self.outbound_table.iter() {
+            check_id(a, &mut violations);
 
-                // Add to roots if outbound vertex doesn't
-                // have other inbound vertices.
-                if self.in_neighbors_count(&vertex) == 0 {
-                    self.roots.insert(vertex);
+            let mut seen = HashSet::with_capacity(outbounds.len());
+            let mut last = f32::MIN;
+            let mut sorted = true;
+
+            for b in outbounds.iter() {
+                match self.edges.get(&Edge::new(*a, *b)) {
+                    Some(weight) if seen.insert(b) => {
+                        sorted &= *weight >= last;
+                        last = *weight;
+                    }
+                    _ => violations.push(InvariantViolation::StrayOutbound(*a, *b)),
                 }
             }
+
+            if !sorted {
+                violations.push(InvariantViolation::UnsortedOutbound(*a));
+            }
         }
 
-        self.roots.remove(&id);
-        self.tips.remove(&id);
-    }
+        for (b, inbounds) in self.inbound_table.iter() {
+            check_id(b, &mut violations);
 
-    /// Removes the specified edge from the graph.
-    ///
-    /// ## Example
-    /// ```rust
-    /// use graphlib::Graph;
-    ///
-    /// let mut graph: Graph<usize> = Graph::new();
-    ///
-    /// let v1 = graph.add_vertex(0);
-    /// let v2 = graph.add_vertex(1);
-    /// let v3 = graph.add_vertex(2);
-    /// let v4 = graph.add_vertex(3);
-    ///
-    /// graph.add_edge(&v1, &v2).unwrap();
-    /// graph.add_edge(&v2, &v3).unwrap();
-    /// graph.add_edge(&v3, &v4).unwrap();
-    ///
-    /// assert_eq!(graph.edge_count(), 3);
-    ///
-    /// // The remove edge operation is idempotent
-    /// graph.remove_edge(&v2, &v3);
-    /// graph.remove_edge(&v2, &v3);
-    /// graph.remove_edge(&v2, &v3);
-    ///
-    /// assert_eq!(graph.edge_count(), 2);
-    /// ```
-    pub fn remove_edge(&mut self, a: &VertexId, b: &VertexId) {
-        if let Some(outbounds) = self.outbound_table.get_mut(a) {
-            outbounds.retain(|v| v != b);
-            if outbounds.is_empty() {
-                self.outbound_table.remove(a);
+            let mut seen = HashSet::with_capacity(inbounds.len());
+            let mut last = f32::MIN;
+            let mut sorted = true;
+
+            for a in inbounds.iter() {
+                match self.edges.get(&Edge::new(*a, *b)) {
+                    Some(weight) if seen.insert(a) => {
+                        sorted &= *weight >= last;
+                        last = *weight;
+                    }
+                    _ => violations.push(InvariantViolation::StrayInbound(*a, *b)),
+                }
             }
-        }
 
-        if let Some(inbounds) = self.inbound_table.get_mut(b) {
-            inbounds.retain(|v| v != a);
-            if inbounds.is_empty() {
-                self.inbound_table.remove(b);
+            if !sorted {
+                violations.push(InvariantViolation::UnsortedInbound(*b));
             }
         }
 
-        // If outbound vertex doesn't have any more inbounds,
-        // mark it as root.
-        if self.in_neighbors_count(&b) == 0 {
-            self.roots.insert(b.clone());
+        for id in self.roots.iter().chain(self.tips.iter()) {
+            check_id(id, &mut violations);
         }
 
-        // Mark vertex as tip if it doesn't have any more outbounds.
-        if self.out_neighbors_count(&a) == 0 {
-            self.tips.insert(a.clone());
+        for id in self.vertices.keys() {
+            let has_inbound = self.inbound_table.get(id).is_some_and(|i| !i.is_empty());
+            let has_outbound = self.outbound_table.get(id).is_some_and(|o| !o.is_empty());
+
+            if has_inbound == self.roots.contains(id) {
+                violations.push(InvariantViolation::WrongRoot(*id));
+            }
+
+            if has_outbound == self.tips.contains(id) {
+                violations.push(InvariantViolation::WrongTip(*id));
+            }
         }
 
-        self.edges.remove(&Edge::new(*a, *b));
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
     }
 
-    /// Iterates through the graph and only keeps
-    /// vertices that match the given condition.
+    /// Returns a one-line summary of the size of the graph,
+    /// which is meant for logs.
     ///
     /// ## Example
     /// ```rust
@@ -691,840 +1041,1061 @@ impl<T> Graph<T> {
     ///
     /// let mut graph: Graph<usize> = Graph::new();
     ///
-    /// graph.add_vertex(1);
-    /// graph.add_vertex(2);
-    /// graph.add_vertex(2);
-    /// graph.add_vertex(2);
-    /// graph.add_vertex(3);
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
     ///
-    /// graph.retain(|v| *v != 2);
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v1, &v3).unwrap();
     ///
-    /// assert_eq!(graph.vertex_count(), 2);
+    /// assert_eq!(
+    ///     graph.summary_string(),
+    ///     "Graph { vertices: 3, edges: 2, roots: 1, tips: 2 }"
+    /// );
     /// ```
-    pub fn retain(&mut self, fun: impl Fn(&T) -> bool) {
-        let vertices: Vec<VertexId> = self
-            .vertices()
-            .filter(|v| !fun(self.fetch(v).unwrap()))
-            .cloned()
-            .collect();
-
-        vertices.iter().for_each(|v| self.remove(&v));
+    pub fn summary_string(&self) -> String {
+        format!(
+            "Graph {{ vertices: {}, edges: {}, roots: {}, tips: {} }}",
+            self.vertex_count(),
+            self.edge_count(),
+            self.roots_count(),
+            self.tips_count()
+        )
     }
 
-    /// Performs a fold over the vertices that are
-    /// situated in the graph in Depth-First Order.
+    /// Returns estimates of the bytes allocated by each table of the
+    /// graph, which helps deciding when to call `Graph::reserve()`,
+    /// `Graph::shrink_to_fit()` or `Graph::compact()`.
     ///
     /// ## Example
     /// ```rust
     /// use graphlib::Graph;
     ///
-    /// let mut graph: Graph<usize> = Graph::new();
+    /// let mut graph: Graph<usize> = Graph::with_capacity(100);
     ///
-    /// graph.add_vertex(1);
-    /// graph.add_vertex(2);
-    /// graph.add_vertex(3);
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
     ///
-    /// let result = graph.fold(0, |v, acc| v + acc);
+    /// graph.add_edge(&v1, &v2).unwrap();
     ///
-    /// assert_eq!(result, 6);
+    /// let usage = graph.memory_usage();
+    /// graph.shrink_to_fit();
+    ///
+    /// assert!(graph.memory_usage().vertices < usage.vertices);
+    /// assert!(graph.memory_usage().total() < usage.total());
     /// ```
-    pub fn fold<A>(&self, initial: A, fun: impl Fn(&T, A) -> A) -> A {
-        let mut acc = initial;
-
-        for v in self.dfs() {
-            acc = fun(self.fetch(v).unwrap(), acc)
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let strings: usize = self
+            .vertex_labels
+            .values()
+            .chain(self.edge_labels.values())
+            .map(|label| label.capacity())
+            .sum();
+        let labels = strings
+            + table_bytes::<(VertexId, String)>(self.vertex_labels.capacity())
+            + table_bytes::<(Edge, String)>(self.edge_labels.capacity());
+
+        MemoryUsage {
+            vertices: table_bytes::<(VertexId, (T, VertexId))>(self.vertices.capacity()),
+            edges: table_bytes::<(Edge, f32)>(self.edges.capacity())
+                + table_bytes::<Edge>(self.unweighted_edges.capacity()),
+            frontier: table_bytes::<VertexId>(self.roots.capacity())
+                + table_bytes::<VertexId>(self.tips.capacity()),
+            inbound: adjacency_bytes(&self.inbound_table),
+            outbound: adjacency_bytes(&self.outbound_table),
+            labels,
+            attributes: attribute_bytes(&self.vertex_attrs) + attribute_bytes(&self.edge_attrs),
         }
-
-        acc
     }
 
-    /// Performs a map over all of the vertices of the graph,
-    /// applying the given transformation function to each one.
+    /// Renumbers the vertices of the graph into a dense range, in the
+    /// order of their ids, and rebuilds its tables with tight capacities.
+    /// Returns the mapping between the old and the new ids.
+    ///
+    /// The recorded history of the graph is cleared, as its
+    /// mutations refer to the old ids.
     ///
-    /// Returns a new graph with the same edges but with transformed
-    /// vertices.
     /// ## Example
     /// ```rust
     /// use graphlib::Graph;
     ///
-    /// let mut graph: Graph<usize> = Graph::new();
-    /// let id1 = graph.add_vertex(1);
-    /// let id2 = graph.add_vertex(2);
+    /// let mut graph: Graph<usize> = Graph::new_sequential();
     ///
-    /// graph.add_edge(&id1, &id2);
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
     ///
-    /// // Map each vertex
-    /// let mapped: Graph<usize> = graph.map(|v| v + 2);
+    /// graph.add_edge(&v1, &v3).unwrap();
+    /// graph.remove(&v2);
     ///
-    /// assert!(graph.has_edge(&id1, &id2));
-    /// assert!(mapped.has_edge(&id1, &id2));
-    /// assert_eq!(graph.fetch(&id1).unwrap(), &1);
-    /// assert_eq!(graph.fetch(&id2).unwrap(), &2);
-    /// assert_eq!(mapped.fetch(&id1).unwrap(), &3);
-    /// assert_eq!(mapped.fetch(&id2).unwrap(), &4);
+    /// let mapping = graph.compact();
+    /// let (v1, v3) = (mapping[&v1], mapping[&v3]);
+    ///
+    /// assert_eq!(v3, v2);
+    /// assert_eq!(graph.fetch(&v3), Some(&3));
+    /// assert!(graph.has_edge(&v1, &v3));
     /// ```
-    pub fn map<R>(&self, fun: impl Fn(&T) -> R) -> Graph<R> {
-        let mut graph: Graph<R> = Graph::new();
+    pub fn compact(&mut self) -> HashMap<VertexId, VertexId>
+    where
+        S: Clone,
+    {
+        let mut ids: Vec<VertexId> = self.vertices.keys().cloned().collect();
+        ids.sort();
 
-        // Copy edge and vertex information
-        graph.edges = self.edges.clone();
-        graph.roots = self.roots.clone();
-        graph.tips = self.tips.clone();
-        graph.inbound_table = self.inbound_table.clone();
-        graph.outbound_table = self.outbound_table.clone();
-        graph.vertices = self
-            .vertices
+        let mapping: HashMap<VertexId, VertexId> = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, VertexId::sequential(self.id_tag.as_ref(), i as u64)))
+            .collect();
+
+        let count = mapping.len();
+        let hasher = self.hasher().clone();
+        let rename =
+            |ids: &[VertexId]| -> Vec<VertexId> { ids.iter().map(|id| mapping[id]).collect() };
+        let rename_edge = |edge: &Edge| {
+            Some(Edge::new(
+                *mapping.get(edge.outbound())?,
+                *mapping.get(edge.inbound())?,
+            ))
+        };
+
+        let old = self.vertices.replace(HashMap::with_hasher(hasher.clone()));
+        let mut vertices = HashMap::with_capacity_and_hasher(count, hasher.clone());
+        vertices.extend(
+            old.into_iter()
+                .map(|(id, (value, _))| (mapping[&id], (value, mapping[&id]))),
+        );
+
+        let mut edges = HashMap::with_capacity_and_hasher(self.edges.len(), hasher.clone());
+        edges.extend(
+            self.edges
+                .iter()
+                .map(|(edge, weight)| (rename_edge(edge).unwrap(), *weight)),
+        );
+
+        let mut roots = HashSet::with_capacity_and_hasher(self.roots.len(), hasher.clone());
+        roots.extend(self.roots.iter().map(|id| mapping[id]));
+
+        let mut tips = HashSet::with_capacity_and_hasher(self.tips.len(), hasher.clone());
+        tips.extend(self.tips.iter().map(|id| mapping[id]));
+
+        let mut inbound_table =
+            HashMap::with_capacity_and_hasher(self.inbound_table.len(), hasher.clone());
+        inbound_table.extend(
+            self.inbound_table
+                .iter()
+                .map(|(id, ids)| (mapping[id], rename(ids))),
+        );
+
+        let mut outbound_table =
+            HashMap::with_capacity_and_hasher(self.outbound_table.len(), hasher);
+        outbound_table.extend(
+            self.outbound_table
+                .iter()
+                .map(|(id, ids)| (mapping[id], rename(ids))),
+        );
+
+        *self.unweighted_edges = self
+            .unweighted_edges
             .iter()
-            .map(|(id, (v, i))| (*id, (fun(v), *i)))
+            .filter_map(rename_edge)
+            .collect();
+        *self.priorities = self.priorities.renamed(&mapping);
+
+        if let Some(ref mut frontier) = self.frontier {
+            frontier.rename(&mapping);
+        }
+
+        *self.vertex_attrs = self
+            .vertex_attrs
+            .drain()
+            .filter_map(|(id, attrs)| Some((*mapping.get(&id)?, attrs)))
+            .collect();
+        *self.edge_attrs = self
+            .edge_attrs
+            .drain()
+            .filter_map(|(edge, attrs)| Some((rename_edge(&edge)?, attrs)))
+            .collect();
+
+        *self.vertex_labels = self
+            .vertex_labels
+            .drain()
+            .filter_map(|(id, label)| Some((*mapping.get(&id)?, label)))
+            .collect();
+        *self.edge_labels = self
+            .edge_labels
+            .drain()
+            .filter_map(|(edge, label)| Some((rename_edge(&edge)?, label)))
             .collect();
 
         #[cfg(feature = "dot")]
         {
-            graph.vertex_labels = self.vertex_labels.clone();
-            graph.edge_labels = self.edge_labels.clone();
+            for (_, group) in self.rank_groups.iter_mut() {
+                *group = group
+                    .iter()
+                    .filter_map(|id| mapping.get(id).cloned())
+                    .collect();
+            }
+
+            *self.clusters = self
+                .clusters
+                .drain()
+                .filter_map(|(id, cluster)| Some((*mapping.get(&id)?, cluster)))
+                .collect();
         }
 
-        graph
+        // Replacing the tables instead of assigning to them
+        // avoids copying the ones still shared with clones
+        self.vertices.replace(vertices);
+        self.edges.replace(edges);
+        self.roots.replace(roots);
+        self.tips.replace(tips);
+        self.inbound_table.replace(inbound_table);
+        self.outbound_table.replace(outbound_table);
+
+        if self.next_id.is_some() {
+            self.next_id = Some(count as u64);
+        }
+
+        if self.history.is_some() {
+            self.history = Some(History::default());
+        }
+
+        self.debug_validate();
+        mapping
     }
 
-    /// Returns true if the graph has cycles.
+    /// Switches the graph to copy-on-write mode, in which its tables of
+    /// vertices, edges, weights, labels, attributes and priorities are
+    /// shared with its clones instead of being copied. Each table is then
+    /// copied lazily the first time a clone that shares it is mutated, and
+    /// clones of the graph stay in copy-on-write mode.
     ///
+    /// The history of the graph, if it is tracked with
+    /// `Graph::track_history()`, and its frontier changes, if they are
+    /// tracked with `Graph::track_frontier_changes()`, are not shared,
+    /// so cloning the graph only takes constant time if neither is.
+    ///
+    /// ## Example
     /// ```rust
     /// use graphlib::Graph;
     ///
     /// let mut graph: Graph<usize> = Graph::new();
     ///
-    /// let v1 = graph.add_vertex(0);
-    /// let v2 = graph.add_vertex(1);
-    /// let v3 = graph.add_vertex(2);
-    /// let v4 = graph.add_vertex(3);
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
     ///
-    /// graph.add_edge(&v1, &v2).unwrap();
-    /// graph.add_edge(&v2, &v3).unwrap();
-    /// graph.add_edge(&v3, &v4).unwrap();
+    /// graph.copy_on_write();
     ///
-    /// assert!(!graph.is_cyclic());
+    /// // Shares the tables of `graph` until either graph is mutated
+    /// let mut snapshot = graph.clone();
+    /// assert!(snapshot.is_copy_on_write());
     ///
-    /// graph.add_edge(&v3, &v1);
+    /// snapshot.add_edge(&v1, &v2).unwrap();
     ///
-    /// assert!(graph.is_cyclic());
+    /// assert!(snapshot.has_edge(&v1, &v2));
+    /// assert!(!graph.has_edge(&v1, &v2));
     /// ```
-    pub fn is_cyclic(&self) -> bool {
-        let mut dfs = self.dfs();
-        dfs.is_cyclic()
+    pub fn copy_on_write(&mut self)
+    where
+        T: Clone,
+        S: Clone + Default,
+    {
+        self.vertices.share();
+        self.edges.share();
+        self.roots.share();
+        self.tips.share();
+        self.inbound_table.share();
+        self.outbound_table.share();
+        self.unweighted_edges.share();
+        self.priorities.share();
+        self.vertex_attrs.share();
+        self.edge_attrs.share();
+        self.vertex_labels.share();
+        self.edge_labels.share();
+
+        #[cfg(feature = "dot")]
+        {
+            self.rank_groups.share();
+            self.clusters.share();
+        }
     }
 
-    /// Returns the number of root vertices
-    /// in the graph.
+    /// Returns true if the graph is in copy-on-write mode.
     ///
     /// ## Example
     /// ```rust
     /// use graphlib::Graph;
     ///
     /// let mut graph: Graph<usize> = Graph::new();
+    /// assert!(!graph.is_copy_on_write());
     ///
-    /// let v1 = graph.add_vertex(0);
-    /// let v2 = graph.add_vertex(1);
-    /// let v3 = graph.add_vertex(2);
-    /// let v4 = graph.add_vertex(3);
-    ///
-    /// graph.add_edge(&v1, &v2).unwrap();
-    /// graph.add_edge(&v3, &v1).unwrap();
-    /// graph.add_edge(&v1, &v4).unwrap();
-    ///
-    /// assert_eq!(graph.roots_count(), 1);
+    /// graph.copy_on_write();
+    /// assert!(graph.is_copy_on_write());
     /// ```
-    pub fn roots_count(&self) -> usize {
-        self.roots.len()
+    pub fn is_copy_on_write(&self) -> bool {
+        self.vertices.is_shared()
     }
 
-    /// Returns the total count of neighboring vertices
-    /// of the vertex with the given id.
+    /// Adds a new vertex to the graph and returns the id
+    /// of the added vertex.
+    ///
+    /// Panics if the id provider of the graph returns the id of a
+    /// vertex which is already in the graph, which can be handled
+    /// with `Graph::try_add_vertex()` instead.
     ///
     /// ## Example
     /// ```rust
     /// use graphlib::Graph;
     ///
     /// let mut graph: Graph<usize> = Graph::new();
+    /// let id = graph.add_vertex(1);
     ///
-    /// let v1 = graph.add_vertex(0);
-    /// let v2 = graph.add_vertex(1);
-    /// let v3 = graph.add_vertex(2);
-    /// let v4 = graph.add_vertex(3);
-    ///
-    /// graph.add_edge(&v1, &v2).unwrap();
-    /// graph.add_edge(&v3, &v1).unwrap();
-    /// graph.add_edge(&v1, &v4).unwrap();
-    ///
-    /// assert_eq!(graph.neighbors_count(&v1), 3);
+    /// assert_eq!(graph.fetch(&id).unwrap(), &1);
     /// ```
-    pub fn neighbors_count(&self, id: &VertexId) -> usize {
-        self.in_neighbors_count(id) + self.out_neighbors_count(id)
+    pub fn add_vertex(&mut self, item: T) -> VertexId {
+        self.try_add_vertex(item).expect(USED_ID_MSG)
     }
 
-    /// Returns the total count of inbound neighboring
-    /// vertices of the vertex with the given id.
+    /// Adds a new vertex to the graph and returns the id
+    /// of the added vertex.
+    ///
+    /// Fails with `GraphErr::VertexExists` if the id provider of the
+    /// graph returns the id of a vertex which is already in the graph.
+    /// Graphs without an id provider never fail to add a vertex.
     ///
     /// ## Example
     /// ```rust
-    /// use graphlib::Graph;
+    /// use graphlib::{Graph, GraphErr, IdProvider, VertexId};
     ///
-    /// let mut graph: Graph<usize> = Graph::new();
+    /// #[derive(Clone)]
+    /// struct ValueIds;
     ///
-    /// let v1 = graph.add_vertex(0);
-    /// let v2 = graph.add_vertex(1);
-    /// let v3 = graph.add_vertex(2);
-    /// let v4 = graph.add_vertex(3);
+    /// impl IdProvider<u8> for ValueIds {
+    ///     fn next_id(&mut self, value: &u8) -> VertexId {
+    ///         VertexId::from_bytes([*value; 16])
+    ///     }
+    /// }
     ///
-    /// graph.add_edge(&v1, &v2).unwrap();
-    /// graph.add_edge(&v3, &v1).unwrap();
-    /// graph.add_edge(&v1, &v4).unwrap();
+    /// let mut graph: Graph<u8> = Graph::new();
+    /// graph.set_id_provider(ValueIds).unwrap();
     ///
-    /// assert_eq!(graph.in_neighbors_count(&v1), 1);
+    /// assert!(graph.try_add_vertex(1).is_ok());
+    /// assert_eq!(graph.try_add_vertex(1), Err(GraphErr::VertexExists));
+    /// assert_eq!(graph.vertex_count(), 1);
     /// ```
-    pub fn in_neighbors_count(&self, id: &VertexId) -> usize {
-        match self.inbound_table.get(id) {
-            Some(ins) => ins.len(),
-            None => 0,
-        }
+    pub fn try_add_vertex(&mut self, item: T) -> Result<VertexId, GraphErr> {
+        let id = match (&mut self.id_provider, self.next_id) {
+            (Some(provider), _) => {
+                let id = provider.next_id(&item);
+
+                if self.vertices.contains_key(&id) {
+                    return Err(GraphErr::VertexExists);
+                }
+
+                id
+            }
+            (None, Some(_)) => self.next_sequential_id(),
+            (None, None) => match self.id_tag {
+                Some(ref tag) => VertexId::random_tagged(tag),
+                None => VertexId::random(),
+            },
+        };
+
+        self.insert_vertex(id, item);
+
+        Ok(id)
     }
 
-    /// Returns the total count of outbound neighboring
-    /// vertices of the vertex with the given id.
+    /// Adds a new vertex with the given id to the graph, which allows
+    /// rebuilding a graph with the original ids of its vertices.
+    ///
+    /// Fails with `GraphErr::VertexExists` if there is
+    /// already a vertex with the given id in the graph.
     ///
     /// ## Example
     /// ```rust
-    /// use graphlib::Graph;
+    /// use graphlib::{Graph, GraphErr};
     ///
-    /// let mut graph: Graph<usize> = Graph::new();
+    /// let mut graph1: Graph<usize> = Graph::new();
+    /// let mut graph2: Graph<usize> = Graph::new();
     ///
-    /// let v1 = graph.add_vertex(0);
-    /// let v2 = graph.add_vertex(1);
-    /// let v3 = graph.add_vertex(2);
-    /// let v4 = graph.add_vertex(3);
-    /// let v5 = graph.add_vertex(4);
+    /// let v1 = graph1.add_vertex(1);
     ///
-    /// graph.add_edge(&v1, &v2).unwrap();
-    /// graph.add_edge(&v3, &v1).unwrap();
-    /// graph.add_edge(&v1, &v4).unwrap();
-    /// graph.add_edge(&v2, &v5).unwrap();
-    /// graph.add_edge(&v2, &v3).unwrap();
+    /// graph2.add_vertex_with_id(v1, 1).unwrap();
     ///
-    /// assert_eq!(graph.out_neighbors_count(&v1), 2);
-    /// assert_eq!(graph.out_neighbors_count(&v2), 2);
+    /// assert_eq!(graph2.fetch(&v1), Some(&1));
+    /// assert_eq!(graph2.add_vertex_with_id(v1, 2), Err(GraphErr::VertexExists));
     /// ```
-    pub fn out_neighbors_count(&self, id: &VertexId) -> usize {
-        match self.outbound_table.get(id) {
-            Some(outs) => outs.len(),
-            None => 0,
+    pub fn add_vertex_with_id(&mut self, id: VertexId, item: T) -> Result<(), GraphErr> {
+        self.check_ids(&[&id]);
+
+        if self.vertices.contains_key(&id) {
+            return Err(GraphErr::VertexExists);
         }
+
+        self.insert_vertex(id, item);
+
+        Ok(())
     }
 
-    /// Returns an iterator over the inbound neighbors
-    /// of the vertex with the given id.
+    /// Attempts to place a new edge in the graph.
     ///
     /// ## Example
     /// ```rust
-    /// use graphlib::Graph;
+    /// use graphlib::{Graph, GraphErr, VertexId};
     ///
     /// let mut graph: Graph<usize> = Graph::new();
-    /// let mut neighbors = vec![];
     ///
-    /// let v1 = graph.add_vertex(0);
-    /// let v2 = graph.add_vertex(1);
-    /// let v3 = graph.add_vertex(2);
-    /// let v4 = graph.add_vertex(3);
+    /// // Id of vertex that is not place in the graph
+    /// let id = VertexId::random();
     ///
-    /// graph.add_edge(&v1, &v2).unwrap();
-    /// graph.add_edge(&v3, &v1).unwrap();
-    /// graph.add_edge(&v1, &v4).unwrap();
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
     ///
-    /// // Iterate over neighbors
-    /// for v in graph.in_neighbors(&v1) {
-    ///     neighbors.push(v);
-    /// }
+    /// // Adding an edge is idempotent
+    /// graph.add_edge(&v1, &v2);
+    /// graph.add_edge(&v1, &v2);
+    /// graph.add_edge(&v1, &v2);
     ///
-    /// assert_eq!(neighbors.len(), 1);
-    /// assert_eq!(neighbors[0], &v3);
+    /// // Fails on adding an edge between an
+    /// // existing vertex and a non-existing one.
+    /// assert_eq!(graph.add_edge(&v1, &id), Err(GraphErr::NoSuchVertex));
     /// ```
-    pub fn in_neighbors(&self, id: &VertexId) -> VertexIter<'_> {
-        match self.inbound_table.get(id) {
-            Some(neighbors) => VertexIter(Box::new(neighbors.iter().map(AsRef::as_ref))),
-            None => VertexIter(Box::new(iter::empty())),
+    pub fn add_edge(&mut self, a: &VertexId, b: &VertexId) -> Result<(), GraphErr> {
+        if self.has_edge(a, b) {
+            return self.add_duplicate_edge(a, b, None);
         }
+
+        self.do_add_default_edge(a, b, false)
     }
 
-    /// Returns an iterator over the outbound neighbors
-    /// of the vertex with the given id.
+    /// Attempts to place a new edge in the graph, checking if the specified
+    /// edge will create a cycle in the graph. If it does, this operation will fail.
+    ///
+    /// Note that this operation has a bigger performance hit than `Graph::add_edge()`.
     ///
     /// ## Example
     /// ```rust
-    /// #[macro_use] extern crate graphlib;
-    /// use std::collections::HashSet;
-    /// use graphlib::Graph;
+    /// use graphlib::{Graph, GraphErr, VertexId};
     ///
     /// let mut graph: Graph<usize> = Graph::new();
     ///
-    /// let v1 = graph.add_vertex(0);
-    /// let v2 = graph.add_vertex(1);
-    /// let v3 = graph.add_vertex(2);
-    /// let v4 = graph.add_vertex(3);
+    /// // Id of vertex that is not place in the graph
+    /// let id = VertexId::random();
     ///
-    /// graph.add_edge(&v1, &v2).unwrap();
-    /// graph.add_edge(&v3, &v1).unwrap();
-    /// graph.add_edge(&v1, &v4).unwrap();
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
     ///
-    /// assert!(set![&v2, &v4] == graph.out_neighbors(&v1).collect());
+    /// // Adding an edge is idempotent
+    /// graph.add_edge_check_cycle(&v1, &v2);
+    /// graph.add_edge_check_cycle(&v1, &v2);
+    /// graph.add_edge_check_cycle(&v1, &v2);
+    ///
+    /// // Fails on adding an edge which creates
+    /// // a cycle in the graph.
+    /// assert_eq!(graph.add_edge_check_cycle(&v2, &v1), Err(GraphErr::CycleError));
     /// ```
-    pub fn out_neighbors(&self, id: &VertexId) -> VertexIter<'_> {
-        match self.outbound_table.get(id) {
-            Some(iter) => VertexIter(Box::new(iter.iter().rev().map(AsRef::as_ref))),
-            None => VertexIter(Box::new(iter::empty())),
+    pub fn add_edge_check_cycle(&mut self, a: &VertexId, b: &VertexId) -> Result<(), GraphErr> {
+        if self.has_edge(a, b) {
+            return self.add_duplicate_edge(a, b, None);
         }
+
+        self.do_add_default_edge(a, b, true)
     }
 
-    /// Returns an iterator over the inbound and outbound neighbors
-    /// of the vertex with the given id.
+    /// Attempts to place a new edge in the graph.
     ///
     /// ## Example
     /// ```rust
-    /// #[macro_use] extern crate graphlib;
-    /// use std::collections::HashSet;
-    /// use graphlib::Graph;
+    /// use graphlib::{Graph, GraphErr, VertexId};
     ///
     /// let mut graph: Graph<usize> = Graph::new();
     ///
-    /// let v1 = graph.add_vertex(0);
-    /// let v2 = graph.add_vertex(1);
-    /// let v3 = graph.add_vertex(2);
-    /// let v4 = graph.add_vertex(3);
+    /// // Id of vertex that is not place in the graph
+    /// let id = VertexId::random();
     ///
-    /// graph.add_edge(&v1, &v2).unwrap();
-    /// graph.add_edge(&v3, &v1).unwrap();
-    /// graph.add_edge(&v1, &v4).unwrap();
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
     ///
-    /// assert!(set![&v2, &v4, &v3] == graph.neighbors(&v1).collect());
+    /// // Adding an edge is idempotent
+    /// graph.add_edge_with_weight(&v1, &v2, 0.3);
+    ///
+    /// // Fails on adding an edge between an
+    /// // existing vertex and a non-existing one.
+    /// assert_eq!(graph.weight(&v1, &v2), Some(0.3));
     /// ```
-    pub fn neighbors(&self, id: &VertexId) -> VertexIter<'_> {
-        let mut visited = HashSet::new();
-        let neighbors = self
-            .out_neighbors(id)
-            .chain(self.in_neighbors(id))
-            //Remove duplicates.
-            .filter(move |&&v| visited.insert(v));
+    pub fn add_edge_with_weight(
+        &mut self,
+        a: &VertexId,
+        b: &VertexId,
+        weight: f32,
+    ) -> Result<(), GraphErr> {
+        if self.has_edge(a, b) {
+            return self.add_duplicate_edge(a, b, Some(weight));
+        }
 
-        VertexIter(Box::new(neighbors))
+        if !self.rules.allows_weight(weight) {
+            return Err(GraphErr::InvalidWeight);
+        }
+
+        self.do_add_edge(a, b, weight, false)
     }
 
-    /// Returns an iterator over all edges that are situated
-    /// in the graph.
+    /// Returns the weight of the specified edge
+    /// if it is listed.
     ///
-    /// ## Example
     /// ```rust
-    /// use graphlib::Graph;
+    /// use graphlib::{Graph, GraphErr, VertexId};
     ///
     /// let mut graph: Graph<usize> = Graph::new();
-    /// let mut edges = vec![];
     ///
-    /// let v1 = graph.add_vertex(0);
-    /// let v2 = graph.add_vertex(1);
-    /// let v3 = graph.add_vertex(2);
-    /// let v4 = graph.add_vertex(3);
+    /// // Id of vertex that is not place in the graph
+    /// let id = VertexId::random();
     ///
-    /// graph.add_edge(&v1, &v2).unwrap();
-    /// graph.add_edge(&v3, &v1).unwrap();
-    /// graph.add_edge(&v1, &v4).unwrap();
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
     ///
-    /// // Iterate over edges
-    /// for v in graph.edges() {
-    ///     edges.push(v);
-    /// }
+    /// // Adding an edge is idempotent
+    /// graph.add_edge_with_weight(&v1, &v2, 0.54543);
     ///
-    /// assert_eq!(edges.len(), 3);
+    /// assert_eq!(graph.weight(&v1, &v2), Some(0.54543));
+    /// assert_eq!(graph.weight(&v1, &v3), None);
     /// ```
-    pub fn edges(&self) -> impl Iterator<Item = (&VertexId, &VertexId)> {
-        self.edges.iter().map(|(e, _)| (e.inbound(), e.outbound()))
+    pub fn weight(&self, a: &VertexId, b: &VertexId) -> Option<f32> {
+        if !self.has_edge(a, b) {
+            return None;
+        }
+
+        if let Some(result) = self.edges.get(&Edge::new(*a, *b)) {
+            Some(*result)
+        } else {
+            None
+        }
     }
 
-    /// Returns an iterator over the root vertices
-    /// of the graph.
+    /// Sets the weight of the edge to the new value
+    /// if the edge exists in the graph. Note that
+    /// the given weight must be a number between
+    /// (and including) `-1.0` and `1.0`, unless other
+    /// bounds were given with `GraphBuilder::weight_bounds()`.
     ///
-    /// ## Example
     /// ```rust
-    /// use graphlib::Graph;
+    /// use graphlib::{Graph, GraphErr, VertexId};
     ///
     /// let mut graph: Graph<usize> = Graph::new();
-    /// let mut roots = vec![];
     ///
-    /// let v1 = graph.add_vertex(0);
-    /// let v2 = graph.add_vertex(1);
-    /// let v3 = graph.add_vertex(2);
-    /// let v4 = graph.add_vertex(3);
+    /// // Id of vertex that is not place in the graph
+    /// let id = VertexId::random();
     ///
-    /// graph.add_edge(&v1, &v2).unwrap();
-    /// graph.add_edge(&v3, &v1).unwrap();
-    /// graph.add_edge(&v1, &v4).unwrap();
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
     ///
-    /// // Iterate over roots
-    /// for v in graph.roots() {
-    ///     roots.push(v);
-    /// }
+    /// graph.add_edge_with_weight(&v1, &v2, 0.54543);
+    /// assert_eq!(graph.weight(&v1, &v2), Some(0.54543));
     ///
-    /// assert_eq!(roots.len(), 1);
-    /// assert_eq!(roots[0], &v3);
+    /// // Set new weight
+    /// graph.set_weight(&v1, &v2, 0.123).unwrap();
+    /// assert_eq!(graph.weight(&v1, &v2), Some(0.123));
     /// ```
-    pub fn roots(&self) -> VertexIter<'_> {
-        VertexIter(Box::new(self.roots.iter().map(AsRef::as_ref)))
+    pub fn set_weight(
+        &mut self,
+        a: &VertexId,
+        b: &VertexId,
+        new_weight: f32,
+    ) -> Result<(), GraphErr> {
+        if !self.has_edge(a, b) {
+            return Err(GraphErr::NoSuchEdge);
+        }
+
+        if !self.rules.allows_weight(new_weight) {
+            return Err(GraphErr::InvalidWeight);
+        }
+
+        let old_weight = self.edges[&Edge::new(*a, *b)];
+
+        // Take the vertices out of the adjacency lists, which
+        // are found by the weight of the edge before it changes
+        let edges = &self.edges;
+        let outbounds = self.outbound_table.get_mut(a).unwrap();
+        let out_index =
+            adjacency_position(edges, outbounds, |v| Edge::new(*a, v), b, old_weight).unwrap();
+        outbounds.remove(out_index);
+
+        let inbounds = self.inbound_table.get_mut(b).unwrap();
+        let in_index =
+            adjacency_position(edges, inbounds, |v| Edge::new(v, *b), a, old_weight).unwrap();
+        inbounds.remove(in_index);
+
+        self.edges.insert(Edge::new(*a, *b), new_weight);
+        self.weight_sum += f64::from(new_weight) - f64::from(old_weight);
+
+        let unweighted = self.is_unweighted(a, b);
+        self.record(
+            Mutation::SetWeight(*a, *b, old_weight, new_weight),
+            None,
+            unweighted,
+        );
+        self.unweighted_edges.remove(&Edge::new(*a, *b));
+
+        // Move the vertices to their places in the order given
+        // by the new weight, keeping them before the vertices
+        // which were after them if their weights are equal.
+        let edges = &self.edges;
+        let outbounds = self.outbound_table.get_mut(a).unwrap();
+        let index = adjacency_index(
+            edges,
+            outbounds,
+            |v| Edge::new(*a, v),
+            new_weight,
+            out_index,
+        );
+        outbounds.insert(index, *b);
+
+        let inbounds = self.inbound_table.get_mut(b).unwrap();
+        let index = adjacency_index(edges, inbounds, |v| Edge::new(v, *b), new_weight, in_index);
+        inbounds.insert(index, *a);
+
+        self.debug_validate();
+        Ok(())
     }
 
-    /// Returns an iterator over the tips of the graph. These
-    /// are all the vertices that have an inbound edge but no
-    /// outbound edge.
+    /// Returns a mutable reference to the weight of the edge
+    /// between the given vertices or `None` if there is no such
+    /// edge. The new weight is set when the reference is dropped.
     ///
     /// ## Example
     /// ```rust
-    /// #[macro_use] extern crate graphlib;
-    /// use std::collections::HashSet;
     /// use graphlib::Graph;
     ///
     /// let mut graph: Graph<usize> = Graph::new();
-    /// let mut tips = set![];
     ///
-    /// let v1 = graph.add_vertex(0);
-    /// let v2 = graph.add_vertex(1);
-    /// let v3 = graph.add_vertex(2);
-    /// let v4 = graph.add_vertex(3);
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
     ///
-    /// graph.add_edge(&v1, &v2).unwrap();
-    /// graph.add_edge(&v3, &v1).unwrap();
-    /// graph.add_edge(&v1, &v4).unwrap();
+    /// graph.add_edge_with_weight(&v1, &v2, 0.5).unwrap();
     ///
-    /// // Iterate over tips
-    /// for v in graph.tips() {
-    ///     tips.insert(v);
+    /// if let Some(mut weight) = graph.weight_mut(&v1, &v2) {
+    ///     *weight = -*weight;
     /// }
     ///
-    /// assert_eq!(tips.len(), 2);
-    /// assert_eq!(tips, set![&v2, &v4]);
+    /// assert_eq!(graph.weight(&v1, &v2), Some(-0.5));
+    /// assert!(graph.weight_mut(&v2, &v1).is_none());
     /// ```
-    pub fn tips(&self) -> VertexIter<'_> {
-        VertexIter(Box::new(self.tips.iter().map(AsRef::as_ref)))
+    pub fn weight_mut(&mut self, a: &VertexId, b: &VertexId) -> Option<WeightMut<'_, T, S>> {
+        WeightMut::new(self, *a, *b)
     }
 
-    /// Returns an iterator over all of the
-    /// vertices that are placed in the graph.
+    /// Returns mutable references to the weights of all edges of
+    /// the graph. The new weights are set when the references are
+    /// dropped.
     ///
     /// ## Example
     /// ```rust
     /// use graphlib::Graph;
     ///
     /// let mut graph: Graph<usize> = Graph::new();
-    /// let mut vertices = vec![];
     ///
-    /// let v1 = graph.add_vertex(0);
-    /// let v2 = graph.add_vertex(1);
-    /// let v3 = graph.add_vertex(2);
-    /// let v4 = graph.add_vertex(3);
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
     ///
-    /// // Iterate over vertices
-    /// for v in graph.vertices() {
-    ///     vertices.push(v);
+    /// graph.add_edge_with_weight(&v1, &v2, 0.2).unwrap();
+    /// graph.add_edge_with_weight(&v1, &v3, 0.6).unwrap();
+    ///
+    /// for (_, _, weight) in &mut graph.weights_mut() {
+    ///     *weight *= 0.5;
     /// }
     ///
-    /// assert_eq!(vertices.len(), 4);
+    /// assert_eq!(graph.weight(&v1, &v2), Some(0.1));
+    /// assert_eq!(graph.weight(&v1, &v3), Some(0.3));
     /// ```
-    pub fn vertices(&self) -> VertexIter<'_> {
-        VertexIter(Box::new(self.vertices.keys().map(AsRef::as_ref)))
+    pub fn weights_mut(&mut self) -> WeightsMut<'_, T, S> {
+        WeightsMut::new(self)
     }
 
-    /// Returns an iterator over the vertices
-    /// of the graph in Depth-First Order. The iterator
-    /// will follow vertices with lower weights first.
+    /// Sets the weights of the given edges which have changed, in a
+    /// single step. Weights out of the bounds of the graph are clamped
+    /// to them and weights which are not numbers are ignored.
+    pub(crate) fn update_weights(
+        &mut self,
+        edges: impl IntoIterator<Item = (VertexId, VertexId, f32)>,
+    ) {
+        self.begin_step();
+
+        for (a, b, weight) in edges {
+            if weight.is_nan() || self.weight(&a, &b) == Some(weight) {
+                continue;
+            }
+
+            let weight = weight.clamp(self.rules.min_weight, self.rules.max_weight);
+            let _ = self.set_weight(&a, &b, weight);
+        }
+
+        self.end_step();
+    }
+
+    /// Sets the policy deciding the weight of the edges which are added
+    /// with `Graph::add_edge()` or `Graph::add_edge_check_cycle()`. The
+    /// default policy is `WeightPolicy::Fixed(0.0)`. Edges already in
+    /// the graph are not affected.
+    ///
+    /// Returns `GraphErr::InvalidWeight` if the weight of a
+    /// `WeightPolicy::Fixed` policy is not between (and including)
+    /// the weight bounds of the graph, `-1.0` and `1.0` by default.
     ///
     /// ## Example
     /// ```rust
-    /// #[macro_use] extern crate graphlib;
-    /// use graphlib::Graph;
-    /// use std::collections::HashSet;
+    /// use graphlib::{Graph, WeightPolicy};
     ///
     /// let mut graph: Graph<usize> = Graph::new();
     ///
-    /// let v1 = graph.add_vertex(0);
-    /// let v2 = graph.add_vertex(1);
-    /// let v3 = graph.add_vertex(2);
-    /// let v4 = graph.add_vertex(3);
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
     ///
+    /// graph.set_weight_policy(WeightPolicy::Fixed(0.5)).unwrap();
     /// graph.add_edge(&v1, &v2).unwrap();
-    /// graph.add_edge(&v3, &v1).unwrap();
-    /// graph.add_edge(&v1, &v4).unwrap();
+    /// assert_eq!(graph.weight(&v1, &v2), Some(0.5));
     ///
-    /// let mut dfs = graph.dfs();
+    /// graph.set_weight_policy(WeightPolicy::Unweighted).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    /// assert_eq!(graph.weight(&v2, &v3), Some(0.0));
+    /// assert!(graph.is_unweighted(&v2, &v3));
+    /// assert!(!graph.is_unweighted(&v1, &v2));
+    /// ```
+    pub fn set_weight_policy(&mut self, policy: WeightPolicy) -> Result<(), GraphErr> {
+        if let WeightPolicy::Fixed(weight) = policy {
+            if !self.rules.allows_weight(weight) {
+                return Err(GraphErr::InvalidWeight);
+            }
+        }
+
+        self.weight_policy = policy;
+        Ok(())
+    }
+
+    /// Returns the policy deciding the weight of
+    /// the edges which are added without one.
     ///
-    /// assert_eq!(dfs.next(), Some(&v3));
-    /// assert_eq!(dfs.next(), Some(&v1));
-    /// assert!(set![&v2, &v4] == dfs.collect());
+    /// ## Example
+    /// ```rust
+    /// use graphlib::{Graph, WeightPolicy};
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    /// assert_eq!(graph.weight_policy(), WeightPolicy::Fixed(0.0));
+    ///
+    /// graph.set_weight_policy(WeightPolicy::Mean).unwrap();
+    /// assert_eq!(graph.weight_policy(), WeightPolicy::Mean);
     /// ```
-    pub fn dfs(&self) -> Dfs<'_, T> {
-        Dfs::new(self)
+    pub fn weight_policy(&self) -> WeightPolicy {
+        self.weight_policy
     }
 
-    /// Returns an iterator over the vertices
-    /// of the graph in Breadth-First Order. The iterator
-    /// will follow vertices with lower weights first.
+    /// Returns true if the edge between the given vertices was added
+    /// under the `WeightPolicy::Unweighted` policy and its weight has
+    /// not been set since.
     ///
     /// ## Example
     /// ```rust
-    /// use graphlib::Graph;
+    /// use graphlib::{Graph, WeightPolicy};
     ///
     /// let mut graph: Graph<usize> = Graph::new();
-    /// let mut vertices = vec![];
+    /// graph.set_weight_policy(WeightPolicy::Unweighted).unwrap();
     ///
-    /// let v1 = graph.add_vertex(0);
-    /// let v2 = graph.add_vertex(1);
-    /// let v3 = graph.add_vertex(2);
-    /// let v4 = graph.add_vertex(3);
-    /// let v5 = graph.add_vertex(4);
-    /// let v6 = graph.add_vertex(5);
-    /// let v7 = graph.add_vertex(6);
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
     ///
     /// graph.add_edge(&v1, &v2).unwrap();
-    /// graph.add_edge(&v3, &v1).unwrap();
-    /// graph.add_edge(&v1, &v4).unwrap();
-    /// graph.add_edge(&v1, &v7).unwrap();
-    /// graph.add_edge(&v2, &v5).unwrap();
-    /// graph.add_edge(&v5, &v6).unwrap();
-    ///
-    /// // Iterate over vertices
-    /// for v in graph.bfs() {
-    ///     vertices.push(v);
-    /// }
+    /// assert!(graph.is_unweighted(&v1, &v2));
     ///
-    /// assert_eq!(vertices.len(), 7);
+    /// graph.set_weight(&v1, &v2, 0.3).unwrap();
+    /// assert!(!graph.is_unweighted(&v1, &v2));
     /// ```
-    pub fn bfs(&self) -> Bfs<'_, T> {
-        Bfs::new(self)
+    pub fn is_unweighted(&self, a: &VertexId, b: &VertexId) -> bool {
+        self.unweighted_edges.contains(&Edge::new(*a, *b))
     }
 
-    /// Returns an iterator over the vertices
-    /// of the graph which follows a DFS based
-    /// topological order (Kahn's algorithm).
-    ///
-    /// Topological sorting is not possible for
-    /// graphs which contain a cycle. You may
-    /// use topo.is_cylic() == false to verify
-    /// that your graph is a DAG.
-    ///
-    /// If you attempt to use a topological
-    /// order without confirming that your graph
-    /// is a DAG, you may encounter a panic!().
-    ///
-    /// The panic!() will be encountered when
-    /// the iterator detects that there are no
-    /// more vertices to visit, but all vertices
-    /// have not been visited.
+    /// Returns the cost of traversing the given edge in shortest
+    /// path computations, which is one for unweighted edges.
+    pub(crate) fn edge_cost(&self, a: &VertexId, b: &VertexId) -> Option<f32> {
+        if self.is_unweighted(a, b) {
+            return Some(1.0);
+        }
+
+        self.weight(a, b)
+    }
+
+    /// Checks whether or not exists an edge between
+    /// the vertices with the given ids.
     ///
     /// ## Example
     /// ```rust
-    /// #[macro_use] extern crate graphlib;
     /// use graphlib::Graph;
-    /// use std::collections::HashSet;
     ///
     /// let mut graph: Graph<usize> = Graph::new();
     ///
     /// let v1 = graph.add_vertex(1);
     /// let v2 = graph.add_vertex(2);
     /// let v3 = graph.add_vertex(3);
-    /// let v4 = graph.add_vertex(4);
     ///
     /// graph.add_edge(&v1, &v2).unwrap();
-    /// graph.add_edge(&v2, &v3).unwrap();
-    /// graph.add_edge(&v3, &v4).unwrap();
-    ///
-    /// let mut topo = graph.topo();
     ///
-    /// assert_eq!(topo.next(), Some(&v1));
-    /// assert_eq!(topo.next(), Some(&v2));
-    /// assert!(set![&v3, &v4] == topo.collect());
+    /// assert!(graph.has_edge(&v1, &v2));
+    /// assert!(!graph.has_edge(&v2, &v3));
     /// ```
-    pub fn topo(&self) -> Topo<'_, T> {
-        Topo::new(self)
+    pub fn has_edge(&self, a: &VertexId, b: &VertexId) -> bool {
+        self.check_ids(&[a, b]);
+
+        match self.outbound_table.get(a) {
+            Some(outbounds) => outbounds.contains(b),
+            None => false,
+        }
     }
 
-    /// Returns an iterator over the shortest path from the source
-    /// vertex to the destination vertex. The iterator will yield
-    /// `None` if there is no such path or the provided vertex ids
-    /// do not belong to any vertices in the graph.
+    /// Checks whether each of the given pairs of vertices has an edge
+    /// between them, returning the results in the order of the pairs.
+    /// Each check is a single lookup, whatever the degree of the vertices.
+    ///
     /// ## Example
     /// ```rust
-    /// #[macro_use] extern crate graphlib;
     /// use graphlib::Graph;
-    /// use std::collections::HashSet;
     ///
     /// let mut graph: Graph<usize> = Graph::new();
     ///
     /// let v1 = graph.add_vertex(1);
     /// let v2 = graph.add_vertex(2);
     /// let v3 = graph.add_vertex(3);
-    /// let v4 = graph.add_vertex(4);
-    /// let v5 = graph.add_vertex(5);
-    /// let v6 = graph.add_vertex(6);
     ///
     /// graph.add_edge(&v1, &v2).unwrap();
     /// graph.add_edge(&v2, &v3).unwrap();
-    /// graph.add_edge(&v3, &v4).unwrap();
-    /// graph.add_edge(&v3, &v5).unwrap();
-    /// graph.add_edge(&v5, &v6).unwrap();
-    /// graph.add_edge(&v6, &v4).unwrap();
     ///
-    /// let mut dijkstra = graph.dijkstra(&v1, &v4);
+    /// let found = graph.has_edges(vec![(&v1, &v2), (&v2, &v1), (&v2, &v3)]);
     ///
-    /// assert_eq!(dijkstra.next(), Some(&v1));
-    /// assert_eq!(dijkstra.next(), Some(&v2));
-    /// assert_eq!(dijkstra.next(), Some(&v3));
-    /// assert_eq!(dijkstra.next(), Some(&v4));
-    /// assert_eq!(dijkstra.next(), None);
+    /// assert_eq!(found, vec![true, false, true]);
     /// ```
-    pub fn dijkstra<'a>(&'a self, src: &'a VertexId, dest: &'a VertexId) -> VertexIter<'a> {
-        if let Some(dijkstra) = Dijkstra::new(&self, src).ok() {
-            if let Some(iter) = dijkstra.get_path_to(dest).ok() {
-                iter
-            } else {
-                VertexIter(Box::new(iter::empty()))
-            }
-        } else {
-            VertexIter(Box::new(iter::empty()))
-        }
+    pub fn has_edges<'b>(
+        &self,
+        edges: impl IntoIterator<Item = (&'b VertexId, &'b VertexId)>,
+    ) -> Vec<bool> {
+        edges
+            .into_iter()
+            .map(|(a, b)| {
+                self.check_ids(&[a, b]);
+                self.edges.contains_key(&Edge::new(*a, *b))
+            })
+            .collect()
     }
 
-    /// Returns an iterator over the values of the vertices
-    /// placed in the graph.
+    /// Returns the total number of edges that are listed
+    /// in the graph.
     ///
     /// ## Example
     /// ```rust
-    /// #[macro_use] extern crate graphlib;
     /// use graphlib::Graph;
-    /// use std::collections::HashSet;
     ///
     /// let mut graph: Graph<usize> = Graph::new();
     ///
-    /// let v1 = graph.add_vertex(1);
-    /// let v2 = graph.add_vertex(2);
-    /// let v3 = graph.add_vertex(3);
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    /// let v4 = graph.add_vertex(3);
     ///
-    /// let mut values = graph.values();
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    /// graph.add_edge(&v3, &v4).unwrap();
     ///
-    /// assert!(set![&1, &2, &3] == values.collect());
+    /// assert_eq!(graph.edge_count(), 3);
     /// ```
-    pub fn values(&self) -> ValuesIter<'_, T> {
-        let iter = self.vertices.values().map(|(v, _)| v);
-
-        ValuesIter(Box::new(iter))
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
     }
 
-    #[cfg(feature = "dot")]
-    /// Creates a file with the dot representation of the graph.
-    /// This method requires the `dot` crate feature.
+    /// Returns the number of vertices that are placed in
+    /// the graph.
     ///
     /// ## Example
     /// ```rust
     /// use graphlib::Graph;
     ///
-    /// use std::fs::File;
-    /// let mut f = File::create("example1.dot").unwrap();
-    ///
-    /// let mut graph: Graph<String> = Graph::new();
+    /// let mut graph: Graph<usize> = Graph::new();
     ///
-    ///  let v1 = graph.add_vertex("test1".to_string());
-    ///  let v2 = graph.add_vertex("test2".to_string());
-    ///  let v3 = graph.add_vertex("test3".to_string());
-    ///  let v4 = graph.add_vertex("test4".to_string());
+    /// graph.add_vertex(1);
+    /// graph.add_vertex(2);
+    /// graph.add_vertex(3);
     ///
-    ///  let v5 = graph.add_vertex("test5".to_string());
-    ///  let v6 = graph.add_vertex("test6".to_string());
-    ///
-    ///  graph.add_edge(&v1, &v2).unwrap();
-    ///  graph.add_edge(&v3, &v1).unwrap();
-    ///  graph.add_edge(&v1, &v4).unwrap();
-    ///  graph.add_edge(&v5, &v6).unwrap();
-    ///
-    ///  assert!(graph.to_dot("example1", &mut f).is_ok());
+    /// assert_eq!(graph.vertex_count(), 3);
     /// ```
-    pub fn to_dot(
-        &self,
-        graph_name: &str,
-        output: &mut impl ::std::io::Write,
-    ) -> Result<(), GraphErr> {
-        let graph = crate::dot::DotGraph::new(&self, graph_name)?;
-        dot::render(&graph, output).map_err(|_| GraphErr::CouldNotRender)
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
     }
 
-    #[cfg(feature = "dot")]
-    /// Labels the vertex with the given id. Returns the old label if successful.
-    ///
-    /// This method requires the `dot` crate feature.
+    /// Attempts to fetch a reference to an item placed
+    /// in the graph using the provided `VertexId`.
     ///
     /// ## Example
     /// ```rust
-    /// use graphlib::{Graph, VertexId};
+    /// use graphlib::Graph;
     ///
     /// let mut graph: Graph<usize> = Graph::new();
-    /// let random_id = VertexId::random();
-    ///
-    /// let v1 = graph.add_vertex(0);
-    /// let v2 = graph.add_vertex(1);
-    /// let v3 = graph.add_vertex(2);
+    /// let id = graph.add_vertex(1);
     ///
-    /// assert!(graph.add_vertex_label(&v1, "V1").is_ok());
-    /// assert!(graph.add_vertex_label(&v2, "V2").is_ok());
-    /// assert!(graph.add_vertex_label(&v3, "V3").is_ok());
-    /// assert!(graph.add_vertex_label(&random_id, "will fail").is_err());
+    /// assert_eq!(*graph.fetch(&id).unwrap(), 1);
     /// ```
-    pub fn add_vertex_label(&mut self, vertex_id: &VertexId, label: &str)
-        -> Result<Option<String>, GraphErr>
-    {
-        if self.vertices.get(vertex_id).is_none() {
-            return Err(GraphErr::NoSuchVertex);
-        }
+    pub fn fetch(&self, id: &VertexId) -> Option<&T> {
+        self.check_ids(&[id]);
 
-        let old_label = self.vertex_labels.insert(vertex_id.clone(), label.to_owned());
-        Ok(old_label)
+        let result = self.vertices.get(id);
+
+        match result {
+            Some((result, _)) => Some(result),
+            None => None,
+        }
     }
 
-    #[cfg(feature = "dot")]
-    /// Labels the edge with between the given vertices. Returns the old label if successful.
-    ///
-    /// This method requires the `dot` crate feature.
+    /// Attempts to fetch a mutable reference to an item placed
+    /// in the graph using the provided `VertexId`.
     ///
     /// ## Example
     /// ```rust
-    /// use graphlib::{Graph, VertexId};
+    /// use graphlib::Graph;
     ///
     /// let mut graph: Graph<usize> = Graph::new();
-    /// let random_id = VertexId::random();
-    ///
-    /// let v1 = graph.add_vertex(0);
-    /// let v2 = graph.add_vertex(1);
-    /// let v3 = graph.add_vertex(2);
+    /// let id = graph.add_vertex(1);
     ///
-    /// graph.add_edge(&v1, &v2).unwrap();
-    /// graph.add_edge(&v3, &v1).unwrap();
+    /// assert_eq!(*graph.fetch(&id).unwrap(), 1);
     ///
-    /// assert!(graph.add_edge_label(&v1, &v2, "V1->V2").is_ok());
-    /// assert!(graph.add_edge_label(&v3, &v1, "V3->V1").is_ok());
-    /// assert!(graph.add_edge_label(&v2, &v3, "V2->V3").is_err());
-    /// assert!(graph.add_edge_label(&v1, &v3, "V1->V3").is_err());
-    /// ```
-    pub fn add_edge_label(&mut self, a: &VertexId, b: &VertexId, label: &str)
-        -> Result<Option<String>, GraphErr>
-    {
-        if !self.has_edge(a, b) {
-            return Err(GraphErr::NoSuchEdge);
-        }
-
-        let edge = Edge::new(a.clone(), b.clone());
-        let old_label = self.edge_labels.insert(edge, label.to_owned());
-        Ok(old_label)
-    }
-
-    #[cfg(feature = "dot")]
-    /// Retrieves the label of the vertex with the given id.
+    /// // Fetch a mutable reference
+    /// let v = graph.fetch_mut(&id).unwrap();
     ///
-    /// This method requires the `dot` crate feature.
+    /// // Mutate vertex value
+    /// *v = 2;
     ///
-    /// Returns `None` if there is no vertex associated with the given id in the graph.
-    pub fn vertex_label(&self, vertex_id: &VertexId) -> Option<&str> {
-        if !self.vertices.contains_key(vertex_id) {
-            return None;
-        }
+    /// assert_eq!(*graph.fetch(&id).unwrap(), 2);
+    /// ```
+    pub fn fetch_mut(&mut self, id: &VertexId) -> Option<&mut T> {
+        self.check_ids(&[id]);
 
-        self.vertex_labels.get(vertex_id)
-            .map(|x| x.as_str())
-            .or(Some(&DEFAULT_LABEL))
-    }
+        let result = self.vertices.get_mut(id);
 
-    #[cfg(feature = "dot")]
-    /// Retrieves the label of the edge with the given vertices.
-    ///
-    /// This method requires the `dot` crate feature.
-    ///
-    /// Returns `None` if there is no edge associated with the given vertices in the graph.
-    pub fn edge_label(&self, a: &VertexId, b: &VertexId) -> Option<&str> {
-        if !self.has_edge(a, b) {
-            return None;
+        match result {
+            Some((result, _)) => Some(result),
+            None => None,
         }
-
-        self.edge_labels.get(&Edge::new(*a, *b))
-            .map(|x| x.as_str())
-            .or(Some(&DEFAULT_LABEL))
     }
 
-    #[cfg(feature = "dot")]
-    /// Maps each label that is placed on a vertex to a new label.
-    ///
-    /// This method requires the `dot` crate feature.
+    /// Returns the entry of the vertex with the given id, which
+    /// allows adding the vertex if it is not in the graph.
     ///
+    /// ## Example
     /// ```rust
-    /// use std::collections::HashMap;
-    /// use graphlib::{Graph, VertexId};
+    /// use graphlib::Graph;
     ///
     /// let mut graph: Graph<usize> = Graph::new();
-    /// let random_id = VertexId::random();
-    /// let mut vertex_id: usize = 1;
     ///
-    /// let v1 = graph.add_vertex(0);
-    /// let v2 = graph.add_vertex(1);
-    /// let v3 = graph.add_vertex(2);
-    /// let v4 = graph.add_vertex(3);
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.entry(v1).or_insert_with(|| 5);
     ///
-    /// assert!(graph.add_vertex_label(&v1, &format!("V{}", vertex_id)).is_ok());
-    /// vertex_id += 1;
+    /// assert_eq!(*v2, 1);
     ///
-    /// assert!(graph.add_vertex_label(&v2, &format!("V{}", vertex_id)).is_ok());
-    /// vertex_id += 1;
+    /// graph.entry(v1).and_modify(|v| *v += 1);
     ///
-    /// assert!(graph.add_vertex_label(&v3, &format!("V{}", vertex_id)).is_ok());
+    /// assert_eq!(graph.fetch(&v1), Some(&2));
+    /// ```
+    pub fn entry(&mut self, id: VertexId) -> VertexEntry<'_, T, S> {
+        self.check_ids(&[&id]);
+        VertexEntry::new(self, id)
+    }
+
+    /// Removes a vertex that matches the given `VertexId`.
     ///
-    /// assert_eq!(graph.vertex_label(&v1).unwrap(), "V1");
-    /// assert_eq!(graph.vertex_label(&v2).unwrap(), "V2");
-    /// assert_eq!(graph.vertex_label(&v3).unwrap(), "V3");
+    /// The vertex is found in the adjacency lists of its neighbors by
+    /// binary searches on the weights of its edges, so only the entries
+    /// whose edges weigh as much as its own are compared to it.
     ///
-    /// let new_labels: HashMap<VertexId, String> = vec![v1.clone(), v2.clone(), v3.clone(), v4.clone()]
-    ///     .iter()
-    ///     .map(|id| {
-    ///         vertex_id += 1;
-    ///         let label = format!("V{}", vertex_id);
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
     ///
-    ///         (id.clone(), label)
-    ///     })
-    ///     .collect();
+    /// let mut graph: Graph<usize> = Graph::new();
     ///
-    /// graph.map_vertex_labels(|id, _old_label| new_labels.get(id).unwrap().clone());
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
     ///
-    /// assert_eq!(graph.vertex_label(&v1).unwrap(), "V4");
-    /// assert_eq!(graph.vertex_label(&v2).unwrap(), "V5");
-    /// assert_eq!(graph.vertex_label(&v3).unwrap(), "V6");
-    /// assert_eq!(graph.vertex_label(&v4).unwrap(), "V7");
+    /// // The remove operation is idempotent
+    /// graph.remove(&v2);
+    /// graph.remove(&v2);
+    /// graph.remove(&v2);
+    ///
+    /// assert_eq!(graph.vertex_count(), 2);
     /// ```
-    pub fn map_vertex_labels(&mut self, mut fun: impl FnMut(&VertexId, Option<&str>) -> String) {
-        for (id, _) in self.vertices.iter() {
-            self.vertex_labels.entry(*id)
-                .and_modify(|e| { *e = fun(id, Some(e)); })
-                .or_insert_with(|| fun(id, None));
+    pub fn remove(&mut self, id: &VertexId) {
+        self.check_ids(&[id]);
+        self.begin_step();
+
+        let removed = self.vertices.remove(id);
+
+        // Remove each inbound edge
+        if let Some(inbounds) = self.inbound_table.remove(id) {
+            for vertex in inbounds {
+                self.unlink_edge(&vertex, id);
+
+                // Add to tips if inbound vertex doesn't
+                // have other outbound vertices.
+                if self.out_neighbors_count(&vertex) == 0 {
+                    self.insert_tip(vertex);
+                }
+            }
+        }
+
+        // Remove each outbound edge
+        if let Some(outbounds) = self.outbound_table.remove(id) {
+            for vertex in outbounds {
+                self.unlink_edge(id, &vertex);
+
+                // Add to roots if outbound vertex doesn't
+                // have other inbound vertices.
+                if self.in_neighbors_count(&vertex) == 0 {
+                    self.insert_root(vertex);
+                }
+            }
+        }
+
+        self.remove_root(id);
+        self.remove_tip(id);
+
+        let data = self.take_vertex_data(id);
+
+        if let Some((value, _)) = removed {
+            self.record(Mutation::RemoveVertex(*id), Some((value, data)), false);
+        }
+
+        self.end_step();
+        self.debug_validate();
+
+        #[cfg(feature = "dot")]
+        {
+            for (_, vertices) in self.rank_groups.iter_mut() {
+                vertices.retain(|v| v != id);
+            }
+
+            self.rank_groups
+                .retain(|(_, vertices)| !vertices.is_empty());
+            self.clusters.remove(id);
         }
     }
 
-    #[cfg(feature = "dot")]
-    /// Maps each label that is placed on an edge to a new label.
-    ///
-    /// This method requires the `dot` crate feature.
+    /// Removes the specified edge from the graph, returning its
+    /// weight or `None` if there is no such edge in the graph.
     ///
+    /// ## Example
     /// ```rust
-    /// use std::collections::HashMap;
-    /// use graphlib::{Graph, VertexId};
+    /// use graphlib::Graph;
     ///
     /// let mut graph: Graph<usize> = Graph::new();
-    /// let random_id = VertexId::random();
-    /// let mut vertex_id: usize = 1;
     ///
     /// let v1 = graph.add_vertex(0);
     /// let v2 = graph.add_vertex(1);
@@ -1532,339 +2103,7086 @@ impl<T> Graph<T> {
     /// let v4 = graph.add_vertex(3);
     ///
     /// graph.add_edge(&v1, &v2).unwrap();
-    /// graph.add_edge(&v2, &v3).unwrap();
-    /// graph.add_edge(&v1, &v4).unwrap();
-    /// graph.add_edge(&v4, &v3).unwrap();
-    ///
-    /// assert!(graph.add_edge_label(&v1, &v2, &"V1->V2").is_ok());
-    /// assert!(graph.add_edge_label(&v2, &v3, &"V2->V3").is_ok());
-    /// assert!(graph.add_edge_label(&v1, &v4, &"V1->V4").is_ok());
-    /// assert!(graph.add_edge_label(&v4, &v3, &"V4->V3").is_ok());
-    /// assert!(graph.add_edge_label(&v1, &v3, &"V1->V3").is_err());
+    /// graph.add_edge_with_weight(&v2, &v3, 0.5).unwrap();
+    /// graph.add_edge(&v3, &v4).unwrap();
     ///
-    /// assert_eq!(graph.edge_label(&v1, &v2).unwrap(), "V1->V2");
-    /// assert_eq!(graph.edge_label(&v2, &v3).unwrap(), "V2->V3");
-    /// assert_eq!(graph.edge_label(&v1, &v4).unwrap(), "V1->V4");
-    /// assert_eq!(graph.edge_label(&v4, &v3).unwrap(), "V4->V3");
+    /// assert_eq!(graph.edge_count(), 3);
     ///
-    /// graph.map_edge_labels(|edge, old_label| format!("*{}*", old_label.unwrap()));
+    /// // The remove edge operation is idempotent
+    /// assert_eq!(graph.remove_edge(&v2, &v3), Some(0.5));
+    /// assert_eq!(graph.remove_edge(&v2, &v3), None);
+    /// assert_eq!(graph.remove_edge(&v2, &v3), None);
     ///
-    /// assert_eq!(graph.edge_label(&v1, &v2).unwrap(), "*V1->V2*");
-    /// assert_eq!(graph.edge_label(&v2, &v3).unwrap(), "*V2->V3*");
-    /// assert_eq!(graph.edge_label(&v1, &v4).unwrap(), "*V1->V4*");
-    /// assert_eq!(graph.edge_label(&v4, &v3).unwrap(), "*V4->V3*");
+    /// assert_eq!(graph.edge_count(), 2);
     /// ```
-    pub fn map_edge_labels(&mut self, mut fun: impl FnMut(&Edge, Option<&str>) -> String) {
-        for (edge, _) in self.edges.iter() {
-            self.edge_labels.entry(Edge::new(*edge.outbound(), *edge.inbound()))
-                .and_modify(|e| { *e = fun(edge, Some(e)); })
-                .or_insert_with(|| fun(edge, None));
+    pub fn remove_edge(&mut self, a: &VertexId, b: &VertexId) -> Option<f32> {
+        let weight = self.unlink_edge(a, b);
+
+        self.debug_validate();
+        weight
+    }
+
+    /// Removes the specified edge from the graph without checking
+    /// the invariants afterwards, since `Graph::remove()` removes
+    /// the edges of a vertex after the vertex itself.
+    fn unlink_edge(&mut self, a: &VertexId, b: &VertexId) -> Option<f32> {
+        self.check_ids(&[a, b]);
+
+        let edge = Edge::new(*a, *b);
+        let weight = *self.edges.get(&edge)?;
+
+        // The vertices are found by binary searches on the weight
+        // of the edge, so it is removed from the table afterwards
+        let edges = &self.edges;
+
+        if let Some(outbounds) = self.outbound_table.get_mut(a) {
+            if let Some(i) = adjacency_position(edges, outbounds, |v| Edge::new(*a, v), b, weight) {
+                outbounds.remove(i);
+            }
+
+            if outbounds.is_empty() {
+                self.outbound_table.remove(a);
+            }
+        }
+
+        if let Some(inbounds) = self.inbound_table.get_mut(b) {
+            if let Some(i) = adjacency_position(edges, inbounds, |v| Edge::new(v, *b), a, weight) {
+                inbounds.remove(i);
+            }
+
+            if inbounds.is_empty() {
+                self.inbound_table.remove(b);
+            }
+        }
+
+        self.edges.remove(&edge);
+
+        // If outbound vertex doesn't have any more inbounds,
+        // mark it as root.
+        if self.in_neighbors_count(&b) == 0 {
+            self.insert_root(*b);
         }
+
+        // Mark vertex as tip if it doesn't have any more outbounds.
+        if self.out_neighbors_count(&a) == 0 {
+            self.insert_tip(*a);
+        }
+
+        self.weight_sum -= f64::from(weight);
+
+        let unweighted = self.is_unweighted(a, b);
+        self.record(Mutation::RemoveEdge(*a, *b, weight), None, unweighted);
+        self.unweighted_edges.remove(&edge);
+        self.edge_attrs.remove(&edge);
+
+        self.edge_labels.remove(&edge);
+
+        Some(weight)
     }
 
-    fn do_add_edge(
-        &mut self,
-        a: &VertexId,
-        b: &VertexId,
-        weight: f32,
-        check_cycle: bool,
-    ) -> Result<(), GraphErr> {
-        let id_ptr1 = if self.vertices.get(a).is_some() {
-            *a
-        } else {
+    /// Iterates through the graph and only keeps
+    /// vertices that match the given condition.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// graph.add_vertex(1);
+    /// graph.add_vertex(2);
+    /// graph.add_vertex(2);
+    /// graph.add_vertex(2);
+    /// graph.add_vertex(3);
+    ///
+    /// graph.retain(|v| *v != 2);
+    ///
+    /// assert_eq!(graph.vertex_count(), 2);
+    /// ```
+    pub fn retain(&mut self, fun: impl Fn(&T) -> bool) {
+        self.retain_with(|_, v, _| fun(v));
+    }
+
+    /// Only keeps the vertices for which the given condition, called
+    /// with the id, the value and the degree of each vertex, is true.
+    /// The degrees are the ones the vertices have before any of them
+    /// is removed.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(10);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(20);
+    /// let v4 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v1, &v3).unwrap();
+    /// graph.add_edge(&v3, &v4).unwrap();
+    /// graph.add_edge(&v4, &v1).unwrap();
+    ///
+    /// // Remove the leaves with a value lower than 5
+    /// graph.retain_with(|_, v, degree| degree.outbound > 0 || *v >= 5);
+    ///
+    /// assert_eq!(graph.vertex_count(), 3);
+    /// assert_eq!(graph.fetch(&v2), None);
+    /// ```
+    pub fn retain_with(&mut self, fun: impl Fn(&VertexId, &T, Degree) -> bool) {
+        let vertices: Vec<VertexId> = self
+            .vertices
+            .iter()
+            .filter(|(id, (v, _))| {
+                let degree = Degree {
+                    inbound: self.in_neighbors_count(id),
+                    outbound: self.out_neighbors_count(id),
+                };
+
+                !fun(id, v, degree)
+            })
+            .map(|(id, _)| *id)
+            .collect();
+
+        self.begin_step();
+        vertices.iter().for_each(|v| self.remove(&v));
+        self.end_step();
+    }
+
+    /// Only keeps the edges for which the given condition, called
+    /// with the outbound vertex, the inbound vertex and the weight of
+    /// each edge, is true. All vertices are kept and the ones left
+    /// without inbound or outbound edges become roots or tips.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge_with_weight(&v1, &v2, 0.1).unwrap();
+    /// graph.add_edge_with_weight(&v1, &v3, 0.8).unwrap();
+    /// graph.add_edge_with_weight(&v2, &v3, 0.5).unwrap();
+    ///
+    /// // Prune the low-weight edges
+    /// graph.retain_edges(|_, _, weight| weight >= 0.5);
+    ///
+    /// assert_eq!(graph.vertex_count(), 3);
+    /// assert_eq!(graph.edge_count(), 2);
+    /// assert!(!graph.has_edge(&v1, &v2));
+    /// assert!(graph.roots().any(|v| v == &v2));
+    /// ```
+    pub fn retain_edges(&mut self, fun: impl Fn(&VertexId, &VertexId, f32) -> bool) {
+        let edges: Vec<Edge> = self
+            .edges
+            .iter()
+            .filter(|(edge, weight)| !fun(edge.outbound(), edge.inbound(), **weight))
+            .map(|(edge, _)| edge.clone())
+            .collect();
+
+        self.begin_step();
+
+        for edge in edges {
+            self.remove_edge(edge.outbound(), edge.inbound());
+        }
+
+        self.end_step();
+    }
+
+    /// Removes all vertices and edges from the graph,
+    /// keeping the allocated capacity of its tables.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::with_capacity(10);
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.clear();
+    ///
+    /// assert_eq!(graph.vertex_count(), 0);
+    /// assert_eq!(graph.edge_count(), 0);
+    /// assert!(graph.capacity() >= 10);
+    /// ```
+    pub fn clear(&mut self) {
+        // Remove the vertices one by one so the removals are recorded
+        if self.history.is_some() || self.frontier.is_some() {
+            self.retain(|_| false);
+            return;
+        }
+
+        self.clear_except_vertices();
+        self.vertices.clear();
+        self.debug_validate();
+    }
+
+    /// Removes all vertices and edges from the graph, returning an
+    /// iterator over the ids and the values of the removed vertices.
+    /// The recorded history of the graph is cleared, as the values
+    /// are moved out of it.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<String> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex("a".to_string());
+    /// let v2 = graph.add_vertex("b".to_string());
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    ///
+    /// let mut values: Vec<String> = graph.drain().map(|(_, value)| value).collect();
+    /// values.sort();
+    ///
+    /// assert_eq!(values, vec!["a", "b"]);
+    /// assert_eq!(graph.vertex_count(), 0);
+    /// assert_eq!(graph.edge_count(), 0);
+    /// ```
+    pub fn drain(&mut self) -> impl Iterator<Item = (VertexId, T)> + '_ {
+        if self.history.is_some() {
+            self.history = Some(History::default());
+        }
+
+        self.clear_except_vertices();
+
+        let drained: Vec<(VertexId, T)> = self
+            .vertices
+            .drain()
+            .map(|(id, (value, _))| (id, value))
+            .collect();
+
+        self.debug_validate();
+        drained.into_iter()
+    }
+
+    /// Clears all tables but the table of vertices
+    /// without recording the removals.
+    fn clear_except_vertices(&mut self) {
+        let history = self.history.take();
+        let frontier = self.frontier.take();
+
+        self.clear_edges();
+        self.roots.clear();
+        self.tips.clear();
+        *self.priorities = PriorityIndex::default();
+        self.vertex_attrs.clear();
+        self.vertex_labels.clear();
+
+        #[cfg(feature = "dot")]
+        {
+            self.rank_groups.clear();
+            self.clusters.clear();
+        }
+
+        self.history = history;
+        self.frontier = frontier;
+    }
+
+    /// Removes all edges from the graph while keeping its vertices,
+    /// which all become roots and tips.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.clear_edges();
+    ///
+    /// assert_eq!(graph.vertex_count(), 2);
+    /// assert_eq!(graph.edge_count(), 0);
+    /// assert_eq!(graph.roots().count(), 2);
+    /// assert_eq!(graph.tips().count(), 2);
+    /// ```
+    pub fn clear_edges(&mut self) {
+        // Remove the edges one by one so the removals are recorded
+        if self.history.is_some() || self.frontier.is_some() {
+            self.retain_edges(|_, _, _| false);
+            return;
+        }
+
+        self.edges.clear();
+        self.inbound_table.clear();
+        self.outbound_table.clear();
+        self.unweighted_edges.clear();
+        self.weight_sum = 0.0;
+
+        let ids: Vec<VertexId> = self.vertices.keys().cloned().collect();
+
+        self.roots.extend(ids.iter().cloned());
+        self.tips.extend(ids);
+        self.edge_attrs.clear();
+
+        self.edge_labels.clear();
+        self.debug_validate();
+    }
+
+    /// Only keeps the `k` outgoing edges with the highest
+    /// weights of each vertex, removing the others.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    /// let v4 = graph.add_vertex(4);
+    ///
+    /// graph.add_edge_with_weight(&v1, &v2, 0.2).unwrap();
+    /// graph.add_edge_with_weight(&v1, &v3, 0.9).unwrap();
+    /// graph.add_edge_with_weight(&v1, &v4, 0.5).unwrap();
+    /// graph.add_edge_with_weight(&v2, &v3, 0.1).unwrap();
+    ///
+    /// graph.keep_top_k_out_edges(2);
+    ///
+    /// assert_eq!(graph.edge_count(), 3);
+    /// assert!(!graph.has_edge(&v1, &v2));
+    /// assert!(graph.has_edge(&v1, &v3));
+    /// assert!(graph.has_edge(&v1, &v4));
+    /// assert!(graph.has_edge(&v2, &v3));
+    ///
+    /// // v2 no longer has any inbound edges
+    /// assert!(graph.roots().any(|v| v == &v2));
+    /// ```
+    pub fn keep_top_k_out_edges(&mut self, k: usize) {
+        let mut removed: HashMap<VertexId, HashSet<VertexId>> = HashMap::new();
+        let mut tips = Vec::new();
+
+        self.begin_step();
+
+        // Outbounds are sorted by weight in ascending order
+        for (a, outbounds) in self.outbound_table.iter_mut() {
+            if outbounds.len() <= k {
+                continue;
+            }
+
+            for b in outbounds.drain(..outbounds.len() - k) {
+                let unweighted = self.unweighted_edges.remove(&Edge::new(*a, b));
+
+                if let Some(weight) = self.edges.remove(&Edge::new(*a, b)) {
+                    self.weight_sum -= f64::from(weight);
+
+                    if let Some(ref mut history) = self.history {
+                        history.record(Mutation::RemoveEdge(*a, b, weight), None, unweighted);
+                    }
+                }
+
+                self.edge_attrs.remove(&Edge::new(*a, b));
+
+                self.edge_labels.remove(&Edge::new(*a, b));
+
+                removed.entry(b).or_default().insert(*a);
+            }
+
+            if outbounds.is_empty() {
+                tips.push(*a);
+            }
+        }
+
+        for a in tips {
+            self.outbound_table.remove(&a);
+            self.insert_tip(a);
+        }
+
+        for (b, sources) in removed {
+            let inbounds = self.inbound_table.get_mut(&b).unwrap();
+            inbounds.retain(|a| !sources.contains(a));
+
+            if inbounds.is_empty() {
+                self.inbound_table.remove(&b);
+                self.insert_root(b);
+            }
+        }
+
+        self.end_step();
+        self.debug_validate();
+    }
+
+    /// Sets the priority of the vertex with the given id, adding it to the
+    /// priority index of the graph. The index is kept up to date as
+    /// vertices are removed from the graph.
+    ///
+    /// Fails with `GraphErr::NoSuchVertex` if there is no such vertex or
+    /// with `GraphErr::InvalidWeight` if the priority is NaN.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge_with_weight(&v1, &v2, 0.4).unwrap();
+    /// graph.add_edge_with_weight(&v1, &v3, 0.2).unwrap();
+    /// graph.add_edge_with_weight(&v3, &v2, 0.9).unwrap();
+    ///
+    /// // Prioritize vertices by their total outbound weight
+    /// for id in [v1, v2, v3].iter() {
+    ///     let score = graph.out_neighbors(id).map(|n| graph.weight(id, n).unwrap()).sum();
+    ///     graph.set_priority(id, score).unwrap();
+    /// }
+    ///
+    /// graph.remove(&v3);
+    ///
+    /// assert_eq!(graph.pop_highest(), Some((v1, 0.6)));
+    /// assert_eq!(graph.pop_highest(), Some((v2, 0.0)));
+    /// assert_eq!(graph.pop_highest(), None);
+    /// ```
+    pub fn set_priority(&mut self, id: &VertexId, priority: f32) -> Result<(), GraphErr> {
+        if self.fetch(id).is_none() {
             return Err(GraphErr::NoSuchVertex);
+        }
+
+        if priority.is_nan() {
+            return Err(GraphErr::InvalidWeight);
+        }
+
+        self.priorities.insert(*id, priority);
+        Ok(())
+    }
+
+    /// Returns the priority of the vertex with the given
+    /// id or `None` if it is not in the priority index.
+    pub fn priority(&self, id: &VertexId) -> Option<f32> {
+        self.priorities.get(id)
+    }
+
+    /// Removes the vertex with the given id from the priority
+    /// index and returns its priority if it had one.
+    pub fn remove_priority(&mut self, id: &VertexId) -> Option<f32> {
+        self.priorities.remove(id)
+    }
+
+    /// Returns the number of vertices in the priority index.
+    pub fn priority_count(&self) -> usize {
+        self.priorities.len()
+    }
+
+    /// Returns the vertex with the highest priority along with its
+    /// priority. Ties are broken in favor of the highest vertex id.
+    pub fn peek_highest(&self) -> Option<(VertexId, f32)> {
+        self.priorities.highest()
+    }
+
+    /// Removes the vertex with the highest priority from the priority
+    /// index, keeping it in the graph, and returns it along with its
+    /// priority. Ties are broken in favor of the highest vertex id.
+    pub fn pop_highest(&mut self) -> Option<(VertexId, f32)> {
+        self.priorities.pop_highest()
+    }
+
+    /// Performs a fold over the vertices that are
+    /// situated in the graph in Depth-First Order.
+    /// Use `Graph::fold_in()` to pick another order.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// graph.add_vertex(1);
+    /// graph.add_vertex(2);
+    /// graph.add_vertex(3);
+    ///
+    /// let result = graph.fold(0, |v, acc| v + acc);
+    ///
+    /// assert_eq!(result, 6);
+    /// ```
+    pub fn fold<A>(&self, initial: A, fun: impl Fn(&T, A) -> A) -> A {
+        self.fold_in(Traversal::Dfs, initial, fun)
+    }
+
+    /// Performs a fold over the vertices that are
+    /// situated in the graph in the given order.
+    ///
+    /// Panics if the order is `Traversal::Topo`
+    /// and the graph contains cycles.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::{Graph, Traversal};
+    ///
+    /// let mut graph: Graph<&str> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex("a");
+    /// let v2 = graph.add_vertex("b");
+    /// let v3 = graph.add_vertex("c");
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    ///
+    /// let result = graph.fold_in(Traversal::Topo, String::new(), |v, acc| acc + v);
+    ///
+    /// assert_eq!(result, "abc");
+    /// ```
+    pub fn fold_in<A>(&self, order: Traversal, initial: A, fun: impl Fn(&T, A) -> A) -> A {
+        let vertices: Box<dyn Iterator<Item = &VertexId>> = match order {
+            Traversal::Dfs => Box::new(self.dfs()),
+            Traversal::Bfs => Box::new(self.bfs()),
+            Traversal::Topo => Box::new(self.topo()),
         };
 
-        let id_ptr2 = if self.vertices.get(b).is_some() {
-            *b
-        } else {
-            return Err(GraphErr::NoSuchVertex);
-        };
+        vertices.fold(initial, |acc, v| fun(self.fetch(v).unwrap(), acc))
+    }
+
+    /// Performs a fold over the edges of the graph, in no particular
+    /// order. The given function is called with the outbound vertex,
+    /// the inbound vertex and the weight of each edge.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge_with_weight(&v1, &v2, 0.25).unwrap();
+    /// graph.add_edge_with_weight(&v2, &v3, 0.5).unwrap();
+    ///
+    /// let total = graph.fold_edges(0.0, |_, _, weight, acc| acc + weight);
+    ///
+    /// assert_eq!(total, 0.75);
+    /// ```
+    pub fn fold_edges<A>(&self, initial: A, fun: impl Fn(&VertexId, &VertexId, f32, A) -> A) -> A {
+        self.edges.iter().fold(initial, |acc, (edge, weight)| {
+            fun(edge.outbound(), edge.inbound(), *weight, acc)
+        })
+    }
+
+    /// Performs a map over all of the vertices of the graph,
+    /// applying the given transformation function to each one.
+    ///
+    /// Returns a new graph with the same edges but with transformed
+    /// vertices.
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    /// let id1 = graph.add_vertex(1);
+    /// let id2 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&id1, &id2);
+    ///
+    /// // Map each vertex
+    /// let mapped: Graph<usize> = graph.map(|v| v + 2);
+    ///
+    /// assert!(graph.has_edge(&id1, &id2));
+    /// assert!(mapped.has_edge(&id1, &id2));
+    /// assert_eq!(graph.fetch(&id1).unwrap(), &1);
+    /// assert_eq!(graph.fetch(&id2).unwrap(), &2);
+    /// assert_eq!(mapped.fetch(&id1).unwrap(), &3);
+    /// assert_eq!(mapped.fetch(&id2).unwrap(), &4);
+    /// ```
+    pub fn map<R>(&self, fun: impl Fn(&T) -> R) -> Graph<R, S>
+    where
+        S: Clone,
+    {
+        self.map_with_id(|_, v| fun(v))
+    }
+
+    /// Performs a map over all of the vertices of the graph, applying
+    /// the given transformation function to the id and the value of
+    /// each one.
+    ///
+    /// Returns a new graph with the same edges but with transformed
+    /// vertices.
+    /// ## Example
+    /// ```rust
+    /// use graphlib::{Graph, VertexId};
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    /// let id1 = graph.add_vertex(1);
+    /// let id2 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&id1, &id2);
+    ///
+    /// // Pair each value with the id of its vertex
+    /// let mapped: Graph<(VertexId, usize)> = graph.map_with_id(|id, v| (*id, *v));
+    ///
+    /// assert!(mapped.has_edge(&id1, &id2));
+    /// assert_eq!(mapped.fetch(&id1).unwrap(), &(id1, 1));
+    /// assert_eq!(mapped.fetch(&id2).unwrap(), &(id2, 2));
+    /// ```
+    pub fn map_with_id<R>(&self, fun: impl Fn(&VertexId, &T) -> R) -> Graph<R, S>
+    where
+        S: Clone,
+    {
+        let mut vertices =
+            HashMap::with_capacity_and_hasher(self.vertex_count(), self.hasher().clone());
+
+        vertices.extend(
+            self.vertices
+                .iter()
+                .map(|(id, (v, i))| (*id, (fun(id, v), *i))),
+        );
+
+        self.with_vertices(vertices)
+    }
+
+    /// Performs a map over all of the vertices of the graph, applying
+    /// the given transformation function to the id and the value of
+    /// each one. The vertices mapped to `None` are left out of the new
+    /// graph along with their edges.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<&str> = Graph::new();
+    /// let id1 = graph.add_vertex("1");
+    /// let id2 = graph.add_vertex("two");
+    /// let id3 = graph.add_vertex("3");
+    ///
+    /// graph.add_edge(&id1, &id2).unwrap();
+    /// graph.add_edge(&id1, &id3).unwrap();
+    ///
+    /// // Keep the vertices whose values parse as numbers
+    /// let mapped: Graph<usize> = graph.filter_map(|_, v| v.parse().ok());
+    ///
+    /// assert_eq!(mapped.vertex_count(), 2);
+    /// assert_eq!(mapped.edge_count(), 1);
+    /// assert!(mapped.has_edge(&id1, &id3));
+    /// assert_eq!(mapped.fetch(&id3).unwrap(), &3);
+    /// assert_eq!(mapped.fetch(&id2), None);
+    /// ```
+    pub fn filter_map<R>(&self, fun: impl Fn(&VertexId, &T) -> Option<R>) -> Graph<R, S>
+    where
+        S: Clone,
+    {
+        let values = self
+            .vertices
+            .iter()
+            .filter_map(|(id, (v, _))| Some((*id, fun(id, v)?)))
+            .collect();
+
+        self.subgraph_with(values)
+    }
+
+    #[cfg(feature = "rayon")]
+    /// Parallel version of `Graph::map()`, which applies the given
+    /// function to the values of the vertices on multiple threads.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let id1 = graph.add_vertex(1);
+    /// let id2 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&id1, &id2).unwrap();
+    ///
+    /// let mapped: Graph<usize> = graph.par_map(|v| v * 10);
+    ///
+    /// assert!(mapped.has_edge(&id1, &id2));
+    /// assert_eq!(mapped.fetch(&id1).unwrap(), &10);
+    /// assert_eq!(mapped.fetch(&id2).unwrap(), &20);
+    /// ```
+    pub fn par_map<R: Send>(&self, fun: impl Fn(&T) -> R + Sync + Send) -> Graph<R, S>
+    where
+        T: Sync,
+        S: Clone + Send + Sync,
+    {
+        let mut vertices =
+            HashMap::with_capacity_and_hasher(self.vertex_count(), self.hasher().clone());
+
+        vertices.par_extend(
+            self.vertices
+                .par_iter()
+                .map(|(id, (v, i))| (*id, (fun(v), *i))),
+        );
+
+        self.with_vertices(vertices)
+    }
+
+    /// Creates a graph with the edges of this graph and the given vertices.
+    fn with_vertices<R>(&self, vertices: HashMap<VertexId, (R, VertexId), S>) -> Graph<R, S>
+    where
+        S: Clone,
+    {
+        let mut graph: Graph<R, S> = Graph::with_hasher(self.hasher().clone());
+
+        // Copy edge and vertex information
+        graph.edges = self.edges.clone();
+        graph.roots = self.roots.clone();
+        graph.tips = self.tips.clone();
+        graph.inbound_table = self.inbound_table.clone();
+        graph.outbound_table = self.outbound_table.clone();
+        graph.id_tag = self.id_tag;
+        graph.next_id = self.next_id;
+        graph.weight_policy = self.weight_policy;
+        graph.rules = self.rules;
+        graph.weight_sum = self.weight_sum;
+        graph.unweighted_edges = self.unweighted_edges.clone();
+        graph.priorities = self.priorities.clone();
+        graph.vertex_attrs = self.vertex_attrs.clone();
+        graph.edge_attrs = self.edge_attrs.clone();
+        graph.vertex_labels = self.vertex_labels.clone();
+        graph.edge_labels = self.edge_labels.clone();
+        graph.vertices = vertices.into();
+
+        #[cfg(feature = "dot")]
+        {
+            graph.rank_groups = self.rank_groups.clone();
+            graph.clusters = self.clusters.clone();
+        }
+
+        graph
+    }
+
+    /// Returns the subgraph induced by the given set of vertices, which
+    /// contains the vertices of the set along with the edges between them.
+    /// The vertices keep their ids in the new graph, and the edges keep
+    /// their weights and labels.
+    ///
+    /// Fails with `GraphErr::GraphMismatch` if the set belongs
+    /// to a different graph.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::{Graph, VertexSet};
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    ///
+    /// let set = VertexSet::from_ids(&graph, &[v1, v2]).unwrap();
+    /// let subgraph = graph.subgraph(&set).unwrap();
+    ///
+    /// assert_eq!(subgraph.vertex_count(), 2);
+    /// assert!(subgraph.has_edge(&v1, &v2));
+    /// assert_eq!(subgraph.fetch(&v3), None);
+    /// ```
+    pub fn subgraph(&self, set: &VertexSet<'_, T, S>) -> Result<Graph<T, S>, GraphErr>
+    where
+        S: Clone,
+        T: Clone,
+    {
+        VertexSet::new(self).check_graph(set)?;
+
+        let values = set
+            .iter()
+            .map(|id| (*id, self.fetch(id).unwrap().clone()))
+            .collect();
+
+        let mut graph = self.subgraph_with(values);
+        graph.id_provider = self.id_provider.clone();
+
+        Ok(graph)
+    }
+
+    /// Creates the subgraph induced by the vertices with the
+    /// ids of the given map, which holds their new values.
+    fn subgraph_with<R>(&self, values: HashMap<VertexId, R>) -> Graph<R, S>
+    where
+        S: Clone,
+    {
+        let mut graph: Graph<R, S> =
+            Graph::with_capacity_and_hasher(values.len(), self.hasher().clone());
+        graph.id_tag = self.id_tag;
+        graph.next_id = self.next_id;
+        graph.weight_policy = self.weight_policy;
+        graph.rules = self.rules;
+
+        let ids: HashSet<VertexId> = values.keys().cloned().collect();
+
+        for (id, value) in values {
+            graph.insert_vertex(id, value);
+        }
+
+        for id in ids.iter() {
+            for n in self.out_neighbors(id).filter(|n| ids.contains(*n)) {
+                let weight = self.weight(id, n).unwrap();
+                graph.do_add_edge(id, n, weight, false).unwrap();
+
+                if self.is_unweighted(id, n) {
+                    graph.unweighted_edges.insert(Edge::new(*id, *n));
+                }
+
+                if let Some(attrs) = self.edge_attrs.get(&Edge::new(*id, *n)) {
+                    graph.edge_attrs.insert(Edge::new(*id, *n), attrs.clone());
+                }
+
+                if let Some(label) = self.edge_labels.get(&Edge::new(*id, *n)) {
+                    graph.edge_labels.insert(Edge::new(*id, *n), label.clone());
+                }
+            }
+
+            if let Some(attrs) = self.vertex_attrs.get(id) {
+                graph.vertex_attrs.insert(*id, attrs.clone());
+            }
+
+            if let Some(label) = self.vertex_labels.get(id) {
+                graph.vertex_labels.insert(*id, label.clone());
+            }
+        }
+
+        graph
+    }
+
+    /// Returns the subgraph induced by the vertices with the given ids.
+    /// This is the same as `Graph::subgraph()` for a set of the given ids.
+    ///
+    /// Fails with `GraphErr::NoSuchVertex` if any of the
+    /// ids does not belong to a vertex of the graph.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    /// use std::collections::HashSet;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge_with_weight(&v1, &v2, 0.5).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    ///
+    /// let ids: HashSet<_> = vec![v1, v2].into_iter().collect();
+    /// let subgraph = graph.induced_subgraph(&ids).unwrap();
+    ///
+    /// assert_eq!(subgraph.vertex_count(), 2);
+    /// assert_eq!(subgraph.weight(&v1, &v2), Some(0.5));
+    /// assert_eq!(subgraph.fetch(&v3), None);
+    /// ```
+    pub fn induced_subgraph<'b>(
+        &self,
+        ids: impl IntoIterator<Item = &'b VertexId>,
+    ) -> Result<Graph<T, S>, GraphErr>
+    where
+        S: Clone,
+        T: Clone,
+    {
+        let set = VertexSet::from_ids(self, ids)?;
+        self.subgraph(&set)
+    }
+
+    /// Returns a copy of the graph which only contains the vertices
+    /// matching the given condition, along with the edges between them.
+    /// This is the non-destructive counterpart of `Graph::retain()`.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    ///
+    /// let filtered = graph.filter(|_, v| *v != 3);
+    ///
+    /// assert_eq!(filtered.vertex_count(), 2);
+    /// assert!(filtered.has_edge(&v1, &v2));
+    /// assert_eq!(graph.vertex_count(), 3);
+    /// ```
+    pub fn filter(&self, fun: impl Fn(&VertexId, &T) -> bool) -> Graph<T, S>
+    where
+        S: Clone,
+        T: Clone,
+    {
+        let ids = self
+            .vertices
+            .iter()
+            .filter(|(id, (v, _))| fun(id, v))
+            .map(|(id, _)| id);
+
+        self.subgraph(&VertexSet::from_ids(self, ids).unwrap())
+            .unwrap()
+    }
+
+    /// Returns a copy of the graph which only contains the edges
+    /// matching the given condition. The condition receives the
+    /// outbound vertex, the inbound vertex and the weight of each edge.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge_with_weight(&v1, &v2, 0.8).unwrap();
+    /// graph.add_edge_with_weight(&v2, &v3, 0.1).unwrap();
+    ///
+    /// let filtered = graph.filter_edges(|_, _, w| w > 0.5);
+    ///
+    /// assert_eq!(filtered.vertex_count(), 3);
+    /// assert!(filtered.has_edge(&v1, &v2));
+    /// assert!(!filtered.has_edge(&v2, &v3));
+    /// assert!(graph.has_edge(&v2, &v3));
+    /// ```
+    pub fn filter_edges(&self, fun: impl Fn(&VertexId, &VertexId, f32) -> bool) -> Graph<T, S>
+    where
+        S: Clone,
+        T: Clone,
+    {
+        let mut graph = self.clone();
+
+        for (edge, weight) in self.edges.iter() {
+            if !fun(edge.outbound(), edge.inbound(), *weight) {
+                graph.remove_edge(edge.outbound(), edge.inbound());
+            }
+        }
+
+        graph
+    }
+
+    /// Copies the vertices and edges of the other graph into this graph,
+    /// along with their weights, and returns the mapping between the ids
+    /// of the vertices in the other graph and their new ids.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph1: Graph<usize> = Graph::new();
+    /// let mut graph2: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph1.add_vertex(1);
+    /// let v2 = graph2.add_vertex(2);
+    /// let v3 = graph2.add_vertex(3);
+    ///
+    /// graph2.add_edge_with_weight(&v2, &v3, 0.5).unwrap();
+    ///
+    /// let mapping = graph1.merge(&graph2);
+    ///
+    /// assert_eq!(graph1.vertex_count(), 3);
+    /// assert_eq!(graph1.fetch(&mapping[&v2]), Some(&2));
+    /// assert_eq!(graph1.weight(&mapping[&v2], &mapping[&v3]), Some(0.5));
+    ///
+    /// // Connect the merged vertices to the original ones
+    /// graph1.add_edge(&v1, &mapping[&v2]).unwrap();
+    /// ```
+    pub fn merge(&mut self, other: &Graph<T, S>) -> HashMap<VertexId, VertexId>
+    where
+        T: Clone,
+    {
+        let mut mapping = HashMap::with_capacity(other.vertex_count());
+        self.begin_step();
+
+        for (id, (value, _)) in other.vertices.iter() {
+            mapping.insert(*id, self.add_vertex(value.clone()));
+        }
+
+        for (edge, weight) in other.edges.iter() {
+            let a = mapping[edge.outbound()];
+            let b = mapping[edge.inbound()];
+
+            self.do_add_edge(&a, &b, *weight, false).unwrap();
+
+            if other.unweighted_edges.contains(edge) {
+                self.unweighted_edges.insert(Edge::new(a, b));
+            }
+        }
+
+        for (id, attrs) in other.vertex_attrs.iter() {
+            self.vertex_attrs.insert(mapping[id], attrs.clone());
+        }
+
+        for (edge, attrs) in other.edge_attrs.iter() {
+            let edge = Edge::new(mapping[edge.outbound()], mapping[edge.inbound()]);
+            self.edge_attrs.insert(edge, attrs.clone());
+        }
+
+        for (id, label) in other.vertex_labels.iter() {
+            self.vertex_labels.insert(mapping[id], label.clone());
+        }
+
+        for (edge, label) in other.edge_labels.iter() {
+            let edge = Edge::new(mapping[edge.outbound()], mapping[edge.inbound()]);
+            self.edge_labels.insert(edge, label.clone());
+        }
+
+        self.end_step();
+        self.debug_validate();
+        mapping
+    }
+
+    /// Creates a graph containing the vertices and edges of both given
+    /// graphs side by side, preserving their weights and labels. The
+    /// vertices of the first graph keep their ids, while the vertices of
+    /// the second one get new ids, which are returned as a mapping from
+    /// their ids in the second graph.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph1: Graph<usize> = Graph::new();
+    /// let mut graph2: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph1.add_vertex(1);
+    /// let v2 = graph1.add_vertex(2);
+    /// let v3 = graph2.add_vertex(3);
+    /// let v4 = graph2.add_vertex(4);
+    ///
+    /// graph1.add_edge(&v1, &v2).unwrap();
+    /// graph2.add_edge(&v3, &v4).unwrap();
+    ///
+    /// let (union, mapping) = Graph::disjoint_union(&graph1, &graph2);
+    ///
+    /// assert_eq!(union.vertex_count(), 4);
+    /// assert_eq!(union.edge_count(), 2);
+    /// assert!(union.has_edge(&v1, &v2));
+    /// assert!(union.has_edge(&mapping[&v3], &mapping[&v4]));
+    /// ```
+    pub fn disjoint_union(
+        a: &Graph<T, S>,
+        b: &Graph<T, S>,
+    ) -> (Graph<T, S>, HashMap<VertexId, VertexId>)
+    where
+        S: Clone,
+        T: Clone,
+    {
+        let mut graph = a.clone();
+        let mapping = graph.merge(b);
+
+        (graph, mapping)
+    }
+
+    /// Groups the vertices of the graph by the keys
+    /// which the given function maps their values to.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// let groups = graph.group_by(|v| v % 2);
+    ///
+    /// assert_eq!(groups.len(), 2);
+    /// assert_eq!(groups[&0].len(), 1);
+    /// assert!(groups[&0].contains(&v2));
+    /// assert!(groups[&1].contains(&v1));
+    /// assert!(groups[&1].contains(&v3));
+    /// ```
+    pub fn group_by<K: Hash + Eq>(&self, fun: impl Fn(&T) -> K) -> HashMap<K, VertexSet<'_, T, S>> {
+        crate::grouping::group_by(self, fun)
+    }
+
+    /// Returns the quotient graph of the grouping computed by
+    /// `Graph::group_by()`, where each group of vertices is collapsed
+    /// into a single vertex holding the key of the group.
+    ///
+    /// Two groups are connected if there are edges between their vertices,
+    /// in the same direction, and the weight of the new edge is the average
+    /// weight of those edges. Edges between vertices of the same group are
+    /// dropped.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<&str> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex("red");
+    /// let v2 = graph.add_vertex("red");
+    /// let v3 = graph.add_vertex("blue");
+    ///
+    /// graph.add_edge_with_weight(&v1, &v3, 0.2).unwrap();
+    /// graph.add_edge_with_weight(&v2, &v3, 0.4).unwrap();
+    /// graph.add_edge(&v1, &v2).unwrap();
+    ///
+    /// let quotient = graph.quotient_graph(|v| *v);
+    ///
+    /// let red = quotient.vertices().find(|v| quotient.fetch(v) == Some(&"red")).unwrap();
+    /// let blue = quotient.vertices().find(|v| quotient.fetch(v) == Some(&"blue")).unwrap();
+    ///
+    /// assert_eq!(quotient.vertex_count(), 2);
+    /// assert_eq!(quotient.edge_count(), 1);
+    /// assert!((quotient.weight(red, blue).unwrap() - 0.3).abs() < 0.0001);
+    /// ```
+    pub fn quotient_graph<K: Hash + Eq + Clone>(&self, fun: impl Fn(&T) -> K) -> Graph<K, S>
+    where
+        S: Clone,
+    {
+        crate::grouping::quotient(self, fun)
+    }
+
+    /// Returns the bipartite double cover of the graph. Each vertex is
+    /// split into two copies and each edge `a -> b` is replaced by the
+    /// edges between the opposite copies, keeping its weight.
+    ///
+    /// The values of the new vertices are built by the given function
+    /// out of the id and value of the original vertex and the copy that
+    /// is created, which is `false` for the first and `true` for the second.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    /// graph.add_edge(&v3, &v1).unwrap();
+    ///
+    /// let cover: Graph<(usize, bool)> = graph.bipartite_double_cover(|_id, v, copy| (*v, copy));
+    ///
+    /// assert_eq!(cover.vertex_count(), 6);
+    /// assert_eq!(cover.edge_count(), 6);
+    ///
+    /// // Edges only connect the two copies
+    /// for (b, a) in cover.edges() {
+    ///     assert_ne!(cover.fetch(a).unwrap().1, cover.fetch(b).unwrap().1);
+    /// }
+    /// ```
+    pub fn bipartite_double_cover<R>(&self, fun: impl Fn(&VertexId, &T, bool) -> R) -> Graph<R, S>
+    where
+        S: Clone,
+    {
+        let mut graph: Graph<R, S> =
+            Graph::with_capacity_and_hasher(self.vertex_count() * 2, self.hasher().clone());
+        let mut copies: HashMap<VertexId, (VertexId, VertexId)> =
+            HashMap::with_capacity(self.vertex_count());
+
+        for (id, (v, _)) in self.vertices.iter() {
+            let first = graph.add_vertex(fun(id, v, false));
+            let second = graph.add_vertex(fun(id, v, true));
+
+            copies.insert(*id, (first, second));
+        }
+
+        for (edge, weight) in self.edges.iter() {
+            let (a1, a2) = copies[edge.outbound()];
+            let (b1, b2) = copies[edge.inbound()];
+
+            graph.do_add_edge(&a1, &b2, *weight, false).unwrap();
+            graph.do_add_edge(&a2, &b1, *weight, false).unwrap();
+        }
+
+        graph
+    }
+
+    /// Returns the line graph of the graph. Each edge `a -> b` becomes
+    /// a vertex, and there is an edge between the vertices of the edges
+    /// `a -> b` and `b -> c` for each such pair of edges.
+    ///
+    /// The values of the new vertices are built by the given function
+    /// out of the outbound vertex, the inbound vertex and the weight
+    /// of the original edge.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::{Graph, VertexId};
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    /// let v4 = graph.add_vertex(4);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    /// graph.add_edge(&v2, &v4).unwrap();
+    ///
+    /// let lines: Graph<(VertexId, VertexId)> = graph.line_graph(|a, b, _weight| (*a, *b));
+    ///
+    /// assert_eq!(lines.vertex_count(), 3);
+    /// assert_eq!(lines.edge_count(), 2);
+    ///
+    /// // The edge between `v1` and `v2` leads to both other edges
+    /// let root = lines.roots().next().unwrap();
+    ///
+    /// assert_eq!(lines.fetch(root), Some(&(v1, v2)));
+    /// assert_eq!(lines.out_neighbors_count(root), 2);
+    /// ```
+    pub fn line_graph<R>(&self, fun: impl Fn(&VertexId, &VertexId, f32) -> R) -> Graph<R, S>
+    where
+        S: Clone,
+    {
+        let mut graph: Graph<R, S> =
+            Graph::with_capacity_and_hasher(self.edge_count(), self.hasher().clone());
+        let mut lines: HashMap<&Edge, VertexId> = HashMap::with_capacity(self.edge_count());
+
+        for (edge, weight) in self.edges.iter() {
+            let id = graph.add_vertex(fun(edge.outbound(), edge.inbound(), *weight));
+            lines.insert(edge, id);
+        }
+
+        for (edge, id) in lines.iter() {
+            for next in self.out_neighbors(edge.inbound()) {
+                let next_id = lines[&Edge::new(*edge.inbound(), *next)];
+                graph.do_add_edge(id, &next_id, 0.0, false).unwrap();
+            }
+        }
+
+        graph
+    }
+
+    /// Returns the structural differences between this graph and the
+    /// given one, matching vertices by their ids. The graphs are equal
+    /// if the returned diff is empty.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph1: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph1.add_vertex(1);
+    /// let v2 = graph1.add_vertex(2);
+    ///
+    /// graph1.add_edge(&v1, &v2).unwrap();
+    ///
+    /// let mut graph2 = graph1.clone();
+    /// assert!(graph1.diff(&graph2).is_empty());
+    ///
+    /// graph2.set_weight(&v1, &v2, 0.5).unwrap();
+    /// let diff = graph1.diff(&graph2);
+    ///
+    /// assert_eq!(diff.weights, vec![(v1, v2, 0.0, 0.5)]);
+    /// assert!(diff.left_edges.is_empty());
+    /// ```
+    pub fn diff<'a>(&'a self, other: &'a Graph<T, S>) -> GraphDiff<'a, T>
+    where
+        T: PartialEq,
+    {
+        crate::diff::diff(self, other)
+    }
+
+    /// Approximates the edit distance between this graph and the given
+    /// one, which is the total cost of the operations that transform
+    /// this graph into the other.
+    ///
+    /// Inserting or deleting a vertex or an edge costs `1.0`. The cost of
+    /// substituting a vertex value or an edge weight is computed by the
+    /// given comparators, which are expected to return numbers between
+    /// (and including) `0.0` and `1.0`. The returned distance is never
+    /// lower than the exact edit distance.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph1: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph1.add_vertex(1);
+    /// let v2 = graph1.add_vertex(2);
+    /// let v3 = graph1.add_vertex(3);
+    ///
+    /// graph1.add_edge(&v1, &v2).unwrap();
+    /// graph1.add_edge(&v2, &v3).unwrap();
+    ///
+    /// let mut graph2 = graph1.clone();
+    /// graph2.remove_edge(&v2, &v3);
+    ///
+    /// let value_cost = |a: &usize, b: &usize| if a == b { 0.0 } else { 1.0 };
+    /// let weight_cost = |a: f32, b: f32| (a - b).abs();
+    ///
+    /// assert_eq!(graph1.edit_distance(&graph1, value_cost, weight_cost), 0.0);
+    /// assert_eq!(graph1.edit_distance(&graph2, value_cost, weight_cost), 1.0);
+    /// ```
+    pub fn edit_distance<U>(
+        &self,
+        other: &Graph<U, S>,
+        value_cost: impl Fn(&T, &U) -> f32,
+        weight_cost: impl Fn(f32, f32) -> f32,
+    ) -> f32 {
+        crate::edit_distance::edit_distance(self, other, value_cost, weight_cost)
+    }
+
+    /// Returns a similarity score between this graph and the given one,
+    /// which is `1.0` for equal graphs and `0.0` for graphs which have
+    /// nothing in common. The score is based on the edit distance that
+    /// is computed by `Graph::edit_distance()`.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph1: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph1.add_vertex(1);
+    /// let v2 = graph1.add_vertex(2);
+    ///
+    /// graph1.add_edge(&v1, &v2).unwrap();
+    ///
+    /// let mut graph2 = graph1.clone();
+    /// graph2.remove_edge(&v1, &v2);
+    ///
+    /// let value_cost = |a: &usize, b: &usize| if a == b { 0.0 } else { 1.0 };
+    /// let weight_cost = |a: f32, b: f32| (a - b).abs();
+    ///
+    /// assert_eq!(graph1.similarity(&graph1, value_cost, weight_cost), 1.0);
+    /// assert_eq!(graph1.similarity(&graph2, value_cost, weight_cost), 0.8);
+    /// assert_eq!(graph1.similarity(&Graph::<usize>::new(), value_cost, weight_cost), 0.0);
+    /// ```
+    pub fn similarity<U>(
+        &self,
+        other: &Graph<U, S>,
+        value_cost: impl Fn(&T, &U) -> f32,
+        weight_cost: impl Fn(f32, f32) -> f32,
+    ) -> f32 {
+        // Cost of deleting this graph and inserting the other
+        let max_distance =
+            (self.vertex_count() + self.edge_count() + other.vertex_count() + other.edge_count())
+                as f32;
+
+        if max_distance == 0.0 {
+            return 1.0;
+        }
+
+        let distance = self.edit_distance(other, value_cost, weight_cost);
+
+        (1.0 - distance / max_distance).max(0.0)
+    }
+
+    /// Returns true if the graph has cycles.
+    ///
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    /// let v4 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    /// graph.add_edge(&v3, &v4).unwrap();
+    ///
+    /// assert!(!graph.is_cyclic());
+    ///
+    /// graph.add_edge(&v3, &v1);
+    ///
+    /// assert!(graph.is_cyclic());
+    /// ```
+    pub fn is_cyclic(&self) -> bool {
+        let mut dfs = self.dfs();
+        dfs.is_cyclic()
+    }
+
+    /// Returns true if there is a bijection between the vertices of the
+    /// graph and the vertices of the other graph which preserves edges
+    /// and for which `value_eq` holds for the values of every vertex
+    /// and its image. Vertex ids and edge weights are ignored, so pass
+    /// `|_, _| true` as `value_eq` to compare only the structures.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph1: Graph<usize> = Graph::new();
+    /// let mut graph2: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph1.add_vertex(1);
+    /// let v2 = graph1.add_vertex(2);
+    /// let v3 = graph2.add_vertex(2);
+    /// let v4 = graph2.add_vertex(1);
+    ///
+    /// graph1.add_edge(&v1, &v2).unwrap();
+    /// graph2.add_edge(&v4, &v3).unwrap();
+    ///
+    /// assert!(graph1.is_isomorphic_to(&graph2, |a, b| a == b));
+    ///
+    /// graph2.remove_edge(&v4, &v3);
+    /// graph2.add_edge(&v3, &v4).unwrap();
+    ///
+    /// assert!(graph1.is_isomorphic_to(&graph2, |_, _| true));
+    /// assert!(!graph1.is_isomorphic_to(&graph2, |a, b| a == b));
+    /// ```
+    pub fn is_isomorphic_to<U>(
+        &self,
+        other: &Graph<U, S>,
+        value_eq: impl Fn(&T, &U) -> bool,
+    ) -> bool {
+        Matcher::new(self, other, MatchMode::Isomorphism, value_eq, |_, _| true)
+            .next_match()
+            .is_some()
+    }
+
+    /// Same as `Graph::is_isomorphic_to()`, but also requires
+    /// `weight_eq` to hold for the weights of every edge and its image.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph1: Graph<usize> = Graph::new();
+    /// let mut graph2: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph1.add_vertex(1);
+    /// let v2 = graph1.add_vertex(2);
+    /// let v3 = graph2.add_vertex(1);
+    /// let v4 = graph2.add_vertex(2);
+    ///
+    /// graph1.add_edge_with_weight(&v1, &v2, 0.5).unwrap();
+    /// graph2.add_edge_with_weight(&v3, &v4, 0.2).unwrap();
+    ///
+    /// assert!(graph1.is_isomorphic_to(&graph2, |a, b| a == b));
+    /// assert!(!graph1.is_isomorphic_to_weighted(&graph2, |a, b| a == b, |w1, w2| w1 == w2));
+    /// ```
+    pub fn is_isomorphic_to_weighted<U>(
+        &self,
+        other: &Graph<U, S>,
+        value_eq: impl Fn(&T, &U) -> bool,
+        weight_eq: impl Fn(f32, f32) -> bool,
+    ) -> bool {
+        Matcher::new(self, other, MatchMode::Isomorphism, value_eq, weight_eq)
+            .next_match()
+            .is_some()
+    }
+
+    /// Returns an iterator over the occurrences of the pattern graph as
+    /// an induced subgraph of this graph. Each item maps the vertices of
+    /// the pattern to the vertices of this graph, such that two pattern
+    /// vertices are connected exactly if their images are connected.
+    /// Vertex values and edge weights are ignored.
+    ///
+    /// Symmetric patterns yield one mapping per symmetry
+    /// for each set of matching vertices.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    /// let v4 = graph.add_vertex(4);
+    ///
+    /// graph.add_edge(&v1, &v3).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    /// graph.add_edge(&v3, &v4).unwrap();
+    ///
+    /// // Edge followed by another edge
+    /// let mut pattern: Graph<()> = Graph::new();
+    ///
+    /// let p1 = pattern.add_vertex(());
+    /// let p2 = pattern.add_vertex(());
+    /// let p3 = pattern.add_vertex(());
+    ///
+    /// pattern.add_edge(&p1, &p2).unwrap();
+    /// pattern.add_edge(&p2, &p3).unwrap();
+    ///
+    /// let mut starts: Vec<usize> = graph
+    ///     .find_subgraph_isomorphisms(&pattern)
+    ///     .map(|mapping| *graph.fetch(&mapping[&p1]).unwrap())
+    ///     .collect();
+    ///
+    /// starts.sort();
+    /// assert_eq!(starts, vec![1, 2]);
+    /// ```
+    pub fn find_subgraph_isomorphisms<'a, U>(
+        &'a self,
+        pattern: &'a Graph<U, S>,
+    ) -> SubgraphMatches<'a, U, T, S> {
+        SubgraphMatches::new(pattern, self)
+    }
+
+    /// Returns a hash of the structure of the graph, which does not depend
+    /// on the vertex ids, the vertex values or the edge weights. Isomorphic
+    /// graphs always have the same hash, while graphs with the same hash
+    /// are very likely, but not guaranteed, to be isomorphic.
+    ///
+    /// The hash is stable across runs, so it can be used in persistent caches.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph1: Graph<usize> = Graph::new();
+    /// let mut graph2: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph1.add_vertex(1);
+    /// let v2 = graph1.add_vertex(2);
+    /// let v3 = graph2.add_vertex(3);
+    /// let v4 = graph2.add_vertex(4);
+    ///
+    /// graph1.add_edge(&v1, &v2).unwrap();
+    /// graph2.add_edge(&v4, &v3).unwrap();
+    ///
+    /// assert_eq!(graph1.structural_hash(), graph2.structural_hash());
+    ///
+    /// graph2.add_edge(&v3, &v4).unwrap();
+    ///
+    /// assert_ne!(graph1.structural_hash(), graph2.structural_hash());
+    /// ```
+    pub fn structural_hash(&self) -> u64 {
+        isomorphism::structural_hash(self)
+    }
+
+    /// Returns the number of root vertices
+    /// in the graph.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    /// let v4 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v3, &v1).unwrap();
+    /// graph.add_edge(&v1, &v4).unwrap();
+    ///
+    /// assert_eq!(graph.roots_count(), 1);
+    /// ```
+    pub fn roots_count(&self) -> usize {
+        self.roots.len()
+    }
+
+    /// Returns the number of tip vertices
+    /// in the graph.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    /// let v4 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v3, &v1).unwrap();
+    /// graph.add_edge(&v1, &v4).unwrap();
+    ///
+    /// assert_eq!(graph.tips_count(), 2);
+    /// ```
+    pub fn tips_count(&self) -> usize {
+        self.tips.len()
+    }
+
+    /// Returns the total count of neighboring vertices
+    /// of the vertex with the given id.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    /// let v4 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v3, &v1).unwrap();
+    /// graph.add_edge(&v1, &v4).unwrap();
+    ///
+    /// assert_eq!(graph.neighbors_count(&v1), 3);
+    /// ```
+    pub fn neighbors_count(&self, id: &VertexId) -> usize {
+        self.in_neighbors_count(id) + self.out_neighbors_count(id)
+    }
+
+    /// Returns the total count of inbound neighboring
+    /// vertices of the vertex with the given id.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    /// let v4 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v3, &v1).unwrap();
+    /// graph.add_edge(&v1, &v4).unwrap();
+    ///
+    /// assert_eq!(graph.in_neighbors_count(&v1), 1);
+    /// ```
+    pub fn in_neighbors_count(&self, id: &VertexId) -> usize {
+        self.check_ids(&[id]);
+
+        match self.inbound_table.get(id) {
+            Some(ins) => ins.len(),
+            None => 0,
+        }
+    }
+
+    /// Returns the total count of outbound neighboring
+    /// vertices of the vertex with the given id.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    /// let v4 = graph.add_vertex(3);
+    /// let v5 = graph.add_vertex(4);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v3, &v1).unwrap();
+    /// graph.add_edge(&v1, &v4).unwrap();
+    /// graph.add_edge(&v2, &v5).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    ///
+    /// assert_eq!(graph.out_neighbors_count(&v1), 2);
+    /// assert_eq!(graph.out_neighbors_count(&v2), 2);
+    /// ```
+    pub fn out_neighbors_count(&self, id: &VertexId) -> usize {
+        self.check_ids(&[id]);
+
+        match self.outbound_table.get(id) {
+            Some(outs) => outs.len(),
+            None => 0,
+        }
+    }
+
+    /// Returns the number of inbound edges of the vertex with
+    /// the given id. This is the same as `Graph::in_neighbors_count()`.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    ///
+    /// assert_eq!(graph.in_degree(&v1), 0);
+    /// assert_eq!(graph.in_degree(&v2), 1);
+    /// ```
+    pub fn in_degree(&self, id: &VertexId) -> usize {
+        self.in_neighbors_count(id)
+    }
+
+    /// Returns the number of outbound edges of the vertex with
+    /// the given id. This is the same as `Graph::out_neighbors_count()`.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    ///
+    /// assert_eq!(graph.out_degree(&v1), 1);
+    /// assert_eq!(graph.out_degree(&v2), 0);
+    /// ```
+    pub fn out_degree(&self, id: &VertexId) -> usize {
+        self.out_neighbors_count(id)
+    }
+
+    /// Returns the number of edges of the vertex with the given id,
+    /// which is the sum of its inbound and outbound edges. An edge
+    /// from the vertex to itself is counted twice.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v1).unwrap();
+    ///
+    /// assert_eq!(graph.degree(&v1), 2);
+    /// ```
+    pub fn degree(&self, id: &VertexId) -> usize {
+        self.in_degree(id) + self.out_degree(id)
+    }
+
+    /// Returns the highest number of inbound edges of a
+    /// vertex of the graph or `None` if the graph is empty.
+    pub fn max_in_degree(&self) -> Option<usize> {
+        self.vertices.keys().map(|v| self.in_degree(v)).max()
+    }
+
+    /// Returns the lowest number of inbound edges of a
+    /// vertex of the graph or `None` if the graph is empty.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// assert_eq!(graph.min_in_degree(), None);
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v1).unwrap();
+    ///
+    /// assert_eq!(graph.min_in_degree(), Some(1));
+    /// ```
+    pub fn min_in_degree(&self) -> Option<usize> {
+        self.vertices.keys().map(|v| self.in_degree(v)).min()
+    }
+
+    /// Returns the highest number of outbound edges of a
+    /// vertex of the graph or `None` if the graph is empty.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v1, &v3).unwrap();
+    ///
+    /// assert_eq!(graph.max_out_degree(), Some(2));
+    /// assert_eq!(graph.min_out_degree(), Some(0));
+    /// ```
+    pub fn max_out_degree(&self) -> Option<usize> {
+        self.vertices.keys().map(|v| self.out_degree(v)).max()
+    }
+
+    /// Returns the lowest number of outbound edges of a
+    /// vertex of the graph or `None` if the graph is empty.
+    pub fn min_out_degree(&self) -> Option<usize> {
+        self.vertices.keys().map(|v| self.out_degree(v)).min()
+    }
+
+    /// Returns an iterator over the inbound neighbors
+    /// of the vertex with the given id.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    /// let mut neighbors = vec![];
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    /// let v4 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v3, &v1).unwrap();
+    /// graph.add_edge(&v1, &v4).unwrap();
+    ///
+    /// // Iterate over neighbors
+    /// for v in graph.in_neighbors(&v1) {
+    ///     neighbors.push(v);
+    /// }
+    ///
+    /// assert_eq!(neighbors.len(), 1);
+    /// assert_eq!(neighbors[0], &v3);
+    /// ```
+    pub fn in_neighbors(&self, id: &VertexId) -> VertexIter<'_> {
+        self.check_ids(&[id]);
+
+        match self.inbound_table.get(id) {
+            Some(neighbors) => VertexIter(Box::new(neighbors.iter().map(AsRef::as_ref))),
+            None => VertexIter(Box::new(iter::empty())),
+        }
+    }
+
+    /// Returns an iterator over the outbound neighbors
+    /// of the vertex with the given id.
+    ///
+    /// ## Example
+    /// ```rust
+    /// #[macro_use] extern crate graphlib;
+    /// use std::collections::HashSet;
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    /// let v4 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v3, &v1).unwrap();
+    /// graph.add_edge(&v1, &v4).unwrap();
+    ///
+    /// assert!(set![&v2, &v4] == graph.out_neighbors(&v1).collect());
+    /// ```
+    pub fn out_neighbors(&self, id: &VertexId) -> VertexIter<'_> {
+        self.check_ids(&[id]);
+
+        match self.outbound_table.get(id) {
+            Some(iter) => VertexIter(Box::new(iter.iter().rev().map(AsRef::as_ref))),
+            None => VertexIter(Box::new(iter::empty())),
+        }
+    }
+
+    /// Returns an iterator over the inbound and outbound neighbors
+    /// of the vertex with the given id.
+    ///
+    /// ## Example
+    /// ```rust
+    /// #[macro_use] extern crate graphlib;
+    /// use std::collections::HashSet;
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    /// let v4 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v3, &v1).unwrap();
+    /// graph.add_edge(&v1, &v4).unwrap();
+    ///
+    /// assert!(set![&v2, &v4, &v3] == graph.neighbors(&v1).collect());
+    /// ```
+    pub fn neighbors(&self, id: &VertexId) -> VertexIter<'_> {
+        let mut visited = HashSet::new();
+        let neighbors = self
+            .out_neighbors(id)
+            .chain(self.in_neighbors(id))
+            //Remove duplicates.
+            .filter(move |&&v| visited.insert(v));
+
+        VertexIter(Box::new(neighbors))
+    }
+
+    /// Returns the vertices which can be reached from the given vertex
+    /// by following at most `k` edges, mapped to the least number of
+    /// edges reaching them. The given vertex is part of its own
+    /// neighborhood, at a distance of `0`.
+    ///
+    /// Fails with `GraphErr::NoSuchVertex` if there is no such vertex.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    /// let v4 = graph.add_vertex(4);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    /// graph.add_edge(&v3, &v4).unwrap();
+    /// graph.add_edge(&v1, &v3).unwrap();
+    ///
+    /// let neighborhood = graph.neighborhood(&v1, 2).unwrap();
+    ///
+    /// assert_eq!(neighborhood.len(), 4);
+    /// assert_eq!(neighborhood[&v3], 1);
+    /// assert_eq!(neighborhood[&v4], 2);
+    /// assert_eq!(graph.neighborhood(&v4, 2).unwrap().len(), 1);
+    /// ```
+    pub fn neighborhood(
+        &self,
+        id: &VertexId,
+        k: usize,
+    ) -> Result<HashMap<VertexId, usize>, GraphErr> {
+        self.neighborhood_by(id, k, |v| self.out_neighbors(v))
+    }
+
+    /// Returns the vertices within `k` hops of the given vertex when
+    /// the directions of the edges are ignored, mapped to their least
+    /// number of hops. This is the ego network of the vertex.
+    ///
+    /// Fails with `GraphErr::NoSuchVertex` if there is no such vertex.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v3, &v2).unwrap();
+    ///
+    /// let neighborhood = graph.undirected_neighborhood(&v3, 2).unwrap();
+    ///
+    /// assert_eq!(neighborhood[&v1], 2);
+    /// assert_eq!(graph.neighborhood(&v3, 2).unwrap().len(), 2);
+    /// ```
+    pub fn undirected_neighborhood(
+        &self,
+        id: &VertexId,
+        k: usize,
+    ) -> Result<HashMap<VertexId, usize>, GraphErr> {
+        self.neighborhood_by(id, k, |v| self.neighbors(v))
+    }
+
+    /// Runs a breadth-first search from the given vertex, stopping
+    /// after `k` hops, with the given function yielding the vertices
+    /// one hop away from each vertex.
+    fn neighborhood_by<'a>(
+        &'a self,
+        id: &VertexId,
+        k: usize,
+        next: impl Fn(&VertexId) -> VertexIter<'a>,
+    ) -> Result<HashMap<VertexId, usize>, GraphErr> {
+        if !self.vertices.contains_key(id) {
+            return Err(GraphErr::NoSuchVertex);
+        }
+
+        let mut distances = HashMap::new();
+        let mut layer = vec![*id];
+
+        distances.insert(*id, 0);
+
+        for distance in 1..=k {
+            let mut next_layer = vec![];
+
+            for v in layer.iter() {
+                for n in next(v) {
+                    if !distances.contains_key(n) {
+                        distances.insert(*n, distance);
+                        next_layer.push(*n);
+                    }
+                }
+            }
+
+            if next_layer.is_empty() {
+                break;
+            }
+
+            layer = next_layer;
+        }
+
+        Ok(distances)
+    }
+
+    /// Returns an iterator over all edges that are situated
+    /// in the graph.
+    ///
+    /// Note that each edge is yielded as a pair of its inbound
+    /// and outbound vertices, so an edge added with
+    /// `graph.add_edge(&a, &b)` is yielded as `(&b, &a)`. New
+    /// code should prefer `Graph::edges_with_weights()`, which
+    /// yields the edges in the order they are added in.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    /// let mut edges = vec![];
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    /// let v4 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v3, &v1).unwrap();
+    /// graph.add_edge(&v1, &v4).unwrap();
+    ///
+    /// // Iterate over edges
+    /// for v in graph.edges() {
+    ///     edges.push(v);
+    /// }
+    ///
+    /// assert_eq!(edges.len(), 3);
+    /// ```
+    pub fn edges(&self) -> impl Iterator<Item = (&VertexId, &VertexId)> {
+        self.edges.iter().map(|(e, _)| (e.inbound(), e.outbound()))
+    }
+
+    /// Returns an iterator over all edges that are situated in the
+    /// graph, in no particular order. Each edge is yielded as its
+    /// outbound vertex, its inbound vertex and its weight.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge_with_weight(&v1, &v2, 0.5).unwrap();
+    ///
+    /// let edges: Vec<_> = graph.edges_with_weights().collect();
+    ///
+    /// assert_eq!(edges, vec![(&v1, &v2, 0.5)]);
+    /// ```
+    pub fn edges_with_weights(&self) -> impl Iterator<Item = (&VertexId, &VertexId, f32)> {
+        self.edges
+            .iter()
+            .map(|(e, w)| (e.outbound(), e.inbound(), *w))
+    }
+
+    #[cfg(feature = "rayon")]
+    /// Returns a parallel iterator over all edges that are
+    /// situated in the graph, in the same form as `Graph::edges()`.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v3, &v1).unwrap();
+    ///
+    /// assert_eq!(graph.par_edges().filter(|(_, src)| *src == &v1).count(), 1);
+    /// ```
+    pub fn par_edges(&self) -> impl ParallelIterator<Item = (&VertexId, &VertexId)>
+    where
+        S: Sync,
+    {
+        self.edges.par_keys().map(|e| (e.inbound(), e.outbound()))
+    }
+
+    /// Returns an iterator over the root vertices
+    /// of the graph, which have no inbound edges.
+    ///
+    /// Note that isolated vertices, which have no edges
+    /// at all, are both roots and tips. Use `Graph::sources()`
+    /// to leave them out.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    /// let mut roots = vec![];
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    /// let v4 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v3, &v1).unwrap();
+    /// graph.add_edge(&v1, &v4).unwrap();
+    ///
+    /// // Iterate over roots
+    /// for v in graph.roots() {
+    ///     roots.push(v);
+    /// }
+    ///
+    /// assert_eq!(roots.len(), 1);
+    /// assert_eq!(roots[0], &v3);
+    /// ```
+    pub fn roots(&self) -> VertexIter<'_> {
+        VertexIter(Box::new(self.roots.iter().map(AsRef::as_ref)))
+    }
+
+    /// Returns an iterator over the tips of the graph. These
+    /// are all the vertices that have no outbound edge.
+    ///
+    /// Note that isolated vertices, which have no edges
+    /// at all, are both roots and tips. Use `Graph::sinks()`
+    /// to leave them out.
+    ///
+    /// ## Example
+    /// ```rust
+    /// #[macro_use] extern crate graphlib;
+    /// use std::collections::HashSet;
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    /// let mut tips = set![];
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    /// let v4 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v3, &v1).unwrap();
+    /// graph.add_edge(&v1, &v4).unwrap();
+    ///
+    /// // Iterate over tips
+    /// for v in graph.tips() {
+    ///     tips.insert(v);
+    /// }
+    ///
+    /// assert_eq!(tips.len(), 2);
+    /// assert_eq!(tips, set![&v2, &v4]);
+    /// ```
+    pub fn tips(&self) -> VertexIter<'_> {
+        VertexIter(Box::new(self.tips.iter().map(AsRef::as_ref)))
+    }
+
+    /// Returns an iterator over the isolated vertices
+    /// of the graph, which have no edges at all.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    ///
+    /// assert_eq!(graph.isolated().collect::<Vec<_>>(), vec![&v3]);
+    /// ```
+    pub fn isolated(&self) -> VertexIter<'_> {
+        let isolated = self.roots.iter().filter(move |v| self.tips.contains(*v));
+        VertexIter(Box::new(isolated.map(AsRef::as_ref)))
+    }
+
+    /// Returns an iterator over the sources of the graph. These
+    /// are the roots which have outbound edges, so unlike
+    /// `Graph::roots()` isolated vertices are left out.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    ///
+    /// assert_eq!(graph.sources().collect::<Vec<_>>(), vec![&v1]);
+    /// assert_eq!(graph.roots().count(), 2);
+    /// ```
+    pub fn sources(&self) -> VertexIter<'_> {
+        let sources = self.roots.iter().filter(move |v| !self.tips.contains(*v));
+        VertexIter(Box::new(sources.map(AsRef::as_ref)))
+    }
+
+    /// Returns an iterator over the sinks of the graph. These
+    /// are the tips which have inbound edges, so unlike
+    /// `Graph::tips()` isolated vertices are left out.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    ///
+    /// assert_eq!(graph.sinks().collect::<Vec<_>>(), vec![&v2]);
+    /// assert_eq!(graph.tips().count(), 2);
+    /// ```
+    pub fn sinks(&self) -> VertexIter<'_> {
+        let sinks = self.tips.iter().filter(move |v| !self.roots.contains(*v));
+        VertexIter(Box::new(sinks.map(AsRef::as_ref)))
+    }
+
+    /// Returns an iterator over the vertices which can be reached from
+    /// the given vertex, starting with it, in Depth-First Order. When the
+    /// graph is a tree, these are the vertices of the subtree rooted at
+    /// the given vertex. Each vertex is visited once, even if it can be
+    /// reached through multiple paths.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    /// let v4 = graph.add_vertex(4);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    /// graph.add_edge(&v1, &v4).unwrap();
+    ///
+    /// let subtree: Vec<_> = graph.subtree(&v2).collect();
+    ///
+    /// assert_eq!(subtree, vec![&v2, &v3]);
+    /// assert_eq!(graph.subtree(&v1).count(), 4);
+    /// ```
+    pub fn subtree<'a>(&'a self, id: &'a VertexId) -> VertexIter<'a> {
+        let mut stack: Vec<&VertexId> = match self.vertices.get_key_value(id) {
+            Some((id, _)) => vec![id],
+            None => vec![],
+        };
+        let mut visited = HashSet::new();
+
+        let subtree = core::iter::from_fn(move || loop {
+            let current = stack.pop()?;
+
+            if visited.insert(current) {
+                stack.extend(self.out_neighbors(current));
+                return Some(current);
+            }
+        });
+
+        VertexIter(Box::new(subtree))
+    }
+
+    /// Returns the parent of the given vertex, which is the vertex
+    /// having an edge to it when it has a single inbound edge. Returns
+    /// `None` if the vertex has no inbound edges or more than one.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    ///
+    /// assert_eq!(graph.parent(&v2), Some(&v1));
+    /// assert_eq!(graph.parent(&v1), None);
+    /// ```
+    pub fn parent(&self, id: &VertexId) -> Option<&VertexId> {
+        match self.inbound_table.get(id) {
+            Some(ins) if ins.len() == 1 => ins.first(),
+            _ => None,
+        }
+    }
+
+    /// Returns the depth of the given vertex, which is the number of
+    /// edges from a root to it when following the parents of the
+    /// vertices. Returns `None` if there is no such vertex or if a
+    /// vertex on the way has more than one inbound edge or is part
+    /// of a cycle.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    ///
+    /// assert_eq!(graph.depth(&v1), Some(0));
+    /// assert_eq!(graph.depth(&v3), Some(2));
+    /// ```
+    pub fn depth(&self, id: &VertexId) -> Option<usize> {
+        self.fetch(id)?;
+
+        let mut current = id;
+        let mut depth = 0;
+
+        while !self.roots.contains(current) {
+            current = self.parent(current)?;
+            depth += 1;
+
+            // A longer chain of parents must go around a cycle
+            if depth >= self.vertex_count() {
+                return None;
+            }
+        }
+
+        Some(depth)
+    }
+
+    /// Starts tracking which vertices become or stop being roots or tips
+    /// of the graph as it is mutated, discarding any tracked changes.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.track_frontier_changes();
+    ///
+    /// // v2 becomes runnable once its dependency is removed
+    /// graph.remove_edge(&v1, &v2);
+    ///
+    /// let changes = graph.take_frontier_changes();
+    ///
+    /// assert_eq!(changes.new_roots, vec![v2]);
+    /// assert_eq!(changes.new_tips, vec![v1]);
+    /// assert!(graph.take_frontier_changes().is_empty());
+    /// ```
+    pub fn track_frontier_changes(&mut self) {
+        self.frontier = Some(FrontierTracker::default());
+    }
+
+    /// Stops tracking changes of the roots and tips of the graph.
+    pub fn untrack_frontier_changes(&mut self) {
+        self.frontier = None;
+    }
+
+    /// Returns the vertices which became or stopped being roots or tips
+    /// since tracking started or the changes were last taken. A vertex
+    /// which changed and then changed back is not reported, and neither
+    /// are vertices which were removed from the graph.
+    ///
+    /// Returns no changes if they are not tracked.
+    pub fn take_frontier_changes(&mut self) -> FrontierChanges {
+        let frontier = match self.frontier {
+            Some(ref mut frontier) => core::mem::take(frontier),
+            None => return FrontierChanges::default(),
+        };
+
+        frontier.changes(&self.roots, &self.tips, |id| self.vertices.contains_key(id))
+    }
+
+    /// Starts recording the mutations of the graph so that they can be
+    /// undone and redone, discarding any recorded mutations. Each call
+    /// which mutates the graph is undone as a whole, including the
+    /// removal of the edges of a removed vertex.
+    ///
+    /// Only the vertices, their values, the edges and their weights are
+    /// restored, along with the labels, attributes and priorities of
+    /// removed vertices. Values changed through `Graph::fetch_mut()` and
+    /// changes to the labels, attributes and priorities of vertices in
+    /// the graph are not recorded.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    /// graph.track_history();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge_with_weight(&v1, &v2, 0.5).unwrap();
+    /// graph.remove(&v2);
+    ///
+    /// assert!(graph.undo());
+    /// assert_eq!(graph.fetch(&v2), Some(&2));
+    /// assert_eq!(graph.weight(&v1, &v2), Some(0.5));
+    ///
+    /// assert!(graph.redo());
+    /// assert_eq!(graph.fetch(&v2), None);
+    /// assert!(!graph.redo());
+    /// ```
+    pub fn track_history(&mut self) {
+        self.history = Some(History::default());
+    }
+
+    /// Stops recording the mutations of the graph,
+    /// discarding the recorded ones.
+    pub fn untrack_history(&mut self) {
+        self.history = None;
+    }
+
+    /// Reverts the last recorded call which mutated the graph and has not
+    /// been undone. Returns false if there is no such call.
+    pub fn undo(&mut self) -> bool {
+        let mut history = match self.history.take() {
+            Some(history) => history,
+            None => return false,
+        };
+
+        let undone = match history.done.pop() {
+            Some(mut step) => {
+                for mutation in step.mutations.clone().iter().rev() {
+                    self.revert(mutation, &mut step);
+                }
+
+                history.undone.push(step);
+                true
+            }
+            None => false,
+        };
+
+        self.history = Some(history);
+        undone
+    }
+
+    /// Applies again the last undone call. Returns false if there is no
+    /// such call or if the graph was mutated since it was undone.
+    pub fn redo(&mut self) -> bool {
+        let mut history = match self.history.take() {
+            Some(history) => history,
+            None => return false,
+        };
+
+        let redone = match history.undone.pop() {
+            Some(mut step) => {
+                for mutation in step.mutations.clone().iter() {
+                    self.reapply(mutation, &mut step);
+                }
+
+                history.done.push(step);
+                true
+            }
+            None => false,
+        };
+
+        self.history = Some(history);
+        redone
+    }
+
+    /// Returns an iterator over the mutations of the recorded calls which
+    /// have not been undone, starting with the oldest. Each item holds
+    /// the mutations of a single call.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::{Graph, Mutation};
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    /// let v1 = graph.add_vertex(1);
+    ///
+    /// graph.track_history();
+    ///
+    /// let v2 = graph.add_vertex(2);
+    /// graph.add_edge_with_weight(&v1, &v2, 0.5).unwrap();
+    /// graph.remove(&v1);
+    ///
+    /// let history: Vec<&[Mutation]> = graph.history().collect();
+    ///
+    /// assert_eq!(history[0], &[Mutation::AddVertex(v2)]);
+    /// assert_eq!(history[1], &[Mutation::AddEdge(v1, v2, 0.5)]);
+    /// assert_eq!(
+    ///     history[2],
+    ///     &[Mutation::RemoveEdge(v1, v2, 0.5), Mutation::RemoveVertex(v1)]
+    /// );
+    /// ```
+    pub fn history(&self) -> impl Iterator<Item = &[Mutation]> {
+        self.history
+            .iter()
+            .flat_map(|history| history.done.iter())
+            .map(|step| step.mutations.as_slice())
+    }
+
+    /// Returns an iterator over all of the
+    /// vertices that are placed in the graph.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    /// let mut vertices = vec![];
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    /// let v4 = graph.add_vertex(3);
+    ///
+    /// // Iterate over vertices
+    /// for v in graph.vertices() {
+    ///     vertices.push(v);
+    /// }
+    ///
+    /// assert_eq!(vertices.len(), 4);
+    /// ```
+    pub fn vertices(&self) -> VertexIter<'_> {
+        VertexIter(Box::new(self.vertices.keys().map(AsRef::as_ref)))
+    }
+
+    #[cfg(feature = "rayon")]
+    /// Returns a parallel iterator over the ids of all of the vertices.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// for i in 0..100 {
+    ///     graph.add_vertex(i);
+    /// }
+    ///
+    /// let sum: usize = graph
+    ///     .par_vertices()
+    ///     .map(|id| graph.fetch(id).unwrap())
+    ///     .sum();
+    ///
+    /// assert_eq!(sum, 4950);
+    /// ```
+    pub fn par_vertices(&self) -> impl ParallelIterator<Item = &VertexId>
+    where
+        T: Sync,
+        S: Sync,
+    {
+        self.vertices.par_keys()
+    }
+
+    /// Returns a set containing all of the
+    /// vertices that are placed in the graph.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    ///
+    /// let set = graph.vertex_set();
+    ///
+    /// assert_eq!(set.len(), 2);
+    /// assert!(set.contains(&v1));
+    /// assert!(set.contains(&v2));
+    /// ```
+    pub fn vertex_set(&self) -> VertexSet<'_, T, S> {
+        VertexSet::from_set(self, self.vertices.keys().cloned().collect())
+    }
+
+    /// Returns an iterator over the vertices
+    /// of the graph in Depth-First Order. The iterator
+    /// will follow vertices with lower weights first.
+    ///
+    /// ## Example
+    /// ```rust
+    /// #[macro_use] extern crate graphlib;
+    /// use graphlib::Graph;
+    /// use std::collections::HashSet;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    /// let v4 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v3, &v1).unwrap();
+    /// graph.add_edge(&v1, &v4).unwrap();
+    ///
+    /// let mut dfs = graph.dfs();
+    ///
+    /// assert_eq!(dfs.next(), Some(&v3));
+    /// assert_eq!(dfs.next(), Some(&v1));
+    /// assert!(set![&v2, &v4] == dfs.collect());
+    /// ```
+    pub fn dfs(&self) -> Dfs<'_, T, S> {
+        Dfs::new(self)
+    }
+
+    /// Returns an iterator over the vertices
+    /// of the graph in Breadth-First Order. The iterator
+    /// will follow vertices with lower weights first.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    /// let mut vertices = vec![];
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    /// let v4 = graph.add_vertex(3);
+    /// let v5 = graph.add_vertex(4);
+    /// let v6 = graph.add_vertex(5);
+    /// let v7 = graph.add_vertex(6);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v3, &v1).unwrap();
+    /// graph.add_edge(&v1, &v4).unwrap();
+    /// graph.add_edge(&v1, &v7).unwrap();
+    /// graph.add_edge(&v2, &v5).unwrap();
+    /// graph.add_edge(&v5, &v6).unwrap();
+    ///
+    /// // Iterate over vertices
+    /// for v in graph.bfs() {
+    ///     vertices.push(v);
+    /// }
+    ///
+    /// assert_eq!(vertices.len(), 7);
+    /// ```
+    pub fn bfs(&self) -> Bfs<'_, T, S> {
+        Bfs::new(self)
+    }
+
+    #[cfg(feature = "rayon")]
+    /// Returns an iterator over the levels of a Breadth-First traversal
+    /// starting from the roots of the graph. Each level holds the vertices
+    /// first reached from the previous one, and the out-neighbors of a level
+    /// are visited on multiple threads, which speeds up graphs with a wide
+    /// fan-out.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    /// let v4 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v1, &v3).unwrap();
+    /// graph.add_edge(&v2, &v4).unwrap();
+    /// graph.add_edge(&v3, &v4).unwrap();
+    ///
+    /// let levels: Vec<Vec<_>> = graph.par_bfs().collect();
+    ///
+    /// assert_eq!(levels.len(), 3);
+    /// assert_eq!(levels[0], vec![&v1]);
+    /// assert_eq!(levels[1].len(), 2);
+    /// assert_eq!(levels[2], vec![&v4]);
+    /// ```
+    pub fn par_bfs(&self) -> ParBfs<'_, T, S>
+    where
+        T: Send + Sync,
+        S: Send + Sync,
+    {
+        ParBfs::new(self)
+    }
+
+    /// Returns an iterator over the edges through which a Breadth-First
+    /// traversal of the graph reaches its vertices, as `(parent, child)`
+    /// pairs. Traversals start from the roots of the graph and then from
+    /// any vertex which was not reached, so that every vertex which does
+    /// not start a traversal is the child of exactly one edge.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    /// graph.add_edge(&v1, &v3).unwrap();
+    ///
+    /// let edges: Vec<_> = graph.bfs_edges().collect();
+    ///
+    /// assert_eq!(edges.len(), 2);
+    /// assert!(edges.contains(&(&v1, &v2)));
+    /// assert!(edges.contains(&(&v1, &v3)));
+    /// ```
+    pub fn bfs_edges(&self) -> BfsEdges<'_, T, S> {
+        BfsEdges::new(self)
+    }
+
+    /// Returns an iterator over the edges through which a Depth-First
+    /// traversal of the graph reaches its vertices, as `(parent, child)`
+    /// pairs. As with `Graph::bfs_edges()`, every vertex which does not
+    /// start a traversal is the child of exactly one edge.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    ///
+    /// let edges: Vec<_> = graph.dfs_edges().collect();
+    ///
+    /// assert_eq!(edges, vec![(&v1, &v2), (&v2, &v3)]);
+    /// ```
+    pub fn dfs_edges(&self) -> DfsEdges<'_, T, S> {
+        DfsEdges::new(self)
+    }
+
+    /// Searches the graph in Breadth-First Order starting from the
+    /// given vertex, until a vertex whose value matches the given
+    /// condition is found. Returns the id of the found vertex along
+    /// with the path leading to it, which begins with the start
+    /// vertex and ends with the found one.
+    ///
+    /// Returns `None` if no reachable vertex matches the condition
+    /// or if the start vertex is not placed in the graph.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    /// let v4 = graph.add_vertex(4);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    /// graph.add_edge(&v1, &v4).unwrap();
+    ///
+    /// let (found, path) = graph.bfs_until(&v1, |v| *v > 2).unwrap();
+    ///
+    /// assert!(found == v3 || found == v4);
+    /// assert_eq!(path.first(), Some(&v1));
+    /// assert_eq!(path.last(), Some(&found));
+    ///
+    /// assert_eq!(graph.bfs_until(&v1, |v| *v == 3), Some((v3, vec![v1, v2, v3])));
+    /// assert_eq!(graph.bfs_until(&v2, |v| *v == 4), None);
+    /// ```
+    pub fn bfs_until(
+        &self,
+        start: &VertexId,
+        fun: impl Fn(&T) -> bool,
+    ) -> Option<(VertexId, Vec<VertexId>)> {
+        let mut parents: HashMap<VertexId, VertexId> = HashMap::new();
+        let mut queue = VecDeque::new();
+
+        self.fetch(start)?;
+        parents.insert(*start, *start);
+        queue.push_back(*start);
+
+        while let Some(current) = queue.pop_front() {
+            if fun(self.fetch(&current).unwrap()) {
+                return Some((current, Self::trace_path(&parents, current)));
+            }
+
+            for n in self.out_neighbors(&current) {
+                if !parents.contains_key(n) {
+                    parents.insert(*n, current);
+                    queue.push_back(*n);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Searches the graph in Depth-First Order starting from the
+    /// given vertex, until a vertex whose value matches the given
+    /// condition is found. Returns the id of the found vertex along
+    /// with the path leading to it, which begins with the start
+    /// vertex and ends with the found one.
+    ///
+    /// Returns `None` if no reachable vertex matches the condition
+    /// or if the start vertex is not placed in the graph.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    /// let v4 = graph.add_vertex(4);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    /// graph.add_edge(&v3, &v4).unwrap();
+    /// graph.add_edge(&v1, &v4).unwrap();
+    ///
+    /// let (found, path) = graph.dfs_until(&v1, |v| *v == 4).unwrap();
+    ///
+    /// assert_eq!(found, v4);
+    /// assert_eq!(path.first(), Some(&v1));
+    /// assert_eq!(path.last(), Some(&v4));
+    ///
+    /// assert_eq!(graph.dfs_until(&v2, |v| *v == 1), None);
+    /// ```
+    pub fn dfs_until(
+        &self,
+        start: &VertexId,
+        fun: impl Fn(&T) -> bool,
+    ) -> Option<(VertexId, Vec<VertexId>)> {
+        let mut parents: HashMap<VertexId, VertexId> = HashMap::new();
+        let mut visited: HashSet<VertexId> = HashSet::new();
+        let mut stack = Vec::new();
+
+        self.fetch(start)?;
+        parents.insert(*start, *start);
+        stack.push(*start);
+
+        while let Some(current) = stack.pop() {
+            if !visited.insert(current) {
+                continue;
+            }
+
+            if fun(self.fetch(&current).unwrap()) {
+                return Some((current, Self::trace_path(&parents, current)));
+            }
+
+            // Push in reverse so that neighbors are
+            // visited in the same order as `Graph::dfs()`.
+            let neighbors: Vec<&VertexId> = self.out_neighbors(&current).collect();
+
+            for n in neighbors.into_iter().rev() {
+                if !visited.contains(n) {
+                    parents.insert(*n, current);
+                    stack.push(*n);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Builds an index answering whether a vertex can reach another one
+    /// in `O(log n)` time, for workloads issuing many such queries. The
+    /// index does not follow later changes to the graph and must be
+    /// rebuilt with `ReachabilityIndex::rebuild()` after them.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    ///
+    /// let index = graph.reachability_index();
+    ///
+    /// assert!(index.reaches(&v1, &v2));
+    /// assert!(index.reaches(&v3, &v3));
+    /// assert!(!index.reaches(&v1, &v3));
+    /// ```
+    pub fn reachability_index(&self) -> ReachabilityIndex {
+        ReachabilityIndex::new(self)
+    }
+
+    /// Builds a reachability index whose tables take at most `budget`
+    /// bytes. Queries from the vertices whose tables do not fit search
+    /// the graph instead, which is slower but keeps the memory usage of
+    /// the index linear in the size of the graph.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    ///
+    /// let index = graph.reachability_index_with_budget(16);
+    ///
+    /// assert!(index.reaches(&v1, &v3));
+    /// assert!(!index.reaches(&v3, &v2));
+    /// ```
+    pub fn reachability_index_with_budget(&self, budget: usize) -> ReachabilityIndex {
+        ReachabilityIndex::with_budget(self, budget)
+    }
+
+    #[cfg(not(feature = "no_std"))]
+    /// Builds a reachability index whose tables take at most `budget`
+    /// bytes of memory. The tables which do not fit are written to a
+    /// temporary file in the given directory, so queries from every
+    /// vertex still take `O(log n)` time. See
+    /// `ReachabilityIndex::with_spill()`.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    ///
+    /// let index = graph
+    ///     .reachability_index_with_spill(0, std::env::temp_dir())
+    ///     .unwrap();
+    ///
+    /// assert!(index.reaches(&v1, &v2));
+    /// assert!(!index.reaches(&v2, &v1));
+    /// ```
+    pub fn reachability_index_with_spill(
+        &self,
+        budget: usize,
+        dir: impl AsRef<std::path::Path>,
+    ) -> std::io::Result<ReachabilityIndex> {
+        ReachabilityIndex::with_spill(self, budget, dir)
+    }
+
+    /// Returns the set of vertices which can be reached from any
+    /// of the vertices in the given set, including the set itself.
+    ///
+    /// Fails with `GraphErr::GraphMismatch` if the set belongs
+    /// to a different graph.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::{Graph, VertexSet};
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    /// let v4 = graph.add_vertex(4);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v4, &v3).unwrap();
+    ///
+    /// let set = VertexSet::from_ids(&graph, &[v1, v3]).unwrap();
+    /// let reachable = graph.reachable_from(&set).unwrap();
+    ///
+    /// assert_eq!(reachable, VertexSet::from_ids(&graph, &[v1, v2, v3]).unwrap());
+    /// ```
+    pub fn reachable_from<'a>(
+        &'a self,
+        set: &VertexSet<'a, T, S>,
+    ) -> Result<VertexSet<'a, T, S>, GraphErr> {
+        let mut reachable = VertexSet::new(self);
+        reachable.check_graph(set)?;
+
+        let mut stack: Vec<&VertexId> = set.iter().collect();
+
+        while let Some(current) = stack.pop() {
+            if reachable.insert(current).unwrap() {
+                stack.extend(self.out_neighbors(current));
+            }
+        }
+
+        Ok(reachable)
+    }
+
+    /// Returns the set of vertices which are transitively reachable from
+    /// the given failed vertex and are therefore invalidated by its failure.
+    /// The failed vertex itself is not part of the set.
+    ///
+    /// Fails with `GraphErr::NoSuchVertex` if there is no such vertex.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::{Graph, VertexSet};
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    /// let v4 = graph.add_vertex(4);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    /// graph.add_edge(&v4, &v3).unwrap();
+    ///
+    /// let invalidated = graph.invalidate_downstream(&v1).unwrap();
+    ///
+    /// assert_eq!(invalidated, VertexSet::from_ids(&graph, &[v2, v3]).unwrap());
+    /// ```
+    pub fn invalidate_downstream(&self, id: &VertexId) -> Result<VertexSet<'_, T, S>, GraphErr> {
+        self.invalidate_downstream_until(id, |_| false)
+    }
+
+    /// Returns the set of vertices which are transitively reachable from
+    /// the given failed vertex without passing through a barrier vertex,
+    /// which is a vertex whose value matches the given condition. Barrier
+    /// vertices stop the failure from propagating and are not invalidated
+    /// themselves. The failed vertex is not part of the set.
+    ///
+    /// Fails with `GraphErr::NoSuchVertex` if there is no such vertex.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::{Graph, VertexSet};
+    ///
+    /// let mut graph: Graph<&str> = Graph::new();
+    ///
+    /// let fetch = graph.add_vertex("fetch");
+    /// let parse = graph.add_vertex("parse");
+    /// let cache = graph.add_vertex("cache");
+    /// let report = graph.add_vertex("report");
+    ///
+    /// graph.add_edge(&fetch, &parse).unwrap();
+    /// graph.add_edge(&fetch, &cache).unwrap();
+    /// graph.add_edge(&cache, &report).unwrap();
+    ///
+    /// // The cache can serve stale data if fetching fails
+    /// let invalidated = graph
+    ///     .invalidate_downstream_until(&fetch, |v| *v == "cache")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(invalidated, VertexSet::from_ids(&graph, &[parse]).unwrap());
+    /// ```
+    pub fn invalidate_downstream_until(
+        &self,
+        id: &VertexId,
+        barrier: impl Fn(&T) -> bool,
+    ) -> Result<VertexSet<'_, T, S>, GraphErr> {
+        if self.fetch(id).is_none() {
+            return Err(GraphErr::NoSuchVertex);
+        }
+
+        let mut invalidated = HashSet::new();
+        let mut stack: Vec<&VertexId> = self.out_neighbors(id).collect();
+
+        while let Some(current) = stack.pop() {
+            if current == id || barrier(self.fetch(current).unwrap()) {
+                continue;
+            }
+
+            if invalidated.insert(*current) {
+                stack.extend(self.out_neighbors(current));
+            }
+        }
+
+        Ok(VertexSet::from_set(self, invalidated))
+    }
+
+    /// Returns an iterator over the vertices
+    /// of the graph which follows a DFS based
+    /// topological order (Kahn's algorithm).
+    ///
+    /// Topological sorting is not possible for
+    /// graphs which contain a cycle. You may
+    /// use topo.is_cylic() == false to verify
+    /// that your graph is a DAG.
+    ///
+    /// If you attempt to use a topological
+    /// order without confirming that your graph
+    /// is a DAG, you may encounter a panic!().
+    ///
+    /// The panic!() will be encountered when
+    /// the iterator detects that there are no
+    /// more vertices to visit, but all vertices
+    /// have not been visited.
+    ///
+    /// ## Example
+    /// ```rust
+    /// #[macro_use] extern crate graphlib;
+    /// use graphlib::Graph;
+    /// use std::collections::HashSet;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    /// let v4 = graph.add_vertex(4);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    /// graph.add_edge(&v3, &v4).unwrap();
+    ///
+    /// let mut topo = graph.topo();
+    ///
+    /// assert_eq!(topo.next(), Some(&v1));
+    /// assert_eq!(topo.next(), Some(&v2));
+    /// assert!(set![&v3, &v4] == topo.collect());
+    /// ```
+    pub fn topo(&self) -> Topo<'_, T, S> {
+        Topo::new(self)
+    }
+
+    /// Returns an iterator over the shortest path from the source
+    /// vertex to the destination vertex. The iterator will yield
+    /// `None` if there is no such path or the provided vertex ids
+    /// do not belong to any vertices in the graph.
+    /// ## Example
+    /// ```rust
+    /// #[macro_use] extern crate graphlib;
+    /// use graphlib::Graph;
+    /// use std::collections::HashSet;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    /// let v4 = graph.add_vertex(4);
+    /// let v5 = graph.add_vertex(5);
+    /// let v6 = graph.add_vertex(6);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    /// graph.add_edge(&v3, &v4).unwrap();
+    /// graph.add_edge(&v3, &v5).unwrap();
+    /// graph.add_edge(&v5, &v6).unwrap();
+    /// graph.add_edge(&v6, &v4).unwrap();
+    ///
+    /// let mut dijkstra = graph.dijkstra(&v1, &v4);
+    ///
+    /// assert_eq!(dijkstra.next(), Some(&v1));
+    /// assert_eq!(dijkstra.next(), Some(&v2));
+    /// assert_eq!(dijkstra.next(), Some(&v3));
+    /// assert_eq!(dijkstra.next(), Some(&v4));
+    /// assert_eq!(dijkstra.next(), None);
+    /// ```
+    pub fn dijkstra<'a>(&'a self, src: &'a VertexId, dest: &'a VertexId) -> VertexIter<'a> {
+        if let Some(dijkstra) = Dijkstra::new(&self, src).ok() {
+            if let Some(iter) = dijkstra.get_path_to(dest).ok() {
+                iter
+            } else {
+                VertexIter(Box::new(iter::empty()))
+            }
+        } else {
+            VertexIter(Box::new(iter::empty()))
+        }
+    }
+
+    /// Returns the eccentricity of the vertex with the given id, which is
+    /// the greatest number of edges on a shortest path from it to any other
+    /// vertex. Returns `None` if there is no vertex with the given id or if
+    /// there are vertices which cannot be reached from it.
+    ///
+    /// Distances are hop counts found by breadth-first search, so the
+    /// weights of the edges are ignored.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    ///
+    /// assert_eq!(graph.eccentricity(&v1), Some(2));
+    /// assert_eq!(graph.eccentricity(&v2), None);
+    /// ```
+    pub fn eccentricity(&self, id: &VertexId) -> Option<usize> {
+        crate::centrality::eccentricity(self, id)
+    }
+
+    /// Returns the diameter of the graph, which is the greatest eccentricity
+    /// of its vertices. Returns `None` if the graph is empty or if there are
+    /// vertices which cannot be reached from some other vertex.
+    ///
+    /// As with `Graph::eccentricity()`, distances are
+    /// hop counts and the weights of the edges are ignored.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    ///
+    /// assert_eq!(graph.diameter(), None);
+    ///
+    /// graph.add_edge(&v3, &v1).unwrap();
+    ///
+    /// assert_eq!(graph.diameter(), Some(2));
+    /// ```
+    pub fn diameter(&self) -> Option<usize> {
+        crate::centrality::diameter(self)
+    }
+
+    /// Returns the radius of the graph, which is the smallest eccentricity
+    /// of its vertices. Returns `None` if no vertex can reach all others.
+    ///
+    /// As with `Graph::eccentricity()`, distances are
+    /// hop counts and the weights of the edges are ignored.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v1, &v3).unwrap();
+    ///
+    /// assert_eq!(graph.radius(), Some(1));
+    /// ```
+    pub fn radius(&self) -> Option<usize> {
+        crate::centrality::radius(self)
+    }
+
+    /// Returns the closeness centrality of each vertex, which is the
+    /// inverse of the average number of edges on the shortest paths to
+    /// the vertices it can reach. The score is multiplied by the fraction
+    /// of the other vertices that can be reached, so that it is between
+    /// `0.0` and `1.0` even if the graph is not connected.
+    ///
+    /// The paths are found by breadth-first search from every vertex,
+    /// so the weights of the edges are ignored.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    ///
+    /// let closeness = graph.closeness_centrality();
+    ///
+    /// assert_eq!(closeness[&v1], 2.0 / 3.0);
+    /// assert_eq!(closeness[&v2], 0.5);
+    /// assert_eq!(closeness[&v3], 0.0);
+    /// ```
+    pub fn closeness_centrality(&self) -> HashMap<VertexId, f32> {
+        crate::centrality::closeness_centrality(self)
+    }
+
+    /// Returns an iterator over the vertices which are neighbors of
+    /// both of the given vertices, ignoring edge directions.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v3).unwrap();
+    /// graph.add_edge(&v3, &v2).unwrap();
+    ///
+    /// assert_eq!(graph.common_neighbors(&v1, &v2).collect::<Vec<_>>(), vec![&v3]);
+    /// ```
+    pub fn common_neighbors(&self, a: &VertexId, b: &VertexId) -> VertexIter<'_> {
+        crate::link_prediction::common_neighbors(self, a, b)
+    }
+
+    /// Returns the Jaccard similarity of the given vertices, which is
+    /// the number of their common neighbors divided by the number of
+    /// vertices neighboring any of them, ignoring edge directions. It
+    /// is `0.0` for vertices without neighbors. Returns `None` if any
+    /// of the vertices is not in the graph.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    /// let v4 = graph.add_vertex(4);
+    ///
+    /// graph.add_edge(&v1, &v3).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    /// graph.add_edge(&v2, &v4).unwrap();
+    ///
+    /// assert_eq!(graph.jaccard_similarity(&v1, &v2), Some(0.5));
+    /// ```
+    pub fn jaccard_similarity(&self, a: &VertexId, b: &VertexId) -> Option<f32> {
+        crate::link_prediction::jaccard_similarity(self, a, b)
+    }
+
+    #[cfg(not(feature = "no_std"))]
+    /// Returns the Adamic-Adar score of the given vertices, which sums
+    /// `1 / ln(degree)` over their common neighbors, so that rare common
+    /// neighbors weigh more than ones connected to many vertices. Edge
+    /// directions are ignored. Returns `None` if any of the vertices is
+    /// not in the graph.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v3).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    ///
+    /// assert_eq!(graph.adamic_adar(&v1, &v2), Some(1.0 / 2f32.ln()));
+    /// ```
+    pub fn adamic_adar(&self, a: &VertexId, b: &VertexId) -> Option<f32> {
+        crate::link_prediction::adamic_adar(self, a, b)
+    }
+
+    #[cfg(not(feature = "no_std"))]
+    /// Computes 2D coordinates for all of the vertices of the graph
+    /// with the Fruchterman-Reingold force-directed algorithm, running
+    /// it for the given number of iterations. Connected vertices are
+    /// pulled together while all vertices push each other apart, and
+    /// the coordinates are placed in the unit square.
+    ///
+    /// The layout is deterministic, so the same graph is always laid
+    /// out the same way. Each iteration takes `O(|V|^2 + |E|)` time.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    ///
+    /// let positions = graph.layout_force_directed(50);
+    ///
+    /// assert_eq!(positions.len(), 3);
+    ///
+    /// for (x, y) in positions.values() {
+    ///     assert!(*x >= 0.0 && *x <= 1.0);
+    ///     assert!(*y >= 0.0 && *y <= 1.0);
+    /// }
+    /// ```
+    pub fn layout_force_directed(&self, iterations: usize) -> HashMap<VertexId, (f32, f32)> {
+        crate::layout::force_directed(self, iterations)
+    }
+
+    #[cfg(not(feature = "no_std"))]
+    /// Computes a layered drawing of the graph, which must be acyclic.
+    /// Vertices are assigned to layers so that every edge points to a
+    /// later layer, and the vertices of each layer are ordered so that
+    /// few edges cross each other. This is the usual way of drawing
+    /// dependency graphs.
+    ///
+    /// Returns `GraphErr::CycleError` if the graph is cyclic.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<&str> = Graph::new();
+    ///
+    /// let app = graph.add_vertex("app");
+    /// let lib = graph.add_vertex("lib");
+    /// let core = graph.add_vertex("core");
+    ///
+    /// graph.add_edge(&app, &lib).unwrap();
+    /// graph.add_edge(&app, &core).unwrap();
+    /// graph.add_edge(&lib, &core).unwrap();
+    ///
+    /// let layout = graph.layout_layered().unwrap();
+    ///
+    /// assert_eq!(layout.layers().len(), 3);
+    /// assert_eq!(layout.layer(&core), Some(2));
+    /// assert_eq!(layout.position(&lib), Some((0.0, 1.0)));
+    /// assert_eq!(layout.crossings(), 0);
+    /// ```
+    pub fn layout_layered(&self) -> Result<crate::layout::LayeredLayout, GraphErr> {
+        crate::layout::layered(self)
+    }
+
+    /// Detects communities using label propagation and returns the id
+    /// of the community of each vertex. Community ids start from `0`.
+    ///
+    /// Each vertex starts in a community of its own and then repeatedly
+    /// joins the community which is the most common among its neighbors,
+    /// ignoring edge directions, until the communities no longer change.
+    /// Vertices are visited in a random order and ties are broken randomly,
+    /// so the results may differ between calls. Use
+    /// `Graph::label_propagation_communities_deterministic()` for
+    /// repeatable results.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    /// let v4 = graph.add_vertex(4);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v3, &v4).unwrap();
+    ///
+    /// let communities = graph.label_propagation_communities();
+    ///
+    /// assert_eq!(communities[&v1], communities[&v2]);
+    /// assert_eq!(communities[&v3], communities[&v4]);
+    /// assert_ne!(communities[&v1], communities[&v3]);
+    /// ```
+    pub fn label_propagation_communities(&self) -> HashMap<VertexId, usize> {
+        crate::communities::label_propagation(self, false)
+    }
+
+    /// Detects communities using label propagation as in
+    /// `Graph::label_propagation_communities()`, but visits
+    /// the vertices in the order of their ids and breaks ties
+    /// in favor of the smallest community id, so that the
+    /// same graph always yields the same communities.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    ///
+    /// assert_eq!(
+    ///     graph.label_propagation_communities_deterministic(),
+    ///     graph.label_propagation_communities_deterministic()
+    /// );
+    /// ```
+    pub fn label_propagation_communities_deterministic(&self) -> HashMap<VertexId, usize> {
+        crate::communities::label_propagation(self, true)
+    }
+
+    /// Returns a minimum spanning tree of the graph, which contains all of
+    /// its vertices and the edges of least total weight that connect them.
+    /// The direction of the edges is ignored while building the tree, but
+    /// the edges keep their direction and weight in the returned graph.
+    ///
+    /// If the graph is not connected, a tree is built for each connected
+    /// component.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge_with_weight(&v1, &v2, 0.2).unwrap();
+    /// graph.add_edge_with_weight(&v2, &v3, 0.5).unwrap();
+    /// graph.add_edge_with_weight(&v3, &v1, 0.3).unwrap();
+    ///
+    /// let tree = graph.minimum_spanning_tree();
+    ///
+    /// assert_eq!(tree.vertex_count(), 3);
+    /// assert_eq!(tree.edge_count(), 2);
+    /// assert!(tree.has_edge(&v1, &v2));
+    /// assert!(tree.has_edge(&v3, &v1));
+    /// assert_eq!(tree.weight(&v3, &v1), Some(0.3));
+    /// ```
+    pub fn minimum_spanning_tree(&self) -> Graph<T, S>
+    where
+        S: Clone,
+        T: Clone,
+    {
+        let mut tree: Graph<T, S> =
+            Graph::with_capacity_and_hasher(self.vertex_count(), self.hasher().clone());
+        tree.id_tag = self.id_tag;
+        tree.next_id = self.next_id;
+        tree.id_provider = self.id_provider.clone();
+        tree.weight_policy = self.weight_policy;
+        tree.rules = self.rules;
+
+        for (id, (value, _)) in self.vertices.iter() {
+            tree.insert_vertex(*id, value.clone());
+        }
+
+        for (a, b) in crate::spanning_tree::kruskal(self) {
+            let weight = self.weight(&a, &b).unwrap();
+            tree.do_add_edge(&a, &b, weight, false).unwrap();
+
+            if self.is_unweighted(&a, &b) {
+                tree.unweighted_edges.insert(Edge::new(a, b));
+            }
+        }
+
+        tree
+    }
+
+    /// Returns the connected components of the graph which are formed
+    /// only by the edges with a weight above the given threshold. The
+    /// direction of the edges is ignored.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge_with_weight(&v1, &v2, 0.8).unwrap();
+    /// graph.add_edge_with_weight(&v3, &v2, 0.2).unwrap();
+    ///
+    /// assert_eq!(graph.components_above(0.1).len(), 1);
+    /// assert_eq!(graph.components_above(0.5).len(), 2);
+    /// assert_eq!(graph.components_above(0.9).len(), 3);
+    /// ```
+    pub fn components_above(&self, threshold: f32) -> Vec<VertexSet<'_, T, S>> {
+        crate::percolation::components_above(self, threshold)
+    }
+
+    /// Sweeps the given thresholds and returns the sizes of the
+    /// connected components formed by the edges with a weight above
+    /// each of them, as in `Graph::components_above()`.
+    ///
+    /// The results are returned in the order of the given thresholds,
+    /// with the sizes of the components sorted in descending order.
+    /// Fails with `GraphErr::InvalidWeight` if a threshold is NaN.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    /// let v4 = graph.add_vertex(4);
+    ///
+    /// graph.add_edge_with_weight(&v1, &v2, 0.8).unwrap();
+    /// graph.add_edge_with_weight(&v3, &v2, 0.2).unwrap();
+    /// graph.add_edge_with_weight(&v3, &v4, 0.6).unwrap();
+    ///
+    /// let sweep = graph.percolation(&[0.9, 0.5, 0.1]).unwrap();
+    ///
+    /// assert_eq!(sweep[0], (0.9, vec![1, 1, 1, 1]));
+    /// assert_eq!(sweep[1], (0.5, vec![2, 2]));
+    /// assert_eq!(sweep[2], (0.1, vec![4]));
+    /// ```
+    pub fn percolation(&self, thresholds: &[f32]) -> Result<Vec<(f32, Vec<usize>)>, GraphErr> {
+        crate::percolation::percolation(self, thresholds)
+    }
+
+    /// Returns an iterator over the maximal cliques of the graph,
+    /// which are computed ignoring the direction of the edges.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    /// let v4 = graph.add_vertex(4);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    /// graph.add_edge(&v3, &v1).unwrap();
+    /// graph.add_edge(&v3, &v4).unwrap();
+    ///
+    /// let mut cliques: Vec<usize> = graph.maximal_cliques().map(|c| c.len()).collect();
+    /// cliques.sort();
+    ///
+    /// assert_eq!(cliques, vec![2, 3]);
+    /// ```
+    pub fn maximal_cliques(&self) -> Cliques<'_, T, S> {
+        Cliques::new(self, 0)
+    }
+
+    /// Returns an iterator over the maximal cliques of the graph
+    /// which have at least the given number of vertices. The cliques
+    /// are computed ignoring the direction of the edges.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    /// let v4 = graph.add_vertex(4);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    /// graph.add_edge(&v3, &v1).unwrap();
+    /// graph.add_edge(&v3, &v4).unwrap();
+    ///
+    /// let cliques: Vec<_> = graph.maximal_cliques_with_min_size(3).collect();
+    ///
+    /// assert_eq!(cliques.len(), 1);
+    /// assert!(cliques[0].contains(&&v1));
+    /// assert!(cliques[0].contains(&&v2));
+    /// assert!(cliques[0].contains(&&v3));
+    /// ```
+    pub fn maximal_cliques_with_min_size(&self, min_size: usize) -> Cliques<'_, T, S> {
+        Cliques::new(self, min_size)
+    }
+
+    /// Returns an iterator over the vertices that are reachable from
+    /// the source vertex, in nondecreasing order of their distance from
+    /// it. The iterator yields each vertex along with its distance, which
+    /// is the sum of the weights of the edges on the shortest path to it.
+    ///
+    /// As opposed to `Graph::dijkstra()`, the distances are computed
+    /// lazily so the iteration can be stopped as soon as the wanted
+    /// vertex is found. Fails if the source vertex is not placed in
+    /// the graph or if the graph contains an edge with a negative weight.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    /// let v4 = graph.add_vertex(4);
+    ///
+    /// graph.add_edge_with_weight(&v1, &v2, 0.5).unwrap();
+    /// graph.add_edge_with_weight(&v1, &v3, 0.25).unwrap();
+    /// graph.add_edge_with_weight(&v3, &v4, 0.125).unwrap();
+    ///
+    /// let mut ucs = graph.uniform_cost(&v1).unwrap();
+    ///
+    /// assert_eq!(ucs.next(), Some((&v1, 0.0)));
+    /// assert_eq!(ucs.next(), Some((&v3, 0.25)));
+    /// assert_eq!(ucs.next(), Some((&v4, 0.375)));
+    /// assert_eq!(ucs.next(), Some((&v2, 0.5)));
+    /// assert_eq!(ucs.next(), None);
+    /// ```
+    pub fn uniform_cost(&self, src: &VertexId) -> Result<UniformCost<'_, T, S>, GraphErr> {
+        UniformCost::new(self, src)
+    }
+
+    /// Returns the `k` walks starting from the source vertex which have
+    /// the highest scores, along with their scores. Walks may visit the
+    /// same vertex several times and have at most `max_len` edges.
+    ///
+    /// The score of a walk is the product of the weights of its edges,
+    /// each of them multiplied by the given decay factor, so longer walks
+    /// are scored lower. Walks are returned in nonincreasing order of
+    /// their score, shorter walks coming first if scores are equal.
+    ///
+    /// Fails if the source vertex is not placed in the graph, if the
+    /// graph contains an edge with a negative weight or if the decay
+    /// factor is not a number between (and including) `0.0` and `1.0`.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge_with_weight(&v1, &v2, 1.0).unwrap();
+    /// graph.add_edge_with_weight(&v2, &v1, 1.0).unwrap();
+    /// graph.add_edge_with_weight(&v2, &v3, 0.5).unwrap();
+    ///
+    /// let walks = graph.top_walks(&v1, 3, 4, 0.5).unwrap();
+    ///
+    /// assert_eq!(walks[0], (vec![v1, v2], 0.5));
+    /// assert_eq!(walks[1], (vec![v1, v2, v1], 0.25));
+    /// assert_eq!(walks[2], (vec![v1, v2, v3], 0.125));
+    /// ```
+    pub fn top_walks(
+        &self,
+        src: &VertexId,
+        k: usize,
+        max_len: usize,
+        decay: f32,
+    ) -> Result<Vec<(Vec<VertexId>, f32)>, GraphErr> {
+        crate::walks::top_walks(self, src, k, max_len, decay)
+    }
+
+    /// Computes the maximum flow from the source vertex to the sink
+    /// vertex using the Edmonds-Karp algorithm. The weights of the
+    /// edges are treated as their capacities, so unweighted edges
+    /// cannot carry any flow.
+    ///
+    /// Fails if any of the vertices is not placed in the graph or
+    /// if the graph contains an edge with a negative weight.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    /// let v4 = graph.add_vertex(4);
+    ///
+    /// graph.add_edge_with_weight(&v1, &v2, 0.5).unwrap();
+    /// graph.add_edge_with_weight(&v1, &v3, 0.25).unwrap();
+    /// graph.add_edge_with_weight(&v2, &v4, 0.25).unwrap();
+    /// graph.add_edge_with_weight(&v3, &v4, 0.5).unwrap();
+    ///
+    /// let flow = graph.max_flow(&v1, &v4).unwrap();
+    ///
+    /// assert_eq!(flow.value(), 0.5);
+    /// assert_eq!(flow.flow(&v1, &v2), Some(0.25));
+    /// assert_eq!(flow.flow(&v3, &v4), Some(0.25));
+    /// ```
+    pub fn max_flow(&self, source: &VertexId, sink: &VertexId) -> Result<MaxFlow, GraphErr> {
+        crate::flow::edmonds_karp(self, source, sink)
+    }
+
+    /// Creates a token propagation simulation over the graph, in which
+    /// a vertex fires once it holds a token for each of its inbound
+    /// edges, sending a token to each of its outbound neighbors.
+    ///
+    /// Fails with `GraphErr::CycleError` if the graph contains cycles.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v3).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    ///
+    /// let mut simulation = graph.token_simulation().unwrap();
+    /// simulation.add_tokens(&v1, 1).unwrap();
+    ///
+    /// let report = simulation.run();
+    ///
+    /// // v3 is waiting for a token from v2
+    /// assert_eq!(report.firing_order().collect::<Vec<_>>(), vec![&v1]);
+    /// assert_eq!(report.stuck(), &[(v3, 1)]);
+    /// ```
+    pub fn token_simulation(&self) -> Result<TokenSimulation<'_, T, S>, GraphErr> {
+        TokenSimulation::new(self)
+    }
+
+    /// Creates a pull-based scheduler for executing the graph, in which
+    /// edges point from dependencies to their dependents. See `Scheduler`
+    /// for more information.
+    ///
+    /// Fails with `GraphErr::CycleError` if the graph contains cycles.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::{FailurePolicy, Graph, TaskState};
+    ///
+    /// let mut graph: Graph<&str> = Graph::new();
+    ///
+    /// let fetch = graph.add_vertex("fetch");
+    /// let build = graph.add_vertex("build");
+    /// let deploy = graph.add_vertex("deploy");
+    ///
+    /// graph.add_edge(&fetch, &build).unwrap();
+    /// graph.add_edge(&build, &deploy).unwrap();
+    ///
+    /// let mut scheduler = graph.scheduler().unwrap();
+    ///
+    /// while let Some(id) = scheduler.start() {
+    ///     if id == build {
+    ///         scheduler.fail(&id, FailurePolicy::Propagate).unwrap();
+    ///     } else {
+    ///         scheduler.complete(&id).unwrap();
+    ///     }
+    /// }
+    ///
+    /// assert!(scheduler.is_finished());
+    /// assert_eq!(scheduler.state(&fetch), Some(TaskState::Completed));
+    /// assert_eq!(scheduler.state(&build), Some(TaskState::Failed));
+    /// assert_eq!(scheduler.state(&deploy), Some(TaskState::Cancelled));
+    /// ```
+    pub fn scheduler(&self) -> Result<Scheduler<'_, T, S>, GraphErr> {
+        Scheduler::new(self)
+    }
+
+    /// Returns an iterator over the values of the vertices
+    /// placed in the graph.
+    ///
+    /// ## Example
+    /// ```rust
+    /// #[macro_use] extern crate graphlib;
+    /// use graphlib::Graph;
+    /// use std::collections::HashSet;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// let mut values = graph.values();
+    ///
+    /// assert!(set![&1, &2, &3] == values.collect());
+    /// ```
+    pub fn values(&self) -> ValuesIter<'_, T> {
+        let iter = self.vertices.values().map(|(v, _)| v);
+
+        ValuesIter(Box::new(iter))
+    }
+
+    /// Returns an iterator over the ids and the values
+    /// of the vertices of the graph, in no particular order.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    ///
+    /// let mut pairs: Vec<_> = graph.iter().collect();
+    /// pairs.sort_by_key(|(_, v)| **v);
+    ///
+    /// assert_eq!(pairs, vec![(&v1, &1), (&v2, &2)]);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter(Box::new(self.vertices.iter().map(|(id, (v, _))| (id, v))))
+    }
+
+    #[cfg(feature = "rayon")]
+    /// Returns a parallel iterator over the values of all of the vertices.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// for i in 0..100 {
+    ///     graph.add_vertex(i);
+    /// }
+    ///
+    /// assert_eq!(graph.par_values().max(), Some(&99));
+    /// ```
+    pub fn par_values(&self) -> impl ParallelIterator<Item = &T>
+    where
+        T: Sync,
+        S: Sync,
+    {
+        self.vertices.par_values().map(|(v, _)| v)
+    }
+
+    #[cfg(all(feature = "dot", not(feature = "no_std")))]
+    /// Creates a file with the dot representation of the graph.
+    /// This method requires the `dot` crate feature and is not
+    /// available with the `no_std` feature.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// use std::fs::File;
+    /// let mut f = File::create("example1.dot").unwrap();
+    ///
+    /// let mut graph: Graph<String> = Graph::new();
+    ///
+    ///  let v1 = graph.add_vertex("test1".to_string());
+    ///  let v2 = graph.add_vertex("test2".to_string());
+    ///  let v3 = graph.add_vertex("test3".to_string());
+    ///  let v4 = graph.add_vertex("test4".to_string());
+    ///
+    ///  let v5 = graph.add_vertex("test5".to_string());
+    ///  let v6 = graph.add_vertex("test6".to_string());
+    ///
+    ///  graph.add_edge(&v1, &v2).unwrap();
+    ///  graph.add_edge(&v3, &v1).unwrap();
+    ///  graph.add_edge(&v1, &v4).unwrap();
+    ///  graph.add_edge(&v5, &v6).unwrap();
+    ///
+    ///  assert!(graph.to_dot("example1", &mut f).is_ok());
+    /// ```
+    pub fn to_dot(
+        &self,
+        graph_name: &str,
+        output: &mut impl ::std::io::Write,
+    ) -> Result<(), GraphErr> {
+        self.to_dot_with_options(graph_name, output, &DotOptions::default())
+    }
+
+    #[cfg(all(feature = "dot", not(feature = "no_std")))]
+    /// Writes the dot representation of the graph to the given
+    /// output, rendered with the given options.
+    ///
+    /// This method requires the `dot` crate feature and is not
+    /// available with the `no_std` feature.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    /// use graphlib::dot::{DotOptions, RankDir};
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    ///
+    /// let options = DotOptions {
+    ///     rank_dir: Some(RankDir::LeftToRight),
+    ///     ..DotOptions::default()
+    /// };
+    ///
+    /// let mut output = Vec::new();
+    /// graph.to_dot_with_options("example", &mut output, &options).unwrap();
+    ///
+    /// let output = String::from_utf8(output).unwrap();
+    /// assert!(output.contains("rankdir=LR;"));
+    /// ```
+    pub fn to_dot_with_options(
+        &self,
+        graph_name: &str,
+        output: &mut impl ::std::io::Write,
+        options: &DotOptions,
+    ) -> Result<(), GraphErr> {
+        let graph = crate::dot::DotGraph::new(self, graph_name, options)?;
+        graph.render(output)
+    }
+
+    #[cfg(all(feature = "dot", not(feature = "no_std")))]
+    /// Writes the dot representation of the graph to the given output,
+    /// rendered with the given options. The nodes and edges are styled
+    /// with the results of the given functions, which are called with
+    /// each vertex along with its value and with each edge along with
+    /// its weight.
+    ///
+    /// This method requires the `dot` crate feature and is not
+    /// available with the `no_std` feature.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    /// use graphlib::dot::{DotOptions, Style};
+    ///
+    /// let mut graph: Graph<Result<(), &str>> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(Ok(()));
+    /// let v2 = graph.add_vertex(Err("timed out"));
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    ///
+    /// let mut output = Vec::new();
+    ///
+    /// graph
+    ///     .to_dot_styled(
+    ///         "tasks",
+    ///         &mut output,
+    ///         &DotOptions::default(),
+    ///         |_, task| match task {
+    ///             Ok(_) => Style::default(),
+    ///             Err(_) => Style {
+    ///                 color: Some("red".to_owned()),
+    ///                 ..Style::default()
+    ///             },
+    ///         },
+    ///         |_, _, _| Style {
+    ///             style: Some("dashed".to_owned()),
+    ///             ..Style::default()
+    ///         },
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let output = String::from_utf8(output).unwrap();
+    ///
+    /// assert_eq!(output.matches("[color=\"red\"]").count(), 1);
+    /// assert_eq!(output.matches("[style=\"dashed\"]").count(), 1);
+    /// ```
+    pub fn to_dot_styled(
+        &self,
+        graph_name: &str,
+        output: &mut impl ::std::io::Write,
+        options: &DotOptions,
+        node_style: impl Fn(&VertexId, &T) -> crate::dot::Style,
+        edge_style: impl Fn(&VertexId, &VertexId, f32) -> crate::dot::Style,
+    ) -> Result<(), GraphErr> {
+        let graph = crate::dot::DotGraph::new(self, graph_name, options)?
+            .with_styles(&node_style, &edge_style);
+
+        graph.render(output)
+    }
+
+    #[cfg(feature = "dot")]
+    /// Returns the dot representation of the graph.
+    ///
+    /// This method requires the `dot` crate feature.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    ///
+    /// let output = graph.to_dot_string("example").unwrap();
+    ///
+    /// assert!(output.starts_with("digraph example {"));
+    /// assert_eq!(output.matches(" -> ").count(), 1);
+    /// ```
+    pub fn to_dot_string(&self, graph_name: &str) -> Result<String, GraphErr> {
+        let options = DotOptions::default();
+        crate::dot::DotGraph::new(self, graph_name, &options)?.render_string()
+    }
+
+    #[cfg(feature = "dot")]
+    /// Writes the dot representation of the graph, rendered with the
+    /// given options, to the given formatter. Unlike
+    /// `Graph::to_dot_with_options()`, this does not need an
+    /// `std::io::Write` output, so the graph can be rendered into a
+    /// `String` or a `core::fmt::Formatter`, including with the
+    /// `no_std` feature.
+    ///
+    /// This method requires the `dot` crate feature.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    /// use graphlib::dot::DotOptions;
+    /// use std::fmt::Write;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    ///
+    /// let mut output = String::new();
+    ///
+    /// writeln!(output, "// Generated graph").unwrap();
+    /// graph.to_dot_fmt("example", &mut output, &DotOptions::default()).unwrap();
+    ///
+    /// assert!(output.starts_with("// Generated graph\ndigraph example {"));
+    /// ```
+    pub fn to_dot_fmt(
+        &self,
+        graph_name: &str,
+        output: &mut impl core::fmt::Write,
+        options: &DotOptions,
+    ) -> Result<(), GraphErr> {
+        let graph = crate::dot::DotGraph::new(self, graph_name, options)?;
+        graph.render_fmt(output)
+    }
+
+    #[cfg(feature = "dot")]
+    /// Adds a rank constraint on the given vertices which is applied
+    /// when the graph is rendered to the dot format. For example,
+    /// `Rank::Same` places all of the vertices on the same rank.
+    ///
+    /// This method requires the `dot` crate feature.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::{Graph, GraphErr, VertexId};
+    /// use graphlib::dot::Rank;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v1, &v3).unwrap();
+    ///
+    /// assert!(graph.add_rank_group(&[v2, v3], Rank::Same).is_ok());
+    /// assert!(graph.add_rank_group(&[v1], Rank::Source).is_ok());
+    /// assert_eq!(
+    ///     graph.add_rank_group(&[v1, VertexId::random()], Rank::Same),
+    ///     Err(GraphErr::NoSuchVertex)
+    /// );
+    ///
+    /// let mut output = Vec::new();
+    /// graph.to_dot("example", &mut output).unwrap();
+    ///
+    /// let output = String::from_utf8(output).unwrap();
+    /// assert!(output.contains("{ rank=same;"));
+    /// assert!(output.contains("{ rank=source;"));
+    /// ```
+    pub fn add_rank_group(&mut self, vertices: &[VertexId], rank: Rank) -> Result<(), GraphErr> {
+        if vertices.iter().any(|v| !self.vertices.contains_key(v)) {
+            return Err(GraphErr::NoSuchVertex);
+        }
+
+        self.rank_groups.push((rank, vertices.to_vec()));
+        Ok(())
+    }
+
+    #[cfg(feature = "dot")]
+    /// Removes all rank constraints placed on the vertices of the graph.
+    ///
+    /// This method requires the `dot` crate feature.
+    pub fn clear_rank_groups(&mut self) {
+        self.rank_groups.clear();
+    }
+
+    #[cfg(feature = "dot")]
+    /// Places the vertex with the given id in the cluster with the given
+    /// name, returning the name of its old cluster. The vertices of each
+    /// cluster are drawn together inside a box when the graph is rendered
+    /// to the dot format, and a vertex belongs to at most one cluster.
+    ///
+    /// This method requires the `dot` crate feature.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::{Graph, GraphErr, VertexId};
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    ///
+    /// assert_eq!(graph.assign_cluster(&v1, "stage1"), Ok(None));
+    /// assert_eq!(graph.assign_cluster(&v1, "stage2"), Ok(Some("stage1".to_owned())));
+    /// assert_eq!(
+    ///     graph.assign_cluster(&VertexId::random(), "stage1"),
+    ///     Err(GraphErr::NoSuchVertex)
+    /// );
+    /// assert_eq!(graph.cluster(&v1), Some("stage2"));
+    ///
+    /// let mut output = Vec::new();
+    /// graph.to_dot("example", &mut output).unwrap();
+    ///
+    /// let output = String::from_utf8(output).unwrap();
+    /// assert!(output.contains("subgraph \"cluster_stage2\" {"));
+    /// ```
+    pub fn assign_cluster(
+        &mut self,
+        vertex_id: &VertexId,
+        cluster: &str,
+    ) -> Result<Option<String>, GraphErr> {
+        if !self.vertices.contains_key(vertex_id) {
+            return Err(GraphErr::NoSuchVertex);
+        }
+
+        Ok(self.clusters.insert(*vertex_id, cluster.to_owned()))
+    }
+
+    #[cfg(feature = "dot")]
+    /// Removes the vertex with the given id from its cluster,
+    /// returning the name of the cluster.
+    ///
+    /// This method requires the `dot` crate feature.
+    pub fn remove_from_cluster(&mut self, vertex_id: &VertexId) -> Option<String> {
+        self.clusters.remove(vertex_id)
+    }
+
+    #[cfg(feature = "dot")]
+    /// Returns the name of the cluster of the vertex with the given id.
+    ///
+    /// This method requires the `dot` crate feature.
+    pub fn cluster(&self, vertex_id: &VertexId) -> Option<&str> {
+        self.clusters.get(vertex_id).map(|c| c.as_str())
+    }
+
+    /// Sets the attribute with the given key of the vertex with the
+    /// given id, returning its old value. Attributes hold metadata of
+    /// the vertices, such as coordinates or timestamps, and follow the
+    /// vertices across `Graph::map()`, subgraphs and merges. They are
+    /// rendered as node attributes by `Graph::to_dot()`.
+    ///
+    /// Fails with `GraphErr::NoSuchVertex` if there is no such vertex.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::{AttrValue, Graph};
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    /// let v1 = graph.add_vertex(1);
+    ///
+    /// assert_eq!(graph.set_attr(&v1, "weight", 2), Ok(None));
+    /// assert_eq!(graph.set_attr(&v1, "weight", 3), Ok(Some(AttrValue::Int(2))));
+    ///
+    /// let mapped = graph.map(|v| v * 2);
+    ///
+    /// assert_eq!(mapped.attr(&v1, "weight"), Some(&AttrValue::Int(3)));
+    /// ```
+    pub fn set_attr(
+        &mut self,
+        id: &VertexId,
+        key: &str,
+        value: impl Into<AttrValue>,
+    ) -> Result<Option<AttrValue>, GraphErr> {
+        if !self.vertices.contains_key(id) {
+            return Err(GraphErr::NoSuchVertex);
+        }
+
+        let attrs = self.vertex_attrs.entry(*id).or_default();
+        Ok(attrs.insert(key.into(), value.into()))
+    }
+
+    /// Returns the attribute with the given key of the vertex
+    /// with the given id or `None` if there is no such attribute.
+    pub fn attr(&self, id: &VertexId, key: &str) -> Option<&AttrValue> {
+        self.vertex_attrs.get(id)?.get(key)
+    }
+
+    /// Removes the attribute with the given key of the vertex
+    /// with the given id, returning its value.
+    pub fn remove_attr(&mut self, id: &VertexId, key: &str) -> Option<AttrValue> {
+        let attrs = self.vertex_attrs.get_mut(id)?;
+        let value = attrs.remove(key);
+
+        if attrs.is_empty() {
+            self.vertex_attrs.remove(id);
+        }
+
+        value
+    }
+
+    /// Returns an iterator over the keys and the values of
+    /// the attributes of the vertex with the given id, in no
+    /// particular order.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    /// let v1 = graph.add_vertex(1);
+    ///
+    /// graph.set_attr(&v1, "x", 0.5).unwrap();
+    /// graph.set_attr(&v1, "y", 1.5).unwrap();
+    ///
+    /// let mut keys: Vec<_> = graph.attrs(&v1).map(|(key, _)| key).collect();
+    /// keys.sort();
+    ///
+    /// assert_eq!(keys, vec!["x", "y"]);
+    /// ```
+    pub fn attrs(&self, id: &VertexId) -> impl Iterator<Item = (&str, &AttrValue)> {
+        self.vertex_attrs
+            .get(id)
+            .into_iter()
+            .flat_map(|attrs| attrs.iter().map(|(key, value)| (key.as_str(), value)))
+    }
+
+    /// Sets the attribute with the given key of the edge between
+    /// the given vertices, returning its old value. Attributes hold
+    /// metadata of the edges, such as categories or provenance, and are
+    /// rendered as edge attributes by `Graph::to_dot()`.
+    ///
+    /// Fails with `GraphErr::NoSuchEdge` if there is no such edge.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::{AttrValue, Graph};
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.set_edge_attr(&v1, &v2, "kind", "import").unwrap();
+    ///
+    /// let cloned = graph.clone();
+    ///
+    /// assert_eq!(cloned.edge_attr(&v1, &v2, "kind"), Some(&AttrValue::from("import")));
+    /// assert!(graph.set_edge_attr(&v2, &v1, "kind", "import").is_err());
+    /// ```
+    pub fn set_edge_attr(
+        &mut self,
+        a: &VertexId,
+        b: &VertexId,
+        key: &str,
+        value: impl Into<AttrValue>,
+    ) -> Result<Option<AttrValue>, GraphErr> {
+        let edge = Edge::new(*a, *b);
+
+        if !self.edges.contains_key(&edge) {
+            return Err(GraphErr::NoSuchEdge);
+        }
+
+        let attrs = self.edge_attrs.entry(edge).or_default();
+        Ok(attrs.insert(key.into(), value.into()))
+    }
+
+    /// Returns the attribute with the given key of the edge between
+    /// the given vertices or `None` if there is no such attribute.
+    pub fn edge_attr(&self, a: &VertexId, b: &VertexId, key: &str) -> Option<&AttrValue> {
+        self.edge_attrs.get(&Edge::new(*a, *b))?.get(key)
+    }
+
+    /// Removes the attribute with the given key of the edge
+    /// between the given vertices, returning its value.
+    pub fn remove_edge_attr(&mut self, a: &VertexId, b: &VertexId, key: &str) -> Option<AttrValue> {
+        let edge = Edge::new(*a, *b);
+        let attrs = self.edge_attrs.get_mut(&edge)?;
+        let value = attrs.remove(key);
+
+        if attrs.is_empty() {
+            self.edge_attrs.remove(&edge);
+        }
+
+        value
+    }
+
+    /// Returns an iterator over the keys and the values of the
+    /// attributes of the edge between the given vertices, in no
+    /// particular order.
+    pub fn edge_attrs(
+        &self,
+        a: &VertexId,
+        b: &VertexId,
+    ) -> impl Iterator<Item = (&str, &AttrValue)> {
+        self.edge_attrs
+            .get(&Edge::new(*a, *b))
+            .into_iter()
+            .flat_map(|attrs| attrs.iter().map(|(key, value)| (key.as_str(), value)))
+    }
+
+    #[cfg(feature = "dot")]
+    /// Returns an iterator over the rank constraints
+    /// and the vertices they are placed on.
+    pub(crate) fn rank_groups(&self) -> impl Iterator<Item = (Rank, &[VertexId])> {
+        self.rank_groups.iter().map(|(r, v)| (*r, v.as_slice()))
+    }
+
+    #[cfg(feature = "dot")]
+    /// Returns the clusters of the graph along with their vertices,
+    /// sorted by their names.
+    pub(crate) fn clusters(&self) -> Vec<(&str, Vec<&VertexId>)> {
+        let mut clusters: HashMap<&str, Vec<&VertexId>> = HashMap::new();
+
+        for (id, cluster) in self.clusters.iter() {
+            clusters.entry(cluster.as_str()).or_default().push(id);
+        }
+
+        let mut clusters: Vec<(&str, Vec<&VertexId>)> = clusters.into_iter().collect();
+        clusters.sort_unstable_by_key(|(cluster, _)| *cluster);
+        clusters
+    }
+
+    /// Labels the vertex with the given id. Returns the old label if successful.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::{Graph, VertexId};
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    /// let random_id = VertexId::random();
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    ///
+    /// assert!(graph.add_vertex_label(&v1, "V1").is_ok());
+    /// assert!(graph.add_vertex_label(&v2, "V2").is_ok());
+    /// assert!(graph.add_vertex_label(&v3, "V3").is_ok());
+    /// assert!(graph.add_vertex_label(&random_id, "will fail").is_err());
+    /// ```
+    pub fn add_vertex_label(
+        &mut self,
+        vertex_id: &VertexId,
+        label: &str,
+    ) -> Result<Option<String>, GraphErr> {
+        if self.vertices.get(vertex_id).is_none() {
+            return Err(GraphErr::NoSuchVertex);
+        }
+
+        let old_label = self.vertex_labels.insert(*vertex_id, label.to_owned());
+        Ok(old_label)
+    }
+
+    /// Labels the edge between the given vertices. Returns the old label if successful.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::{Graph, VertexId};
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v3, &v1).unwrap();
+    ///
+    /// assert_eq!(graph.label_edge(&v1, &v2, "V1->V2"), Ok(None));
+    /// assert_eq!(graph.label_edge(&v1, &v2, "V1=>V2"), Ok(Some("V1->V2".to_owned())));
+    /// assert!(graph.label_edge(&v3, &v1, "V3->V1").is_ok());
+    /// assert!(graph.label_edge(&v2, &v3, "V2->V3").is_err());
+    /// assert!(graph.label_edge(&v1, &v3, "V1->V3").is_err());
+    /// ```
+    pub fn label_edge(
+        &mut self,
+        a: &VertexId,
+        b: &VertexId,
+        label: &str,
+    ) -> Result<Option<String>, GraphErr> {
+        if !self.has_edge(a, b) {
+            return Err(GraphErr::NoSuchEdge);
+        }
+
+        let edge = Edge::new(*a, *b);
+        let old_label = self.edge_labels.insert(edge, label.to_owned());
+        Ok(old_label)
+    }
+
+    /// Labels the edge between the given vertices. Returns the old label if successful.
+    ///
+    /// This is an alias of `Graph::label_edge()`.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::{Graph, VertexId};
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v3, &v1).unwrap();
+    ///
+    /// assert!(graph.add_edge_label(&v1, &v2, "V1->V2").is_ok());
+    /// assert!(graph.add_edge_label(&v3, &v1, "V3->V1").is_ok());
+    /// assert!(graph.add_edge_label(&v2, &v3, "V2->V3").is_err());
+    /// assert!(graph.add_edge_label(&v1, &v3, "V1->V3").is_err());
+    /// ```
+    pub fn add_edge_label(
+        &mut self,
+        a: &VertexId,
+        b: &VertexId,
+        label: &str,
+    ) -> Result<Option<String>, GraphErr> {
+        self.label_edge(a, b, label)
+    }
+
+    /// Retrieves the label of the vertex with the given id. Vertices
+    /// which were not labeled have the empty label, which is not stored.
+    /// Use `Graph::label_or_generate()` to store a generated label instead.
+    ///
+    /// Returns `None` if there is no vertex associated with the given id in the graph.
+    pub fn vertex_label(&self, vertex_id: &VertexId) -> Option<&str> {
+        if !self.vertices.contains_key(vertex_id) {
+            return None;
+        }
+
+        self.vertex_labels
+            .get(vertex_id)
+            .map(|x| x.as_str())
+            .or(Some(DEFAULT_LABEL))
+    }
+
+    /// Retrieves the label of the vertex with the given id, labeling
+    /// the vertex with the result of the given function if it was not
+    /// labeled yet.
+    ///
+    /// Returns `None` if there is no vertex associated with the given id in the graph.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::{Graph, VertexId};
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    ///
+    /// graph.add_vertex_label(&v1, "first").unwrap();
+    ///
+    /// let generate = |_: &VertexId, value: &usize| format!("V{}", value);
+    ///
+    /// assert_eq!(graph.label_or_generate(&v1, generate), Some("first"));
+    /// assert_eq!(graph.label_or_generate(&v2, generate), Some("V2"));
+    /// assert_eq!(graph.vertex_label(&v2), Some("V2"));
+    /// assert_eq!(graph.label_or_generate(&VertexId::random(), generate), None);
+    /// ```
+    pub fn label_or_generate(
+        &mut self,
+        vertex_id: &VertexId,
+        fun: impl FnOnce(&VertexId, &T) -> String,
+    ) -> Option<&str> {
+        let value = self.vertices.get(vertex_id)?;
+
+        let label = self
+            .vertex_labels
+            .entry(*vertex_id)
+            .or_insert_with(|| fun(vertex_id, &value.0));
+
+        Some(label.as_str())
+    }
+
+    /// Labels each vertex which was not labeled yet with the result
+    /// of the given function, leaving the other labels untouched.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::{Graph, VertexId};
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    ///
+    /// graph.add_vertex_label(&v1, "first").unwrap();
+    /// graph.ensure_labels(|_, value| format!("V{}", value));
+    ///
+    /// assert_eq!(graph.vertex_label(&v1), Some("first"));
+    /// assert_eq!(graph.vertex_label(&v2), Some("V2"));
+    /// ```
+    pub fn ensure_labels(&mut self, mut fun: impl FnMut(&VertexId, &T) -> String) {
+        for (id, value) in self.vertices.iter() {
+            self.vertex_labels
+                .entry(*id)
+                .or_insert_with(|| fun(id, &value.0));
+        }
+    }
+
+    /// Retrieves the label of the edge with the given vertices.
+    ///
+    /// Returns `None` if there is no edge associated with the given vertices in the graph.
+    pub fn edge_label(&self, a: &VertexId, b: &VertexId) -> Option<&str> {
+        if !self.has_edge(a, b) {
+            return None;
+        }
+
+        self.edge_labels
+            .get(&Edge::new(*a, *b))
+            .map(|x| x.as_str())
+            .or(Some(DEFAULT_LABEL))
+    }
+
+    /// Maps each label that is placed on a vertex to a new label.
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use graphlib::{Graph, VertexId};
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    /// let random_id = VertexId::random();
+    /// let mut vertex_id: usize = 1;
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    /// let v4 = graph.add_vertex(3);
+    ///
+    /// assert!(graph.add_vertex_label(&v1, &format!("V{}", vertex_id)).is_ok());
+    /// vertex_id += 1;
+    ///
+    /// assert!(graph.add_vertex_label(&v2, &format!("V{}", vertex_id)).is_ok());
+    /// vertex_id += 1;
+    ///
+    /// assert!(graph.add_vertex_label(&v3, &format!("V{}", vertex_id)).is_ok());
+    ///
+    /// assert_eq!(graph.vertex_label(&v1).unwrap(), "V1");
+    /// assert_eq!(graph.vertex_label(&v2).unwrap(), "V2");
+    /// assert_eq!(graph.vertex_label(&v3).unwrap(), "V3");
+    ///
+    /// let new_labels: HashMap<VertexId, String> = vec![v1.clone(), v2.clone(), v3.clone(), v4.clone()]
+    ///     .iter()
+    ///     .map(|id| {
+    ///         vertex_id += 1;
+    ///         let label = format!("V{}", vertex_id);
+    ///
+    ///         (id.clone(), label)
+    ///     })
+    ///     .collect();
+    ///
+    /// graph.map_vertex_labels(|id, _old_label| new_labels.get(id).unwrap().clone());
+    ///
+    /// assert_eq!(graph.vertex_label(&v1).unwrap(), "V4");
+    /// assert_eq!(graph.vertex_label(&v2).unwrap(), "V5");
+    /// assert_eq!(graph.vertex_label(&v3).unwrap(), "V6");
+    /// assert_eq!(graph.vertex_label(&v4).unwrap(), "V7");
+    /// ```
+    pub fn map_vertex_labels(&mut self, mut fun: impl FnMut(&VertexId, Option<&str>) -> String) {
+        for (id, _) in self.vertices.iter() {
+            self.vertex_labels
+                .entry(*id)
+                .and_modify(|e| {
+                    *e = fun(id, Some(e));
+                })
+                .or_insert_with(|| fun(id, None));
+        }
+    }
+
+    /// Maps each label that is placed on an edge to a new label.
+    ///
+    /// ```rust
+    /// use graphlib::{Graph, VertexId};
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(0);
+    /// let v2 = graph.add_vertex(1);
+    /// let v3 = graph.add_vertex(2);
+    /// let v4 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    /// graph.add_edge(&v1, &v4).unwrap();
+    /// graph.add_edge(&v4, &v3).unwrap();
+    ///
+    /// assert!(graph.label_edge(&v1, &v2, "V1->V2").is_ok());
+    /// assert!(graph.label_edge(&v2, &v3, "V2->V3").is_ok());
+    /// assert!(graph.label_edge(&v1, &v4, "V1->V4").is_ok());
+    /// assert!(graph.label_edge(&v1, &v3, "V1->V3").is_err());
+    ///
+    /// assert_eq!(graph.edge_label(&v1, &v2).unwrap(), "V1->V2");
+    /// assert_eq!(graph.edge_label(&v2, &v3).unwrap(), "V2->V3");
+    /// assert_eq!(graph.edge_label(&v1, &v4).unwrap(), "V1->V4");
+    /// assert_eq!(graph.edge_label(&v4, &v3).unwrap(), "");
+    ///
+    /// graph.map_edge_labels(|_a, _b, old_label| match old_label {
+    ///     Some(old_label) => format!("*{}*", old_label),
+    ///     None => "unlabeled".to_owned(),
+    /// });
+    ///
+    /// assert_eq!(graph.edge_label(&v1, &v2).unwrap(), "*V1->V2*");
+    /// assert_eq!(graph.edge_label(&v2, &v3).unwrap(), "*V2->V3*");
+    /// assert_eq!(graph.edge_label(&v1, &v4).unwrap(), "*V1->V4*");
+    /// assert_eq!(graph.edge_label(&v4, &v3).unwrap(), "unlabeled");
+    /// ```
+    pub fn map_edge_labels(
+        &mut self,
+        mut fun: impl FnMut(&VertexId, &VertexId, Option<&str>) -> String,
+    ) {
+        for (edge, _) in self.edges.iter() {
+            let (a, b) = (edge.outbound(), edge.inbound());
+
+            self.edge_labels
+                .entry(Edge::new(*a, *b))
+                .and_modify(|e| {
+                    *e = fun(a, b, Some(e));
+                })
+                .or_insert_with(|| fun(a, b, None));
+        }
+    }
+
+    /// Returns the weight of the edges added without one.
+    fn default_weight(&self) -> f32 {
+        match self.weight_policy {
+            WeightPolicy::Fixed(weight) => weight,
+            WeightPolicy::Mean if self.edges.is_empty() => 0.0,
+            WeightPolicy::Mean => {
+                let mean = self.weight_sum / self.edges.len() as f64;
+                (mean as f32).clamp(self.rules.min_weight, self.rules.max_weight)
+            }
+            WeightPolicy::Unweighted => 0.0,
+        }
+    }
+
+    /// Applies the duplicate edge policy to the edge from `a` to `b`,
+    /// which is already in the graph, given the weight of the added edge.
+    fn add_duplicate_edge(
+        &mut self,
+        a: &VertexId,
+        b: &VertexId,
+        weight: Option<f32>,
+    ) -> Result<(), GraphErr> {
+        match self.rules.duplicate_edges {
+            DuplicateEdgePolicy::Ignore => Ok(()),
+            DuplicateEdgePolicy::Reject => Err(GraphErr::CannotAddEdge),
+            DuplicateEdgePolicy::Replace => {
+                let weight = weight.unwrap_or_else(|| self.default_weight());
+                self.set_weight(a, b, weight)
+            }
+        }
+    }
+
+    fn do_add_default_edge(
+        &mut self,
+        a: &VertexId,
+        b: &VertexId,
+        check_cycle: bool,
+    ) -> Result<(), GraphErr> {
+        let weight = self.default_weight();
+
+        if !self.rules.allows_weight(weight) && self.weight_policy != WeightPolicy::Unweighted {
+            return Err(GraphErr::InvalidWeight);
+        }
+
+        self.do_add_edge(a, b, weight, check_cycle)?;
+
+        if self.weight_policy == WeightPolicy::Unweighted {
+            self.unweighted_edges.insert(Edge::new(*a, *b));
+        }
+
+        Ok(())
+    }
+
+    fn do_add_edge(
+        &mut self,
+        a: &VertexId,
+        b: &VertexId,
+        weight: f32,
+        check_cycle: bool,
+    ) -> Result<(), GraphErr> {
+        self.check_ids(&[a, b]);
+
+        let id_ptr1 = if self.vertices.get(a).is_some() {
+            *a
+        } else {
+            return Err(GraphErr::NoSuchVertex);
+        };
+
+        let id_ptr2 = if self.vertices.get(b).is_some() {
+            *b
+        } else {
+            return Err(GraphErr::NoSuchVertex);
+        };
+
+        if id_ptr1 == id_ptr2 && !self.rules.self_loops {
+            return Err(GraphErr::CannotAddEdge);
+        }
+
+        let edge = Edge::new(id_ptr1, id_ptr2);
+
+        // Push edge
+        self.edges.insert(edge, weight);
+        self.weight_sum += f64::from(weight);
+
+        // Insert in the outbound table after the
+        // vertices whose edges weigh as much
+        let edges = &self.edges;
+        let outbounds = self.outbound_table.entry(id_ptr1).or_default();
+        let index = adjacency_index(
+            edges,
+            outbounds,
+            |v| Edge::new(id_ptr1, v),
+            weight,
+            outbounds.len(),
+        );
+
+        outbounds.insert(index, id_ptr2);
+
+        // Likewise in the inbound table
+        let inbounds = self.inbound_table.entry(id_ptr2).or_default();
+        let index = adjacency_index(
+            edges,
+            inbounds,
+            |v| Edge::new(v, id_ptr2),
+            weight,
+            inbounds.len(),
+        );
+
+        inbounds.insert(index, id_ptr1);
+
+        // Remove outbound vertex from roots
+        let was_root = self.remove_root(b);
+
+        // Remove inbound vertex from tips
+        let was_tip = self.remove_tip(a);
+
+        let mut is_cyclic = false;
+
+        if check_cycle || self.rules.acyclic {
+            let mut dfs = Dfs::new(&self);
+            is_cyclic = dfs.is_cyclic();
+        }
+
+        // Roll-back changes if cycle check succeeds
+        if is_cyclic {
+            // Remove from edge table without recording the removal
+            let history = self.history.take();
+            self.remove_edge(a, b);
+            self.history = history;
+
+            if was_root {
+                self.insert_root(*b);
+            }
+
+            if was_tip {
+                self.insert_tip(*a);
+            }
+
+            return Err(GraphErr::CycleError);
+        }
+
+        self.record(Mutation::AddEdge(*a, *b, weight), None, false);
+        self.debug_validate();
+
+        Ok(())
+    }
+
+    /// Panics if the internal tables of the graph are inconsistent.
+    /// The check only runs in debug builds with the `debug-invariants`
+    /// feature, after each mutation of the graph.
+    #[inline]
+    fn debug_validate(&self) {
+        #[cfg(feature = "debug-invariants")]
+        debug_assert_eq!(self.validate(), Ok(()));
+    }
+
+    /// Panics if the graph is checked and any of the
+    /// given ids was not created by it.
+    fn check_ids(&self, ids: &[&VertexId]) {
+        if let Some(ref tag) = self.id_tag {
+            if ids.iter().any(|id| !id.has_tag(tag)) {
+                panic!("{}", FOREIGN_ID_MSG);
+            }
+        }
+    }
+
+    /// Returns the next sequential id which is not used by a vertex.
+    fn next_sequential_id(&mut self) -> VertexId {
+        loop {
+            let number = self.next_id.unwrap_or(0);
+            let id = VertexId::sequential(self.id_tag.as_ref(), number);
+
+            self.next_id = Some(number + 1);
+
+            if !self.vertices.contains_key(&id) {
+                return id;
+            }
+        }
+    }
+
+    /// Places a new vertex with the given id in the graph.
+    pub(crate) fn insert_vertex(&mut self, id: VertexId, item: T) {
+        self.vertices.insert(id, (item, id));
+        self.insert_root(id);
+        self.insert_tip(id);
+        self.record(Mutation::AddVertex(id), None, false);
+        self.debug_validate();
+    }
+
+    /// Records the mutation if the history of the graph is tracked.
+    fn record(&mut self, mutation: Mutation, value: Option<(T, VertexData)>, unweighted: bool) {
+        if let Some(ref mut history) = self.history {
+            history.record(mutation, value, unweighted);
+        }
+    }
+
+    /// Starts an operation whose mutations are undone and redone together.
+    fn begin_step(&mut self) {
+        if let Some(ref mut history) = self.history {
+            history.begin();
+        }
+    }
+
+    fn end_step(&mut self) {
+        if let Some(ref mut history) = self.history {
+            history.end();
+        }
+    }
+
+    /// Reverts a mutation of the given step, keeping the values
+    /// of the vertices it removes in the step.
+    fn revert(&mut self, mutation: &Mutation, step: &mut Step<T>) {
+        match *mutation {
+            Mutation::AddVertex(id) => self.stash_vertex(id, step),
+            Mutation::RemoveVertex(id) => self.restore_vertex(id, step),
+            Mutation::AddEdge(a, b, _) => {
+                if self.is_unweighted(&a, &b) {
+                    step.unweighted.insert(Edge::new(a, b));
+                }
+
+                self.remove_edge(&a, &b);
+            }
+            Mutation::RemoveEdge(a, b, weight) => {
+                self.do_add_edge(&a, &b, weight, false).unwrap();
+                self.restore_unweighted(a, b, step);
+            }
+            Mutation::SetWeight(a, b, old_weight, _) => {
+                self.set_weight(&a, &b, old_weight).unwrap();
+                self.restore_unweighted(a, b, step);
+            }
+        }
+    }
+
+    /// Applies again a mutation of the given step which was reverted.
+    fn reapply(&mut self, mutation: &Mutation, step: &mut Step<T>) {
+        match *mutation {
+            Mutation::AddVertex(id) => self.restore_vertex(id, step),
+            Mutation::RemoveVertex(id) => self.stash_vertex(id, step),
+            Mutation::AddEdge(a, b, weight) => {
+                self.do_add_edge(&a, &b, weight, false).unwrap();
+                self.restore_unweighted(a, b, step);
+            }
+            Mutation::RemoveEdge(a, b, _) => {
+                self.remove_edge(&a, &b);
+            }
+            Mutation::SetWeight(a, b, _, new_weight) => {
+                self.set_weight(&a, &b, new_weight).unwrap();
+            }
+        }
+    }
+
+    /// Removes the vertex with the given id, keeping its value
+    /// along with its label, attributes and priority in the step.
+    fn stash_vertex(&mut self, id: VertexId, step: &mut Step<T>) {
+        if let Some((value, _)) = self.vertices.remove(&id) {
+            step.values.insert(id, (value, self.take_vertex_data(&id)));
+        }
+
+        self.remove(&id);
+    }
+
+    /// Places back the vertex with the given id which was
+    /// removed by the step, along with its label, attributes
+    /// and priority.
+    fn restore_vertex(&mut self, id: VertexId, step: &mut Step<T>) {
+        if let Some((value, data)) = step.values.remove(&id) {
+            self.insert_vertex(id, value);
+
+            if let Some(label) = data.label {
+                self.vertex_labels.insert(id, label);
+            }
+
+            if let Some(attrs) = data.attrs {
+                self.vertex_attrs.insert(id, attrs);
+            }
+
+            if let Some(priority) = data.priority {
+                self.priorities.insert(id, priority);
+            }
+        }
+    }
+
+    /// Removes the label, attributes and priority of the vertex with the given id.
+    fn take_vertex_data(&mut self, id: &VertexId) -> VertexData {
+        VertexData {
+            label: self.vertex_labels.remove(id),
+            attrs: self.vertex_attrs.remove(id),
+            priority: self.priorities.remove(id),
+        }
+    }
+
+    fn restore_unweighted(&mut self, a: VertexId, b: VertexId, step: &Step<T>) {
+        if step.unweighted.contains(&Edge::new(a, b)) {
+            self.unweighted_edges.insert(Edge::new(a, b));
+        }
+    }
+
+    fn insert_root(&mut self, id: VertexId) {
+        if self.roots.insert(id) {
+            if let Some(ref mut frontier) = self.frontier {
+                frontier.root_changed(id, false);
+            }
+        }
+    }
+
+    fn remove_root(&mut self, id: &VertexId) -> bool {
+        let removed = self.roots.remove(id);
+
+        if removed {
+            if let Some(ref mut frontier) = self.frontier {
+                frontier.root_changed(*id, true);
+            }
+        }
+
+        removed
+    }
+
+    fn insert_tip(&mut self, id: VertexId) {
+        if self.tips.insert(id) {
+            if let Some(ref mut frontier) = self.frontier {
+                frontier.tip_changed(id, false);
+            }
+        }
+    }
+
+    fn remove_tip(&mut self, id: &VertexId) -> bool {
+        let removed = self.tips.remove(id);
+
+        if removed {
+            if let Some(ref mut frontier) = self.frontier {
+                frontier.tip_changed(*id, true);
+            }
+        }
+
+        removed
+    }
+
+    /// Follows the given parent links back from the given vertex
+    /// and returns the path leading to it. The start of the path
+    /// is the vertex which is its own parent.
+    fn trace_path(parents: &HashMap<VertexId, VertexId>, end: VertexId) -> Vec<VertexId> {
+        let mut path = vec![end];
+        let mut current = end;
+
+        while parents[&current] != current {
+            current = parents[&current];
+            path.push(current);
+        }
+
+        path.reverse();
+        path
+    }
+
+    /// Attempts to fetch a reference to a stored vertex id
+    /// which is equal to the given `VertexId`.
+    pub(crate) fn fetch_id_ref<'b>(&'b self, id: &VertexId) -> Option<&'b VertexId> {
+        match self.vertices.get(id) {
+            Some((_, id_ptr)) => Some(id_ptr),
+            None => None,
+        }
+    }
+}
+
+impl Graph<usize> {
+    /// Creates a complete directed graph with `n` vertices, holding
+    /// the numbers `0..n`, with an edge between every two distinct
+    /// vertices in both directions. Returns the graph along with the
+    /// ids of its vertices, ordered by their values.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let (graph, ids) = Graph::complete(4);
+    ///
+    /// assert_eq!(graph.edge_count(), 12);
+    /// assert!(graph.has_edge(&ids[3], &ids[0]));
+    /// ```
+    pub fn complete(n: usize) -> (Graph<usize>, Vec<VertexId>) {
+        crate::generators::complete(n)
+    }
+
+    /// Creates a directed cycle with `n` vertices, holding the numbers
+    /// `0..n`, with edges from each vertex to the next one and from the
+    /// last vertex to the first one. Returns the graph along with the
+    /// ids of its vertices, ordered by their values.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let (graph, ids) = Graph::cycle(3);
+    ///
+    /// assert_eq!(graph.edge_count(), 3);
+    /// assert!(graph.has_edge(&ids[2], &ids[0]));
+    /// assert!(graph.is_cyclic());
+    /// ```
+    pub fn cycle(n: usize) -> (Graph<usize>, Vec<VertexId>) {
+        crate::generators::cycle(n)
+    }
+
+    /// Creates a directed path with `n` vertices, holding the numbers
+    /// `0..n`, with edges from each vertex to the next one. Returns the
+    /// graph along with the ids of its vertices, ordered by their values.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let (graph, ids) = Graph::path(3);
+    ///
+    /// assert_eq!(graph.edge_count(), 2);
+    /// assert_eq!(graph.topo().collect::<Vec<_>>(), vec![&ids[0], &ids[1], &ids[2]]);
+    /// ```
+    pub fn path(n: usize) -> (Graph<usize>, Vec<VertexId>) {
+        crate::generators::path(n)
+    }
+
+    /// Creates a directed star with `n` vertices, holding the numbers
+    /// `0..n`, with edges from the first vertex to each of the others.
+    /// Returns the graph along with the ids of its vertices, ordered by
+    /// their values.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let (graph, ids) = Graph::star(5);
+    ///
+    /// assert_eq!(graph.out_neighbors_count(&ids[0]), 4);
+    /// assert_eq!(graph.tips().count(), 4);
+    /// ```
+    pub fn star(n: usize) -> (Graph<usize>, Vec<VertexId>) {
+        crate::generators::star(n)
+    }
+
+    /// Creates a directed grid with `width * height` vertices, where the
+    /// vertex in column `x` and row `y` holds the number `y * width + x`
+    /// and has edges to its right and bottom neighbors. Returns the graph
+    /// along with the ids of its vertices, ordered by their values.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let (graph, ids) = Graph::grid(3, 2);
+    ///
+    /// assert_eq!(graph.vertex_count(), 6);
+    /// assert_eq!(graph.edge_count(), 7);
+    /// assert!(graph.has_edge(&ids[0], &ids[1]));
+    /// assert!(graph.has_edge(&ids[0], &ids[3]));
+    /// ```
+    pub fn grid(width: usize, height: usize) -> (Graph<usize>, Vec<VertexId>) {
+        crate::generators::grid(width, height)
+    }
+
+    /// Generates a random directed graph with `n` vertices, holding the
+    /// numbers `0..n`, where each of the possible edges between distinct
+    /// vertices is added with probability `p` (the Erdős–Rényi `G(n, p)`
+    /// model).
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    /// use rand::SeedableRng;
+    /// use rand_isaac::IsaacRng;
+    ///
+    /// let mut rng = IsaacRng::seed_from_u64(42);
+    /// let graph = Graph::random_gnp(100, 0.1, &mut rng);
+    ///
+    /// assert_eq!(graph.vertex_count(), 100);
+    /// assert!(graph.edge_count() > 0);
+    /// ```
+    pub fn random_gnp<R: Rng + ?Sized>(n: usize, p: f64, rng: &mut R) -> Graph<usize> {
+        crate::generators::gnp(n, p, rng)
+    }
+
+    /// Generates a random directed graph with `n` vertices, holding the
+    /// numbers `0..n`, and `m` distinct edges between distinct vertices
+    /// chosen uniformly at random (the Erdős–Rényi `G(n, m)` model).
+    ///
+    /// Fails with `GraphErr::CannotAddEdge` if `m` is larger than the
+    /// number of possible edges, which is `n * (n - 1)`.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    /// use rand::SeedableRng;
+    /// use rand_isaac::IsaacRng;
+    ///
+    /// let mut rng = IsaacRng::seed_from_u64(42);
+    /// let graph = Graph::random_gnm(100, 500, &mut rng).unwrap();
+    ///
+    /// assert_eq!(graph.vertex_count(), 100);
+    /// assert_eq!(graph.edge_count(), 500);
+    /// ```
+    pub fn random_gnm<R: Rng + ?Sized>(
+        n: usize,
+        m: usize,
+        rng: &mut R,
+    ) -> Result<Graph<usize>, GraphErr> {
+        crate::generators::gnm(n, m, rng)
+    }
+
+    /// Generates a random scale-free directed graph with `n` vertices,
+    /// holding the numbers `0..n`, using the Barabási–Albert preferential
+    /// attachment model. The first `m` vertices have no edges between
+    /// them and every vertex added after them gets edges towards `m`
+    /// distinct earlier vertices, which are chosen with a probability
+    /// proportional to their degree. The graph has `(n - m) * m` edges.
+    ///
+    /// Fails with `GraphErr::CannotAddEdge` if `m` is zero
+    /// or not smaller than `n`.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    /// use rand::SeedableRng;
+    /// use rand_isaac::IsaacRng;
+    ///
+    /// let mut rng = IsaacRng::seed_from_u64(42);
+    /// let graph = Graph::barabasi_albert(100, 2, &mut rng).unwrap();
+    ///
+    /// assert_eq!(graph.vertex_count(), 100);
+    /// assert_eq!(graph.edge_count(), 196);
+    /// ```
+    pub fn barabasi_albert<R: Rng + ?Sized>(
+        n: usize,
+        m: usize,
+        rng: &mut R,
+    ) -> Result<Graph<usize>, GraphErr> {
+        crate::generators::barabasi_albert(n, m, rng)
+    }
+}
+
+impl Graph<String> {
+    /// Parses a graph from a small textual language and returns it along
+    /// with the mapping between the names of its vertices and their ids.
+    /// The values of the vertices are their names.
+    ///
+    /// Statements are separated by semicolons or newlines and each of them
+    /// is either a vertex name or a chain of edges such as `a -> b -> c`.
+    /// The weight of an edge can be given in brackets after its target,
+    /// as in `a -> b [0.5]`, and defaults to `0.0`. Names are made of
+    /// letters, digits, underscores and dots, or can be quoted. Text after
+    /// a `#` is ignored until the end of the line.
+    ///
+    /// Fails with `GraphErr::CouldNotParse` if the input is malformed or
+    /// with `GraphErr::InvalidWeight` if a weight is out of range.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let (graph, ids) = Graph::parse("a -> b -> c; a -> d [0.5]").unwrap();
+    ///
+    /// assert_eq!(graph.vertex_count(), 4);
+    /// assert_eq!(graph.edge_count(), 3);
+    /// assert!(graph.has_edge(&ids["b"], &ids["c"]));
+    /// assert_eq!(graph.weight(&ids["a"], &ids["d"]), Some(0.5));
+    /// assert_eq!(graph.fetch(&ids["a"]).unwrap(), "a");
+    /// ```
+    pub fn parse(input: &str) -> Result<(Graph<String>, HashMap<String, VertexId>), GraphErr> {
+        crate::parse::parse(input)
+    }
+}
+
+#[cfg(feature = "dot")]
+impl Graph<String> {
+    /// Loads a directed graph from its dot representation. The
+    /// values of the vertices are the labels of their nodes, or
+    /// the names of the nodes if they are not labeled.
+    ///
+    /// Nodes which have an `id` attribute, such as the ones rendered
+    /// with `DotOptions::embed_ids`, keep their `VertexId`. All other
+    /// vertices are assigned a new id.
+    ///
+    /// This method requires the `dot` crate feature.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    /// use graphlib::dot::DotOptions;
+    ///
+    /// let mut graph: Graph<String> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex("a".to_owned());
+    /// let v2 = graph.add_vertex("b".to_owned());
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_vertex_label(&v1, "a").unwrap();
+    ///
+    /// let options = DotOptions {
+    ///     embed_ids: true,
+    ///     ..DotOptions::default()
+    /// };
+    ///
+    /// let mut output = Vec::new();
+    /// graph.to_dot_with_options("example", &mut output, &options).unwrap();
+    ///
+    /// let loaded = Graph::from_dot(&String::from_utf8(output).unwrap()).unwrap();
+    ///
+    /// assert!(loaded.has_edge(&v1, &v2));
+    /// assert_eq!(loaded.fetch(&v1).unwrap(), "a");
+    /// assert_eq!(loaded.vertex_label(&v1), Some("a"));
+    /// ```
+    pub fn from_dot(input: &str) -> Result<Graph<String>, GraphErr> {
+        crate::dot::parse(input)
+    }
+}
+
+impl<T, S: BuildHasher + Clone + Default> Default for Graph<T, S> {
+    fn default() -> Graph<T, S> {
+        Graph::with_hasher(S::default())
+    }
+}
+
+/// Formats the entries of a table as a map without requiring
+/// its hasher to implement `BuildHasher`.
+struct DebugMap<I>(I);
+
+impl<I: Clone + Iterator<Item = (K, V)>, K: Debug, V: Debug> Debug for DebugMap<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.0.clone()).finish()
+    }
+}
+
+/// Formats the entries of a table as a set without requiring
+/// its hasher to implement `BuildHasher`.
+struct DebugSet<I>(I);
+
+impl<I: Clone + Iterator> Debug for DebugSet<I>
+where
+    I::Item: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.0.clone()).finish()
+    }
+}
+
+impl<T: Debug, S> Debug for Graph<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("Graph");
+
+        debug
+            .field("vertices", &DebugMap(self.vertices.iter()))
+            .field("edges", &DebugMap(self.edges.iter()))
+            .field("roots", &DebugSet(self.roots.iter()))
+            .field("tips", &DebugSet(self.tips.iter()))
+            .field("inbound_table", &DebugMap(self.inbound_table.iter()))
+            .field("outbound_table", &DebugMap(self.outbound_table.iter()))
+            .field("id_tag", &self.id_tag)
+            .field("next_id", &self.next_id)
+            .field("id_provider", &self.id_provider.is_some())
+            .field("weight_policy", &self.weight_policy)
+            .field("rules", &self.rules)
+            .field("weight_sum", &self.weight_sum)
+            .field("unweighted_edges", &self.unweighted_edges)
+            .field("frontier", &self.frontier)
+            .field("history", &self.history)
+            .field("priorities", &self.priorities)
+            .field("vertex_attrs", &self.vertex_attrs)
+            .field("edge_attrs", &self.edge_attrs)
+            .field("vertex_labels", &self.vertex_labels)
+            .field("edge_labels", &self.edge_labels);
+
+        #[cfg(feature = "dot")]
+        debug
+            .field("rank_groups", &self.rank_groups)
+            .field("clusters", &self.clusters);
+
+        debug.finish()
+    }
+}
+
+impl<T: Display, S: BuildHasher> Display for Graph<T, S> {
+    /// Writes one line for each vertex, holding its value followed by
+    /// the values of its outbound neighbors and the weights of the
+    /// edges to them, such as `A -> B (0.3), C`. Edges weighing `0.0`,
+    /// as do the edges added without a weight by default, are written
+    /// without their weights.
+    ///
+    /// The neighbors are sorted by decreasing weight, as returned by
+    /// `Graph::out_neighbors()`, and then by their text, and the lines
+    /// are sorted, so equal graphs are written the same way.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines: Vec<String> = self
+            .vertices()
+            .map(|v| {
+                let mut neighbors: Vec<(f32, String)> = self
+                    .out_neighbors(v)
+                    .map(|n| {
+                        let weight = self.weight(v, n).unwrap();
+                        let mut text = format!("{}", self.fetch(n).unwrap());
+
+                        if weight != 0.0 {
+                            text.push_str(&format!(" ({})", weight));
+                        }
+
+                        (weight, text)
+                    })
+                    .collect();
+
+                neighbors.sort_by(|(w1, t1), (w2, t2)| w2.total_cmp(w1).then_with(|| t1.cmp(t2)));
+
+                let mut line = format!("{}", self.fetch(v).unwrap());
+
+                for (i, (_, text)) in neighbors.iter().enumerate() {
+                    line.push_str(if i == 0 { " -> " } else { ", " });
+                    line.push_str(text);
+                }
+
+                line
+            })
+            .collect();
+
+        lines.sort();
+        f.write_str(&lines.join("\n"))
+    }
+}
+
+impl<T: PartialEq, S: BuildHasher> PartialEq for Graph<T, S> {
+    /// Graphs are equal if they have the same vertices, with equal
+    /// values, and the same edges, with equal weights. Edges added under
+    /// `WeightPolicy::Unweighted` only equal edges which were also added
+    /// under it, since their path costs differ.
+    fn eq(&self, other: &Graph<T, S>) -> bool {
+        self.diff(other).is_empty() && self.unweighted_edges == other.unweighted_edges
+    }
+}
+
+impl<'a, T, S: BuildHasher> IntoIterator for &'a Graph<T, S> {
+    type Item = (&'a VertexId, &'a T);
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T, S> IntoIterator for Graph<T, S> {
+    type Item = (VertexId, T);
+    type IntoIter = IntoIter<T>;
+
+    /// Consumes the graph, returning an iterator over
+    /// the ids and the values of its vertices.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<String> = Graph::new();
+    /// let id = graph.add_vertex("a".to_string());
+    ///
+    /// for (v, value) in graph {
+    ///     assert_eq!(v, id);
+    ///     assert_eq!(value, "a");
+    /// }
+    /// ```
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self.vertices.into_inner().into_iter())
+    }
+}
+
+impl<T, S: BuildHasher> Index<&VertexId> for Graph<T, S> {
+    type Output = T;
+
+    /// Returns a reference to the value of the vertex with the given
+    /// id, panicking if there is no such vertex in the graph.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    /// let id = graph.add_vertex(1);
+    ///
+    /// assert_eq!(graph[&id], 1);
+    /// ```
+    fn index(&self, id: &VertexId) -> &T {
+        self.fetch(id).expect(MISSING_VERTEX_MSG)
+    }
+}
+
+impl<T, S: BuildHasher> IndexMut<&VertexId> for Graph<T, S> {
+    /// Returns a mutable reference to the value of the vertex with
+    /// the given id, panicking if there is no such vertex in the graph.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    /// let id = graph.add_vertex(1);
+    ///
+    /// graph[&id] += 1;
+    ///
+    /// assert_eq!(graph[&id], 2);
+    /// ```
+    fn index_mut(&mut self, id: &VertexId) -> &mut T {
+        self.fetch_mut(id).expect(MISSING_VERTEX_MSG)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_thread_safe() {
+        let mut graph: Graph<usize> = Graph::new();
+        graph.add_vertex(0);
+
+        std::panic::set_hook(Box::new(move |_| {
+            let mut graph = graph.clone();
+
+            graph.add_vertex(1);
+            graph.add_vertex(2);
+        }));
+    }
+
+    #[test]
+    fn dfs() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(0);
+        let v2 = graph.add_vertex(1);
+        let v3 = graph.add_vertex(2);
+        let v4 = graph.add_vertex(3);
+        let v5 = graph.add_vertex(4);
+        let v6 = graph.add_vertex(5);
+        let v7 = graph.add_vertex(6);
+
+        graph.add_edge_with_weight(&v1, &v2, -0.23).unwrap();
+        graph.add_edge(&v3, &v1).unwrap();
+        graph.add_edge_with_weight(&v1, &v4, -0.56).unwrap();
+        graph.add_edge_with_weight(&v1, &v5, 0.44).unwrap();
+        graph.add_edge(&v5, &v6).unwrap();
+        graph.add_edge(&v5, &v7).unwrap();
+
+        graph.set_weight(&v5, &v6, 0.23).unwrap();
+        graph.set_weight(&v5, &v7, 0.33).unwrap();
+
+        let mut dfs = graph.dfs();
+
+        assert_eq!(dfs.next(), Some(&v3));
+        assert_eq!(dfs.next(), Some(&v1));
+        assert_eq!(dfs.next(), Some(&v4));
+        assert_eq!(dfs.next(), Some(&v2));
+        assert_eq!(dfs.next(), Some(&v5));
+        assert_eq!(dfs.next(), Some(&v6));
+        assert_eq!(dfs.next(), Some(&v7));
+    }
+
+    #[test]
+    fn dfs_mul_roots() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(0);
+        let v2 = graph.add_vertex(1);
+        let v3 = graph.add_vertex(2);
+        let v4 = graph.add_vertex(3);
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v3, &v1).unwrap();
+        graph.add_edge(&v1, &v4).unwrap();
+
+        let v5 = graph.add_vertex(4);
+        let v6 = graph.add_vertex(5);
+
+        graph.add_edge(&v5, &v6).unwrap();
+
+        // Iterate over vertices
+        let mut dfs = graph.dfs();
+
+        for _ in 0..2 {
+            let v = dfs.next();
+
+            if v == Some(&v3) {
+                assert_eq!(dfs.next(), Some(&v1));
+                assert!(set![&v2, &v4] == (&mut dfs).take(2).collect());
+            } else if v == Some(&v5) {
+                assert_eq!(dfs.next(), Some(&v6));
+            } else {
+                panic!("Not a root node")
+            }
+        }
+
+        assert_eq!(dfs.count(), 0, "There were remaining nodes");
+    }
+
+    #[test]
+    #[should_panic(expected = "vertex id belongs to a different graph")]
+    fn test_checked_graph_rejects_foreign_edges() {
+        let mut graph1: Graph<usize> = Graph::new_checked();
+        let mut graph2: Graph<usize> = Graph::new();
+
+        let v1 = graph1.add_vertex(1);
+        let v2 = graph2.add_vertex(2);
+
+        // Inserting the same id into both graphs
+        graph1.insert_vertex(v2, 2);
+
+        let _ = graph1.add_edge(&v1, &v2);
+    }
+
+    #[test]
+    fn test_unchecked_graph_accepts_foreign_ids() {
+        let mut graph1: Graph<usize> = Graph::new_checked();
+        let graph2: Graph<usize> = Graph::new();
+
+        let v1 = graph1.add_vertex(1);
+
+        assert_eq!(graph2.fetch(&v1), None);
+        assert!(!graph2.has_edge(&v1, &v1));
+    }
+
+    #[test]
+    fn test_keep_top_k_out_edges() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+
+        graph.add_edge_with_weight(&v1, &v2, 0.3).unwrap();
+        graph.add_edge_with_weight(&v1, &v3, -0.4).unwrap();
+        graph.add_edge_with_weight(&v3, &v2, 0.5).unwrap();
+
+        graph.keep_top_k_out_edges(1);
+
+        assert_eq!(graph.edge_count(), 2);
+        assert_eq!(graph.out_neighbors(&v1).collect::<Vec<_>>(), vec![&v2]);
+        assert_eq!(graph.in_neighbors_count(&v3), 0);
+        assert!(graph.roots.contains(&v3));
+
+        graph.keep_top_k_out_edges(0);
+
+        assert_eq!(graph.edge_count(), 0);
+        assert!(graph.inbound_table.is_empty());
+        assert!(graph.outbound_table.is_empty());
+        assert_eq!(graph.roots.len(), 3);
+        assert_eq!(graph.tips.len(), 3);
+    }
+
+    #[test]
+    fn test_remove_edge() {
+        let mut graph = Graph::new();
+
+        let v1 = graph.add_vertex(0);
+        let v2 = graph.add_vertex(1);
+        let v3 = graph.add_vertex(2);
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v2, &v3).unwrap();
+
+        let old_inbound = graph.inbound_table.clone();
+        let old_outbound = graph.outbound_table.clone();
+
+        graph.add_edge(&v3, &v1).unwrap();
+        graph.remove_edge(&v3, &v1);
+
+        assert_eq!(old_inbound, graph.inbound_table.clone());
+        assert_eq!(old_outbound, graph.outbound_table);
+    }
+
+    #[test]
+    fn test_remove_drops_labels() {
+        let mut graph = Graph::new();
+
+        let v1 = graph.add_vertex(0);
+        let v2 = graph.add_vertex(1);
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_vertex_label(&v1, "V1").unwrap();
+        graph.label_edge(&v1, &v2, "V1->V2").unwrap();
+
+        graph.remove_edge(&v1, &v2);
+        assert!(graph.edge_labels.is_empty());
+
+        graph.add_edge(&v1, &v2).unwrap();
+        assert_eq!(graph.edge_label(&v1, &v2), Some(""));
+
+        graph.remove(&v1);
+        assert!(graph.vertex_labels.is_empty());
+    }
+
+    #[test]
+    fn test_non_clonable_type() {
+        // this simply tests that a Graph that has a non-clonable type can be created
+        // this is done easiest by adding dyn Trait object, which can never be cloned
+        //
+        // It also tests that the dyn object can still be used as expected
+        let mut graph = Graph::<Box<dyn std::fmt::Display>>::new();
+
+        graph.add_vertex(Box::new(String::from("Hello World")));
+        let mut result = String::new();
+        for vertex_identifier in graph.vertices() {
+            if let Some(v) = graph.fetch(vertex_identifier) {
+                result = format!("{}", v);
+            }
+        }
+
+        assert_eq!(result, "Hello World");
+    }
+    #[test]
+    fn test_clonable() {
+        let mut graph = Graph::new();
+        graph.add_vertex(String::from("Test"));
+
+        let cloned = graph.clone();
+        assert_eq!(graph, cloned);
+    }
+
+    #[test]
+    fn test_add_edge_cycle_check() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        // Id of vertex that is not place in the graph
+        let id = VertexId::random();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+
+        // Adding an edge is idempotent
+        graph.add_edge_check_cycle(&v1, &v2).unwrap();
+        graph.add_edge_check_cycle(&v1, &v2).unwrap();
+        graph.add_edge_check_cycle(&v1, &v2).unwrap();
+
+        let mut graph2 = graph.clone();
+
+        // Fails on adding an edge which creates
+        // a cycle in the graph.
+        assert_eq!(
+            graph2.add_edge_check_cycle(&v2, &v1),
+            Err(GraphErr::CycleError)
+        );
+
+        // Check that the graph state has rolled back
+        assert_eq!(graph.edges, graph2.edges);
+        assert_eq!(graph.roots, graph2.roots);
+        assert_eq!(graph.tips, graph2.tips);
+        assert_eq!(graph.inbound_table, graph2.inbound_table);
+        assert_eq!(graph.outbound_table, graph2.outbound_table);
+    }
+
+    #[test]
+    fn test_mean_weight_policy() {
+        let mut graph: Graph<usize> = Graph::new();
+        graph.set_weight_policy(WeightPolicy::Mean).unwrap();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+        let v4 = graph.add_vertex(4);
+
+        // No edges to average
+        graph.add_edge(&v1, &v2).unwrap();
+        assert_eq!(graph.weight(&v1, &v2), Some(0.0));
+
+        graph.set_weight(&v1, &v2, 0.2).unwrap();
+        graph.add_edge_with_weight(&v2, &v3, 0.6).unwrap();
+        graph.add_edge(&v3, &v4).unwrap();
+        assert!((graph.weight(&v3, &v4).unwrap() - 0.4).abs() < 1e-6);
+
+        graph.remove_edge(&v3, &v4);
+        graph.remove_edge(&v1, &v2);
+        graph.add_edge(&v1, &v4).unwrap();
+        assert!((graph.weight(&v1, &v4).unwrap() - 0.6).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_invalid_weight_policy() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        assert_eq!(
+            graph.set_weight_policy(WeightPolicy::Fixed(1.5)),
+            Err(GraphErr::InvalidWeight)
+        );
+        assert_eq!(graph.weight_policy(), WeightPolicy::Fixed(0.0));
+    }
+
+    #[test]
+    fn test_unweighted_edges_count_as_hops() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+
+        // Zero weight edges make every path equally short
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v2, &v3).unwrap();
+        assert_eq!(graph.dijkstra(&v1, &v3).count(), 3);
+
+        graph.set_weight_policy(WeightPolicy::Unweighted).unwrap();
+        graph.add_edge(&v1, &v3).unwrap();
+        graph.set_weight(&v1, &v2, 0.0).unwrap();
+        graph.set_weight(&v2, &v3, 0.0).unwrap();
+        assert_eq!(graph.dijkstra(&v1, &v3).count(), 3);
+
+        // v1 -> v2 -> v3 now costs 1.5 while v1 -> v3 costs 1
+        graph.remove_edge(&v1, &v2);
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.set_weight(&v2, &v3, 0.5).unwrap();
+
+        let subgraph = graph.subgraph(&graph.vertex_set()).unwrap();
+        assert!(subgraph.is_unweighted(&v1, &v2));
+        assert!(!subgraph.is_unweighted(&v2, &v3));
+        assert_eq!(graph.dijkstra(&v1, &v3).count(), 2);
+    }
+
+    #[test]
+    fn test_invalidate_downstream_with_cycle() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v2, &v1).unwrap();
+        graph.add_edge(&v2, &v3).unwrap();
+
+        let invalidated = graph.invalidate_downstream(&v1).unwrap();
+
+        assert_eq!(invalidated.len(), 2);
+        assert!(!invalidated.contains(&v1));
+        assert!(graph
+            .invalidate_downstream_until(&v1, |v| *v == 2)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            graph
+                .invalidate_downstream(&VertexId::random())
+                .unwrap_err(),
+            GraphErr::NoSuchVertex
+        );
+    }
+
+    #[test]
+    fn test_merge_into_itself() {
+        let mut graph: Graph<usize> = Graph::new();
+        graph.set_weight_policy(WeightPolicy::Unweighted).unwrap();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+
+        graph.add_edge(&v1, &v2).unwrap();
+
+        let copy = graph.clone();
+        let mapping = graph.merge(&copy);
+
+        assert_eq!(graph.vertex_count(), 4);
+        assert_eq!(graph.edge_count(), 2);
+        assert_ne!(mapping[&v1], v1);
+        assert!(graph.has_edge(&mapping[&v1], &mapping[&v2]));
+        assert!(graph.is_unweighted(&mapping[&v1], &mapping[&v2]));
+        assert_eq!(graph.roots().count(), 2);
+    }
+
+    #[test]
+    fn test_disjoint_union_keeps_labels() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+
+        graph.add_edge_with_weight(&v1, &v2, 0.3).unwrap();
+        graph.add_vertex_label(&v1, "first").unwrap();
+        graph.add_edge_label(&v1, &v2, "edge").unwrap();
+
+        let (union, mapping) = Graph::disjoint_union(&graph, &graph);
+        let (c1, c2) = (mapping[&v1], mapping[&v2]);
+
+        assert_eq!(union.vertex_count(), 4);
+        assert_eq!(union.vertex_label(&v1), Some("first"));
+        assert_eq!(union.vertex_label(&c1), Some("first"));
+        assert_eq!(union.edge_label(&c1, &c2), Some("edge"));
+        assert_eq!(union.weight(&c1, &c2), Some(0.3));
+    }
+
+    #[test]
+    fn test_frontier_changes() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+
+        assert!(graph.take_frontier_changes().is_empty());
+        graph.track_frontier_changes();
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v2, &v3).unwrap();
+
+        let changes = graph.take_frontier_changes();
+        let mut lost_tips = vec![v1, v2];
+        lost_tips.sort();
+        let mut lost_roots = vec![v2, v3];
+        lost_roots.sort();
+
+        assert_eq!(changes.lost_tips, lost_tips);
+        assert_eq!(changes.lost_roots, lost_roots);
+        assert!(changes.new_roots.is_empty());
+
+        // Removing v2 turns v3 into a root and v1 into a tip
+        graph.remove(&v2);
+
+        let changes = graph.take_frontier_changes();
+
+        assert_eq!(changes.new_roots, vec![v3]);
+        assert_eq!(changes.new_tips, vec![v1]);
+        assert!(changes.lost_roots.is_empty());
+
+        // Cycle check rollbacks cancel out
+        graph.add_edge(&v1, &v3).unwrap();
+        graph.take_frontier_changes();
+        assert!(graph.add_edge_check_cycle(&v3, &v1).is_err());
+        assert!(graph.take_frontier_changes().is_empty());
+
+        graph.untrack_frontier_changes();
+        graph.remove_edge(&v1, &v3);
+        assert!(graph.take_frontier_changes().is_empty());
+    }
+
+    #[test]
+    fn test_induced_subgraph_keeps_labels() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v2, &v3).unwrap();
+        graph.add_vertex_label(&v1, "one").unwrap();
+        graph.add_edge_label(&v1, &v2, "first").unwrap();
+        graph.add_edge_label(&v2, &v3, "second").unwrap();
+
+        let subgraph = graph.induced_subgraph(&[v1, v2]).unwrap();
+
+        assert_eq!(subgraph.vertex_label(&v1), Some("one"));
+        assert_eq!(subgraph.edge_label(&v1, &v2), Some("first"));
+        assert_eq!(subgraph.edge_count(), 1);
+        assert_eq!(
+            graph.induced_subgraph(&[VertexId::random()]).unwrap_err(),
+            GraphErr::NoSuchVertex
+        );
+    }
+
+    #[test]
+    fn test_percolation_with_nan_threshold() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+
+        graph.add_edge_with_weight(&v1, &v2, 0.5).unwrap();
+
+        assert_eq!(
+            graph.percolation(&[0.1, f32::NAN]),
+            Err(GraphErr::InvalidWeight)
+        );
+        assert_eq!(
+            graph.percolation(&[0.1, 0.9]),
+            Ok(vec![(0.1, vec![2]), (0.9, vec![1, 1])])
+        );
+    }
+
+    #[test]
+    fn test_remove_hub_keeps_lists_sorted() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let hub = graph.add_vertex(0);
+        let others: Vec<VertexId> = (1..=50).map(|i| graph.add_vertex(i)).collect();
+
+        for (i, v) in others.iter().enumerate() {
+            let weight = (i % 5) as f32 / 5.0;
+
+            graph.add_edge_with_weight(&hub, v, weight).unwrap();
+            graph.add_edge_with_weight(v, &hub, -weight).unwrap();
+        }
+
+        // Each vertex also points to the next ones, with weights
+        // equal to the weights of its edges to and from the hub
+        for (i, v) in others.iter().enumerate() {
+            for n in others.iter().skip(i + 1).take(3) {
+                graph.add_edge(v, n).unwrap();
+                graph.add_edge_with_weight(n, v, 0.2).unwrap();
+            }
+        }
+
+        let expected: HashMap<VertexId, (Vec<VertexId>, Vec<VertexId>)> = others
+            .iter()
+            .map(|v| {
+                let out = graph.out_neighbors(v).filter(|n| **n != hub).cloned();
+                let inbound = graph.in_neighbors(v).filter(|n| **n != hub).cloned();
+
+                (*v, (out.collect(), inbound.collect()))
+            })
+            .collect();
+
+        graph.remove(&hub);
+
+        assert_eq!(graph.validate(), Ok(()));
+
+        for v in others.iter() {
+            let out: Vec<VertexId> = graph.out_neighbors(v).cloned().collect();
+            let inbound: Vec<VertexId> = graph.in_neighbors(v).cloned().collect();
+
+            assert_eq!((out, inbound), expected[v]);
+        }
+    }
+
+    #[test]
+    fn test_subgraph_of_foreign_set() {
+        let mut graph1: Graph<usize> = Graph::new();
+        let mut graph2: Graph<usize> = Graph::new();
+
+        graph1.add_vertex(1);
+        graph2.add_vertex(2);
+
+        assert_eq!(
+            graph1.subgraph(&graph2.vertex_set()).unwrap_err(),
+            GraphErr::GraphMismatch
+        );
+        assert_eq!(
+            graph1
+                .subgraph(&graph1.vertex_set())
+                .unwrap()
+                .vertex_count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_filter_edges_updates_frontier() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+
+        graph.add_edge_with_weight(&v1, &v2, 0.2).unwrap();
+        graph.add_edge_with_weight(&v2, &v3, 0.7).unwrap();
+
+        let filtered = graph.filter_edges(|a, _, w| a != &v1 && w > 0.5);
+
+        assert_eq!(filtered.edge_count(), 1);
+        assert!(filtered.roots().any(|v| v == &v2));
+        assert!(filtered.tips().any(|v| v == &v1));
+        assert_eq!(graph.edge_count(), 2);
+        assert!(!graph.roots().any(|v| v == &v2));
+
+        let filtered = graph.filter(|id, _| id != &v2);
+
+        assert_eq!(filtered.edge_count(), 0);
+        assert_eq!(filtered.roots().count(), 2);
+    }
+
+    #[test]
+    fn test_eq_compares_unweighted_edges() {
+        let mut graph1: Graph<usize> = Graph::new();
+        let v1 = graph1.add_vertex(1);
+        let v2 = graph1.add_vertex(2);
+
+        let mut graph2 = graph1.clone();
+        graph1.set_weight_policy(WeightPolicy::Unweighted).unwrap();
+        graph2.set_weight_policy(WeightPolicy::Unweighted).unwrap();
+
+        graph1.add_edge(&v1, &v2).unwrap();
+        graph2.add_edge_with_weight(&v1, &v2, 0.0).unwrap();
+
+        assert_eq!(graph1.weight(&v1, &v2), graph2.weight(&v1, &v2));
+        assert_ne!(graph1, graph2);
+
+        graph2.remove_edge(&v1, &v2);
+        graph2.add_edge(&v1, &v2).unwrap();
+
+        assert_eq!(graph1, graph2);
+    }
+
+    #[test]
+    fn test_undo_redo_restores_snapshots() {
+        let mut graph: Graph<usize> = Graph::new();
+        graph.track_history();
+
+        let mut snapshots = vec![graph.clone()];
+
+        let v1 = graph.add_vertex(1);
+        snapshots.push(graph.clone());
+        let v2 = graph.add_vertex(2);
+        snapshots.push(graph.clone());
+        let v3 = graph.add_vertex(3);
+        snapshots.push(graph.clone());
+
+        graph.add_edge_with_weight(&v1, &v2, 0.1).unwrap();
+        snapshots.push(graph.clone());
+        graph.add_edge_with_weight(&v1, &v3, 0.9).unwrap();
+        snapshots.push(graph.clone());
+        graph.add_edge(&v3, &v2).unwrap();
+        snapshots.push(graph.clone());
+        graph.set_weight(&v3, &v2, -0.4).unwrap();
+        snapshots.push(graph.clone());
+        graph.keep_top_k_out_edges(1);
+        snapshots.push(graph.clone());
+        graph.retain(|v| *v != 3);
+        snapshots.push(graph.clone());
+        graph.add_edge(&v2, &v1).unwrap();
+        snapshots.push(graph.clone());
+
+        // Calls which do not change the graph are not recorded
+        graph.remove_edge(&v1, &v3);
+        graph.remove(&v3);
+
+        assert_eq!(graph.history().count(), snapshots.len() - 1);
+
+        for snapshot in snapshots.iter().rev().skip(1) {
+            assert!(graph.undo());
+            assert_eq!(&graph, snapshot);
+            assert_eq!(graph.roots, snapshot.roots);
+            assert_eq!(graph.tips, snapshot.tips);
+        }
+
+        assert!(!graph.undo());
+
+        for snapshot in snapshots.iter().skip(1) {
+            assert!(graph.redo());
+            assert_eq!(&graph, snapshot);
+            assert_eq!(graph.outbound_table, snapshot.outbound_table);
+        }
+
+        assert!(!graph.redo());
+    }
+
+    #[test]
+    fn test_undo_remove_restores_vertex_data() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_vertex_label(&v2, "target").unwrap();
+        graph.set_attr(&v2, "kind", 3).unwrap();
+        graph.set_priority(&v2, 0.5).unwrap();
+        graph.track_history();
+
+        graph.remove(&v2);
+        assert_eq!(graph.vertex_label(&v2), None);
+
+        assert!(graph.undo());
+        assert!(graph.has_edge(&v1, &v2));
+        assert_eq!(graph.vertex_label(&v2), Some("target"));
+        assert_eq!(graph.attr(&v2, "kind"), Some(&AttrValue::Int(3)));
+        assert_eq!(graph.priority(&v2), Some(0.5));
+
+        // Redoing and undoing again keeps the data of the vertex
+        assert!(graph.redo());
+        assert_eq!(graph.priority(&v2), None);
+        assert!(graph.undo());
+        assert_eq!(graph.vertex_label(&v2), Some("target"));
+        assert_eq!(graph.priority(&v2), Some(0.5));
+        assert_eq!(graph.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_history_edge_cases() {
+        let mut graph: Graph<usize> = Graph::new();
+        graph.set_weight_policy(WeightPolicy::Unweighted).unwrap();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+
+        // Mutations before tracking can not be undone
+        graph.track_history();
+        assert!(!graph.undo());
+
+        graph.add_edge_check_cycle(&v1, &v2).unwrap();
+        assert!(graph.add_edge_check_cycle(&v2, &v1).is_err());
+
+        // The rolled back edge is not recorded
+        assert_eq!(graph.history().count(), 1);
+
+        graph.set_weight(&v1, &v2, 0.3).unwrap();
+        assert!(graph.undo());
+        assert!(graph.is_unweighted(&v1, &v2));
+
+        assert!(graph.undo());
+        assert!(graph.redo());
+        assert!(graph.is_unweighted(&v1, &v2));
+
+        // New mutations discard the undone ones
+        graph.remove(&v2);
+        assert!(!graph.redo());
+
+        assert!(graph.undo());
+        assert!(graph.is_unweighted(&v1, &v2));
+
+        graph.untrack_history();
+        assert!(!graph.undo());
+        assert_eq!(graph.history().count(), 0);
+    }
+
+    #[test]
+    fn test_copy_on_write_clones() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+
+        graph.add_edge_with_weight(&v1, &v2, 0.5).unwrap();
+        graph.copy_on_write();
+
+        let snapshot = graph.clone();
+        let mut clone = graph.clone();
+
+        // Mutating the shared tables of one clone leaves the others intact
+        graph.add_edge(&v2, &v3).unwrap();
+        *graph.fetch_mut(&v1).unwrap() = 10;
+        clone.remove(&v2);
+        clone.add_vertex(4);
+
+        assert_eq!(snapshot.vertex_count(), 3);
+        assert_eq!(snapshot.edge_count(), 1);
+        assert_eq!(snapshot.fetch(&v1), Some(&1));
+        assert_eq!(snapshot.roots().count(), 2);
+        assert_eq!(snapshot.tips().count(), 2);
+
+        assert_eq!(graph.edge_count(), 2);
+        assert_eq!(graph.fetch(&v1), Some(&10));
+        assert_eq!(graph.tips().collect::<Vec<_>>(), vec![&v3]);
+
+        assert_eq!(clone.vertex_count(), 3);
+        assert_eq!(clone.edge_count(), 0);
+        assert_eq!(clone.in_neighbors_count(&v3), 0);
+        assert!(clone.is_copy_on_write());
+
+        // Attributes are shared too and compacting a clone keeps it shared
+        graph.set_attr(&v1, "kind", 1).unwrap();
+        let mut compacted = graph.clone();
+        compacted.set_attr(&v1, "kind", 2).unwrap();
+        compacted.remove(&v2);
+        let mapping = compacted.compact();
+
+        assert_eq!(graph.attr(&v1, "kind"), Some(&AttrValue::Int(1)));
+        assert_eq!(graph.vertex_count(), 3);
+        assert_eq!(
+            compacted.attr(&mapping[&v1], "kind"),
+            Some(&AttrValue::Int(2))
+        );
+        assert_eq!(compacted.vertex_count(), 2);
+        assert!(compacted.is_copy_on_write());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_iterators() {
+        let mut graph: Graph<usize> = Graph::new();
+        let ids: Vec<VertexId> = (0..1000).map(|i| graph.add_vertex(i)).collect();
+
+        for pair in ids.windows(2) {
+            graph.add_edge(&pair[0], &pair[1]).unwrap();
+        }
+
+        let mut vertices: Vec<_> = graph.par_vertices().collect();
+        let mut expected: Vec<_> = graph.vertices().collect();
+        vertices.sort();
+        expected.sort();
+        assert_eq!(vertices, expected);
+
+        let mut edges: Vec<_> = graph.par_edges().collect();
+        let mut expected: Vec<_> = graph.edges().collect();
+        edges.sort();
+        expected.sort();
+        assert_eq!(edges, expected);
+
+        assert_eq!(graph.par_values().sum::<usize>(), 499_500);
+        assert_eq!(graph.par_map(|v| v * 2), graph.map(|v| v * 2));
+    }
+
+    #[test]
+    fn test_custom_hasher() {
+        use core::hash::{BuildHasherDefault, Hasher};
+
+        /// Hasher using the last bytes written to it, which
+        /// are random for vertex ids.
+        #[derive(Default)]
+        struct IdHasher(u64);
+
+        impl Hasher for IdHasher {
+            fn finish(&self) -> u64 {
+                self.0
+            }
+
+            fn write(&mut self, bytes: &[u8]) {
+                for byte in bytes {
+                    self.0 = self.0.rotate_left(8) ^ u64::from(*byte);
+                }
+            }
+        }
+
+        type IdHashBuilder = BuildHasherDefault<IdHasher>;
+
+        let mut graph: Graph<usize, IdHashBuilder> = Graph::default();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v2, &v3).unwrap();
+
+        assert_eq!(graph.bfs().collect::<Vec<_>>(), vec![&v1, &v2, &v3]);
+        assert_eq!(graph.topo().count(), 3);
+        assert_eq!(graph.dijkstra(&v1, &v3).count(), 3);
+
+        // Derived graphs keep the hasher of the graph
+        let mapped: Graph<usize, IdHashBuilder> = graph.map(|v| v * 2);
+        let filtered = graph.filter(|_, v| *v > 1);
+
+        assert_eq!(mapped.fetch(&v3), Some(&6));
+        assert_eq!(filtered.roots().collect::<Vec<_>>(), vec![&v2]);
+
+        graph.remove(&v2);
+
+        assert_eq!(graph.edge_count(), 0);
+        assert_eq!(graph.roots().count(), 2);
+    }
+
+    #[test]
+    fn test_sequential_ids() {
+        let mut graph: Graph<usize> = Graph::new_sequential();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+
+        graph.add_edge(&v1, &v2).unwrap();
+
+        // Clones keep allocating from the same sequence
+        let mut clone = graph.clone();
+        let v3 = graph.add_vertex(3);
+
+        assert_eq!(clone.add_vertex(3), v3);
+
+        // Ids of removed vertices are not reused
+        graph.remove(&v3);
+
+        let v4 = graph.add_vertex(4);
+
+        assert_ne!(v4, v3);
+        assert_eq!(
+            graph.vertices().collect::<HashSet<_>>(),
+            [&v1, &v2, &v4].iter().cloned().collect()
+        );
+    }
+
+    #[test]
+    fn test_id_provider() {
+        use crate::id_provider::SeededIds;
+
+        /// Provider deriving ids from the values of the vertices
+        #[derive(Clone)]
+        struct ValueIds;
+
+        impl IdProvider<u64> for ValueIds {
+            fn next_id(&mut self, value: &u64) -> VertexId {
+                let mut bytes = [0; 16];
+                bytes[8..].copy_from_slice(&value.to_be_bytes());
+
+                VertexId::from_bytes(bytes)
+            }
+        }
+
+        let mut graph: Graph<u64> = Graph::new();
+        graph.set_id_provider(ValueIds).unwrap();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+
+        graph.add_edge(&v1, &v2).unwrap();
+
+        // Graphs derived from the graph keep its provider
+        let mut subgraph = graph.induced_subgraph(&[v1]).unwrap();
+
+        assert_eq!(subgraph.add_vertex(2), v2);
+        assert_eq!(graph.clone().add_vertex(3), graph.add_vertex(3));
+
+        let mut graph1: Graph<u64> = Graph::new();
+        let mut graph2: Graph<u64> = Graph::new();
+
+        graph1.set_id_provider(SeededIds::new(7)).unwrap();
+        graph2.set_id_provider(SeededIds::new(7)).unwrap();
+
+        let ids1: Vec<_> = (0..10).map(|i| graph1.add_vertex(i)).collect();
+        let ids2: Vec<_> = (0..10).map(|i| graph2.add_vertex(i)).collect();
+
+        assert_eq!(ids1, ids2);
+    }
+
+    #[test]
+    #[should_panic(expected = "id provider returned the id of a vertex in the graph")]
+    fn test_id_provider_used_id() {
+        #[derive(Clone)]
+        struct SameId;
+
+        impl IdProvider<u64> for SameId {
+            fn next_id(&mut self, _value: &u64) -> VertexId {
+                VertexId::from_bytes([1; 16])
+            }
+        }
+
+        let mut graph: Graph<u64> = Graph::new();
+        graph.set_id_provider(SameId).unwrap();
+
+        assert_eq!(
+            graph.try_add_vertex(1).unwrap(),
+            VertexId::from_bytes([1; 16])
+        );
+        assert_eq!(graph.try_add_vertex(2), Err(GraphErr::VertexExists));
+        assert_eq!(graph.vertex_count(), 1);
+
+        graph.add_vertex(3);
+    }
+
+    #[test]
+    fn test_add_vertex_with_id() {
+        let mut graph: Graph<usize> = Graph::new_sequential();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+
+        graph.add_edge(&v1, &v2).unwrap();
+
+        // Rebuild the graph from its vertices and edges
+        let mut copy: Graph<usize> = Graph::new_sequential();
+
+        for id in graph.vertices() {
+            copy.add_vertex_with_id(*id, *graph.fetch(id).unwrap())
+                .unwrap();
+        }
+
+        for (a, b) in graph.edges() {
+            copy.add_edge(b, a).unwrap();
+        }
+
+        assert_eq!(copy, graph);
+
+        // Sequential ids skip the inserted ones
+        let v3 = copy.add_vertex(3);
+
+        assert!(v3 != v1 && v3 != v2);
+        assert_eq!(copy.vertex_count(), 3);
+    }
+
+    #[test]
+    fn test_compact() {
+        let mut graph: Graph<usize> = Graph::new();
+        let ids: Vec<VertexId> = (0..10).map(|i| graph.add_vertex(i)).collect();
+
+        for pair in ids.windows(2) {
+            graph.add_edge(&pair[0], &pair[1]).unwrap();
+        }
+
+        graph.set_priority(&ids[9], 1.0).unwrap();
+        graph.track_history();
+
+        for id in ids.iter().take(5) {
+            graph.remove(id);
+        }
+
+        let before = graph.clone();
+        let mapping = graph.compact();
+
+        // The kept vertices are numbered densely
+        let mut new_ids: Vec<u128> = mapping.values().map(|id| u128::from(*id)).collect();
+        new_ids.sort();
+
+        assert_eq!(new_ids, (0..5).collect::<Vec<u128>>());
+        assert_eq!(graph.vertex_count(), 5);
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(graph.priority(&mapping[&ids[9]]), Some(1.0));
+        assert_eq!(graph.fetch(&mapping[&ids[5]]), Some(&5));
+        assert_eq!(graph.roots().collect::<Vec<_>>(), vec![&mapping[&ids[5]]]);
+        assert_eq!(graph.tips().collect::<Vec<_>>(), vec![&mapping[&ids[9]]]);
+        assert!(!graph.undo());
+
+        for (a, b) in before.edges() {
+            assert!(graph.has_edge(&mapping[b], &mapping[a]));
+        }
+    }
+
+    #[test]
+    fn test_outbound_order_after_mutations() {
+        use rand::{Rng, SeedableRng};
+        use rand_isaac::IsaacRng;
+
+        let mut rng = IsaacRng::seed_from_u64(3);
+        let mut graph: Graph<usize> = Graph::new();
+
+        let source = graph.add_vertex(0);
+        let targets: Vec<VertexId> = (1..40).map(|i| graph.add_vertex(i)).collect();
+
+        // Outbound vertices sorted stably by weight after each
+        // mutation, which are iterated over in reverse order
+        let mut expected: Vec<(VertexId, f32)> = vec![];
+
+        for _ in 0..500 {
+            let target = targets[rng.gen_range(0, targets.len())];
+            let weight = [-0.5, 0.0, 0.5][rng.gen_range(0, 3)];
+
+            match expected.iter().position(|(v, _)| *v == target) {
+                Some(index) if rng.gen::<bool>() => {
+                    graph.set_weight(&source, &target, weight).unwrap();
+                    expected[index].1 = weight;
+                }
+                Some(index) => {
+                    graph.remove_edge(&source, &target);
+                    expected.remove(index);
+                }
+                None => {
+                    graph
+                        .add_edge_with_weight(&source, &target, weight)
+                        .unwrap();
+                    expected.push((target, weight));
+                }
+            }
+
+            expected.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+
+            assert!(graph
+                .out_neighbors(&source)
+                .eq(expected.iter().rev().map(|(v, _)| v)));
+        }
+    }
+
+    #[test]
+    fn test_remove_missing_edge() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v2, &v3).unwrap();
+
+        // Removing an edge which is not in the graph changes nothing
+        assert_eq!(graph.remove_edge(&v1, &v3), None);
+        assert_eq!(graph.remove_edge(&v1, &VertexId::random()), None);
+        assert_eq!(graph.roots().collect::<Vec<_>>(), vec![&v1]);
+        assert_eq!(graph.tips().collect::<Vec<_>>(), vec![&v3]);
+
+        assert_eq!(graph.remove_edge(&v1, &v2), Some(0.0));
+        assert_eq!(graph.roots().count(), 2);
+    }
+
+    #[test]
+    fn test_retain_edges() {
+        let mut graph: Graph<usize> = Graph::new();
+        let ids: Vec<VertexId> = (0..6).map(|i| graph.add_vertex(i)).collect();
+
+        for (i, a) in ids.iter().enumerate() {
+            for b in ids.iter().skip(i + 1) {
+                graph.add_edge(a, b).unwrap();
+            }
+        }
+
+        graph.track_history();
+
+        // Only keep the edges between consecutive vertices
+        graph.retain_edges(|a, b, _| {
+            let a = ids.iter().position(|v| v == a).unwrap();
+            let b = ids.iter().position(|v| v == b).unwrap();
+
+            b == a + 1
+        });
+
+        assert_eq!(graph.edge_count(), 5);
+        assert_eq!(graph.vertex_count(), 6);
+        assert_eq!(graph.roots().collect::<Vec<_>>(), vec![&ids[0]]);
+        assert_eq!(graph.tips().collect::<Vec<_>>(), vec![&ids[5]]);
+
+        // The removals are undone together
+        assert!(graph.undo());
+        assert_eq!(graph.edge_count(), 15);
+    }
+
+    #[test]
+    fn test_clear_with_history() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.track_history();
+        graph.track_frontier_changes();
+
+        graph.clear_edges();
+
+        assert_eq!(graph.edge_count(), 0);
+        assert_eq!(graph.roots().count(), 2);
+        assert_eq!(graph.take_frontier_changes().new_roots, vec![v2]);
+
+        graph.clear();
+
+        assert_eq!(graph.vertex_count(), 0);
+        assert_eq!(graph.roots().count(), 0);
+
+        // Both operations are undone as single steps
+        assert!(graph.undo());
+        assert_eq!(graph.vertex_count(), 2);
+        assert_eq!(graph.edge_count(), 0);
+
+        assert!(graph.undo());
+        assert!(graph.has_edge(&v1, &v2));
+    }
 
-        let edge = Edge::new(id_ptr1, id_ptr2);
+    #[test]
+    fn test_drain_moves_values() {
+        let mut graph: Graph<Vec<usize>> = Graph::new();
 
-        // Push edge
-        self.edges.insert(edge, weight);
+        let v1 = graph.add_vertex(vec![1]);
+        let v2 = graph.add_vertex(vec![2, 2]);
 
-        // Update outbound table
-        match self.outbound_table.get(&id_ptr1) {
-            Some(outbounds) => {
-                let mut outbounds = outbounds.clone();
-                outbounds.push(id_ptr2.clone());
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.set_priority(&v1, 1.0).unwrap();
+        graph.track_history();
 
-                self.sort_outbounds(id_ptr1.clone(), &mut outbounds);
-                self.outbound_table.insert(id_ptr1.clone(), outbounds);
-            }
-            None => {
-                self.outbound_table.insert(id_ptr1.clone(), vec![id_ptr2]);
-            }
-        }
+        let mut drained: Vec<(VertexId, Vec<usize>)> = graph.drain().collect();
+        drained.sort_by_key(|(_, value)| value.len());
 
-        // Update inbound table
-        match self.inbound_table.get_mut(&id_ptr2) {
-            Some(inbounds) => {
-                inbounds.push(id_ptr1);
-            }
-            None => {
-                self.inbound_table.insert(id_ptr2, vec![id_ptr1]);
-            }
-        }
+        assert_eq!(drained, vec![(v1, vec![1]), (v2, vec![2, 2])]);
+        assert_eq!(graph.vertex_count(), 0);
+        assert_eq!(graph.edge_count(), 0);
+        assert_eq!(graph.roots().count(), 0);
+        assert_eq!(graph.priority_count(), 0);
+        assert!(!graph.undo());
 
-        // Remove outbound vertex from roots
-        let was_root = self.roots.remove(&b);
+        // The graph can be reused afterwards
+        let v3 = graph.add_vertex(vec![3]);
 
-        // Remove inbound vertex from tips
-        let was_tip = self.tips.remove(&a);
+        assert_eq!(graph.roots().collect::<Vec<_>>(), vec![&v3]);
+    }
 
-        let mut is_cyclic = false;
+    #[test]
+    fn test_vertex_entry() {
+        let mut graph: Graph<Vec<usize>> = Graph::new_checked();
+        let id = graph.add_vertex(vec![]);
 
-        if check_cycle {
-            let mut dfs = Dfs::new(&self);
-            is_cyclic = dfs.is_cyclic();
-        }
+        graph.entry(id).or_default().push(1);
+        graph
+            .entry(id)
+            .and_modify(|v| v.push(2))
+            .or_default()
+            .push(3);
 
-        // Roll-back changes if cycle check succeeds
-        if is_cyclic {
-            // Remove from edge table
-            self.remove_edge(a, b);
+        assert_eq!(graph.fetch(&id), Some(&vec![1, 2, 3]));
+        assert!(graph.entry(id).is_occupied());
 
-            if was_root {
-                self.roots.insert(b.clone());
-            }
+        graph.remove(&id);
+        graph.track_history();
 
-            if was_tip {
-                self.tips.insert(a.clone());
-            }
+        // Vacant entries are filled with the same id
+        let entry = graph.entry(id).and_modify(|_| unreachable!());
 
-            return Err(GraphErr::CycleError);
-        }
+        assert!(!entry.is_occupied());
+        assert_eq!(entry.or_insert(vec![4]), &vec![4]);
+        assert_eq!(graph.roots().collect::<Vec<_>>(), vec![&id]);
 
-        Ok(())
+        assert!(graph.undo());
+        assert_eq!(graph.vertex_count(), 0);
     }
 
-    fn sort_outbounds(&self, inbound: VertexId, outbounds: &mut Vec<VertexId>) {
-        let outbound_weights: HashMap<VertexId, f32> = outbounds
-            .iter()
-            .map(|id| (*id, *self.edges.get(&Edge::new(inbound, *id)).unwrap()))
-            .collect();
-
-        // Sort outbounds
-        outbounds.sort_by(|a, b| {
-            let a_weight = outbound_weights.get(a).cloned();
-            let b_weight = outbound_weights.get(b).cloned();
+    #[test]
+    #[should_panic(expected = "no vertex with the given id in the graph")]
+    fn test_index_missing_vertex() {
+        let mut graph: Graph<usize> = Graph::new();
+        let id = graph.add_vertex(1);
 
-            match (a_weight, b_weight) {
-                // Sort normally if both weights are set
-                (Some(a_weight), Some(b_weight)) => {
-                    a_weight.partial_cmp(&b_weight).unwrap_or_else(|| a.cmp(b))
-                }
-                (Some(weight), None) => {
-                    if weight != 0.00 {
-                        weight.partial_cmp(&0.00).unwrap_or_else(|| a.cmp(b))
-                    } else {
-                        // Fallback to lexicographic sort
-                        a.cmp(b)
-                    }
-                }
-                (None, Some(weight)) => {
-                    if weight != 0.00 {
-                        weight.partial_cmp(&0.00).unwrap_or_else(|| a.cmp(b))
-                    } else {
-                        // Fallback to lexicographic sort
-                        a.cmp(b)
-                    }
-                }
-                // Sort lexicographically by ids if no weight is set
-                (None, None) => a.cmp(b),
-            }
-        });
+        graph.remove(&id);
+        let _ = graph[&id];
     }
 
-    /// Attempts to fetch a reference to a stored vertex id
-    /// which is equal to the given `VertexId`.
-    pub(crate) fn fetch_id_ref<'b>(&'b self, id: &VertexId) -> Option<&'b VertexId> {
-        match self.vertices.get(id) {
-            Some((_, id_ptr)) => Some(id_ptr),
-            None => None,
-        }
+    #[test]
+    fn test_into_iter_shared_graph() {
+        let mut graph: Graph<String> = Graph::new();
+
+        let v1 = graph.add_vertex("a".to_string());
+        let v2 = graph.add_vertex("b".to_string());
+
+        graph.copy_on_write();
+
+        let clone = graph.clone();
+        let mut borrowed: Vec<(&VertexId, &String)> = (&clone).into_iter().collect();
+        let mut owned: Vec<(VertexId, String)> = graph.into_iter().collect();
+
+        let mut expected = vec![(v1, "a".to_string()), (v2, "b".to_string())];
+
+        borrowed.sort();
+        owned.sort();
+        expected.sort();
+
+        assert_eq!(owned, expected);
+        assert_eq!(
+            borrowed,
+            expected.iter().map(|(id, v)| (id, v)).collect::<Vec<_>>()
+        );
+        assert_eq!(clone.vertex_count(), 2);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_from_edges() {
+        let (graph, ids) = Graph::from_edges(vec![(1, 2), (2, 3), (3, 1), (1, 2)]).unwrap();
+
+        assert_eq!(graph.vertex_count(), 3);
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(graph.fetch(&ids[&2]), Some(&2));
+        assert!(graph.has_edge(&ids[&3], &ids[&1]));
+
+        let result = Graph::from_edges(vec![("a", "b", 0.5), ("b", "c", 2.0)]);
+
+        assert_eq!(result.unwrap_err(), GraphErr::InvalidWeight);
+    }
 
     #[test]
-    fn is_thread_safe() {
+    fn test_filter_map_keeps_edge_data() {
         let mut graph: Graph<usize> = Graph::new();
-        graph.add_vertex(0);
 
-        std::panic::set_hook(Box::new(move |_| {
-            let mut graph = graph.clone();
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
 
-            graph.add_vertex(1);
-            graph.add_vertex(2);
-        }));
+        graph.add_edge_with_weight(&v1, &v3, 0.5).unwrap();
+        graph.add_edge(&v2, &v3).unwrap();
+        graph.set_weight_policy(WeightPolicy::Unweighted).unwrap();
+        graph.add_edge(&v3, &v1).unwrap();
+
+        let mapped = graph.filter_map(|id, v| if *id == v2 { None } else { Some(v * 10) });
+
+        assert_eq!(mapped.vertex_count(), 2);
+        assert_eq!(mapped.edge_count(), 2);
+        assert_eq!(mapped.weight(&v1, &v3), Some(0.5));
+        assert!(mapped.is_unweighted(&v3, &v1));
+        assert_eq!(mapped.in_neighbors_count(&v3), 1);
+        assert_eq!(mapped.fetch(&v3), Some(&30));
+        assert!(mapped.roots().next().is_none());
     }
 
     #[test]
-    fn dfs() {
+    fn test_retain_with_degrees() {
         let mut graph: Graph<usize> = Graph::new();
 
-        let v1 = graph.add_vertex(0);
-        let v2 = graph.add_vertex(1);
-        let v3 = graph.add_vertex(2);
-        let v4 = graph.add_vertex(3);
-        let v5 = graph.add_vertex(4);
-        let v6 = graph.add_vertex(5);
-        let v7 = graph.add_vertex(6);
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
 
-        graph.add_edge_with_weight(&v1, &v2, -0.23).unwrap();
-        graph.add_edge(&v3, &v1).unwrap();
-        graph.add_edge_with_weight(&v1, &v4, -0.56).unwrap();
-        graph.add_edge_with_weight(&v1, &v5, 0.44).unwrap();
-        graph.add_edge(&v5, &v6).unwrap();
-        graph.add_edge(&v5, &v7).unwrap();
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v2, &v3).unwrap();
+        graph.add_edge(&v1, &v1).unwrap();
 
-        graph.set_weight(&v5, &v6, 0.23).unwrap();
-        graph.set_weight(&v5, &v7, 0.33).unwrap();
+        // Degrees are taken before any removal, so
+        // removing v3 does not make v2 a leaf
+        graph.retain_with(|id, _, degree| {
+            if *id == v1 {
+                assert_eq!(
+                    degree,
+                    Degree {
+                        inbound: 1,
+                        outbound: 2
+                    }
+                );
+            }
 
-        let mut dfs = graph.dfs();
+            degree.outbound > 0
+        });
 
-        assert_eq!(dfs.next(), Some(&v3));
-        assert_eq!(dfs.next(), Some(&v1));
-        assert_eq!(dfs.next(), Some(&v4));
-        assert_eq!(dfs.next(), Some(&v2));
-        assert_eq!(dfs.next(), Some(&v5));
-        assert_eq!(dfs.next(), Some(&v6));
-        assert_eq!(dfs.next(), Some(&v7));
+        assert_eq!(graph.vertex_count(), 2);
+        assert_eq!(graph.fetch(&v3), None);
+        assert!(graph.tips().any(|v| v == &v2));
     }
 
     #[test]
-    fn dfs_mul_roots() {
+    fn test_fold_in_order() {
         let mut graph: Graph<usize> = Graph::new();
 
-        let v1 = graph.add_vertex(0);
-        let v2 = graph.add_vertex(1);
-        let v3 = graph.add_vertex(2);
-        let v4 = graph.add_vertex(3);
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+        let v4 = graph.add_vertex(4);
 
         graph.add_edge(&v1, &v2).unwrap();
-        graph.add_edge(&v3, &v1).unwrap();
-        graph.add_edge(&v1, &v4).unwrap();
+        graph.add_edge(&v2, &v4).unwrap();
+        graph.add_edge(&v1, &v3).unwrap();
 
-        let v5 = graph.add_vertex(4);
-        let v6 = graph.add_vertex(5);
+        let visit = |v: &usize, mut acc: Vec<usize>| {
+            acc.push(*v);
+            acc
+        };
 
-        graph.add_edge(&v5, &v6).unwrap();
+        let bfs = graph.fold_in(Traversal::Bfs, vec![], visit);
+        let dfs = graph.fold_in(Traversal::Dfs, vec![], visit);
 
-        // Iterate over vertices
-        let mut dfs = graph.dfs();
+        assert_eq!(bfs[0], 1);
+        assert_eq!(bfs[3], 4);
+        assert_eq!(dfs, graph.fold(vec![], visit));
+        assert_eq!(
+            graph
+                .fold_edges(vec![], |a, b, _, mut acc| {
+                    acc.push((*a, *b));
+                    acc
+                })
+                .len(),
+            3
+        );
+        assert!(graph.fold_edges(true, |a, b, _, acc| acc && graph.has_edge(a, b)));
+    }
 
-        for _ in 0..2 {
-            let v = dfs.next();
+    #[test]
+    fn test_weights_mut_keeps_invariants() {
+        let mut graph: Graph<usize> = Graph::new();
 
-            if v == Some(&v3) {
-                assert_eq!(dfs.next(), Some(&v1));
-                assert!(set![&v2, &v4] == (&mut dfs).take(2).collect());
-            } else if v == Some(&v5) {
-                assert_eq!(dfs.next(), Some(&v6));
-            } else {
-                panic!("Not a root node")
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+
+        graph.add_edge_with_weight(&v1, &v2, 0.2).unwrap();
+        graph.add_edge_with_weight(&v1, &v3, 0.4).unwrap();
+        graph.set_weight_policy(WeightPolicy::Unweighted).unwrap();
+        graph.add_edge(&v2, &v3).unwrap();
+        graph.track_history();
+
+        for (a, b, weight) in &mut graph.weights_mut() {
+            if (*a, *b) == (v1, v2) {
+                *weight = 3.0;
+            } else if (*a, *b) == (v1, v3) {
+                *weight = f32::NAN;
             }
         }
 
-        assert_eq!(dfs.count(), 0, "There were remaining nodes");
+        // Out of bounds weights are clamped and the
+        // outbound order follows the new weights
+        assert_eq!(graph.weight(&v1, &v2), Some(1.0));
+        assert_eq!(graph.weight(&v1, &v3), Some(0.4));
+        assert_eq!(graph.out_neighbors(&v1).next(), Some(&v2));
+        assert!(graph.is_unweighted(&v2, &v3));
+        assert_eq!(
+            graph.fold_edges(0.0, |_, _, w, acc| acc + f64::from(w)),
+            graph.weight_sum
+        );
+
+        assert!(graph.undo());
+        assert_eq!(graph.weight(&v1, &v2), Some(0.2));
+        assert!(!graph.undo());
     }
 
     #[test]
-    fn test_remove_edge() {
-        let mut graph = Graph::new();
+    fn test_neighborhood_distances() {
+        let mut graph: Graph<usize> = Graph::new();
 
-        let v1 = graph.add_vertex(0);
-        let v2 = graph.add_vertex(1);
-        let v3 = graph.add_vertex(2);
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+        let v4 = graph.add_vertex(4);
 
         graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v2, &v1).unwrap();
         graph.add_edge(&v2, &v3).unwrap();
+        graph.add_edge(&v4, &v3).unwrap();
 
-        let old_inbound = graph.inbound_table.clone();
-        let old_outbound = graph.outbound_table.clone();
+        let directed = graph.neighborhood(&v1, 5).unwrap();
+        let undirected = graph.undirected_neighborhood(&v1, 5).unwrap();
 
-        graph.add_edge(&v3, &v1).unwrap();
-        graph.remove_edge(&v3, &v1);
+        assert_eq!(directed.len(), 3);
+        assert_eq!(directed[&v1], 0);
+        assert_eq!(directed[&v3], 2);
+        assert_eq!(undirected[&v4], 3);
+        assert_eq!(graph.neighborhood(&v1, 0).unwrap().len(), 1);
+        assert_eq!(
+            graph.neighborhood(&VertexId::random(), 1).unwrap_err(),
+            GraphErr::NoSuchVertex
+        );
+    }
 
-        assert_eq!(old_inbound, graph.inbound_table.clone());
-        assert_eq!(old_outbound, graph.outbound_table);
+    #[test]
+    fn test_tree_helpers_on_dags() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+        let v4 = graph.add_vertex(4);
+        let v5 = graph.add_vertex(5);
+        let v6 = graph.add_vertex(6);
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v1, &v3).unwrap();
+        graph.add_edge(&v2, &v4).unwrap();
+        graph.add_edge(&v3, &v4).unwrap();
+        graph.add_edge(&v5, &v6).unwrap();
+        graph.add_edge(&v6, &v5).unwrap();
+
+        // The shared vertex is only visited once
+        assert_eq!(graph.subtree(&v1).count(), 4);
+        assert_eq!(graph.subtree(&VertexId::random()).count(), 0);
+        assert_eq!(graph.parent(&v4), None);
+        assert_eq!(graph.depth(&v4), None);
+        assert_eq!(graph.parent(&v5), Some(&v6));
+        assert_eq!(graph.depth(&v5), None);
+        assert_eq!(graph.depth(&VertexId::random()), None);
     }
 
     #[test]
-    fn test_non_clonable_type() {
-        // this simply tests that a Graph that has a non-clonable type can be created
-        // this is done easiest by adding dyn Trait object, which can never be cloned
-        //
-        // It also tests that the dyn object can still be used as expected
-        let mut graph = Graph::<Box<dyn std::fmt::Display>>::new();
+    fn test_attrs_follow_vertices() {
+        let mut graph: Graph<usize> = Graph::new();
 
-        graph.add_vertex(Box::new(String::from("Hello World")));
-        let mut result = String::new();
-        for vertex_identifier in graph.vertices() {
-            if let Some(v) = graph.fetch(vertex_identifier) {
-                result = format!("{}", v);
-            }
-        }
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
 
-        assert_eq!(result, "Hello World");
+        graph.set_attr(&v1, "color", "red").unwrap();
+        graph.set_attr(&v2, "color", "blue").unwrap();
+
+        let subgraph = graph.induced_subgraph(&[v2]).unwrap();
+        let mapping = graph.compact();
+
+        assert_eq!(subgraph.attr(&v2, "color"), Some(&"blue".into()));
+        assert_eq!(subgraph.attr(&v1, "color"), None);
+        assert_eq!(graph.attr(&mapping[&v1], "color"), Some(&"red".into()));
+
+        let v1 = mapping[&v1];
+        graph.remove(&v1);
+
+        assert_eq!(graph.attrs(&v1).count(), 0);
+        assert_eq!(
+            graph.set_attr(&v1, "color", "red"),
+            Err(GraphErr::NoSuchVertex)
+        );
+        assert_eq!(
+            graph.remove_attr(&mapping[&v2], "color"),
+            Some("blue".into())
+        );
+        assert!(graph.vertex_attrs.is_empty());
     }
+
     #[test]
-    fn test_clonable() {
-        let mut graph = Graph::new();
-        graph.add_vertex(String::from("Test"));
+    fn test_validate_after_mutations() {
+        let mut graph: Graph<usize> = Graph::new();
+        let vertices: Vec<VertexId> = (0..30).map(|i| graph.add_vertex(i)).collect();
 
-        let cloned = graph.clone();
-        assert_eq!(graph.vertex_count(), cloned.vertex_count());
-        let mut cloned_iter = cloned.vertices();
-        for vertex_identifier in graph.vertices() {
-            if let Some(cloned_identifier) = cloned_iter.next() {
-                assert_eq!(
-                    graph.fetch(vertex_identifier),
-                    cloned.fetch(cloned_identifier)
-                );
-            } else {
-                panic!("graph and clone of graph are not equal!");
-            }
+        for i in 0..90 {
+            let a = vertices[i % 30];
+            let b = vertices[(i * 11 + 3) % 30];
+
+            graph
+                .add_edge_with_weight(&a, &b, (i % 5) as f32 / 5.0)
+                .ok();
+        }
+
+        assert_eq!(graph.validate(), Ok(()));
+
+        for (i, a) in vertices.iter().enumerate().step_by(3) {
+            let b = vertices[(i * 7 + 1) % vertices.len()];
+
+            graph.remove_edge(a, &b);
+            graph.add_edge_with_weight(&b, a, 0.5).ok();
+            graph.set_weight(&b, a, -0.25).ok();
+            assert_eq!(graph.validate(), Ok(()));
+        }
+
+        for a in vertices.iter().step_by(4) {
+            graph.remove(a);
+        }
+
+        graph.compact();
+        assert_eq!(graph.validate(), Ok(()));
+
+        // Corrupt the tables by hand
+        let (b, a) = graph.edges().next().map(|(b, a)| (*b, *a)).unwrap();
+        graph.tips.insert(a);
+        graph
+            .outbound_table
+            .get_mut(&a)
+            .unwrap()
+            .retain(|v| *v != b);
+
+        let violations = graph.validate().unwrap_err();
+
+        assert!(violations.contains(&InvariantViolation::MissingOutbound(a, b)));
+
+        if graph.out_neighbors_count(&a) > 0 {
+            assert!(violations.contains(&InvariantViolation::WrongTip(a)));
         }
     }
 
     #[test]
-    fn test_add_edge_cycle_check() {
-        let mut graph: Graph<usize> = Graph::new();
+    fn test_display_adjacency() {
+        let mut graph: Graph<&str> = Graph::new();
 
-        // Id of vertex that is not place in the graph
-        let id = VertexId::random();
+        let a = graph.add_vertex("A");
+        let b = graph.add_vertex("B");
+        let c = graph.add_vertex("C");
 
-        let v1 = graph.add_vertex(1);
-        let v2 = graph.add_vertex(2);
+        graph.add_edge_with_weight(&a, &b, 0.3).unwrap();
+        graph.add_edge(&a, &c).unwrap();
+        graph.add_edge_with_weight(&c, &b, -0.5).unwrap();
 
-        // Adding an edge is idempotent
-        graph.add_edge_check_cycle(&v1, &v2).unwrap();
-        graph.add_edge_check_cycle(&v1, &v2).unwrap();
-        graph.add_edge_check_cycle(&v1, &v2).unwrap();
+        assert_eq!(format!("{}", graph), "A -> B (0.3), C\nB\nC -> B (-0.5)");
+        assert_eq!(format!("{}", Graph::<&str>::new()), "");
 
-        let mut graph2 = graph.clone();
+        // Neighbors with equal weights are sorted by their text
+        let mut graph: Graph<&str> = Graph::new();
 
-        // Fails on adding an edge which creates
-        // a cycle in the graph.
-        assert_eq!(
-            graph2.add_edge_check_cycle(&v2, &v1),
-            Err(GraphErr::CycleError)
-        );
+        let a = graph.add_vertex("A");
+        let c = graph.add_vertex("C");
+        let b = graph.add_vertex("B");
 
-        // Check that the graph state has rolled back
-        assert_eq!(graph.edges, graph2.edges);
-        assert_eq!(graph.roots, graph2.roots);
-        assert_eq!(graph.tips, graph2.tips);
-        assert_eq!(graph.inbound_table, graph2.inbound_table);
-        assert_eq!(graph.outbound_table, graph2.outbound_table);
+        graph.add_edge(&a, &c).unwrap();
+        graph.add_edge(&a, &b).unwrap();
+
+        assert_eq!(format!("{}", graph), "A -> B, C\nB\nC");
     }
 }
@@ -0,0 +1,121 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::Graph;
+use crate::vertex_id::VertexId;
+use crate::vertex_set::VertexSet;
+
+use hashbrown::{HashMap, HashSet};
+
+#[cfg(feature = "no_std")]
+use core::hash::Hash;
+#[cfg(not(feature = "no_std"))]
+use std::hash::Hash;
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+/// Groups the vertices of the graph by the keys of their values.
+pub(crate) fn group_by<T, K: Hash + Eq, S: BuildHasher>(
+    graph: &Graph<T, S>,
+    fun: impl Fn(&T) -> K,
+) -> HashMap<K, VertexSet<'_, T, S>> {
+    let mut groups: HashMap<K, HashSet<VertexId>> = HashMap::new();
+
+    for v in graph.vertices() {
+        groups
+            .entry(fun(graph.fetch(v).unwrap()))
+            .or_default()
+            .insert(*v);
+    }
+
+    groups
+        .into_iter()
+        .map(|(key, ids)| (key, VertexSet::from_set(graph, ids)))
+        .collect()
+}
+
+/// Collapses each group of vertices into a single vertex holding the key
+/// of the group. Groups are connected if there are edges between their
+/// vertices, with the average weight of those edges.
+pub(crate) fn quotient<T, K: Hash + Eq + Clone, S: BuildHasher + Clone>(
+    graph: &Graph<T, S>,
+    fun: impl Fn(&T) -> K,
+) -> Graph<K, S> {
+    let mut quotient: Graph<K, S> = Graph::with_hasher(graph.hasher().clone());
+    let mut groups: HashMap<K, VertexId> = HashMap::new();
+    let mut members: HashMap<VertexId, VertexId> = HashMap::with_capacity(graph.vertex_count());
+
+    for v in graph.vertices() {
+        let key = fun(graph.fetch(v).unwrap());
+        let group = match groups.get(&key) {
+            Some(group) => *group,
+            None => {
+                let group = quotient.add_vertex(key.clone());
+                groups.insert(key, group);
+                group
+            }
+        };
+
+        members.insert(*v, group);
+    }
+
+    let mut weights: HashMap<(VertexId, VertexId), (f32, usize)> = HashMap::new();
+
+    for (b, a) in graph.edges() {
+        let (group_a, group_b) = (members[a], members[b]);
+
+        // Edges inside of a group are collapsed along with it
+        if group_a != group_b {
+            let entry = weights.entry((group_a, group_b)).or_insert((0.0, 0));
+
+            entry.0 += graph.weight(a, b).unwrap();
+            entry.1 += 1;
+        }
+    }
+
+    for ((a, b), (total, count)) in weights {
+        let weight = (total / count as f32).clamp(-1.0, 1.0);
+        quotient.add_edge_with_weight(&a, &b, weight).unwrap();
+    }
+
+    quotient
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quotient_weights() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(3);
+        let v3 = graph.add_vertex(2);
+        let v4 = graph.add_vertex(4);
+
+        // Grouping odd and even values
+        graph.add_edge_with_weight(&v1, &v2, 0.9).unwrap();
+        graph.add_edge_with_weight(&v1, &v3, 0.2).unwrap();
+        graph.add_edge_with_weight(&v2, &v4, 0.6).unwrap();
+        graph.add_edge_with_weight(&v4, &v1, -0.5).unwrap();
+
+        let quotient = quotient(&graph, |v| v % 2 == 0);
+        let odd = quotient
+            .vertices()
+            .find(|v| !*quotient.fetch(v).unwrap())
+            .unwrap();
+        let even = quotient
+            .vertices()
+            .find(|v| *quotient.fetch(v).unwrap())
+            .unwrap();
+
+        assert_eq!(quotient.vertex_count(), 2);
+        assert_eq!(quotient.edge_count(), 2);
+        assert_eq!(quotient.weight(odd, even), Some(0.4));
+        assert_eq!(quotient.weight(even, odd), Some(-0.5));
+        assert!(!quotient.has_edge(odd, odd));
+    }
+}
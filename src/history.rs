@@ -0,0 +1,143 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::attributes::Attributes;
+use crate::edge::Edge;
+use crate::vertex_id::VertexId;
+
+use hashbrown::{HashMap, HashSet};
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::string::String;
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Change to the structure of a graph, recorded
+/// once `Graph::track_history()` is called.
+pub enum Mutation {
+    /// A vertex with the given id was added
+    AddVertex(VertexId),
+
+    /// The vertex with the given id was removed
+    RemoveVertex(VertexId),
+
+    /// An edge was added between the given vertices with the given weight
+    AddEdge(VertexId, VertexId, f32),
+
+    /// The edge between the given vertices, which had the given weight, was removed
+    RemoveEdge(VertexId, VertexId, f32),
+
+    /// The weight of the edge between the given vertices
+    /// was changed from the first weight to the second one
+    SetWeight(VertexId, VertexId, f32, f32),
+}
+
+#[derive(Clone, Debug, Default)]
+/// Label, attributes and priority of a removed vertex,
+/// which are restored along with its value.
+pub(crate) struct VertexData {
+    pub(crate) label: Option<String>,
+    pub(crate) attrs: Option<Attributes>,
+    pub(crate) priority: Option<f32>,
+}
+
+#[derive(Clone, Debug)]
+/// Mutations which are undone and redone together.
+pub(crate) struct Step<T> {
+    pub(crate) mutations: Vec<Mutation>,
+
+    /// Values of the vertices of the step which are currently
+    /// not in the graph, along with their labels, attributes
+    /// and priorities
+    pub(crate) values: HashMap<VertexId, (T, VertexData)>,
+
+    /// Edges of the step which were added without a weight
+    pub(crate) unweighted: HashSet<Edge>,
+}
+
+impl<T> Default for Step<T> {
+    fn default() -> Step<T> {
+        Step {
+            mutations: Vec::new(),
+            values: HashMap::new(),
+            unweighted: HashSet::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Journal of the steps applied to a graph and of the undone
+/// steps which can still be redone.
+pub(crate) struct History<T> {
+    pub(crate) done: Vec<Step<T>>,
+    pub(crate) undone: Vec<Step<T>>,
+
+    /// Step collecting the mutations of the current operation
+    open: Option<Step<T>>,
+
+    /// Number of nested operations which are in progress
+    depth: usize,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> History<T> {
+        History {
+            done: Vec::new(),
+            undone: Vec::new(),
+            open: None,
+            depth: 0,
+        }
+    }
+}
+
+impl<T> History<T> {
+    /// Starts an operation whose mutations form a single step.
+    pub(crate) fn begin(&mut self) {
+        self.depth += 1;
+    }
+
+    /// Ends the current operation, closing its step
+    /// if it is not nested in another one.
+    pub(crate) fn end(&mut self) {
+        self.depth -= 1;
+
+        if self.depth == 0 {
+            if let Some(step) = self.open.take() {
+                self.done.push(step);
+            }
+        }
+    }
+
+    /// Records the mutation along with the value and the data of the
+    /// removed vertex or whether the removed edge had no weight. Any
+    /// undone steps can not be redone afterwards.
+    pub(crate) fn record(
+        &mut self,
+        mutation: Mutation,
+        value: Option<(T, VertexData)>,
+        unweighted: bool,
+    ) {
+        let step = self.open.get_or_insert_with(Step::default);
+
+        match mutation {
+            Mutation::RemoveVertex(id) => {
+                if let Some(value) = value {
+                    step.values.insert(id, value);
+                }
+            }
+            Mutation::RemoveEdge(a, b, _) | Mutation::SetWeight(a, b, _, _) if unweighted => {
+                step.unweighted.insert(Edge::new(a, b));
+            }
+            _ => {}
+        }
+
+        step.mutations.push(mutation);
+        self.undone.clear();
+
+        if self.depth == 0 {
+            self.done.extend(self.open.take());
+        }
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::vertex_id::VertexId;
+
+use rand::{Rng, SeedableRng};
+use rand_isaac::IsaacRng;
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::boxed::Box;
+
+/// Source of the ids of the vertices added to a graph, which
+/// can be given to it with `Graph::set_id_provider()`.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::{Graph, IdProvider, VertexId};
+///
+/// #[derive(Clone)]
+/// struct Namespaced {
+///     namespace: u64,
+///     next: u64,
+/// }
+///
+/// impl IdProvider<usize> for Namespaced {
+///     fn next_id(&mut self, _value: &usize) -> VertexId {
+///         let mut bytes = [0; 16];
+///
+///         bytes[..8].copy_from_slice(&self.namespace.to_be_bytes());
+///         bytes[8..].copy_from_slice(&self.next.to_be_bytes());
+///         self.next += 1;
+///
+///         VertexId::from_bytes(bytes)
+///     }
+/// }
+///
+/// let mut graph: Graph<usize> = Graph::new();
+/// graph.set_id_provider(Namespaced { namespace: 7, next: 0 }).unwrap();
+///
+/// let id = graph.add_vertex(1);
+///
+/// assert_eq!(&id.bytes()[..8], &7u64.to_be_bytes());
+/// ```
+pub trait IdProvider<T>: CloneIdProvider<T> + Send + Sync {
+    /// Returns the id of the vertex with the given value which is
+    /// being added to the graph. The id must not be used by another
+    /// vertex of the graph.
+    fn next_id(&mut self, value: &T) -> VertexId;
+}
+
+/// Clones boxed id providers, which is implemented
+/// for every id provider implementing `Clone`.
+pub trait CloneIdProvider<T> {
+    fn clone_box(&self) -> Box<dyn IdProvider<T>>;
+}
+
+impl<T, P: IdProvider<T> + Clone + 'static> CloneIdProvider<T> for P {
+    fn clone_box(&self) -> Box<dyn IdProvider<T>> {
+        Box::new(self.clone())
+    }
+}
+
+impl<T> Clone for Box<dyn IdProvider<T>> {
+    fn clone(&self) -> Box<dyn IdProvider<T>> {
+        self.clone_box()
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Id provider generating random ids from the given seed, so
+/// graphs built the same way get the same ids on every run.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::{Graph, SeededIds};
+///
+/// let mut graph1: Graph<usize> = Graph::new();
+/// let mut graph2: Graph<usize> = Graph::new();
+///
+/// graph1.set_id_provider(SeededIds::new(42)).unwrap();
+/// graph2.set_id_provider(SeededIds::new(42)).unwrap();
+///
+/// assert_eq!(graph1.add_vertex(1), graph2.add_vertex(1));
+/// ```
+pub struct SeededIds(IsaacRng);
+
+impl SeededIds {
+    /// Creates a new provider from the given seed.
+    pub fn new(seed: u64) -> SeededIds {
+        SeededIds(IsaacRng::seed_from_u64(seed))
+    }
+}
+
+impl<T> IdProvider<T> for SeededIds {
+    fn next_id(&mut self, _value: &T) -> VertexId {
+        VertexId::from_bytes(self.0.gen::<[u8; 16]>())
+    }
+}
@@ -0,0 +1,501 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::{DefaultHashBuilder, Graph};
+use crate::vertex_id::VertexId;
+
+use hashbrown::{HashMap, HashSet};
+
+#[cfg(not(feature = "no_std"))]
+use std::collections::VecDeque;
+#[cfg(not(feature = "no_std"))]
+use std::fmt::{self, Debug, Formatter};
+
+#[cfg(feature = "no_std")]
+use core::fmt::{self, Debug, Formatter};
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::boxed::Box;
+#[cfg(feature = "no_std")]
+use alloc::collections::vec_deque::VecDeque;
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum MatchMode {
+    /// The whole pattern must be mapped onto the whole host
+    Isomorphism,
+
+    /// The pattern must be mapped onto an induced subgraph of the host
+    Subgraph,
+}
+
+/// Predicate which decides whether a pattern vertex value
+/// matches a host vertex value.
+type ValueEq<'a, T, U> = Box<dyn Fn(&T, &U) -> bool + 'a>;
+
+/// Resumable VF2 style search for mappings between the vertices of
+/// a pattern graph and the vertices of a host graph which preserve
+/// values, edges and non-edges.
+pub(crate) struct Matcher<'a, T, U, S> {
+    pattern: &'a Graph<T, S>,
+    host: &'a Graph<U, S>,
+    mode: MatchMode,
+    value_eq: ValueEq<'a, T, U>,
+    weight_eq: Box<dyn Fn(f32, f32) -> bool + 'a>,
+
+    /// Order in which the pattern vertices are mapped
+    order: Vec<VertexId>,
+
+    /// Candidates for each mapped pattern vertex along
+    /// with the index of the next candidate to try
+    stack: Vec<(Vec<VertexId>, usize)>,
+
+    /// Mapping from pattern vertices to host vertices
+    core_pattern: HashMap<VertexId, VertexId>,
+
+    /// Mapping from host vertices to pattern vertices
+    core_host: HashMap<VertexId, VertexId>,
+
+    started: bool,
+}
+
+impl<'a, T, U, S: BuildHasher> Matcher<'a, T, U, S> {
+    pub(crate) fn new(
+        pattern: &'a Graph<T, S>,
+        host: &'a Graph<U, S>,
+        mode: MatchMode,
+        value_eq: impl Fn(&T, &U) -> bool + 'a,
+        weight_eq: impl Fn(f32, f32) -> bool + 'a,
+    ) -> Matcher<'a, T, U, S> {
+        Matcher {
+            pattern,
+            host,
+            mode,
+            value_eq: Box::new(value_eq),
+            weight_eq: Box::new(weight_eq),
+            order: order(pattern),
+            stack: Vec::new(),
+            core_pattern: HashMap::with_capacity(pattern.vertex_count()),
+            core_host: HashMap::with_capacity(pattern.vertex_count()),
+            started: false,
+        }
+    }
+
+    /// Returns the next mapping, resuming the search where
+    /// the previous call left it.
+    pub(crate) fn next_match(&mut self) -> Option<HashMap<VertexId, VertexId>> {
+        if !self.started {
+            self.started = true;
+
+            let sizes_match = match self.mode {
+                MatchMode::Isomorphism => {
+                    self.pattern.vertex_count() == self.host.vertex_count()
+                        && self.pattern.edge_count() == self.host.edge_count()
+                }
+                MatchMode::Subgraph => {
+                    self.pattern.vertex_count() <= self.host.vertex_count()
+                        && self.pattern.edge_count() <= self.host.edge_count()
+                }
+            };
+
+            if !sizes_match {
+                return None;
+            }
+
+            if self.order.is_empty() {
+                return Some(HashMap::new());
+            }
+
+            let candidates = self.candidates(0);
+            self.stack.push((candidates, 0));
+        }
+
+        while let Some(depth) = self.stack.len().checked_sub(1) {
+            let u = self.order[depth];
+
+            // Undo the mapping of the current level before trying the next candidate
+            if let Some(v) = self.core_pattern.remove(&u) {
+                self.core_host.remove(&v);
+            }
+
+            let (start, len) = {
+                let (candidates, idx) = &self.stack[depth];
+                (*idx, candidates.len())
+            };
+
+            let next = (start..len).find(|&i| self.is_feasible(&u, &self.stack[depth].0[i]));
+
+            match next {
+                Some(i) => {
+                    let v = self.stack[depth].0[i];
+                    self.stack[depth].1 = i + 1;
+
+                    self.core_pattern.insert(u, v);
+                    self.core_host.insert(v, u);
+
+                    if depth + 1 == self.order.len() {
+                        return Some(self.core_pattern.clone());
+                    }
+
+                    let candidates = self.candidates(depth + 1);
+                    self.stack.push((candidates, 0));
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Returns the unmapped host vertices which the pattern vertex
+    /// at the given depth may be mapped onto. When the pattern vertex
+    /// has an already mapped neighbor, only the matching neighbors of
+    /// its image are considered.
+    fn candidates(&self, depth: usize) -> Vec<VertexId> {
+        let u = &self.order[depth];
+
+        let mut candidates: Vec<VertexId> = if let Some(m) = self
+            .pattern
+            .out_neighbors(u)
+            .find(|m| self.core_pattern.contains_key(*m))
+        {
+            self.host
+                .in_neighbors(&self.core_pattern[m])
+                .cloned()
+                .collect()
+        } else if let Some(m) = self
+            .pattern
+            .in_neighbors(u)
+            .find(|m| self.core_pattern.contains_key(*m))
+        {
+            self.host
+                .out_neighbors(&self.core_pattern[m])
+                .cloned()
+                .collect()
+        } else {
+            self.host.vertices().cloned().collect()
+        };
+
+        candidates.retain(|v| !self.core_host.contains_key(v));
+        candidates.sort();
+        candidates
+    }
+
+    fn is_feasible(&self, u: &VertexId, v: &VertexId) -> bool {
+        let (pattern, host) = (self.pattern, self.host);
+
+        if self.core_host.contains_key(v)
+            || !(self.value_eq)(pattern.fetch(u).unwrap(), host.fetch(v).unwrap())
+        {
+            return false;
+        }
+
+        let (p_in, p_out) = (
+            pattern.in_neighbors_count(u),
+            pattern.out_neighbors_count(u),
+        );
+        let (h_in, h_out) = (host.in_neighbors_count(v), host.out_neighbors_count(v));
+
+        let degrees_match = match self.mode {
+            MatchMode::Isomorphism => p_in == h_in && p_out == h_out,
+            MatchMode::Subgraph => p_in <= h_in && p_out <= h_out,
+        };
+
+        if !degrees_match || !self.edges_match(u, u, v, v) {
+            return false;
+        }
+
+        // Edges between the pattern vertex and the mapped ones must exist in the host
+        for m in pattern.out_neighbors(u) {
+            if let Some(n) = self.core_pattern.get(m) {
+                if !self.edges_match(u, m, v, n) {
+                    return false;
+                }
+            }
+        }
+
+        for m in pattern.in_neighbors(u) {
+            if let Some(n) = self.core_pattern.get(m) {
+                if !self.edges_match(m, u, n, v) {
+                    return false;
+                }
+            }
+        }
+
+        // And the other way around
+        let mut host_out = host.out_neighbors(v).filter_map(|n| self.core_host.get(n));
+        let mut host_in = host.in_neighbors(v).filter_map(|n| self.core_host.get(n));
+
+        host_out.all(|m| pattern.has_edge(u, m)) && host_in.all(|m| pattern.has_edge(m, u))
+    }
+
+    /// Returns true if the edge from `a` to `b` in the pattern and the
+    /// edge from `c` to `d` in the host either both do not exist or
+    /// both exist with matching weights.
+    fn edges_match(&self, a: &VertexId, b: &VertexId, c: &VertexId, d: &VertexId) -> bool {
+        match (self.pattern.weight(a, b), self.host.weight(c, d)) {
+            (None, None) => true,
+            (Some(w1), Some(w2)) => (self.weight_eq)(w1, w2),
+            _ => false,
+        }
+    }
+}
+
+/// Iterator over the mappings from the vertices of a pattern graph
+/// to the vertices of a host graph. Created by
+/// `Graph::find_subgraph_isomorphisms()`.
+pub struct SubgraphMatches<'a, T, U, S = DefaultHashBuilder> {
+    matcher: Matcher<'a, T, U, S>,
+}
+
+impl<'a, T, U, S: BuildHasher> SubgraphMatches<'a, T, U, S> {
+    pub(crate) fn new(
+        pattern: &'a Graph<T, S>,
+        host: &'a Graph<U, S>,
+    ) -> SubgraphMatches<'a, T, U, S> {
+        SubgraphMatches {
+            matcher: Matcher::new(pattern, host, MatchMode::Subgraph, |_, _| true, |_, _| true),
+        }
+    }
+}
+
+impl<'a, T, U, S: BuildHasher> Iterator for SubgraphMatches<'a, T, U, S> {
+    type Item = HashMap<VertexId, VertexId>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.matcher.next_match()
+    }
+}
+
+impl<'a, T, U, S: BuildHasher> Debug for SubgraphMatches<'a, T, U, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SubgraphMatches")
+            .field("order", &self.matcher.order)
+            .field("mapping", &self.matcher.core_pattern)
+            .finish()
+    }
+}
+
+/// Mixes the value into the hash. This is deterministic
+/// across runs and platforms, unlike the hashers of the maps.
+fn mix(hash: u64, value: u64) -> u64 {
+    let mut x = hash ^ value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// Mixes the given values into the hash in ascending order,
+/// so that the result does not depend on their original order.
+fn mix_sorted(hash: u64, mut values: Vec<u64>) -> u64 {
+    values.sort_unstable();
+    values.into_iter().fold(mix(hash, 0), mix)
+}
+
+/// Computes a hash of the structure of the graph by refining the labels of
+/// the vertices with the labels of their neighbors (Weisfeiler-Lehman)
+/// until the partition of the vertices stops changing.
+pub(crate) fn structural_hash<T, S: BuildHasher>(graph: &Graph<T, S>) -> u64 {
+    let mut labels: HashMap<VertexId, u64> = graph
+        .vertices()
+        .map(|v| {
+            let degrees = mix(
+                graph.in_neighbors_count(v) as u64,
+                graph.out_neighbors_count(v) as u64,
+            );
+            (*v, mix(degrees, graph.has_edge(v, v) as u64))
+        })
+        .collect();
+
+    let mut classes = distinct(&labels);
+
+    for _ in 0..graph.vertex_count() {
+        let refined: HashMap<VertexId, u64> = graph
+            .vertices()
+            .map(|v| {
+                let outbound = graph.out_neighbors(v).map(|n| labels[n]).collect();
+                let inbound = graph.in_neighbors(v).map(|n| labels[n]).collect();
+
+                (*v, mix_sorted(mix_sorted(labels[v], outbound), inbound))
+            })
+            .collect();
+
+        labels = refined;
+
+        let refined_classes = distinct(&labels);
+
+        if refined_classes == classes {
+            break;
+        }
+
+        classes = refined_classes;
+    }
+
+    let hash = mix(graph.vertex_count() as u64, graph.edge_count() as u64);
+    mix_sorted(hash, labels.values().cloned().collect())
+}
+
+fn distinct(labels: &HashMap<VertexId, u64>) -> usize {
+    labels.values().collect::<HashSet<_>>().len()
+}
+
+/// Orders the vertices of the graph so that each vertex is, where
+/// possible, adjacent to a vertex before it, which keeps the number of
+/// candidates low. Each component starts from its vertex of highest degree.
+fn order<T, S: BuildHasher>(graph: &Graph<T, S>) -> Vec<VertexId> {
+    let mut vertices: Vec<VertexId> = graph.vertices().cloned().collect();
+    vertices.sort_by_key(|v| (usize::MAX - graph.neighbors_count(v), *v));
+
+    let mut order = Vec::with_capacity(vertices.len());
+    let mut visited: HashSet<VertexId> = HashSet::with_capacity(vertices.len());
+    let mut queue = VecDeque::new();
+
+    for start in vertices {
+        if !visited.insert(start) {
+            continue;
+        }
+
+        queue.push_back(start);
+
+        while let Some(current) = queue.pop_front() {
+            order.push(current);
+
+            let mut neighbors: Vec<VertexId> = graph
+                .out_neighbors(&current)
+                .chain(graph.in_neighbors(&current))
+                .filter(|n| !visited.contains(*n))
+                .cloned()
+                .collect();
+
+            neighbors.sort();
+
+            for n in neighbors {
+                if visited.insert(n) {
+                    queue.push_back(n);
+                }
+            }
+        }
+    }
+
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diamond() -> Graph<usize> {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(0);
+        let v2 = graph.add_vertex(0);
+        let v3 = graph.add_vertex(0);
+        let v4 = graph.add_vertex(0);
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v1, &v3).unwrap();
+        graph.add_edge(&v2, &v4).unwrap();
+        graph.add_edge(&v3, &v4).unwrap();
+
+        graph
+    }
+
+    #[test]
+    fn test_matcher_enumerates_automorphisms() {
+        let graph = diamond();
+        let mut matcher = Matcher::new(
+            &graph,
+            &graph,
+            MatchMode::Isomorphism,
+            |_, _| true,
+            |_, _| true,
+        );
+
+        let mut count = 0;
+
+        while let Some(mapping) = matcher.next_match() {
+            assert_eq!(mapping.len(), 4);
+
+            for (a, b) in graph.edges() {
+                assert!(graph.has_edge(&mapping[b], &mapping[a]));
+            }
+
+            count += 1;
+        }
+
+        // The two middle vertices can be swapped
+        assert_eq!(count, 2);
+        assert_eq!(matcher.next_match(), None);
+    }
+
+    #[test]
+    fn test_subgraph_matches_are_induced() {
+        let host = diamond();
+
+        // Two vertices with a common successor
+        let mut pattern: Graph<usize> = Graph::new();
+        let p1 = pattern.add_vertex(0);
+        let p2 = pattern.add_vertex(0);
+        let p3 = pattern.add_vertex(0);
+
+        pattern.add_edge(&p1, &p3).unwrap();
+        pattern.add_edge(&p2, &p3).unwrap();
+
+        let matches: Vec<_> = SubgraphMatches::new(&pattern, &host).collect();
+
+        // Only the two middle vertices share a successor, in either order
+        assert_eq!(matches.len(), 2);
+
+        for mapping in matches {
+            for (b, a) in pattern.edges() {
+                assert!(host.has_edge(&mapping[a], &mapping[b]));
+            }
+
+            assert!(!host.has_edge(&mapping[&p1], &mapping[&p2]));
+            assert!(!host.has_edge(&mapping[&p2], &mapping[&p1]));
+        }
+
+        // The pattern with an extra edge is not an induced subgraph
+        pattern.add_edge(&p1, &p2).unwrap();
+        assert_eq!(SubgraphMatches::new(&pattern, &host).count(), 0);
+    }
+
+    #[test]
+    fn test_structural_hash_ignores_ids() {
+        let graph = diamond();
+
+        // The same diamond with the vertices added in another order
+        let mut other: Graph<usize> = Graph::new();
+
+        let v4 = other.add_vertex(0);
+        let v3 = other.add_vertex(0);
+        let v1 = other.add_vertex(0);
+        let v2 = other.add_vertex(0);
+
+        other.add_edge(&v3, &v4).unwrap();
+        other.add_edge(&v1, &v3).unwrap();
+        other.add_edge(&v2, &v4).unwrap();
+        other.add_edge(&v1, &v2).unwrap();
+
+        assert_eq!(structural_hash(&graph), structural_hash(&other));
+
+        // Reversing an edge changes the structure
+        other.remove_edge(&v3, &v4);
+        other.add_edge(&v4, &v3).unwrap();
+
+        assert_ne!(structural_hash(&graph), structural_hash(&other));
+        assert_ne!(
+            structural_hash(&Graph::<usize>::new()),
+            structural_hash(&graph)
+        );
+    }
+}
@@ -1,6 +1,6 @@
 // Copyright 2019 Octavian Oncescu
 
-use crate::graph::Graph;
+use crate::graph::{DefaultHashBuilder, Graph};
 use crate::vertex_id::VertexId;
 
 use hashbrown::HashSet;
@@ -21,18 +21,23 @@ use core::fmt::Debug;
 #[cfg(not(feature = "no_std"))]
 use std::fmt::Debug;
 
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
 #[derive(Debug)]
 /// Breadth-First Iterator
-pub struct Bfs<'a, T> {
+pub struct Bfs<'a, T, S = DefaultHashBuilder> {
     queue: VecDeque<VertexId>,
     current_ptr: Option<VertexId>,
     visited_set: HashSet<VertexId>,
     roots_stack: Vec<VertexId>,
-    iterable: &'a Graph<T>,
+    iterable: &'a Graph<T, S>,
 }
 
-impl<'a, T> Bfs<'a, T> {
-    pub fn new(graph: &'a Graph<T>) -> Bfs<'_, T> {
+impl<'a, T, S: BuildHasher> Bfs<'a, T, S> {
+    pub fn new(graph: &'a Graph<T, S>) -> Bfs<'_, T, S> {
         let mut roots_stack = Vec::with_capacity(graph.roots_count());
 
         for v in graph.roots() {
@@ -51,7 +56,7 @@ impl<'a, T> Bfs<'a, T> {
     }
 }
 
-impl<'a, T> Iterator for Bfs<'a, T> {
+impl<'a, T, S: BuildHasher> Iterator for Bfs<'a, T, S> {
     type Item = &'a VertexId;
 
     fn next(&mut self) -> Option<Self::Item> {
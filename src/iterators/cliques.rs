@@ -0,0 +1,218 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::{DefaultHashBuilder, Graph};
+use crate::vertex_id::VertexId;
+
+use hashbrown::HashSet;
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "no_std")]
+use core::fmt::Debug;
+
+#[cfg(not(feature = "no_std"))]
+use std::fmt::Debug;
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+#[derive(Debug)]
+/// Pending step of the Bron–Kerbosch algorithm.
+struct Frame {
+    /// Vertices of the clique being built.
+    clique: Vec<VertexId>,
+    /// Vertices which may still extend the clique.
+    candidates: HashSet<VertexId>,
+    /// Vertices which have already been used to extend the clique.
+    excluded: HashSet<VertexId>,
+    /// Candidates left to branch on, excluding the pivot's neighbors.
+    branches: Vec<VertexId>,
+}
+
+#[derive(Debug)]
+/// Maximal Cliques Iterator
+///
+/// Yields the maximal cliques of the graph, ignoring the direction
+/// of its edges, using the Bron–Kerbosch algorithm with pivoting.
+pub struct Cliques<'a, T, S = DefaultHashBuilder> {
+    /// Steps of the algorithm which are yet to be processed.
+    stack: Vec<Frame>,
+    /// Cliques with fewer vertices are not yielded.
+    min_size: usize,
+    /// The Graph being iterated.
+    iterable: &'a Graph<T, S>,
+}
+
+impl<'a, T, S: BuildHasher> Cliques<'a, T, S> {
+    pub fn new(graph: &'a Graph<T, S>, min_size: usize) -> Cliques<'a, T, S> {
+        let mut cliques = Cliques {
+            stack: Vec::new(),
+            min_size,
+            iterable: graph,
+        };
+
+        let candidates = graph.vertices().cloned().collect();
+
+        if graph.vertex_count() >= min_size {
+            cliques.push_frame(Vec::new(), candidates, HashSet::new());
+        }
+
+        cliques
+    }
+
+    /// Returns the neighbors of the given vertex, ignoring self-loops.
+    fn neighbors(&self, id: &VertexId) -> HashSet<VertexId> {
+        self.iterable
+            .neighbors(id)
+            .filter(|n| *n != id)
+            .cloned()
+            .collect()
+    }
+
+    fn push_frame(
+        &mut self,
+        clique: Vec<VertexId>,
+        candidates: HashSet<VertexId>,
+        excluded: HashSet<VertexId>,
+    ) {
+        // Pick the pivot with the most neighbors among the
+        // candidates, as these do not have to be branched on.
+        let pivot_neighbors = candidates
+            .iter()
+            .chain(excluded.iter())
+            .map(|v| self.neighbors(v))
+            .max_by_key(|n| n.intersection(&candidates).count())
+            .unwrap_or_default();
+
+        let branches = candidates
+            .iter()
+            .filter(|v| !pivot_neighbors.contains(v))
+            .cloned()
+            .collect();
+
+        self.stack.push(Frame {
+            clique,
+            candidates,
+            excluded,
+            branches,
+        });
+    }
+}
+
+impl<'a, T, S: BuildHasher> Iterator for Cliques<'a, T, S> {
+    type Item = Vec<&'a VertexId>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let frame = self.stack.last_mut()?;
+
+            let v = match frame.branches.pop() {
+                Some(v) => v,
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+            };
+
+            let mut clique = frame.clique.clone();
+            clique.push(v);
+
+            let neighbors = self.neighbors(&v);
+            let frame = self.stack.last_mut().unwrap();
+
+            let candidates: HashSet<VertexId> =
+                frame.candidates.intersection(&neighbors).cloned().collect();
+            let excluded: HashSet<VertexId> =
+                frame.excluded.intersection(&neighbors).cloned().collect();
+
+            frame.candidates.remove(&v);
+            frame.excluded.insert(v);
+
+            if candidates.is_empty() && excluded.is_empty() {
+                if clique.len() >= self.min_size {
+                    let iterable = self.iterable;
+
+                    return Some(
+                        clique
+                            .iter()
+                            .map(|id| iterable.fetch_id_ref(id).unwrap())
+                            .collect(),
+                    );
+                }
+            } else if !candidates.is_empty() && clique.len() + candidates.len() >= self.min_size {
+                self.push_frame(clique, candidates, excluded);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(mut cliques: Vec<Vec<&VertexId>>) -> Vec<Vec<&VertexId>> {
+        for c in cliques.iter_mut() {
+            c.sort();
+        }
+
+        cliques.sort();
+        cliques
+    }
+
+    #[test]
+    fn test_cliques() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+        let v4 = graph.add_vertex(4);
+        let v5 = graph.add_vertex(5);
+        let v6 = graph.add_vertex(6);
+
+        // Triangle with an edge in each direction
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v2, &v3).unwrap();
+        graph.add_edge(&v3, &v1).unwrap();
+        graph.add_edge(&v1, &v3).unwrap();
+
+        // Square with a diagonal
+        graph.add_edge(&v3, &v4).unwrap();
+        graph.add_edge(&v4, &v5).unwrap();
+        graph.add_edge(&v5, &v2).unwrap();
+        graph.add_edge(&v3, &v5).unwrap();
+
+        // Self-loop on an isolated vertex
+        graph.add_edge(&v6, &v6).unwrap();
+
+        let expected = sorted(vec![
+            vec![&v1, &v2, &v3],
+            vec![&v2, &v3, &v5],
+            vec![&v3, &v4, &v5],
+            vec![&v6],
+        ]);
+
+        assert_eq!(sorted(Cliques::new(&graph, 0).collect()), expected);
+        assert_eq!(
+            sorted(Cliques::new(&graph, 3).collect()),
+            expected
+                .iter()
+                .filter(|c| c.len() >= 3)
+                .cloned()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Cliques::new(&graph, 4).count(), 0);
+    }
+
+    #[test]
+    fn test_cliques_on_empty_graph() {
+        let graph: Graph<usize> = Graph::new();
+
+        assert_eq!(Cliques::new(&graph, 0).count(), 0);
+    }
+}
@@ -1,6 +1,6 @@
 // Copyright 2019 Octavian Oncescu
 
-use crate::graph::Graph;
+use crate::graph::{DefaultHashBuilder, Graph};
 use crate::iterators::VertexIter;
 use crate::vertex_id::VertexId;
 
@@ -21,9 +21,14 @@ use core::fmt::Debug;
 #[cfg(not(feature = "no_std"))]
 use std::fmt::Debug;
 
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
 #[derive(Debug)]
 /// Depth-First Iterator
-pub struct Dfs<'a, T> {
+pub struct Dfs<'a, T, S = DefaultHashBuilder> {
     /// All the vertices to be checked with the roots coming first.
     unchecked: Peekable<Cloned<Chain<VertexIter<'a>, VertexIter<'a>>>>,
     /// All black vertices.
@@ -33,13 +38,13 @@ pub struct Dfs<'a, T> {
     /// All vertices pending processing.
     pending_stack: Vec<(VertexId, bool)>,
     /// The Graph being iterated.
-    iterable: &'a Graph<T>,
+    iterable: &'a Graph<T, S>,
     /// A cached answer to the question: does this Graph contain cycles.
     cached_cyclic: bool,
 }
 
-impl<'a, T> Dfs<'a, T> {
-    pub fn new(graph: &'a Graph<T>) -> Dfs<'_, T> {
+impl<'a, T, S: BuildHasher> Dfs<'a, T, S> {
+    pub fn new(graph: &'a Graph<T, S>) -> Dfs<'_, T, S> {
         let unchecked = graph.roots().chain(graph.vertices()).cloned().peekable();
 
         Dfs {
@@ -100,6 +105,13 @@ impl<'a, T> Dfs<'a, T> {
             .filter_map(|v| {
                 let (v, already_seen) = v;
 
+                // a vertex can be pushed by several predecessors
+                // before it is processed, so skip it once it is done
+                if !*already_seen && self.black.contains(v) {
+                    should_return = false;
+                    return None;
+                }
+
                 // if we have seen the vertex before,
                 // we remove it from grey and add it to black
                 if *already_seen {
@@ -138,7 +150,7 @@ impl<'a, T> Dfs<'a, T> {
     }
 }
 
-impl<'a, T> Iterator for Dfs<'a, T> {
+impl<'a, T, S: BuildHasher> Iterator for Dfs<'a, T, S> {
     type Item = &'a VertexId;
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -274,4 +286,26 @@ mod tests {
 
         assert!(graph.is_cyclic());
     }
+
+    #[test]
+    fn no_duplicates() {
+        // The order of the successors depends on the random ids,
+        // so try both orders of the shared successor.
+        for _ in 0..100 {
+            let mut graph = Graph::new();
+
+            let v1 = graph.add_vertex(1);
+            let v2 = graph.add_vertex(2);
+            let v3 = graph.add_vertex(3);
+
+            graph.add_edge(&v1, &v2).unwrap();
+            graph.add_edge(&v1, &v3).unwrap();
+            graph.add_edge(&v2, &v3).unwrap();
+
+            let visited: HashSet<&VertexId> = graph.dfs().collect();
+
+            assert_eq!(graph.dfs().count(), 3);
+            assert_eq!(visited.len(), 3);
+        }
+    }
 }
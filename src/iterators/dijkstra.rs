@@ -1,7 +1,6 @@
 // Copyright 2019 Chakrapani Gautam
 
-use crate::graph::{Graph, GraphErr};
-use crate::iterators::owning_iterator::OwningIterator;
+use crate::graph::{DefaultHashBuilder, Graph, GraphErr};
 use crate::iterators::vertices::VertexIter;
 use crate::vertex_id::VertexId;
 
@@ -9,28 +8,29 @@ use hashbrown::HashMap;
 use hashbrown::HashSet;
 
 #[cfg(not(feature = "no_std"))]
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, VecDeque},
-    f32,
-    fmt::Debug,
-    iter,
-};
+use std::{cmp::Ordering, collections::BinaryHeap, f32, fmt::Debug};
 
 #[cfg(feature = "no_std")]
 extern crate alloc;
 #[cfg(feature = "no_std")]
 use alloc::boxed::Box;
 #[cfg(feature = "no_std")]
-use alloc::collections::{binary_heap::BinaryHeap, vec_deque::VecDeque};
+use alloc::collections::binary_heap::BinaryHeap;
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "no_std")]
+use core::{cmp::Ordering, f32, fmt::Debug};
 
 #[cfg(feature = "no_std")]
-use core::{cmp::Ordering, f32, fmt::Debug, iter};
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
 
 #[derive(PartialEq, Debug)]
-struct VertexMeta {
-    id: VertexId,
-    distance: f32,
+pub(crate) struct VertexMeta {
+    pub(crate) id: VertexId,
+    pub(crate) distance: f32,
 }
 
 impl Eq for VertexMeta {}
@@ -49,32 +49,26 @@ impl Ord for VertexMeta {
 
 #[derive(Clone, Debug)]
 /// Dijkstra Single-source Shortest Path Iterator
-pub struct Dijkstra<'a, T> {
+pub struct Dijkstra<'a, T, S = DefaultHashBuilder> {
     source: &'a VertexId,
-    iterable: &'a Graph<T>,
-    iterator: VecDeque<VertexId>,
+    iterable: &'a Graph<T, S>,
     distances: HashMap<VertexId, f32>,
     previous: HashMap<VertexId, Option<VertexId>>,
 }
 
-impl<'a, T> Dijkstra<'a, T> {
-    pub fn new(graph: &'a Graph<T>, src: &'a VertexId) -> Result<Dijkstra<'a, T>, GraphErr> {
+impl<'a, T, S: BuildHasher> Dijkstra<'a, T, S> {
+    pub fn new(graph: &'a Graph<T, S>, src: &'a VertexId) -> Result<Dijkstra<'a, T, S>, GraphErr> {
         if graph.fetch(src).is_none() {
             return Err(GraphErr::NoSuchVertex);
         }
 
-        for edge in graph.edges() {
-            if let Some(w) = graph.weight(edge.1, edge.0) {
-                if w < 0.0 {
-                    return Err(GraphErr::InvalidWeight);
-                }
-            }
+        if graph.edges_with_weights().any(|(_, _, w)| w < 0.0) {
+            return Err(GraphErr::InvalidWeight);
         }
 
         let mut instance = Dijkstra {
             source: src,
             iterable: graph,
-            iterator: VecDeque::with_capacity(graph.vertex_count()),
             distances: HashMap::with_capacity(graph.vertex_count()),
             previous: HashMap::with_capacity(graph.vertex_count()),
         };
@@ -97,31 +91,37 @@ impl<'a, T> Dijkstra<'a, T> {
         Ok(())
     }
 
-    pub fn get_path_to(mut self, vert: &'a VertexId) -> Result<VertexIter, GraphErr> {
+    /// Returns the shortest path from the source to the given vertex.
+    ///
+    /// The returned iterator only borrows the graph, so the same
+    /// instance can answer any number of path queries.
+    pub fn get_path_to(&self, vert: &VertexId) -> Result<VertexIter<'a>, GraphErr> {
         if self.iterable.fetch(vert).is_none() {
             return Err(GraphErr::NoSuchVertex);
         }
 
-        if self.previous.contains_key(vert) {
-            let mut cur_vert = Some(vert);
-            self.iterator.clear();
+        let mut path: Vec<&'a VertexId> = Vec::new();
+        let mut cur_vert = if self.previous.contains_key(vert) {
+            self.iterable.fetch_id_ref(vert)
+        } else {
+            None
+        };
 
-            while cur_vert.is_some() {
-                self.iterator.push_front(*cur_vert.unwrap());
+        while let Some(v) = cur_vert {
+            path.push(v);
 
-                match self.previous.get(cur_vert.unwrap()) {
-                    Some(v) => cur_vert = v.as_ref(),
-                    None => cur_vert = None,
-                }
-            }
-
-            return Ok(VertexIter(Box::new(OwningIterator::new(self.iterator))));
+            cur_vert = match self.previous.get(v) {
+                Some(Some(prev)) => self.iterable.fetch_id_ref(prev),
+                _ => None,
+            };
         }
 
-        Ok(VertexIter(Box::new(iter::empty())))
+        path.reverse();
+
+        Ok(VertexIter(Box::new(path.into_iter())))
     }
 
-    pub fn get_distance(&mut self, vert: &'a VertexId) -> Result<f32, GraphErr> {
+    pub fn get_distance(&self, vert: &VertexId) -> Result<f32, GraphErr> {
         if self.iterable.fetch(vert).is_none() {
             return Err(GraphErr::NoSuchVertex);
         }
@@ -159,7 +159,7 @@ impl<'a, T> Dijkstra<'a, T> {
                 if !visited.contains(&neighbor) {
                     let mut alt_dist = *self.distances.get(&vert_meta.id).unwrap();
 
-                    if let Some(w) = self.iterable.weight(&vert_meta.id, &neighbor) {
+                    if let Some(w) = self.iterable.edge_cost(&vert_meta.id, &neighbor) {
                         alt_dist += w;
                     }
 
@@ -244,7 +244,7 @@ mod tests {
         let v2 = graph.add_vertex(2);
         graph.add_edge_with_weight(&v1, &v2, 0.0);
 
-        let mut iterator = Dijkstra::new(&graph, &v1).unwrap();
+        let iterator = Dijkstra::new(&graph, &v1).unwrap();
         let result = iterator.get_path_to(&random_vertex);
 
         assert!(result.is_err());
@@ -259,7 +259,7 @@ mod tests {
         let v2 = graph.add_vertex(2);
         graph.add_edge_with_weight(&v1, &v2, 0.0);
 
-        let mut iterator = Dijkstra::new(&graph, &v1).unwrap();
+        let iterator = Dijkstra::new(&graph, &v1).unwrap();
         let result = iterator.get_distance(&random_vertex);
 
         assert!(result.is_err());
@@ -286,7 +286,7 @@ mod tests {
         graph.add_edge_with_weight(&v_d, &v_f, 0.8).unwrap();
 
         {
-            let mut iterator = Dijkstra::new(&graph, &v_a).unwrap();
+            let iterator = Dijkstra::new(&graph, &v_a).unwrap();
 
             assert_eq!(iterator.get_distance(&v_a).unwrap(), 0.0);
             assert_eq!(iterator.get_distance(&v_b).unwrap(), 0.1);
@@ -322,12 +322,12 @@ mod tests {
         assert_eq!(iterator.get_distance(&v_f).unwrap(), 0.900_000_04);
         // Ugh! I wish there was something like `assert_approx_eq!()`. Too lazy to write on my own.
 
-        assert_eq!(iterator.clone().get_path_to(&v_a).unwrap().count(), 4);
-        assert_eq!(iterator.clone().get_path_to(&v_b).unwrap().count(), 3);
-        assert_eq!(iterator.clone().get_path_to(&v_c).unwrap().count(), 1);
-        assert_eq!(iterator.clone().get_path_to(&v_d).unwrap().count(), 2);
-        assert_eq!(iterator.clone().get_path_to(&v_e).unwrap().count(), 2);
-        assert_eq!(iterator.clone().get_path_to(&v_f).unwrap().count(), 3);
+        assert_eq!(iterator.get_path_to(&v_a).unwrap().count(), 4);
+        assert_eq!(iterator.get_path_to(&v_b).unwrap().count(), 3);
+        assert_eq!(iterator.get_path_to(&v_c).unwrap().count(), 1);
+        assert_eq!(iterator.get_path_to(&v_d).unwrap().count(), 2);
+        assert_eq!(iterator.get_path_to(&v_e).unwrap().count(), 2);
+        assert_eq!(iterator.get_path_to(&v_f).unwrap().count(), 3);
 
         /*
         // To run these tests, uncomment and use `-- --nocapture` flag in `cargo test`
@@ -372,12 +372,12 @@ mod tests {
         assert_eq!(iterator.get_distance(&v_e).unwrap(), infinity);
         assert_eq!(iterator.get_distance(&v_f).unwrap(), 0.0);
 
-        assert_eq!(iterator.clone().get_path_to(&v_a).unwrap().count(), 1);
-        assert_eq!(iterator.clone().get_path_to(&v_b).unwrap().count(), 2);
-        assert_eq!(iterator.clone().get_path_to(&v_c).unwrap().count(), 0);
-        assert_eq!(iterator.clone().get_path_to(&v_d).unwrap().count(), 3);
-        assert_eq!(iterator.clone().get_path_to(&v_e).unwrap().count(), 0);
-        assert_eq!(iterator.clone().get_path_to(&v_f).unwrap().count(), 4);
+        assert_eq!(iterator.get_path_to(&v_a).unwrap().count(), 1);
+        assert_eq!(iterator.get_path_to(&v_b).unwrap().count(), 2);
+        assert_eq!(iterator.get_path_to(&v_c).unwrap().count(), 0);
+        assert_eq!(iterator.get_path_to(&v_d).unwrap().count(), 3);
+        assert_eq!(iterator.get_path_to(&v_e).unwrap().count(), 0);
+        assert_eq!(iterator.get_path_to(&v_f).unwrap().count(), 4);
 
         iterator.set_source(&v_c).unwrap();
 
@@ -388,12 +388,12 @@ mod tests {
         assert_eq!(iterator.get_distance(&v_e).unwrap(), 0.0);
         assert_eq!(iterator.get_distance(&v_f).unwrap(), 0.0);
 
-        assert_eq!(iterator.clone().get_path_to(&v_a).unwrap().count(), 0);
-        assert_eq!(iterator.clone().get_path_to(&v_b).unwrap().count(), 2);
-        assert_eq!(iterator.clone().get_path_to(&v_c).unwrap().count(), 1);
-        assert_eq!(iterator.clone().get_path_to(&v_d).unwrap().count(), 2);
-        assert_eq!(iterator.clone().get_path_to(&v_e).unwrap().count(), 2);
-        assert_eq!(iterator.clone().get_path_to(&v_f).unwrap().count(), 3);
+        assert_eq!(iterator.get_path_to(&v_a).unwrap().count(), 0);
+        assert_eq!(iterator.get_path_to(&v_b).unwrap().count(), 2);
+        assert_eq!(iterator.get_path_to(&v_c).unwrap().count(), 1);
+        assert_eq!(iterator.get_path_to(&v_d).unwrap().count(), 2);
+        assert_eq!(iterator.get_path_to(&v_e).unwrap().count(), 2);
+        assert_eq!(iterator.get_path_to(&v_f).unwrap().count(), 3);
 
         /*
         // To run these tests, uncomment and use `-- --nocapture` flag in `cargo test`
@@ -408,4 +408,34 @@ mod tests {
         }
         */
     }
+
+    #[test]
+    fn test_shared_path_queries() {
+        use std::sync::Arc;
+        use std::thread;
+
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v_a = graph.add_vertex(1);
+        let v_b = graph.add_vertex(2);
+        let v_c = graph.add_vertex(3);
+
+        graph.add_edge_with_weight(&v_a, &v_b, 0.1).unwrap();
+        graph.add_edge_with_weight(&v_b, &v_c, 0.1).unwrap();
+        graph.add_edge_with_weight(&v_a, &v_c, 0.5).unwrap();
+
+        let dijkstra = Arc::new(Dijkstra::new(&graph, &v_a).unwrap());
+        let to_c: Vec<&VertexId> = dijkstra.get_path_to(&v_c).unwrap().collect();
+        let to_b: Vec<&VertexId> = dijkstra.get_path_to(&v_b).unwrap().collect();
+
+        assert_eq!(to_c, vec![&v_a, &v_b, &v_c]);
+        assert_eq!(to_b, vec![&v_a, &v_b]);
+
+        thread::scope(|s| {
+            for _ in 0..2 {
+                let dijkstra = Arc::clone(&dijkstra);
+                s.spawn(move || assert_eq!(dijkstra.get_path_to(&v_c).unwrap().count(), 3));
+            }
+        });
+    }
 }
@@ -1,16 +1,27 @@
 // Copyright 2019 Octavian Oncescu
 
 mod bfs;
+mod cliques;
 mod dfs;
 mod dijkstra;
-mod owning_iterator;
+mod pairs;
+#[cfg(feature = "rayon")]
+mod par_bfs;
 mod topo;
+mod traversal_edges;
+mod uniform_cost;
 mod values;
 mod vertices;
 
 pub use bfs::*;
+pub use cliques::*;
 pub use dfs::*;
 pub use dijkstra::*;
+pub use pairs::*;
+#[cfg(feature = "rayon")]
+pub use par_bfs::*;
 pub use topo::*;
+pub use traversal_edges::*;
+pub use uniform_cost::*;
 pub use values::*;
 pub use vertices::*;
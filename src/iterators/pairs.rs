@@ -0,0 +1,39 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::vertex_id::VertexId;
+
+use hashbrown::hash_map;
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::boxed::Box;
+
+/// Iterator over the ids and the values of the vertices of a graph.
+pub struct Iter<'a, T>(pub(crate) Box<dyn 'a + Iterator<Item = (&'a VertexId, &'a T)>>);
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (&'a VertexId, &'a T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+/// Iterator moving the ids and the values out of the vertices of a graph.
+pub struct IntoIter<T>(pub(crate) hash_map::IntoIter<VertexId, (T, VertexId)>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = (VertexId, T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(id, (value, _))| (id, value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
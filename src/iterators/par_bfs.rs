@@ -0,0 +1,102 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::{DefaultHashBuilder, Graph};
+use crate::vertex_id::VertexId;
+
+use hashbrown::HashSet;
+use rayon::prelude::*;
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+#[derive(Debug)]
+/// Level-synchronous parallel Breadth-First Iterator, which
+/// yields the vertices of the graph one level at a time.
+pub struct ParBfs<'a, T, S = DefaultHashBuilder> {
+    frontier: Vec<&'a VertexId>,
+    visited_set: HashSet<VertexId>,
+    iterable: &'a Graph<T, S>,
+}
+
+impl<'a, T: Send + Sync, S: BuildHasher + Send + Sync> ParBfs<'a, T, S> {
+    pub fn new(graph: &'a Graph<T, S>) -> ParBfs<'a, T, S> {
+        let frontier: Vec<&VertexId> = graph.roots().collect();
+        let visited_set = frontier.iter().map(|v| **v).collect();
+
+        ParBfs {
+            frontier,
+            visited_set,
+            iterable: graph,
+        }
+    }
+}
+
+impl<'a, T: Send + Sync, S: BuildHasher + Send + Sync> Iterator for ParBfs<'a, T, S> {
+    type Item = Vec<&'a VertexId>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.frontier.is_empty() {
+            return None;
+        }
+
+        let graph = self.iterable;
+        let visited_set = &self.visited_set;
+
+        // Expand the frontier on multiple threads, then keep
+        // the first occurrence of each newly reached vertex.
+        let reached: Vec<&'a VertexId> = self
+            .frontier
+            .par_iter()
+            .flat_map_iter(|v| graph.out_neighbors(v))
+            .filter(|n| !visited_set.contains(*n))
+            .collect();
+
+        let next_level = reached
+            .into_iter()
+            .filter(|n| self.visited_set.insert(**n))
+            .collect();
+
+        Some(std::mem::replace(&mut self.frontier, next_level))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_match_bfs() {
+        let mut graph: Graph<usize> = Graph::new();
+        let ids: Vec<VertexId> = (0..500).map(|i| graph.add_vertex(i)).collect();
+
+        // Binary tree with extra edges between levels and a cycle
+        for i in 1..ids.len() {
+            graph.add_edge(&ids[(i - 1) / 2], &ids[i]).unwrap();
+
+            if i > 2 {
+                graph.add_edge(&ids[i / 2 - 1], &ids[i]).unwrap();
+            }
+        }
+
+        graph.add_edge(&ids[499], &ids[1]).unwrap();
+
+        let levels: Vec<Vec<&VertexId>> = graph.par_bfs().collect();
+        let visited: Vec<&VertexId> = levels.iter().flatten().cloned().collect();
+        let unique: HashSet<&VertexId> = visited.iter().cloned().collect();
+
+        assert_eq!(levels[0], vec![&ids[0]]);
+        assert_eq!(visited.len(), graph.bfs().count());
+        assert_eq!(unique.len(), visited.len());
+
+        // Each vertex is one level below its closest parent
+        for (depth, level) in levels.iter().enumerate().skip(1) {
+            for v in level {
+                assert!(graph
+                    .in_neighbors(v)
+                    .any(|n| levels[depth - 1].contains(&n)));
+            }
+        }
+    }
+}
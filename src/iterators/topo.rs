@@ -1,6 +1,6 @@
 // Copyright 2019 Gary Pennington
 
-use crate::graph::Graph;
+use crate::graph::{DefaultHashBuilder, Graph};
 use crate::vertex_id::VertexId;
 
 use hashbrown::HashMap;
@@ -14,13 +14,18 @@ use core::fmt::Debug;
 #[cfg(not(feature = "no_std"))]
 use std::fmt::Debug;
 
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
 const PANIC_MSG: &str = "graph contains cycle(s)";
 
 #[derive(Debug)]
 /// Topological Iterator
-pub struct Topo<'a, T> {
+pub struct Topo<'a, T, S = DefaultHashBuilder> {
     /// The Graph being iterated.
-    iterable: &'a Graph<T>,
+    iterable: &'a Graph<T, S>,
     /// Processed vertices
     vertices: Vec<&'a VertexId>,
     /// Working set of vertices
@@ -29,8 +34,8 @@ pub struct Topo<'a, T> {
     vertex_edges: HashMap<&'a VertexId, usize>,
 }
 
-impl<'a, T> Topo<'a, T> {
-    pub fn new(graph: &'a Graph<T>) -> Topo<'_, T> {
+impl<'a, T, S: BuildHasher> Topo<'a, T, S> {
+    pub fn new(graph: &'a Graph<T, S>) -> Topo<'_, T, S> {
         let mut roots = vec![];
         for node in graph.roots() {
             roots.push(node);
@@ -93,7 +98,7 @@ impl<'a, T> Topo<'a, T> {
     }
 }
 
-impl<'a, T> Iterator for Topo<'a, T> {
+impl<'a, T, S: BuildHasher> Iterator for Topo<'a, T, S> {
     type Item = &'a VertexId;
 
     fn size_hint(&self) -> (usize, Option<usize>) {
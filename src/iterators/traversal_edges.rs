@@ -0,0 +1,243 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::{DefaultHashBuilder, Graph};
+use crate::iterators::VertexIter;
+use crate::vertex_id::VertexId;
+
+use hashbrown::HashSet;
+
+#[cfg(not(feature = "no_std"))]
+use std::{
+    collections::VecDeque,
+    iter::{Chain, Cloned},
+};
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::collections::vec_deque::VecDeque;
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
+#[cfg(feature = "no_std")]
+use core::iter::{Chain, Cloned};
+
+#[cfg(feature = "no_std")]
+use core::fmt::Debug;
+
+#[cfg(not(feature = "no_std"))]
+use std::fmt::Debug;
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+/// Vertices to start traversals from, with the roots coming first.
+type Starts<'a> = Cloned<Chain<VertexIter<'a>, VertexIter<'a>>>;
+
+#[derive(Debug)]
+/// Breadth-First Edges Iterator
+///
+/// Yields the `(parent, child)` edges through which a breadth-first
+/// traversal of the graph reaches each vertex. Traversals start from
+/// the roots of the graph and then from any vertex left unvisited.
+pub struct BfsEdges<'a, T, S = DefaultHashBuilder> {
+    /// Vertices to start traversals from.
+    starts: Starts<'a>,
+    /// Edges leading to discovered vertices, in discovery order.
+    queue: VecDeque<(VertexId, VertexId)>,
+    /// Vertices which have been discovered.
+    visited: HashSet<VertexId>,
+    /// The Graph being iterated.
+    iterable: &'a Graph<T, S>,
+}
+
+impl<'a, T, S: BuildHasher> BfsEdges<'a, T, S> {
+    pub fn new(graph: &'a Graph<T, S>) -> BfsEdges<'a, T, S> {
+        BfsEdges {
+            starts: graph.roots().chain(graph.vertices()).cloned(),
+            queue: VecDeque::new(),
+            visited: HashSet::with_capacity(graph.vertex_count()),
+            iterable: graph,
+        }
+    }
+
+    /// Queues the edges to the undiscovered neighbors of the given vertex.
+    fn discover(&mut self, id: &VertexId) {
+        for n in self.iterable.out_neighbors(id) {
+            if self.visited.insert(*n) {
+                self.queue.push_back((*id, *n));
+            }
+        }
+    }
+}
+
+impl<'a, T, S: BuildHasher> Iterator for BfsEdges<'a, T, S> {
+    type Item = (&'a VertexId, &'a VertexId);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((parent, child)) = self.queue.pop_front() {
+                self.discover(&child);
+
+                return Some((
+                    self.iterable.fetch_id_ref(&parent)?,
+                    self.iterable.fetch_id_ref(&child)?,
+                ));
+            }
+
+            // Start a new traversal from the next unvisited vertex
+            let visited = &self.visited;
+            let start = self.starts.find(|v| !visited.contains(v))?;
+
+            self.visited.insert(start);
+            self.discover(&start);
+        }
+    }
+}
+
+#[derive(Debug)]
+/// Depth-First Edges Iterator
+///
+/// Yields the `(parent, child)` edges through which a depth-first
+/// traversal of the graph reaches each vertex. Traversals start from
+/// the roots of the graph and then from any vertex left unvisited.
+pub struct DfsEdges<'a, T, S = DefaultHashBuilder> {
+    /// Vertices to start traversals from.
+    starts: Starts<'a>,
+    /// Edges leading to vertices which are pending processing.
+    stack: Vec<(VertexId, VertexId)>,
+    /// Vertices which have been reached.
+    visited: HashSet<VertexId>,
+    /// The Graph being iterated.
+    iterable: &'a Graph<T, S>,
+}
+
+impl<'a, T, S: BuildHasher> DfsEdges<'a, T, S> {
+    pub fn new(graph: &'a Graph<T, S>) -> DfsEdges<'a, T, S> {
+        DfsEdges {
+            starts: graph.roots().chain(graph.vertices()).cloned(),
+            stack: Vec::new(),
+            visited: HashSet::with_capacity(graph.vertex_count()),
+            iterable: graph,
+        }
+    }
+
+    /// Pushes the edges to the unvisited neighbors of the given vertex.
+    fn expand(&mut self, id: &VertexId) {
+        for n in self.iterable.out_neighbors(id) {
+            if !self.visited.contains(n) {
+                self.stack.push((*id, *n));
+            }
+        }
+    }
+}
+
+impl<'a, T, S: BuildHasher> Iterator for DfsEdges<'a, T, S> {
+    type Item = (&'a VertexId, &'a VertexId);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            while let Some((parent, child)) = self.stack.pop() {
+                // The vertex may have been reached through another edge
+                if !self.visited.insert(child) {
+                    continue;
+                }
+
+                self.expand(&child);
+
+                return Some((
+                    self.iterable.fetch_id_ref(&parent)?,
+                    self.iterable.fetch_id_ref(&child)?,
+                ));
+            }
+
+            // Start a new traversal from the next unvisited vertex
+            let visited = &self.visited;
+            let start = self.starts.find(|v| !visited.contains(v))?;
+
+            self.visited.insert(start);
+            self.expand(&start);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edges_form_spanning_forest() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+        let v4 = graph.add_vertex(4);
+        let v5 = graph.add_vertex(5);
+        let v6 = graph.add_vertex(6);
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v1, &v3).unwrap();
+        graph.add_edge(&v2, &v4).unwrap();
+        graph.add_edge(&v3, &v4).unwrap();
+
+        // Cycle without roots
+        graph.add_edge(&v5, &v6).unwrap();
+        graph.add_edge(&v6, &v5).unwrap();
+
+        let bfs: Vec<_> = BfsEdges::new(&graph).collect();
+        let dfs: Vec<_> = DfsEdges::new(&graph).collect();
+
+        for edges in &[bfs, dfs] {
+            // Each vertex except for the start of each tree is reached once
+            assert_eq!(edges.len(), 4);
+
+            let children: HashSet<_> = edges.iter().map(|(_, c)| *c).collect();
+
+            assert_eq!(children.len(), 4);
+            assert!(!children.contains(&v1));
+            assert!(edges.iter().all(|(p, c)| graph.has_edge(p, c)));
+        }
+    }
+
+    #[test]
+    fn test_bfs_edges_order() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+        let v4 = graph.add_vertex(4);
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v2, &v3).unwrap();
+        graph.add_edge(&v1, &v4).unwrap();
+
+        let edges: Vec<_> = BfsEdges::new(&graph).collect();
+
+        // Both edges of the root come before the edge of the next level
+        assert_eq!(edges.len(), 3);
+        assert_eq!(edges[2], (&v2, &v3));
+    }
+
+    #[test]
+    fn test_dfs_edges_order() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v2, &v3).unwrap();
+        graph.add_edge(&v1, &v3).unwrap();
+
+        let edges: Vec<_> = DfsEdges::new(&graph).collect();
+
+        assert_eq!(edges.len(), 2);
+
+        // Either v3 is reached first from v1 or through v2
+        assert!(edges == vec![(&v1, &v2), (&v2, &v3)] || edges == vec![(&v1, &v3), (&v1, &v2)]);
+    }
+}
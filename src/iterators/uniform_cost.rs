@@ -0,0 +1,146 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::{DefaultHashBuilder, Graph, GraphErr};
+use crate::iterators::dijkstra::VertexMeta;
+use crate::vertex_id::VertexId;
+
+use hashbrown::HashSet;
+
+#[cfg(not(feature = "no_std"))]
+use std::{collections::BinaryHeap, fmt::Debug};
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::collections::binary_heap::BinaryHeap;
+
+#[cfg(feature = "no_std")]
+use core::fmt::Debug;
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+#[derive(Debug)]
+/// Uniform-Cost Iterator
+///
+/// Yields the vertices reachable from the source vertex along with
+/// their accumulated weight, in nondecreasing order of that weight.
+/// Unlike `Dijkstra`, distances are only computed for the vertices
+/// that have been reached so far.
+pub struct UniformCost<'a, T, S = DefaultHashBuilder> {
+    /// Vertices to be visited, ordered by their tentative distance.
+    frontier: BinaryHeap<VertexMeta>,
+    /// All vertices that have been yielded.
+    visited: HashSet<VertexId>,
+    /// The Graph being iterated.
+    iterable: &'a Graph<T, S>,
+}
+
+impl<'a, T, S: BuildHasher> UniformCost<'a, T, S> {
+    pub fn new(graph: &'a Graph<T, S>, src: &VertexId) -> Result<UniformCost<'a, T, S>, GraphErr> {
+        if graph.fetch(src).is_none() {
+            return Err(GraphErr::NoSuchVertex);
+        }
+
+        if graph.edges_with_weights().any(|(_, _, w)| w < 0.0) {
+            return Err(GraphErr::InvalidWeight);
+        }
+
+        let mut frontier = BinaryHeap::new();
+
+        frontier.push(VertexMeta {
+            id: *src,
+            distance: 0.0,
+        });
+
+        Ok(UniformCost {
+            frontier,
+            visited: HashSet::new(),
+            iterable: graph,
+        })
+    }
+}
+
+impl<'a, T, S: BuildHasher> Iterator for UniformCost<'a, T, S> {
+    type Item = (&'a VertexId, f32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(vert_meta) = self.frontier.pop() {
+            // A vertex may be pushed several times before
+            // it is visited, only the closest entry counts.
+            if !self.visited.insert(vert_meta.id) {
+                continue;
+            }
+
+            for neighbor in self.iterable.out_neighbors(&vert_meta.id) {
+                if !self.visited.contains(neighbor) {
+                    let weight = self.iterable.edge_cost(&vert_meta.id, neighbor).unwrap();
+
+                    self.frontier.push(VertexMeta {
+                        id: *neighbor,
+                        distance: vert_meta.distance + weight,
+                    });
+                }
+            }
+
+            return self
+                .iterable
+                .fetch_id_ref(&vert_meta.id)
+                .map(|id| (id, vert_meta.distance));
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_with_invalid_source() {
+        let graph: Graph<usize> = Graph::new();
+
+        assert!(UniformCost::new(&graph, &VertexId::random()).is_err());
+    }
+
+    #[test]
+    fn test_new_with_negative_weight_edge() {
+        let mut graph: Graph<usize> = Graph::new();
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+
+        graph.add_edge_with_weight(&v1, &v2, -0.1).unwrap();
+
+        assert_eq!(
+            UniformCost::new(&graph, &v1).err(),
+            Some(GraphErr::InvalidWeight)
+        );
+    }
+
+    #[test]
+    fn test_yields_in_nondecreasing_order() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v_a = graph.add_vertex(1);
+        let v_b = graph.add_vertex(2);
+        let v_c = graph.add_vertex(3);
+        let v_d = graph.add_vertex(4);
+        let v_e = graph.add_vertex(5);
+
+        graph.add_edge_with_weight(&v_a, &v_b, 0.5).unwrap();
+        graph.add_edge_with_weight(&v_a, &v_c, 0.1).unwrap();
+        graph.add_edge_with_weight(&v_c, &v_b, 0.25).unwrap();
+        graph.add_edge_with_weight(&v_b, &v_d, 0.125).unwrap();
+        graph.add_edge_with_weight(&v_e, &v_a, 0.1).unwrap();
+
+        let visited: Vec<(&VertexId, f32)> = UniformCost::new(&graph, &v_a).unwrap().collect();
+
+        assert_eq!(
+            visited,
+            vec![(&v_a, 0.0), (&v_c, 0.1), (&v_b, 0.35), (&v_d, 0.475)]
+        );
+    }
+}
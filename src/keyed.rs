@@ -0,0 +1,261 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::{Graph, GraphErr};
+use crate::vertex_id::VertexId;
+
+use hashbrown::HashMap;
+
+#[cfg(feature = "no_std")]
+use core::hash::Hash;
+#[cfg(not(feature = "no_std"))]
+use std::hash::Hash;
+
+/// Graph whose vertices are identified by keys given by the user,
+/// such as package names or account hashes, along with their ids.
+///
+/// Each key maps to a single vertex and the mapping is kept in both
+/// directions, so the key of a vertex reached by any of the graph
+/// algorithms run on `KeyedGraph::graph()` can be looked up.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::KeyedGraph;
+///
+/// let mut graph: KeyedGraph<&str, usize> = KeyedGraph::new();
+///
+/// graph.add_vertex("app", 1);
+/// graph.add_vertex("lib", 2);
+///
+/// graph.add_edge(&"app", &"lib").unwrap();
+///
+/// assert!(graph.has_edge(&"app", &"lib"));
+/// assert_eq!(graph.fetch(&"lib"), Some(&2));
+///
+/// let tip = graph.graph().tips().next().unwrap();
+///
+/// assert_eq!(graph.key(tip), Some(&"lib"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct KeyedGraph<K: Hash + Eq, T> {
+    /// Graph holding the vertices and their edges
+    graph: Graph<T>,
+
+    /// Mapping between keys and vertex ids
+    ids: HashMap<K, VertexId>,
+
+    /// Mapping between vertex ids and keys
+    keys: HashMap<VertexId, K>,
+}
+
+impl<K: Hash + Eq + Clone, T> KeyedGraph<K, T> {
+    /// Creates a new keyed graph.
+    pub fn new() -> KeyedGraph<K, T> {
+        KeyedGraph {
+            graph: Graph::new(),
+            ids: HashMap::new(),
+            keys: HashMap::new(),
+        }
+    }
+
+    /// Returns the underlying graph.
+    pub fn graph(&self) -> &Graph<T> {
+        &self.graph
+    }
+
+    /// Adds a new vertex with the given key to the graph and returns
+    /// its id. If there is already a vertex with the given key, its
+    /// value is replaced and its id is returned.
+    pub fn add_vertex(&mut self, key: K, item: T) -> VertexId {
+        if let Some(id) = self.ids.get(&key) {
+            *self.graph.fetch_mut(id).unwrap() = item;
+            return *id;
+        }
+
+        let id = self.graph.add_vertex(item);
+
+        self.ids.insert(key.clone(), id);
+        self.keys.insert(id, key);
+
+        id
+    }
+
+    /// Returns the id of the vertex with the given key.
+    pub fn vertex(&self, key: &K) -> Option<&VertexId> {
+        self.ids.get(key)
+    }
+
+    /// Returns the key of the vertex with the given id.
+    pub fn key(&self, id: &VertexId) -> Option<&K> {
+        self.keys.get(id)
+    }
+
+    /// Returns true if there is a vertex with the given key.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.ids.contains_key(key)
+    }
+
+    /// Returns an iterator over the keys of the
+    /// vertices, in no particular order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.ids.keys()
+    }
+
+    /// Returns a reference to the value of the vertex with
+    /// the given key or `None` if there is no such vertex.
+    pub fn fetch(&self, key: &K) -> Option<&T> {
+        self.graph.fetch(self.ids.get(key)?)
+    }
+
+    /// Returns a mutable reference to the value of the vertex
+    /// with the given key or `None` if there is no such vertex.
+    pub fn fetch_mut(&mut self, key: &K) -> Option<&mut T> {
+        let id = self.ids.get(key)?;
+        self.graph.fetch_mut(id)
+    }
+
+    /// Returns a mutable reference to the value of the vertex with the
+    /// given key, adding the vertex with the value returned by the given
+    /// function if there is no such vertex.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::KeyedGraph;
+    ///
+    /// let mut graph: KeyedGraph<&str, Vec<&str>> = KeyedGraph::new();
+    ///
+    /// for (package, version) in vec![("a", "1.0"), ("b", "0.1"), ("a", "1.1")] {
+    ///     graph.fetch_or_add(package, Vec::new).push(version);
+    /// }
+    ///
+    /// assert_eq!(graph.fetch(&"a"), Some(&vec!["1.0", "1.1"]));
+    /// assert_eq!(graph.graph().vertex_count(), 2);
+    /// ```
+    pub fn fetch_or_add(&mut self, key: K, fun: impl FnOnce() -> T) -> &mut T {
+        let id = match self.ids.get(&key) {
+            Some(id) => *id,
+            None => self.add_vertex(key, fun()),
+        };
+
+        self.graph.fetch_mut(&id).unwrap()
+    }
+
+    /// Removes the vertex with the given key along with all of its edges.
+    pub fn remove(&mut self, key: &K) {
+        if let Some(id) = self.ids.remove(key) {
+            self.keys.remove(&id);
+            self.graph.remove(&id);
+        }
+    }
+
+    /// Adds an edge between the vertices with the given keys.
+    pub fn add_edge(&mut self, a: &K, b: &K) -> Result<(), GraphErr> {
+        let (a, b) = self.edge_ids(a, b)?;
+        self.graph.add_edge(&a, &b)
+    }
+
+    /// Adds an edge with the given weight between the vertices with the given keys.
+    pub fn add_edge_with_weight(&mut self, a: &K, b: &K, weight: f32) -> Result<(), GraphErr> {
+        let (a, b) = self.edge_ids(a, b)?;
+        self.graph.add_edge_with_weight(&a, &b, weight)
+    }
+
+    /// Removes the edge between the vertices with the given keys,
+    /// returning its weight or `None` if there is no such edge.
+    pub fn remove_edge(&mut self, a: &K, b: &K) -> Option<f32> {
+        let (a, b) = self.edge_ids(a, b).ok()?;
+        self.graph.remove_edge(&a, &b)
+    }
+
+    /// Returns true if there is an edge between the vertices with the given keys.
+    pub fn has_edge(&self, a: &K, b: &K) -> bool {
+        match self.edge_ids(a, b) {
+            Ok((a, b)) => self.graph.has_edge(&a, &b),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns an iterator over the keys of the vertices which the
+    /// vertex with the given key has outbound edges to.
+    pub fn out_neighbors<'a>(&'a self, key: &K) -> impl Iterator<Item = &'a K> {
+        self.ids
+            .get(key)
+            .into_iter()
+            .flat_map(move |id| self.graph.out_neighbors(id))
+            .map(move |id| &self.keys[id])
+    }
+
+    /// Returns an iterator over the keys of the vertices which have
+    /// outbound edges to the vertex with the given key.
+    pub fn in_neighbors<'a>(&'a self, key: &K) -> impl Iterator<Item = &'a K> {
+        self.ids
+            .get(key)
+            .into_iter()
+            .flat_map(move |id| self.graph.in_neighbors(id))
+            .map(move |id| &self.keys[id])
+    }
+
+    fn edge_ids(&self, a: &K, b: &K) -> Result<(VertexId, VertexId), GraphErr> {
+        match (self.ids.get(a), self.ids.get(b)) {
+            (Some(a), Some(b)) => Ok((*a, *b)),
+            _ => Err(GraphErr::NoSuchVertex),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, T> Default for KeyedGraph<K, T> {
+    fn default() -> KeyedGraph<K, T> {
+        KeyedGraph::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_map_to_vertices() {
+        let mut graph: KeyedGraph<String, usize> = KeyedGraph::new();
+
+        let v1 = graph.add_vertex("a".to_string(), 1);
+        let v2 = graph.add_vertex("b".to_string(), 2);
+
+        // Adding an existing key replaces its value
+        assert_eq!(graph.add_vertex("a".to_string(), 3), v1);
+        assert_eq!(graph.fetch(&"a".to_string()), Some(&3));
+        assert_eq!(graph.graph().vertex_count(), 2);
+
+        graph.add_edge(&"a".to_string(), &"b".to_string()).unwrap();
+
+        assert_eq!(graph.vertex(&"b".to_string()), Some(&v2));
+        assert_eq!(graph.key(&v2), Some(&"b".to_string()));
+        assert_eq!(
+            graph.out_neighbors(&"a".to_string()).collect::<Vec<_>>(),
+            vec!["b"]
+        );
+        assert_eq!(
+            graph.in_neighbors(&"b".to_string()).collect::<Vec<_>>(),
+            vec!["a"]
+        );
+        assert_eq!(
+            graph.add_edge(&"a".to_string(), &"c".to_string()),
+            Err(GraphErr::NoSuchVertex)
+        );
+    }
+
+    #[test]
+    fn test_remove_vertex_removes_key() {
+        let mut graph: KeyedGraph<u64, usize> = KeyedGraph::new();
+
+        let v1 = graph.add_vertex(10, 1);
+        graph.add_vertex(20, 2);
+        graph.add_edge(&10, &20).unwrap();
+
+        graph.remove(&10);
+
+        assert!(!graph.contains_key(&10));
+        assert_eq!(graph.key(&v1), None);
+        assert_eq!(graph.keys().collect::<Vec<_>>(), vec![&20]);
+        assert_eq!(graph.graph().edge_count(), 0);
+        assert!(!graph.has_edge(&10, &20));
+    }
+}
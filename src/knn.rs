@@ -0,0 +1,282 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::{Graph, GraphErr};
+use crate::vertex_id::VertexId;
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "no_std")]
+use core::cmp::Ordering;
+#[cfg(not(feature = "no_std"))]
+use std::cmp::Ordering;
+
+/// Nearest neighbors of an item along with their similarities.
+type Neighbors = Vec<(usize, f32)>;
+
+#[derive(Clone, Debug)]
+/// Builder of k-nearest neighbor graphs.
+///
+/// Each item becomes a vertex of the graph, with edges going to the
+/// `k` items that are most similar to it. The similarities are used as
+/// the weights of the edges, so they must be between `-1.0` and `1.0`.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::KnnBuilder;
+///
+/// let items = vec![0.0_f32, 0.1, 0.5, 0.55];
+///
+/// let (graph, ids) = KnnBuilder::new(items, 1)
+///     .build(|a, b| 1.0 - (a - b).abs())
+///     .unwrap();
+///
+/// assert_eq!(graph.edge_count(), 4);
+/// assert!(graph.has_edge(&ids[0], &ids[1]));
+/// assert!(graph.has_edge(&ids[2], &ids[3]));
+/// assert!(graph.has_edge(&ids[3], &ids[2]));
+/// ```
+pub struct KnnBuilder<T> {
+    /// Items which become the vertices of the graph.
+    items: Vec<T>,
+    /// Number of neighbors of each item.
+    k: usize,
+    #[cfg(not(feature = "no_std"))]
+    /// Number of threads computing the similarities.
+    threads: usize,
+}
+
+impl<T> KnnBuilder<T> {
+    /// Creates a builder connecting each of the
+    /// given items to its `k` most similar items.
+    pub fn new(items: Vec<T>, k: usize) -> KnnBuilder<T> {
+        KnnBuilder {
+            items,
+            k,
+            #[cfg(not(feature = "no_std"))]
+            threads: 1,
+        }
+    }
+
+    #[cfg(not(feature = "no_std"))]
+    /// Sets the number of threads the similarities
+    /// are computed on. Defaults to `1`.
+    ///
+    /// The similarities are computed on scoped `std` threads,
+    /// so this method is not available with the `no_std` feature.
+    pub fn threads(mut self, threads: usize) -> KnnBuilder<T> {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Builds the graph, computing the similarities of the items with the
+    /// given function. Returns the graph along with the ids of the items,
+    /// in the order they were given.
+    ///
+    /// Fails with `GraphErr::InvalidWeight` if any of the similarities
+    /// is not between `-1.0` and `1.0`.
+    pub fn build(
+        self,
+        similarity: impl Fn(&T, &T) -> f32 + Sync,
+    ) -> Result<(Graph<T>, Vec<VertexId>), GraphErr>
+    where
+        T: Sync,
+    {
+        let items = &self.items;
+        let neighbors = self.neighbors(|i, j| similarity(&items[i], &items[j]))?;
+
+        Ok(self.into_graph(neighbors))
+    }
+
+    /// Builds the graph from the given matrix of precomputed similarities,
+    /// where `matrix[i][j]` is the similarity of the `i`th item to the `j`th.
+    ///
+    /// ## Panics
+    /// Panics if the matrix does not have a row and a column for each item.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::KnnBuilder;
+    ///
+    /// let matrix = vec![
+    ///     vec![1.0, 0.2, 0.7],
+    ///     vec![0.2, 1.0, 0.4],
+    ///     vec![0.7, 0.4, 1.0],
+    /// ];
+    ///
+    /// let (graph, ids) = KnnBuilder::new(vec!['a', 'b', 'c'], 1)
+    ///     .build_from_matrix(&matrix)
+    ///     .unwrap();
+    ///
+    /// assert!(graph.has_edge(&ids[0], &ids[2]));
+    /// assert!(graph.has_edge(&ids[1], &ids[2]));
+    /// assert!(graph.has_edge(&ids[2], &ids[0]));
+    /// assert_eq!(graph.weight(&ids[1], &ids[2]), Some(0.4));
+    /// ```
+    pub fn build_from_matrix(
+        self,
+        matrix: &[Vec<f32>],
+    ) -> Result<(Graph<T>, Vec<VertexId>), GraphErr> {
+        let n = self.items.len();
+
+        assert!(
+            matrix.len() == n && matrix.iter().all(|row| row.len() == n),
+            "similarity matrix must be {} by {}",
+            n,
+            n
+        );
+
+        let neighbors = self.neighbors(|i, j| matrix[i][j])?;
+
+        Ok(self.into_graph(neighbors))
+    }
+
+    /// Computes the nearest neighbors of every item.
+    fn neighbors(
+        &self,
+        similarity: impl Fn(usize, usize) -> f32 + Sync,
+    ) -> Result<Vec<Neighbors>, GraphErr> {
+        let (n, k) = (self.items.len(), self.k);
+        let row = |i| nearest(n, k, i, &similarity);
+
+        #[cfg(not(feature = "no_std"))]
+        {
+            if self.threads > 1 && n > 1 {
+                let chunk = n.div_ceil(self.threads);
+
+                return std::thread::scope(|scope| {
+                    let handles: Vec<_> = (0..n)
+                        .step_by(chunk)
+                        .map(|start| {
+                            let row = &row;
+                            scope.spawn(move || {
+                                (start..n.min(start + chunk))
+                                    .map(row)
+                                    .collect::<Result<Vec<_>, _>>()
+                            })
+                        })
+                        .collect();
+
+                    let mut neighbors = Vec::with_capacity(n);
+
+                    for handle in handles {
+                        neighbors.extend(handle.join().unwrap()?);
+                    }
+
+                    Ok(neighbors)
+                });
+            }
+        }
+
+        (0..n).map(row).collect()
+    }
+
+    fn into_graph(self, neighbors: Vec<Neighbors>) -> (Graph<T>, Vec<VertexId>) {
+        let mut graph = Graph::with_capacity(self.items.len());
+        let ids: Vec<VertexId> = self
+            .items
+            .into_iter()
+            .map(|item| graph.add_vertex(item))
+            .collect();
+
+        for (i, row) in neighbors.into_iter().enumerate() {
+            for (j, weight) in row {
+                graph
+                    .add_edge_with_weight(&ids[i], &ids[j], weight)
+                    .unwrap();
+            }
+        }
+
+        (graph, ids)
+    }
+}
+
+/// Returns the `k` items which are the most similar to the
+/// `i`th one, breaking ties in favor of the earlier items.
+fn nearest(
+    n: usize,
+    k: usize,
+    i: usize,
+    similarity: impl Fn(usize, usize) -> f32,
+) -> Result<Neighbors, GraphErr> {
+    let mut row = Vec::with_capacity(n.saturating_sub(1));
+
+    for j in (0..n).filter(|j| *j != i) {
+        let weight = similarity(i, j);
+
+        if !(-1.0..=1.0).contains(&weight) {
+            return Err(GraphErr::InvalidWeight);
+        }
+
+        row.push((j, weight));
+    }
+
+    row.sort_by(|(a, x), (b, y)| {
+        y.partial_cmp(x)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.cmp(b))
+    });
+    row.truncate(k);
+
+    Ok(row)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn similarity(a: &usize, b: &usize) -> f32 {
+        1.0 / (1.0 + (*a as f32 - *b as f32).abs())
+    }
+
+    #[test]
+    fn test_threads_build_the_same_graph() {
+        let items: Vec<usize> = (0..50).map(|i| (i * 37) % 101).collect();
+
+        let (graph1, ids1) = KnnBuilder::new(items.clone(), 3).build(similarity).unwrap();
+        let (graph2, ids2) = KnnBuilder::new(items, 3)
+            .threads(4)
+            .build(similarity)
+            .unwrap();
+
+        assert_eq!(graph1.edge_count(), 150);
+        assert_eq!(graph2.edge_count(), 150);
+
+        for i in 0..50 {
+            let out1: Vec<usize> = graph1
+                .out_neighbors(&ids1[i])
+                .map(|v| *graph1.fetch(v).unwrap())
+                .collect();
+            let out2: Vec<usize> = graph2
+                .out_neighbors(&ids2[i])
+                .map(|v| *graph2.fetch(v).unwrap())
+                .collect();
+
+            assert_eq!(out1, out2);
+        }
+    }
+
+    #[test]
+    fn test_invalid_similarity() {
+        let result = KnnBuilder::new(vec![1, 2, 3], 1)
+            .threads(2)
+            .build(|_, _| 1.5);
+
+        assert_eq!(result.unwrap_err(), GraphErr::InvalidWeight);
+
+        let result = KnnBuilder::new(vec![1, 2], 1).build(|_, _| f32::NAN);
+
+        assert_eq!(result.unwrap_err(), GraphErr::InvalidWeight);
+    }
+
+    #[test]
+    fn test_k_larger_than_items() {
+        let (graph, _) = KnnBuilder::new(vec![1, 2, 3], 10)
+            .build(similarity)
+            .unwrap();
+
+        assert_eq!(graph.edge_count(), 6);
+    }
+}
@@ -0,0 +1,433 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::{Graph, GraphErr};
+use crate::vertex_id::VertexId;
+
+use hashbrown::HashMap;
+
+use std::hash::BuildHasher;
+
+/// Maximum distance a vertex is moved by in the first iteration
+/// of the force-directed layout, which decreases linearly to 0.
+const START_TEMPERATURE: f32 = 0.1;
+
+/// Distance below which two vertices are treated as being that far
+/// apart, so coincident vertices still push each other away.
+const MIN_DISTANCE: f32 = 0.01;
+
+/// Number of sweeps over the layers made to reduce edge crossings.
+const SWEEPS: usize = 8;
+
+/// Angle between consecutive vertices on the starting spiral
+/// of the force-directed layout, which spreads them evenly.
+const GOLDEN_ANGLE: f32 = 2.399_963;
+
+type Point = (f32, f32);
+
+#[derive(Clone, Debug, PartialEq)]
+/// Layered drawing of a directed acyclic graph, as
+/// returned by `Graph::layout_layered()`.
+///
+/// Every edge points from a layer to a later one and the vertices
+/// of each layer are ordered so that few edges cross each other.
+/// The `y` coordinate of each vertex is the index of its layer, and
+/// its `x` coordinate is its position in the layer, with each layer
+/// centered on `x = 0`.
+pub struct LayeredLayout {
+    /// Vertices of each layer, in their order on the layer
+    layers: Vec<Vec<VertexId>>,
+
+    /// Mapping between vertices and their coordinates
+    positions: HashMap<VertexId, Point>,
+
+    /// Number of edge crossings of the layout
+    crossings: usize,
+}
+
+impl LayeredLayout {
+    /// Returns the layers of the layout, each containing
+    /// its vertices in their order on the layer.
+    pub fn layers(&self) -> &[Vec<VertexId>] {
+        &self.layers
+    }
+
+    /// Returns the index of the layer of the given vertex
+    /// or `None` if the vertex is not in the layout.
+    pub fn layer(&self, id: &VertexId) -> Option<usize> {
+        self.positions.get(id).map(|(_, y)| *y as usize)
+    }
+
+    /// Returns the coordinates of the given vertex
+    /// or `None` if the vertex is not in the layout.
+    pub fn position(&self, id: &VertexId) -> Option<(f32, f32)> {
+        self.positions.get(id).cloned()
+    }
+
+    /// Returns the coordinates of all of the vertices of the layout.
+    pub fn positions(&self) -> &HashMap<VertexId, (f32, f32)> {
+        &self.positions
+    }
+
+    /// Returns the number of pairs of edges crossing each other. Edges
+    /// spanning several layers are counted on each pair of layers they
+    /// pass through.
+    pub fn crossings(&self) -> usize {
+        self.crossings
+    }
+}
+
+/// Computes the positions of the vertices of the given graph in
+/// the unit square with the Fruchterman-Reingold algorithm. The
+/// vertices start on a spiral, ordered by their ids, so the same
+/// graph is always laid out the same way. Unlike a circle, the
+/// spiral has no symmetries which the forces could get stuck in.
+///
+/// Each iteration takes `O(|V|^2 + |E|)` time, as every pair of
+/// vertices repels each other and every edge pulls its vertices
+/// together, regardless of its direction.
+pub(crate) fn force_directed<T, S: BuildHasher>(
+    graph: &Graph<T, S>,
+    iterations: usize,
+) -> HashMap<VertexId, Point> {
+    let mut ids: Vec<VertexId> = graph.vertices().cloned().collect();
+    ids.sort_unstable();
+
+    let n = ids.len();
+    let indices: HashMap<VertexId, usize> =
+        ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+    let edges: Vec<(usize, usize)> = graph
+        .edges()
+        .map(|(a, b)| (indices[a], indices[b]))
+        .filter(|(a, b)| a != b)
+        .collect();
+
+    let mut positions: Vec<Point> = (0..n)
+        .map(|i| {
+            let angle = GOLDEN_ANGLE * i as f32;
+            let radius = 0.4 * ((i as f32 + 0.5) / n as f32).sqrt();
+
+            (0.5 + radius * angle.cos(), 0.5 + radius * angle.sin())
+        })
+        .collect();
+
+    if n == 1 {
+        positions[0] = (0.5, 0.5);
+    }
+
+    // Ideal distance between vertices, which spreads
+    // them evenly over the unit square
+    let k = (1.0 / n.max(1) as f32).sqrt();
+    let mut displacements: Vec<Point> = vec![(0.0, 0.0); n];
+
+    for i in 0..iterations {
+        let temperature = START_TEMPERATURE * (1.0 - i as f32 / iterations as f32);
+
+        for d in displacements.iter_mut() {
+            *d = (0.0, 0.0);
+        }
+
+        for a in 0..n {
+            for b in (a + 1)..n {
+                let (dx, dy, d) = delta(positions[a], positions[b]);
+                let force = k * k / d / d;
+
+                displacements[a].0 += dx * force;
+                displacements[a].1 += dy * force;
+                displacements[b].0 -= dx * force;
+                displacements[b].1 -= dy * force;
+            }
+        }
+
+        for (a, b) in edges.iter() {
+            let (dx, dy, d) = delta(positions[*a], positions[*b]);
+            let force = d / k;
+
+            displacements[*a].0 -= dx * force;
+            displacements[*a].1 -= dy * force;
+            displacements[*b].0 += dx * force;
+            displacements[*b].1 += dy * force;
+        }
+
+        for (p, d) in positions.iter_mut().zip(displacements.iter()) {
+            let length = (d.0 * d.0 + d.1 * d.1).sqrt();
+
+            if length > 0.0 {
+                let step = length.min(temperature) / length;
+
+                p.0 = (p.0 + d.0 * step).clamp(0.0, 1.0);
+                p.1 = (p.1 + d.1 * step).clamp(0.0, 1.0);
+            }
+        }
+    }
+
+    ids.into_iter().zip(positions).collect()
+}
+
+/// Computes the layered layout of the given graph with the Sugiyama
+/// method. Each vertex is placed on the layer after the last of its
+/// inbound neighbors, and edges spanning several layers are split by
+/// dummy vertices, one on each layer they pass through. The vertices
+/// of each layer are then ordered by the barycenters of their neighbors
+/// on the adjacent layer, sweeping the layers downwards and upwards and
+/// keeping the ordering with the fewest crossings.
+pub(crate) fn layered<T, S: BuildHasher>(graph: &Graph<T, S>) -> Result<LayeredLayout, GraphErr> {
+    if graph.is_cyclic() {
+        return Err(GraphErr::CycleError);
+    }
+
+    // Vertices are assigned to layers in topological order
+    let order: Vec<VertexId> = graph.topo().cloned().collect();
+    let mut ranks: HashMap<VertexId, usize> = HashMap::with_capacity(order.len());
+
+    for v in order.iter() {
+        let rank = graph
+            .in_neighbors(v)
+            .map(|n| ranks[n] + 1)
+            .max()
+            .unwrap_or(0);
+
+        ranks.insert(*v, rank);
+    }
+
+    let mut ids = order;
+    ids.sort_unstable();
+
+    let layer_count = ranks.values().max().map_or(0, |max| max + 1);
+    let mut layers: Vec<Vec<usize>> = vec![Vec::new(); layer_count];
+    let mut node_layers: Vec<usize> = Vec::with_capacity(ids.len());
+
+    for id in ids.iter() {
+        layers[ranks[id]].push(node_layers.len());
+        node_layers.push(ranks[id]);
+    }
+
+    let indices: HashMap<VertexId, usize> =
+        ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+    // Outbound and inbound neighbors of each node on the adjacent layers
+    let mut down: Vec<Vec<usize>> = vec![Vec::new(); ids.len()];
+    let mut up: Vec<Vec<usize>> = vec![Vec::new(); ids.len()];
+
+    for (b, a) in graph.edges() {
+        let (a, b) = (indices[a], indices[b]);
+        let mut prev = a;
+
+        let (first, last) = (node_layers[a] + 1, node_layers[b]);
+
+        for (layer, nodes) in layers.iter_mut().enumerate().take(last).skip(first) {
+            let dummy = node_layers.len();
+
+            node_layers.push(layer);
+            nodes.push(dummy);
+            down.push(Vec::new());
+            up.push(Vec::new());
+
+            down[prev].push(dummy);
+            up[dummy].push(prev);
+            prev = dummy;
+        }
+
+        down[prev].push(b);
+        up[b].push(prev);
+    }
+
+    let mut best = layers.clone();
+    let mut best_crossings = crossings(&layers, &down, node_layers.len());
+
+    for sweep in 0..SWEEPS {
+        if best_crossings == 0 {
+            break;
+        }
+
+        if sweep % 2 == 0 {
+            for i in 1..layers.len() {
+                reorder(&mut layers, i, &up, node_layers.len());
+            }
+        } else {
+            for i in (0..layers.len().saturating_sub(1)).rev() {
+                reorder(&mut layers, i, &down, node_layers.len());
+            }
+        }
+
+        let count = crossings(&layers, &down, node_layers.len());
+
+        if count < best_crossings {
+            best = layers.clone();
+            best_crossings = count;
+        }
+    }
+
+    let mut positions = HashMap::with_capacity(ids.len());
+    let layers = best
+        .into_iter()
+        .enumerate()
+        .map(|(y, layer)| {
+            let layer: Vec<VertexId> = layer
+                .into_iter()
+                .filter(|node| *node < ids.len())
+                .map(|node| ids[node])
+                .collect();
+            let offset = (layer.len() as f32 - 1.0) / 2.0;
+
+            for (x, id) in layer.iter().enumerate() {
+                positions.insert(*id, (x as f32 - offset, y as f32));
+            }
+
+            layer
+        })
+        .collect();
+
+    Ok(LayeredLayout {
+        layers,
+        positions,
+        crossings: best_crossings,
+    })
+}
+
+/// Returns the position of each node on its layer.
+fn positions(layers: &[Vec<usize>], node_count: usize) -> Vec<usize> {
+    let mut positions = vec![0; node_count];
+
+    for layer in layers.iter() {
+        for (i, node) in layer.iter().enumerate() {
+            positions[*node] = i;
+        }
+    }
+
+    positions
+}
+
+/// Orders the nodes of the given layer by the barycenters of the
+/// positions of their given neighbors, which are on an adjacent layer.
+/// Nodes without such neighbors keep their current positions.
+fn reorder(layers: &mut [Vec<usize>], layer: usize, neighbors: &[Vec<usize>], node_count: usize) {
+    let positions = positions(layers, node_count);
+
+    let mut barycenters: Vec<(f32, usize)> = layers[layer]
+        .iter()
+        .map(|node| {
+            let adjacent = &neighbors[*node];
+
+            let barycenter = if adjacent.is_empty() {
+                positions[*node] as f32
+            } else {
+                adjacent.iter().map(|n| positions[*n] as f32).sum::<f32>() / adjacent.len() as f32
+            };
+
+            (barycenter, *node)
+        })
+        .collect();
+
+    barycenters.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    layers[layer] = barycenters.into_iter().map(|(_, node)| node).collect();
+}
+
+/// Returns the number of pairs of edges crossing each other
+/// between all pairs of adjacent layers.
+fn crossings(layers: &[Vec<usize>], down: &[Vec<usize>], node_count: usize) -> usize {
+    let positions = positions(layers, node_count);
+    let mut count = 0;
+
+    for layer in layers.iter() {
+        let edges: Vec<(usize, usize)> = layer
+            .iter()
+            .flat_map(|a| down[*a].iter().map(move |b| (*a, *b)))
+            .map(|(a, b)| (positions[a], positions[b]))
+            .collect();
+
+        for (i, (a1, b1)) in edges.iter().enumerate() {
+            for (a2, b2) in edges[(i + 1)..].iter() {
+                if (a1 < a2 && b1 > b2) || (a1 > a2 && b1 < b2) {
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    count
+}
+
+/// Returns the offset from `b` to `a` along with its length.
+fn delta(a: Point, b: Point) -> (f32, f32, f32) {
+    let (dx, dy) = (a.0 - b.0, a.1 - b.1);
+    let d = (dx * dx + dy * dy).sqrt();
+
+    if d < MIN_DISTANCE {
+        (MIN_DISTANCE, 0.0, MIN_DISTANCE)
+    } else {
+        (dx, dy, d)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn distance(a: Point, b: Point) -> f32 {
+        delta(a, b).2
+    }
+
+    #[test]
+    fn test_force_directed_pulls_neighbors_together() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+        let v4 = graph.add_vertex(4);
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v3, &v4).unwrap();
+
+        let positions = force_directed(&graph, 100);
+
+        assert_eq!(positions.len(), 4);
+        assert_eq!(positions, force_directed(&graph, 100));
+
+        for (x, y) in positions.values() {
+            assert!(*x >= 0.0 && *x <= 1.0);
+            assert!(*y >= 0.0 && *y <= 1.0);
+        }
+
+        let near = distance(positions[&v1], positions[&v2]);
+
+        assert!(near < distance(positions[&v1], positions[&v3]));
+        assert!(near < distance(positions[&v1], positions[&v4]));
+    }
+
+    #[test]
+    fn test_layered_removes_crossings() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let a = graph.add_vertex(1);
+        let b = graph.add_vertex(2);
+        let c = graph.add_vertex(3);
+        let d = graph.add_vertex(4);
+        let e = graph.add_vertex(5);
+
+        // Drawn in the order of the ids, either a -> c and b -> d
+        // or a -> d and b -> c cross each other
+        graph.add_edge(&a, &c).unwrap();
+        graph.add_edge(&b, &d).unwrap();
+        graph.add_edge(&a, &e).unwrap();
+        graph.add_edge(&c, &e).unwrap();
+
+        let layout = layered(&graph).unwrap();
+
+        assert_eq!(layout.layers().len(), 3);
+        assert_eq!(layout.layer(&a), Some(0));
+        assert_eq!(layout.layer(&b), Some(0));
+        assert_eq!(layout.layer(&c), Some(1));
+        assert_eq!(layout.layer(&d), Some(1));
+        assert_eq!(layout.layer(&e), Some(2));
+        assert_eq!(layout.crossings(), 0);
+        assert_eq!(layout.position(&e), Some((0.0, 2.0)));
+
+        let x = |id| layout.position(id).unwrap().0;
+
+        assert_eq!(x(&a) < x(&b), x(&c) < x(&d));
+
+        graph.add_edge(&e, &a).unwrap();
+        assert_eq!(layered(&graph), Err(GraphErr::CycleError));
+    }
+}
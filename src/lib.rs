@@ -36,14 +36,47 @@
 //! assert_eq!(graph.edge_count(), 0);
 //! ```
 
-#![allow(mutable_transmutes)]
-
+mod attributes;
+mod builder;
+mod centrality;
+mod communities;
+#[cfg(not(feature = "no_std"))]
+mod concurrent;
+mod cow;
+mod diff;
+mod disjoint_set;
 mod edge;
+mod edit_distance;
+mod entry;
 #[macro_use]
 mod macros;
+pub mod flow;
+mod frontier;
+mod generators;
 mod graph;
+mod grouping;
+mod history;
+mod id_provider;
+mod isomorphism;
 pub mod iterators;
+mod keyed;
+mod knn;
+#[cfg(not(feature = "no_std"))]
+pub mod layout;
+mod link_prediction;
+mod parse;
+mod percolation;
+mod priority;
+mod reachability;
+mod scheduler;
+mod simulation;
+mod spanning_tree;
+mod traits;
+mod typed;
 mod vertex_id;
+mod vertex_set;
+mod walks;
+mod weight_mut;
 
 // use global variables to create VertexId::random()
 use core::sync::atomic::AtomicUsize;
@@ -51,8 +84,34 @@ use core::sync::atomic::AtomicUsize;
 #[cfg(feature = "dot")]
 pub mod dot;
 
+#[cfg(feature = "datasets")]
+pub mod datasets;
+
+#[cfg(feature = "persistent")]
+pub mod persistent;
+
+pub use attributes::*;
+pub use builder::*;
+#[cfg(not(feature = "no_std"))]
+pub use concurrent::*;
+pub use diff::*;
+pub use disjoint_set::*;
+pub use entry::*;
+pub use frontier::*;
 pub use graph::*;
+pub use history::*;
+pub use id_provider::*;
+pub use isomorphism::*;
+pub use keyed::*;
+pub use knn::*;
+pub use reachability::*;
+pub use scheduler::*;
+pub use simulation::*;
+pub use traits::*;
+pub use typed::*;
 pub use vertex_id::*;
+pub use vertex_set::*;
+pub use weight_mut::*;
 
 static SEED: AtomicUsize = AtomicUsize::new(0);
 
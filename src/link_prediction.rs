@@ -0,0 +1,95 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::Graph;
+use crate::iterators::VertexIter;
+use crate::vertex_id::VertexId;
+
+use hashbrown::HashSet;
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::boxed::Box;
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+pub(crate) fn common_neighbors<'a, T, S: BuildHasher>(
+    graph: &'a Graph<T, S>,
+    a: &VertexId,
+    b: &VertexId,
+) -> VertexIter<'a> {
+    let others: HashSet<&VertexId> = graph.neighbors(b).collect();
+    let common = graph.neighbors(a).filter(move |v| others.contains(v));
+
+    VertexIter(Box::new(common))
+}
+
+pub(crate) fn jaccard_similarity<T, S: BuildHasher>(
+    graph: &Graph<T, S>,
+    a: &VertexId,
+    b: &VertexId,
+) -> Option<f32> {
+    graph.fetch(a)?;
+    graph.fetch(b)?;
+
+    let left: HashSet<&VertexId> = graph.neighbors(a).collect();
+    let right: HashSet<&VertexId> = graph.neighbors(b).collect();
+    let union = left.union(&right).count();
+
+    if union == 0 {
+        return Some(0.0);
+    }
+
+    Some(left.intersection(&right).count() as f32 / union as f32)
+}
+
+#[cfg(not(feature = "no_std"))]
+pub(crate) fn adamic_adar<T, S: BuildHasher>(
+    graph: &Graph<T, S>,
+    a: &VertexId,
+    b: &VertexId,
+) -> Option<f32> {
+    graph.fetch(a)?;
+    graph.fetch(b)?;
+
+    let score = common_neighbors(graph, a, b)
+        .map(|v| graph.neighbors(v).count())
+        // A vertex with a single neighbor is only
+        // a common neighbor of a vertex and itself
+        .filter(|degree| *degree > 1)
+        .map(|degree| 1.0 / (degree as f32).ln())
+        .sum();
+
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scores_ignore_directions() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+        let v4 = graph.add_vertex(4);
+
+        graph.add_edge(&v1, &v3).unwrap();
+        graph.add_edge(&v3, &v2).unwrap();
+        graph.add_edge(&v4, &v1).unwrap();
+
+        assert_eq!(
+            common_neighbors(&graph, &v1, &v2).collect::<Vec<_>>(),
+            vec![&v3]
+        );
+        assert_eq!(jaccard_similarity(&graph, &v1, &v2), Some(0.5));
+        assert_eq!(jaccard_similarity(&graph, &v1, &VertexId::random()), None);
+        assert_eq!(adamic_adar(&graph, &v1, &v2), Some(1.0 / 2f32.ln()));
+        assert_eq!(adamic_adar(&graph, &v1, &v4), Some(0.0));
+    }
+}
@@ -30,3 +30,56 @@ macro_rules! count {
     ($fst:expr, $snd:expr) => (2);
     ($fst:expr, $snd:expr $(, $v:expr)*) => (1 + count!($snd $(, $v)*));
 }
+
+/// Asserts that two graphs are structurally equal, matching vertices
+/// by their ids. On failure, the panic message lists the vertices
+/// and edges which differ between the graphs.
+///
+/// ## Example
+/// ```rust
+/// #[macro_use]
+/// extern crate graphlib;
+///
+/// use graphlib::Graph;
+///
+/// # fn main() {
+/// let mut graph: Graph<usize> = Graph::new();
+///
+/// let v1 = graph.add_vertex(1);
+/// let v2 = graph.add_vertex(2);
+///
+/// graph.add_edge(&v1, &v2).unwrap();
+///
+/// assert_graph_eq!(graph, graph.clone());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! assert_graph_eq {
+    ($left:expr, $right:expr $(,)?) => ({
+        match (&$left, &$right) {
+            (left, right) => {
+                let diff = left.diff(right);
+
+                if !diff.is_empty() {
+                    panic!("assertion failed: `(left == right)`\n{}", diff);
+                }
+            }
+        }
+    });
+
+    ($left:expr, $right:expr, $($arg:tt)+) => ({
+        match (&$left, &$right) {
+            (left, right) => {
+                let diff = left.diff(right);
+
+                if !diff.is_empty() {
+                    panic!(
+                        "assertion failed: `(left == right)`: {}\n{}",
+                        format_args!($($arg)+),
+                        diff
+                    );
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,174 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::{Graph, GraphErr};
+use crate::vertex_id::VertexId;
+
+use hashbrown::HashMap;
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::string::String;
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "no_std")]
+use core::iter::Peekable;
+#[cfg(feature = "no_std")]
+use core::str::Chars;
+#[cfg(not(feature = "no_std"))]
+use std::iter::Peekable;
+#[cfg(not(feature = "no_std"))]
+use std::str::Chars;
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Name(String),
+    Arrow,
+    Weight(f32),
+    End,
+}
+
+/// Splits the input into tokens, treating semicolons and
+/// newlines as statement ends and `#` as the start of a comment.
+fn tokenize(input: &str) -> Result<Vec<Token>, GraphErr> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            ';' | '\n' => tokens.push(Token::End),
+            '#' => while chars.next_if(|c| *c != '\n').is_some() {},
+            '-' if chars.peek() == Some(&'>') => {
+                chars.next();
+                tokens.push(Token::Arrow);
+            }
+            '[' => {
+                let weight = take_until(&mut chars, ']')?;
+                let weight = weight.trim().parse().map_err(|_| GraphErr::CouldNotParse)?;
+
+                tokens.push(Token::Weight(weight));
+            }
+            '"' => tokens.push(Token::Name(take_until(&mut chars, '"')?)),
+            c if c.is_whitespace() => {}
+            c if is_name_char(c) => {
+                let mut name = String::new();
+                name.push(c);
+
+                while let Some(c) = chars.next_if(|c| is_name_char(*c)) {
+                    name.push(c);
+                }
+
+                tokens.push(Token::Name(name));
+            }
+            _ => return Err(GraphErr::CouldNotParse),
+        }
+    }
+
+    tokens.push(Token::End);
+    Ok(tokens)
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.'
+}
+
+/// Consumes the characters up to the given delimiter,
+/// failing if the input ends before it.
+fn take_until(chars: &mut Peekable<Chars<'_>>, delimiter: char) -> Result<String, GraphErr> {
+    let mut result = String::new();
+
+    loop {
+        match chars.next() {
+            Some(c) if c == delimiter => return Ok(result),
+            Some(c) => result.push(c),
+            None => return Err(GraphErr::CouldNotParse),
+        }
+    }
+}
+
+pub(crate) fn parse(input: &str) -> Result<(Graph<String>, HashMap<String, VertexId>), GraphErr> {
+    let mut graph = Graph::new();
+    let mut ids: HashMap<String, VertexId> = HashMap::new();
+
+    // Vertex the next edge of the current chain starts from
+    let mut previous: Option<VertexId> = None;
+    let mut arrow = false;
+
+    let mut tokens = tokenize(input)?.into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Name(name) => {
+                if previous.is_some() && !arrow {
+                    return Err(GraphErr::CouldNotParse);
+                }
+
+                let id = match ids.get(&name) {
+                    Some(id) => *id,
+                    None => {
+                        let id = graph.add_vertex(name.clone());
+                        ids.insert(name, id);
+                        id
+                    }
+                };
+
+                if let Some(a) = previous {
+                    let weight = match tokens.peek() {
+                        Some(Token::Weight(weight)) => {
+                            let weight = *weight;
+                            tokens.next();
+                            weight
+                        }
+                        _ => 0.0,
+                    };
+
+                    graph.add_edge_with_weight(&a, &id, weight)?;
+                }
+
+                previous = Some(id);
+                arrow = false;
+            }
+            Token::Arrow if previous.is_some() && !arrow => arrow = true,
+            Token::End if !arrow => previous = None,
+            _ => return Err(GraphErr::CouldNotParse),
+        }
+    }
+
+    Ok((graph, ids))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_statements() {
+        let input = r#"
+            # Comments and blank lines are ignored
+            a -> b [0.2] -> c
+            "long name" -> a; lonely
+        "#;
+
+        let (graph, ids) = parse(input).unwrap();
+
+        assert_eq!(graph.vertex_count(), 5);
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(graph.weight(&ids["a"], &ids["b"]), Some(0.2));
+        assert_eq!(graph.weight(&ids["b"], &ids["c"]), Some(0.0));
+        assert!(graph.has_edge(&ids["long name"], &ids["a"]));
+        assert_eq!(graph.fetch(&ids["lonely"]).unwrap(), "lonely");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse("a -> ").unwrap_err(), GraphErr::CouldNotParse);
+        assert_eq!(parse("-> a").unwrap_err(), GraphErr::CouldNotParse);
+        assert_eq!(parse("a b").unwrap_err(), GraphErr::CouldNotParse);
+        assert_eq!(parse("a -> b [x]").unwrap_err(), GraphErr::CouldNotParse);
+        assert_eq!(parse("a -> b [0.5").unwrap_err(), GraphErr::CouldNotParse);
+        assert_eq!(parse("a [0.5]").unwrap_err(), GraphErr::CouldNotParse);
+        assert_eq!(parse("a -> b [2]").unwrap_err(), GraphErr::InvalidWeight);
+        assert_eq!(parse("").unwrap().0.vertex_count(), 0);
+    }
+}
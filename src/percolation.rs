@@ -0,0 +1,104 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::disjoint_set::DisjointSet;
+use crate::graph::{Graph, GraphErr};
+use crate::vertex_id::VertexId;
+use crate::vertex_set::VertexSet;
+
+use hashbrown::{HashMap, HashSet};
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+/// Places every vertex of the graph in a set of its own.
+fn singletons<T, S: BuildHasher>(graph: &Graph<T, S>) -> DisjointSet {
+    let mut sets = DisjointSet::with_capacity(graph.vertex_count());
+
+    for v in graph.vertices() {
+        sets.make_set(*v);
+    }
+
+    sets
+}
+
+/// Returns the edges of the graph along with their weights.
+fn weighted_edges<T, S: BuildHasher>(graph: &Graph<T, S>) -> Vec<(VertexId, VertexId, f32)> {
+    graph
+        .edges_with_weights()
+        .map(|(a, b, w)| (*a, *b, w))
+        .collect()
+}
+
+/// Returns the connected components of the graph, ignoring edge
+/// directions and the edges with weights not above the threshold.
+pub(crate) fn components_above<T, S: BuildHasher>(
+    graph: &Graph<T, S>,
+    threshold: f32,
+) -> Vec<VertexSet<'_, T, S>> {
+    let mut sets = singletons(graph);
+
+    for (a, b, weight) in weighted_edges(graph) {
+        if weight > threshold {
+            sets.union(&a, &b);
+        }
+    }
+
+    let mut components: HashMap<VertexId, HashSet<VertexId>> = HashMap::new();
+
+    for v in graph.vertices() {
+        let root = sets.find(v).unwrap();
+        components.entry(root).or_default().insert(*v);
+    }
+
+    components
+        .into_iter()
+        .map(|(_, c)| VertexSet::from_set(graph, c))
+        .collect()
+}
+
+/// Returns the sizes of the components above each of the given
+/// thresholds, in the order of the thresholds. The thresholds are
+/// swept from the highest to the lowest one, adding the edges to the
+/// components in decreasing order of their weight, so each edge is
+/// processed once.
+pub(crate) fn percolation<T, S: BuildHasher>(
+    graph: &Graph<T, S>,
+    thresholds: &[f32],
+) -> Result<Vec<(f32, Vec<usize>)>, GraphErr> {
+    if thresholds.iter().any(|t| t.is_nan()) {
+        return Err(GraphErr::InvalidWeight);
+    }
+
+    let mut sets = singletons(graph);
+    let mut edges = weighted_edges(graph);
+
+    edges.sort_by(|x, y| y.2.partial_cmp(&x.2).unwrap());
+
+    let mut order: Vec<usize> = (0..thresholds.len()).collect();
+    order.sort_by(|x, y| thresholds[*y].partial_cmp(&thresholds[*x]).unwrap());
+
+    let mut result: Vec<(f32, Vec<usize>)> = thresholds.iter().map(|t| (*t, Vec::new())).collect();
+    let mut edges = edges.into_iter().peekable();
+
+    for idx in order {
+        let threshold = thresholds[idx];
+
+        while let Some((a, b, _)) = edges.next_if(|(_, _, weight)| *weight > threshold) {
+            sets.union(&a, &b);
+        }
+
+        let mut sizes: Vec<usize> = sets.set_sizes().collect();
+        sizes.sort_by(|x, y| y.cmp(x));
+
+        result[idx].1 = sizes;
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,468 @@
+// Copyright 2019 Octavian Oncescu
+
+//! Persistent graph whose mutations return new graphs.
+//!
+//! This module requires the `persistent` crate feature.
+
+use crate::edge::Edge;
+use crate::graph::{Graph, GraphErr};
+use crate::iterators::VertexIter;
+use crate::traits::GraphRead;
+use crate::vertex_id::VertexId;
+
+use im::{HashMap, HashSet};
+
+use std::fmt::{self, Debug, Formatter};
+use std::hash::BuildHasher;
+use std::iter;
+
+#[derive(Clone, Debug)]
+/// Immutable graph whose mutating methods return a new graph, leaving the
+/// original untouched. The new graph shares all of its unchanged internals
+/// with the original through hash array mapped tries, so cloning a graph is
+/// O(1) and each mutation only copies O(log n) of its structure. This makes
+/// it cheap to keep many versions of a graph around.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::persistent::PersistentGraph;
+///
+/// let graph: PersistentGraph<usize> = PersistentGraph::new();
+///
+/// let (graph, v1) = graph.add_vertex(1);
+/// let (graph, v2) = graph.add_vertex(2);
+///
+/// let connected = graph.add_edge(&v1, &v2).unwrap();
+///
+/// assert!(connected.has_edge(&v1, &v2));
+///
+/// // The previous version is unchanged
+/// assert!(!graph.has_edge(&v1, &v2));
+/// assert_eq!(graph.vertex_count(), 2);
+/// ```
+pub struct PersistentGraph<T: Clone> {
+    /// Mapping of vertex ids and vertex values
+    vertices: HashMap<VertexId, T>,
+
+    /// Mapping between edges and weights
+    edges: HashMap<Edge, f32>,
+
+    /// Mapping between vertex ids and inbound neighbors
+    inbound_table: HashMap<VertexId, HashSet<VertexId>>,
+
+    /// Mapping between vertex ids and outbound neighbors
+    outbound_table: HashMap<VertexId, HashSet<VertexId>>,
+
+    /// Set containing the roots of the graph
+    roots: HashSet<VertexId>,
+
+    /// Set containing the tips of the graph
+    tips: HashSet<VertexId>,
+}
+
+impl<T: Clone> PersistentGraph<T> {
+    /// Creates a new persistent graph.
+    pub fn new() -> PersistentGraph<T> {
+        PersistentGraph {
+            vertices: HashMap::new(),
+            edges: HashMap::new(),
+            inbound_table: HashMap::new(),
+            outbound_table: HashMap::new(),
+            roots: HashSet::new(),
+            tips: HashSet::new(),
+        }
+    }
+
+    /// Returns a graph with a new vertex holding the given
+    /// value, along with the id of the new vertex.
+    pub fn add_vertex(&self, item: T) -> (PersistentGraph<T>, VertexId) {
+        let id = VertexId::random();
+        let mut graph = self.clone();
+
+        graph.insert_vertex(id, item);
+
+        (graph, id)
+    }
+
+    /// Returns a graph in which the vertex with the given id holds the
+    /// given value. Fails with `GraphErr::NoSuchVertex` if there is
+    /// no such vertex.
+    pub fn set_value(&self, id: &VertexId, item: T) -> Result<PersistentGraph<T>, GraphErr> {
+        if !self.vertices.contains_key(id) {
+            return Err(GraphErr::NoSuchVertex);
+        }
+
+        let mut graph = self.clone();
+        graph.vertices.insert(*id, item);
+
+        Ok(graph)
+    }
+
+    /// Returns a graph without the vertex with the given id and its edges.
+    pub fn remove(&self, id: &VertexId) -> PersistentGraph<T> {
+        let mut graph = self.clone();
+
+        for n in self.in_neighbors(id) {
+            graph.delete_edge(n, id);
+        }
+
+        for n in self.out_neighbors(id) {
+            graph.delete_edge(id, n);
+        }
+
+        graph.vertices.remove(id);
+        graph.roots.remove(id);
+        graph.tips.remove(id);
+
+        graph
+    }
+
+    /// Returns a graph with an edge from `a` to `b`, which has a weight
+    /// of `0.0` unless it already exists. Fails with `GraphErr::NoSuchVertex`
+    /// if either of the vertices is not in the graph.
+    pub fn add_edge(&self, a: &VertexId, b: &VertexId) -> Result<PersistentGraph<T>, GraphErr> {
+        if self.has_edge(a, b) {
+            return Ok(self.clone());
+        }
+
+        self.add_edge_with_weight(a, b, 0.0)
+    }
+
+    /// Returns a graph with an edge from `a` to `b` with the given weight,
+    /// which must be between `-1.0` and `1.0`. Fails with
+    /// `GraphErr::NoSuchVertex` if either of the vertices is not in the graph.
+    pub fn add_edge_with_weight(
+        &self,
+        a: &VertexId,
+        b: &VertexId,
+        weight: f32,
+    ) -> Result<PersistentGraph<T>, GraphErr> {
+        if !self.vertices.contains_key(a) || !self.vertices.contains_key(b) {
+            return Err(GraphErr::NoSuchVertex);
+        }
+
+        if !(-1.0..=1.0).contains(&weight) {
+            return Err(GraphErr::InvalidWeight);
+        }
+
+        let mut graph = self.clone();
+
+        graph.edges.insert(Edge::new(*a, *b), weight);
+        graph.outbound_table.entry(*a).or_default().insert(*b);
+        graph.inbound_table.entry(*b).or_default().insert(*a);
+        graph.roots.remove(b);
+        graph.tips.remove(a);
+
+        Ok(graph)
+    }
+
+    /// Returns a graph in which the edge from `a` to `b` has the given weight.
+    /// Fails with `GraphErr::NoSuchEdge` if there is no such edge.
+    pub fn set_weight(
+        &self,
+        a: &VertexId,
+        b: &VertexId,
+        weight: f32,
+    ) -> Result<PersistentGraph<T>, GraphErr> {
+        if !self.has_edge(a, b) {
+            return Err(GraphErr::NoSuchEdge);
+        }
+
+        self.add_edge_with_weight(a, b, weight)
+    }
+
+    /// Returns a graph without the edge from `a` to `b`.
+    pub fn remove_edge(&self, a: &VertexId, b: &VertexId) -> PersistentGraph<T> {
+        let mut graph = self.clone();
+        graph.delete_edge(a, b);
+
+        graph
+    }
+
+    /// Returns the number of vertices in the graph.
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /// Returns the number of edges in the graph.
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Returns the value of the vertex with the given id.
+    pub fn fetch(&self, id: &VertexId) -> Option<&T> {
+        self.vertices.get(id)
+    }
+
+    /// Returns true if there is an edge from `a` to `b`.
+    pub fn has_edge(&self, a: &VertexId, b: &VertexId) -> bool {
+        self.edges.contains_key(&Edge::new(*a, *b))
+    }
+
+    /// Returns the weight of the edge from `a` to `b`.
+    pub fn weight(&self, a: &VertexId, b: &VertexId) -> Option<f32> {
+        self.edges.get(&Edge::new(*a, *b)).cloned()
+    }
+
+    /// Returns an iterator over all of the vertices.
+    pub fn vertices(&self) -> VertexIter<'_> {
+        VertexIter(Box::new(Ids(self.vertices.keys())))
+    }
+
+    /// Returns an iterator over the vertices without inbound edges.
+    pub fn roots(&self) -> VertexIter<'_> {
+        VertexIter(Box::new(Ids(self.roots.iter())))
+    }
+
+    /// Returns an iterator over the vertices without outbound edges.
+    pub fn tips(&self) -> VertexIter<'_> {
+        VertexIter(Box::new(Ids(self.tips.iter())))
+    }
+
+    /// Returns an iterator over the inbound neighbors of the vertex.
+    pub fn in_neighbors(&self, id: &VertexId) -> VertexIter<'_> {
+        match self.inbound_table.get(id) {
+            Some(neighbors) => VertexIter(Box::new(Ids(neighbors.iter()))),
+            None => VertexIter(Box::new(iter::empty())),
+        }
+    }
+
+    /// Returns an iterator over the outbound neighbors of the vertex.
+    pub fn out_neighbors(&self, id: &VertexId) -> VertexIter<'_> {
+        match self.outbound_table.get(id) {
+            Some(neighbors) => VertexIter(Box::new(Ids(neighbors.iter()))),
+            None => VertexIter(Box::new(iter::empty())),
+        }
+    }
+
+    /// Creates a mutable graph with the same vertices, ids and edges.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::persistent::PersistentGraph;
+    /// use graphlib::Graph;
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    ///
+    /// graph.add_edge_with_weight(&v1, &v2, 0.5).unwrap();
+    ///
+    /// let persistent = PersistentGraph::from(&graph);
+    /// let persistent = persistent.remove_edge(&v1, &v2);
+    ///
+    /// assert_eq!(graph.edge_count(), 1);
+    /// assert_eq!(persistent.to_graph().edge_count(), 0);
+    /// assert_eq!(PersistentGraph::from(&graph).to_graph(), graph);
+    /// ```
+    pub fn to_graph(&self) -> Graph<T> {
+        let mut graph = Graph::with_capacity(self.vertex_count());
+
+        for (id, value) in self.vertices.iter() {
+            graph.insert_vertex(*id, value.clone());
+        }
+
+        for (edge, weight) in self.edges.iter() {
+            graph
+                .add_edge_with_weight(edge.outbound(), edge.inbound(), *weight)
+                .unwrap();
+        }
+
+        graph
+    }
+
+    fn insert_vertex(&mut self, id: VertexId, item: T) {
+        self.vertices.insert(id, item);
+        self.roots.insert(id);
+        self.tips.insert(id);
+    }
+
+    fn delete_edge(&mut self, a: &VertexId, b: &VertexId) {
+        if self.edges.remove(&Edge::new(*a, *b)).is_none() {
+            return;
+        }
+
+        if let Some(outbounds) = self.outbound_table.get_mut(a) {
+            outbounds.remove(b);
+
+            if outbounds.is_empty() {
+                self.outbound_table.remove(a);
+                self.tips.insert(*a);
+            }
+        }
+
+        if let Some(inbounds) = self.inbound_table.get_mut(b) {
+            inbounds.remove(a);
+
+            if inbounds.is_empty() {
+                self.inbound_table.remove(b);
+                self.roots.insert(*b);
+            }
+        }
+    }
+}
+
+impl<T: Clone> Default for PersistentGraph<T> {
+    fn default() -> PersistentGraph<T> {
+        PersistentGraph::new()
+    }
+}
+
+impl<T: Clone, S: BuildHasher> From<&Graph<T, S>> for PersistentGraph<T> {
+    fn from(graph: &Graph<T, S>) -> PersistentGraph<T> {
+        let mut persistent = PersistentGraph::new();
+
+        for id in graph.vertices() {
+            persistent.insert_vertex(*id, graph.fetch(id).unwrap().clone());
+        }
+
+        for (b, a) in graph.edges() {
+            persistent = persistent
+                .add_edge_with_weight(a, b, graph.weight(a, b).unwrap())
+                .unwrap();
+        }
+
+        persistent
+    }
+}
+
+impl<T: Clone> GraphRead<T> for PersistentGraph<T> {
+    fn vertex_count(&self) -> usize {
+        PersistentGraph::vertex_count(self)
+    }
+
+    fn edge_count(&self) -> usize {
+        PersistentGraph::edge_count(self)
+    }
+
+    fn fetch(&self, id: &VertexId) -> Option<&T> {
+        PersistentGraph::fetch(self, id)
+    }
+
+    fn has_edge(&self, a: &VertexId, b: &VertexId) -> bool {
+        PersistentGraph::has_edge(self, a, b)
+    }
+
+    fn weight(&self, a: &VertexId, b: &VertexId) -> Option<f32> {
+        PersistentGraph::weight(self, a, b)
+    }
+
+    fn vertices(&self) -> VertexIter<'_> {
+        PersistentGraph::vertices(self)
+    }
+
+    fn roots(&self) -> VertexIter<'_> {
+        PersistentGraph::roots(self)
+    }
+
+    fn tips(&self) -> VertexIter<'_> {
+        PersistentGraph::tips(self)
+    }
+
+    fn in_neighbors(&self, id: &VertexId) -> VertexIter<'_> {
+        PersistentGraph::in_neighbors(self, id)
+    }
+
+    fn out_neighbors(&self, id: &VertexId) -> VertexIter<'_> {
+        PersistentGraph::out_neighbors(self, id)
+    }
+}
+
+/// Iterator over ids adapting the iterators of the persistent
+/// collections, which do not implement `Debug`.
+struct Ids<I>(I);
+
+impl<'a, I: Iterator<Item = &'a VertexId>> Iterator for Ids<I> {
+    type Item = &'a VertexId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+impl<I> Debug for Ids<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("Ids")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions_are_independent() {
+        let graph: PersistentGraph<usize> = PersistentGraph::new();
+
+        let (graph, v1) = graph.add_vertex(1);
+        let (graph, v2) = graph.add_vertex(2);
+        let (graph, v3) = graph.add_vertex(3);
+
+        let v1_graph = graph.add_edge_with_weight(&v1, &v2, 0.5).unwrap();
+        let v2_graph = v1_graph.add_edge(&v2, &v3).unwrap();
+        let v3_graph = v2_graph.remove(&v2);
+        let v4_graph = v2_graph.set_value(&v3, 30).unwrap();
+
+        assert_eq!(graph.edge_count(), 0);
+        assert_eq!(v1_graph.edge_count(), 1);
+        assert_eq!(v2_graph.edge_count(), 2);
+        assert_eq!(v3_graph.edge_count(), 0);
+        assert_eq!(v3_graph.vertex_count(), 2);
+
+        assert_eq!(v2_graph.fetch(&v3), Some(&3));
+        assert_eq!(v4_graph.fetch(&v3), Some(&30));
+
+        // Roots and tips follow the edges of each version
+        assert_eq!(v2_graph.roots().collect::<Vec<_>>(), vec![&v1]);
+        assert_eq!(v2_graph.tips().collect::<Vec<_>>(), vec![&v3]);
+        assert_eq!(v3_graph.roots().count(), 2);
+        assert_eq!(v3_graph.tips().count(), 2);
+
+        assert_eq!(v1_graph.weight(&v1, &v2), Some(0.5));
+        assert_eq!(
+            v1_graph
+                .add_edge(&v1, &v3)
+                .unwrap()
+                .add_edge(&v1, &v3)
+                .unwrap()
+                .edge_count(),
+            2
+        );
+        assert_eq!(
+            graph.add_edge_with_weight(&v1, &v2, 2.0).unwrap_err(),
+            GraphErr::InvalidWeight
+        );
+        assert_eq!(
+            graph.set_weight(&v1, &v2, 0.1).unwrap_err(),
+            GraphErr::NoSuchEdge
+        );
+        assert_eq!(
+            v3_graph.add_edge(&v1, &v2).unwrap_err(),
+            GraphErr::NoSuchVertex
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+
+        graph.add_edge_with_weight(&v1, &v2, 0.3).unwrap();
+        graph.add_edge_with_weight(&v3, &v2, -0.2).unwrap();
+        graph.add_edge(&v3, &v3).unwrap();
+
+        let persistent = PersistentGraph::from(&graph);
+        let converted = persistent.to_graph();
+
+        assert_eq!(converted, graph);
+        assert_eq!(
+            persistent.in_neighbors(&v2).count(),
+            graph.in_neighbors_count(&v2)
+        );
+        assert_eq!(GraphRead::roots(&persistent).collect::<Vec<_>>(), vec![&v1]);
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::vertex_id::VertexId;
+
+use hashbrown::HashMap;
+
+#[cfg(not(feature = "no_std"))]
+use std::cmp::Ordering;
+#[cfg(not(feature = "no_std"))]
+use std::collections::BTreeSet;
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::collections::BTreeSet;
+#[cfg(feature = "no_std")]
+use core::cmp::Ordering;
+
+#[derive(Clone, Copy, Debug)]
+/// Totally ordered priority.
+struct Priority(f32);
+
+impl PartialEq for Priority {
+    fn eq(&self, other: &Priority) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Priority {}
+
+impl PartialOrd for Priority {
+    fn partial_cmp(&self, other: &Priority) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Priority {
+    fn cmp(&self, other: &Priority) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+/// Index of vertices ordered by their priorities, where
+/// ties are broken by the vertex ids.
+pub(crate) struct PriorityIndex {
+    /// Mapping between vertices and their priorities
+    priorities: HashMap<VertexId, f32>,
+
+    /// Prioritized vertices in ascending order
+    order: BTreeSet<(Priority, VertexId)>,
+}
+
+impl PriorityIndex {
+    /// Sets the priority of the given vertex, which must not be NaN.
+    pub(crate) fn insert(&mut self, id: VertexId, priority: f32) {
+        self.remove(&id);
+        self.priorities.insert(id, priority);
+        self.order.insert((Priority(priority), id));
+    }
+
+    pub(crate) fn get(&self, id: &VertexId) -> Option<f32> {
+        self.priorities.get(id).cloned()
+    }
+
+    pub(crate) fn remove(&mut self, id: &VertexId) -> Option<f32> {
+        let priority = self.priorities.remove(id)?;
+        self.order.remove(&(Priority(priority), *id));
+
+        Some(priority)
+    }
+
+    /// Returns the index with the vertices renamed by the
+    /// given mapping, dropping the ones which are not in it.
+    pub(crate) fn renamed(&self, mapping: &HashMap<VertexId, VertexId>) -> PriorityIndex {
+        let mut index = PriorityIndex::default();
+
+        for (id, priority) in self.priorities.iter() {
+            if let Some(id) = mapping.get(id) {
+                index.insert(*id, *priority);
+            }
+        }
+
+        index
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.priorities.len()
+    }
+
+    pub(crate) fn highest(&self) -> Option<(VertexId, f32)> {
+        self.order.iter().next_back().map(|(p, id)| (*id, p.0))
+    }
+
+    pub(crate) fn pop_highest(&mut self) -> Option<(VertexId, f32)> {
+        let (id, priority) = self.highest()?;
+        self.remove(&id);
+
+        Some((id, priority))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_updates_keep_order() {
+        let mut index = PriorityIndex::default();
+
+        let v1 = VertexId::random();
+        let v2 = VertexId::random();
+        let v3 = VertexId::random();
+
+        index.insert(v1, 0.5);
+        index.insert(v2, 2.0);
+        index.insert(v3, -1.0);
+        index.insert(v2, 0.1);
+
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.pop_highest(), Some((v1, 0.5)));
+        assert_eq!(index.remove(&v3), Some(-1.0));
+        assert_eq!(index.pop_highest(), Some((v2, 0.1)));
+        assert_eq!(index.pop_highest(), None);
+        assert_eq!(index.order.len(), 0);
+    }
+}
@@ -0,0 +1,785 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::Graph;
+use crate::vertex_id::VertexId;
+
+use hashbrown::{HashMap, HashSet};
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "no_std")]
+use core::mem;
+#[cfg(not(feature = "no_std"))]
+use std::mem;
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+#[cfg(not(feature = "no_std"))]
+use std::convert::TryInto;
+#[cfg(not(feature = "no_std"))]
+use std::fs::{self, File, OpenOptions};
+#[cfg(not(feature = "no_std"))]
+use std::io::{self, Read, Seek, SeekFrom, Write};
+#[cfg(not(feature = "no_std"))]
+use std::path::{Path, PathBuf};
+#[cfg(not(feature = "no_std"))]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(not(feature = "no_std"))]
+use std::sync::{Arc, Mutex};
+
+/// Number of bytes taken by a range in a spill file.
+#[cfg(not(feature = "no_std"))]
+const SPILLED_INTERVAL_SIZE: u64 = 16;
+
+/// Number of spill files created so far, which keeps their names unique.
+#[cfg(not(feature = "no_std"))]
+static SPILL_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// Inclusive range of component numbers.
+type Interval = (usize, usize);
+
+#[derive(Clone, Debug)]
+/// Components which can be reached from a component.
+enum Reach {
+    /// Sorted and disjoint ranges of the reachable components
+    Intervals(Vec<Interval>),
+
+    /// Components at the end of the edges leaving the component,
+    /// which are searched when the index is queried
+    Successors(Vec<usize>),
+
+    /// Offset in the spill file and number of the ranges
+    /// of the reachable components
+    #[cfg(not(feature = "no_std"))]
+    Spilled(u64, usize),
+}
+
+#[derive(Clone, Debug, Default)]
+/// Index answering whether a vertex can reach another one in
+/// `O(log n)` time, as returned by `Graph::reachability_index()`.
+///
+/// The strongly connected components of the graph are numbered in
+/// post-order, which makes the components reachable from each one
+/// fall into a few ranges of numbers. Each query is then a binary
+/// search over the ranges of a single component.
+///
+/// The ranges take `O(n^2)` space in the worst case, which can be
+/// limited with `ReachabilityIndex::with_budget()`. The components
+/// whose ranges do not fit in the budget only keep the components
+/// their edges lead to, and queries from them search these instead.
+/// With `ReachabilityIndex::with_spill()`, their ranges are written
+/// to a temporary file instead and searched on disk.
+///
+/// The index is a snapshot of the graph it is built from, so it
+/// must be rebuilt with `ReachabilityIndex::rebuild()` after the
+/// graph changes.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::Graph;
+///
+/// let mut graph: Graph<usize> = Graph::new();
+///
+/// let v1 = graph.add_vertex(1);
+/// let v2 = graph.add_vertex(2);
+/// let v3 = graph.add_vertex(3);
+///
+/// graph.add_edge(&v1, &v2).unwrap();
+/// graph.add_edge(&v2, &v3).unwrap();
+///
+/// let mut index = graph.reachability_index();
+///
+/// assert!(index.reaches(&v1, &v3));
+/// assert!(!index.reaches(&v3, &v1));
+///
+/// graph.add_edge(&v3, &v1).unwrap();
+/// index.rebuild(&graph);
+///
+/// assert!(index.reaches(&v3, &v1));
+/// ```
+pub struct ReachabilityIndex {
+    /// Mapping between vertices and the numbers of their components
+    components: HashMap<VertexId, usize>,
+
+    /// Components reachable from each component
+    reach: Vec<Reach>,
+
+    /// Number of ranges kept by the index
+    interval_count: usize,
+
+    /// Number of bytes the ranges can take, if limited
+    budget: Option<usize>,
+
+    /// Directory of the files holding the ranges which
+    /// do not fit in the budget, if they are spilled
+    #[cfg(not(feature = "no_std"))]
+    spill_dir: Option<PathBuf>,
+
+    /// File holding the ranges which do not fit in the budget
+    #[cfg(not(feature = "no_std"))]
+    spill: Option<Arc<Spill>>,
+}
+
+impl ReachabilityIndex {
+    /// Builds the index of the given graph.
+    pub fn new<T, S: BuildHasher>(graph: &Graph<T, S>) -> ReachabilityIndex {
+        let mut index = ReachabilityIndex::default();
+        index.rebuild(graph);
+        index
+    }
+
+    /// Builds the index of the given graph, keeping the ranges of
+    /// reachable components in at most `budget` bytes. Queries from
+    /// the components whose ranges do not fit search the graph,
+    /// which takes up to `O(|V| + |E|)` time.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::{Graph, ReachabilityIndex};
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    ///
+    /// let index = ReachabilityIndex::with_budget(&graph, 0);
+    ///
+    /// assert!(!index.is_complete());
+    /// assert!(index.reaches(&v1, &v3));
+    /// assert!(!index.reaches(&v3, &v1));
+    /// ```
+    pub fn with_budget<T, S: BuildHasher>(graph: &Graph<T, S>, budget: usize) -> ReachabilityIndex {
+        let mut index = ReachabilityIndex {
+            budget: Some(budget),
+            ..ReachabilityIndex::default()
+        };
+
+        index.rebuild(graph);
+        index
+    }
+
+    #[cfg(not(feature = "no_std"))]
+    /// Builds the index of the given graph, keeping the ranges of
+    /// reachable components in at most `budget` bytes of memory. The
+    /// ranges which do not fit are written to a temporary file in the
+    /// given directory, which is removed when the index is dropped.
+    /// Queries from the components whose ranges were spilled search
+    /// them on disk, which takes `O(log n)` reads.
+    ///
+    /// Fails if the file can not be created. The ranges which can
+    /// not be written to it are handled as with
+    /// `ReachabilityIndex::with_budget()`.
+    ///
+    /// ## Panics
+    /// Queries panic if the spilled ranges can not be read back.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use graphlib::{Graph, ReachabilityIndex};
+    ///
+    /// let mut graph: Graph<usize> = Graph::new();
+    ///
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let v3 = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(&v1, &v2).unwrap();
+    /// graph.add_edge(&v2, &v3).unwrap();
+    ///
+    /// let index = ReachabilityIndex::with_spill(&graph, 0, std::env::temp_dir()).unwrap();
+    ///
+    /// assert!(index.is_complete());
+    /// assert!(index.reaches(&v1, &v3));
+    /// assert!(!index.reaches(&v3, &v1));
+    /// ```
+    pub fn with_spill<T, S: BuildHasher>(
+        graph: &Graph<T, S>,
+        budget: usize,
+        dir: impl AsRef<Path>,
+    ) -> io::Result<ReachabilityIndex> {
+        let dir = dir.as_ref().to_owned();
+        let spill = Spill::create(&dir)?;
+
+        let mut index = ReachabilityIndex {
+            budget: Some(budget),
+            spill_dir: Some(dir),
+            spill: Some(Arc::new(spill)),
+            ..ReachabilityIndex::default()
+        };
+
+        index.rebuild(graph);
+        Ok(index)
+    }
+
+    /// Rebuilds the index from the given graph, reusing its
+    /// allocations and keeping its memory budget.
+    pub fn rebuild<T, S: BuildHasher>(&mut self, graph: &Graph<T, S>) {
+        self.components.clear();
+        self.reach.clear();
+        self.interval_count = 0;
+
+        #[cfg(not(feature = "no_std"))]
+        self.reset_spill();
+
+        let mut tarjan = Tarjan::new(graph.vertex_count());
+
+        for v in graph.vertices() {
+            if !tarjan.indices.contains_key(v) {
+                self.visit(graph, &mut tarjan, *v);
+            }
+        }
+    }
+
+    /// Returns true if there is a path from the vertex `a` to the
+    /// vertex `b`, which is the case if they are the same vertex.
+    /// Returns false if any of the vertices is not in the index.
+    pub fn reaches(&self, a: &VertexId, b: &VertexId) -> bool {
+        let (a, b) = match (self.components.get(a), self.components.get(b)) {
+            (Some(a), Some(b)) => (*a, *b),
+            _ => return false,
+        };
+
+        if let Some(found) = self.ranges_contain(a, b) {
+            return found;
+        }
+
+        // Components are numbered after the ones they reach,
+        // so only components numbered at least `b` can reach it
+        let mut visited = HashSet::new();
+        let mut stack = vec![a];
+
+        while let Some(current) = stack.pop() {
+            if let Some(found) = self.ranges_contain(current, b) {
+                if found {
+                    return true;
+                }
+            } else if current == b {
+                return true;
+            } else if let Reach::Successors(successors) = &self.reach[current] {
+                for n in successors.iter().filter(|n| **n >= b) {
+                    if visited.insert(*n) {
+                        stack.push(*n);
+                    }
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Returns the number of vertices in the index.
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Returns true if there are no vertices in the index.
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// Returns the number of strongly connected components of the
+    /// indexed graph, which is the number of vertices if it is acyclic.
+    pub fn component_count(&self) -> usize {
+        self.reach.len()
+    }
+
+    /// Returns true if the ranges of every component fit in the
+    /// memory budget or were spilled to disk, so that every query
+    /// takes `O(log n)`.
+    pub fn is_complete(&self) -> bool {
+        self.reach
+            .iter()
+            .all(|reach| !matches!(reach, Reach::Successors(_)))
+    }
+
+    /// Returns whether the ranges of the given component contain
+    /// the other component or `None` if it has no ranges.
+    fn ranges_contain(&self, component: usize, other: usize) -> Option<bool> {
+        match &self.reach[component] {
+            Reach::Intervals(intervals) => Some(contains(intervals, other)),
+            Reach::Successors(_) => None,
+            #[cfg(not(feature = "no_std"))]
+            Reach::Spilled(offset, count) => {
+                let spill = self.spill.as_ref()?;
+                Some(
+                    spill
+                        .contains(*offset, *count, other)
+                        .expect(SPILL_READ_MSG),
+                )
+            }
+        }
+    }
+
+    /// Runs Tarjan's algorithm from the given vertex, numbering the
+    /// components as they are completed. A component is completed
+    /// after all of the components it reaches, so their ranges are
+    /// known by then.
+    fn visit<T, S: BuildHasher>(
+        &mut self,
+        graph: &Graph<T, S>,
+        tarjan: &mut Tarjan,
+        root: VertexId,
+    ) {
+        let mut frames: Vec<(VertexId, Vec<VertexId>)> = vec![(root, tarjan.push(graph, root))];
+
+        while let Some((v, neighbors)) = frames.last_mut() {
+            let v = *v;
+
+            if let Some(n) = neighbors.pop() {
+                match tarjan.indices.get(&n) {
+                    None => {
+                        let neighbors = tarjan.push(graph, n);
+                        frames.push((n, neighbors));
+                    }
+                    Some(&index) if tarjan.on_stack(&n) => tarjan.lower(&v, index),
+                    Some(_) => {}
+                }
+
+                continue;
+            }
+
+            frames.pop();
+
+            if let Some((parent, _)) = frames.last() {
+                let low = tarjan.lows[&v];
+                tarjan.lower(parent, low);
+            }
+
+            if tarjan.lows[&v] == tarjan.indices[&v] {
+                self.complete(graph, tarjan.pop_component(&v));
+            }
+        }
+    }
+
+    /// Numbers the given component and computes its ranges, or keeps
+    /// its successors if they do not fit in the memory budget.
+    fn complete<T, S: BuildHasher>(&mut self, graph: &Graph<T, S>, members: Vec<VertexId>) {
+        let component = self.reach.len();
+
+        for v in members.iter() {
+            self.components.insert(*v, component);
+        }
+
+        let mut successors: Vec<usize> = members
+            .iter()
+            .flat_map(|v| graph.out_neighbors(v))
+            .map(|n| self.components[n])
+            .filter(|other| *other != component)
+            .collect();
+
+        successors.sort_unstable();
+        successors.dedup();
+
+        let reach = match self.merge_intervals(component, &successors) {
+            Some(merged) if self.fits(merged.len()) => {
+                self.interval_count += merged.len();
+                Reach::Intervals(merged)
+            }
+            merged => match merged.and_then(|merged| self.spill(&merged)) {
+                Some(reach) => reach,
+                None => {
+                    successors.shrink_to_fit();
+                    Reach::Successors(successors)
+                }
+            },
+        };
+
+        self.reach.push(reach);
+    }
+
+    /// Returns the ranges of the components reachable from the given
+    /// component or `None` if any of its successors has no ranges.
+    fn merge_intervals(&self, component: usize, successors: &[usize]) -> Option<Vec<Interval>> {
+        let mut intervals = vec![(component, component)];
+
+        for other in successors.iter() {
+            match &self.reach[*other] {
+                Reach::Intervals(other) => intervals.extend(other.iter().cloned()),
+                Reach::Successors(_) => return None,
+                #[cfg(not(feature = "no_std"))]
+                Reach::Spilled(offset, count) => {
+                    let spill = self.spill.as_ref()?;
+                    intervals.extend(spill.read(*offset, *count).ok()?);
+                }
+            }
+        }
+
+        intervals.sort_unstable();
+
+        let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+
+        for (start, end) in intervals {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+
+        merged.shrink_to_fit();
+        Some(merged)
+    }
+
+    /// Returns true if the given number of ranges
+    /// can be added without exceeding the budget.
+    fn fits(&self, count: usize) -> bool {
+        self.budget.is_none_or(|budget| {
+            (self.interval_count + count) * mem::size_of::<Interval>() <= budget
+        })
+    }
+
+    #[cfg(not(feature = "no_std"))]
+    /// Writes the given ranges to the spill file, returning `None`
+    /// if they are not spilled or could not be written.
+    fn spill(&mut self, intervals: &[Interval]) -> Option<Reach> {
+        let spill = Arc::get_mut(self.spill.as_mut()?)?;
+        let offset = spill.write(intervals).ok()?;
+
+        Some(Reach::Spilled(offset, intervals.len()))
+    }
+
+    #[cfg(feature = "no_std")]
+    fn spill(&mut self, _intervals: &[Interval]) -> Option<Reach> {
+        None
+    }
+
+    #[cfg(not(feature = "no_std"))]
+    /// Empties the spill file before the index is rebuilt. The file is
+    /// replaced by a new one if it is shared with a clone of the index.
+    fn reset_spill(&mut self) {
+        let dir = match &self.spill_dir {
+            Some(dir) => dir,
+            None => return,
+        };
+
+        let reset = self
+            .spill
+            .as_mut()
+            .and_then(Arc::get_mut)
+            .is_some_and(|spill| spill.clear().is_ok());
+
+        if !reset {
+            self.spill = Spill::create(dir).ok().map(Arc::new);
+        }
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+const SPILL_READ_MSG: &str = "could not read the spilled ranges of the reachability index";
+
+#[cfg(not(feature = "no_std"))]
+#[derive(Debug)]
+/// Temporary file holding the ranges which do not fit in the
+/// memory budget of an index, removed when it is dropped.
+struct Spill {
+    path: PathBuf,
+    file: Mutex<File>,
+
+    /// Number of bytes written to the file
+    len: u64,
+}
+
+#[cfg(not(feature = "no_std"))]
+impl Spill {
+    /// Creates a new spill file in the given directory.
+    fn create(dir: &Path) -> io::Result<Spill> {
+        let name = format!(
+            "graphlib-reachability-{}-{}.tmp",
+            std::process::id(),
+            SPILL_COUNT.fetch_add(1, Ordering::Relaxed)
+        );
+        let path = dir.join(name);
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+
+        Ok(Spill {
+            path,
+            file: Mutex::new(file),
+            len: 0,
+        })
+    }
+
+    /// Appends the given ranges to the file, returning their offset.
+    fn write(&mut self, intervals: &[Interval]) -> io::Result<u64> {
+        let offset = self.len;
+        let mut bytes = Vec::with_capacity(intervals.len() * SPILLED_INTERVAL_SIZE as usize);
+
+        for (start, end) in intervals {
+            bytes.extend_from_slice(&(*start as u64).to_le_bytes());
+            bytes.extend_from_slice(&(*end as u64).to_le_bytes());
+        }
+
+        let file = self.file.get_mut().unwrap();
+        file.seek(SeekFrom::Start(offset))?;
+        file.write_all(&bytes)?;
+
+        self.len += bytes.len() as u64;
+        Ok(offset)
+    }
+
+    /// Reads the given number of ranges starting at the given offset.
+    fn read(&self, offset: u64, count: usize) -> io::Result<Vec<Interval>> {
+        let mut bytes = vec![0; count * SPILLED_INTERVAL_SIZE as usize];
+
+        let mut file = self.file.lock().unwrap();
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(&mut bytes)?;
+
+        Ok(bytes
+            .chunks_exact(SPILLED_INTERVAL_SIZE as usize)
+            .map(decode_interval)
+            .collect())
+    }
+
+    /// Returns true if the given component is in one of the ranges
+    /// starting at the given offset, binary searching the file.
+    fn contains(&self, offset: u64, count: usize, component: usize) -> io::Result<bool> {
+        let mut file = self.file.lock().unwrap();
+        let mut bytes = [0; SPILLED_INTERVAL_SIZE as usize];
+
+        // Finds the last range starting at or before the component
+        let (mut low, mut high) = (0, count);
+        let mut last = None;
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+
+            file.seek(SeekFrom::Start(offset + mid as u64 * SPILLED_INTERVAL_SIZE))?;
+            file.read_exact(&mut bytes)?;
+
+            let interval = decode_interval(&bytes);
+
+            if interval.0 <= component {
+                last = Some(interval);
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        Ok(last.is_some_and(|(_, end)| end >= component))
+    }
+
+    /// Empties the file.
+    fn clear(&mut self) -> io::Result<()> {
+        self.file.get_mut().unwrap().set_len(0)?;
+        self.len = 0;
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+impl Drop for Spill {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+/// Decodes a range written to a spill file.
+fn decode_interval(bytes: &[u8]) -> Interval {
+    let start = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+    let end = u64::from_le_bytes(bytes[8..16].try_into().unwrap());
+
+    (start as usize, end as usize)
+}
+
+/// Returns true if the given component is in one of the ranges.
+fn contains(intervals: &[Interval], component: usize) -> bool {
+    match intervals.binary_search_by(|(start, _)| start.cmp(&component)) {
+        Ok(_) => true,
+        Err(0) => false,
+        Err(i) => intervals[i - 1].1 >= component,
+    }
+}
+
+/// State of Tarjan's strongly connected components algorithm.
+struct Tarjan {
+    /// Order in which the vertices were visited
+    indices: HashMap<VertexId, usize>,
+
+    /// Lowest index reachable from each vertex on the stack
+    lows: HashMap<VertexId, usize>,
+
+    /// Visited vertices which are not part of a completed component
+    stack: Vec<VertexId>,
+
+    /// Positions of the vertices on the stack
+    positions: HashMap<VertexId, usize>,
+}
+
+impl Tarjan {
+    fn new(capacity: usize) -> Tarjan {
+        Tarjan {
+            indices: HashMap::with_capacity(capacity),
+            lows: HashMap::with_capacity(capacity),
+            stack: Vec::new(),
+            positions: HashMap::new(),
+        }
+    }
+
+    /// Visits the given vertex, returning its outbound neighbors.
+    fn push<T, S: BuildHasher>(&mut self, graph: &Graph<T, S>, v: VertexId) -> Vec<VertexId> {
+        let index = self.indices.len();
+
+        self.indices.insert(v, index);
+        self.lows.insert(v, index);
+        self.positions.insert(v, self.stack.len());
+        self.stack.push(v);
+
+        graph.out_neighbors(&v).cloned().collect()
+    }
+
+    fn on_stack(&self, v: &VertexId) -> bool {
+        self.positions.contains_key(v)
+    }
+
+    fn lower(&mut self, v: &VertexId, index: usize) {
+        let low = self.lows.get_mut(v).unwrap();
+        *low = (*low).min(index);
+    }
+
+    /// Pops the component whose first visited vertex is the given one.
+    fn pop_component(&mut self, v: &VertexId) -> Vec<VertexId> {
+        let members = self.stack.split_off(self.positions[v]);
+
+        for member in members.iter() {
+            self.positions.remove(member);
+        }
+
+        members
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VertexSet;
+    use rand::SeedableRng;
+    use rand_isaac::IsaacRng;
+
+    #[test]
+    fn test_matches_reachable_from() {
+        let mut rng = IsaacRng::seed_from_u64(5);
+
+        for m in &[0, 20, 40, 80] {
+            let graph = Graph::random_gnm(30, *m, &mut rng).unwrap();
+            let index = graph.reachability_index();
+
+            for a in graph.vertices() {
+                let set = VertexSet::from_ids(&graph, &[*a]).unwrap();
+                let reachable = graph.reachable_from(&set).unwrap();
+
+                for b in graph.vertices() {
+                    assert_eq!(index.reaches(a, b), reachable.contains(b));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_components_of_cycles() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v2, &v1).unwrap();
+        graph.add_edge(&v3, &v3).unwrap();
+
+        let index = graph.reachability_index();
+
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.component_count(), 2);
+        assert!(index.reaches(&v2, &v1));
+        assert!(!index.reaches(&v1, &v3));
+        assert!(!index.reaches(&v1, &VertexId::random()));
+    }
+
+    #[test]
+    fn test_budget_matches_unbounded_index() {
+        let mut rng = IsaacRng::seed_from_u64(7);
+
+        for m in &[0, 20, 40, 80] {
+            let graph = Graph::random_gnm(30, *m, &mut rng).unwrap();
+            let index = graph.reachability_index();
+
+            for budget in &[0, 64, 512] {
+                let bounded = ReachabilityIndex::with_budget(&graph, *budget);
+
+                assert_eq!(bounded.component_count(), index.component_count());
+
+                for a in graph.vertices() {
+                    for b in graph.vertices() {
+                        assert_eq!(bounded.reaches(a, b), index.reaches(a, b));
+                    }
+                }
+            }
+
+            assert!(index.is_complete());
+            assert!(!ReachabilityIndex::with_budget(&graph, 0).is_complete());
+        }
+    }
+
+    #[test]
+    fn test_spill_matches_unbounded_index() {
+        let mut rng = IsaacRng::seed_from_u64(11);
+        let dir = std::env::temp_dir().join(format!("graphlib-spill-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        for m in &[0, 20, 40, 80] {
+            let mut graph = Graph::random_gnm(30, *m, &mut rng).unwrap();
+            let index = graph.reachability_index();
+
+            for budget in &[0, 64, 512] {
+                let spilled = ReachabilityIndex::with_spill(&graph, *budget, &dir).unwrap();
+
+                assert!(spilled.is_complete());
+
+                for a in graph.vertices() {
+                    for b in graph.vertices() {
+                        assert_eq!(spilled.reaches(a, b), index.reaches(a, b));
+                    }
+                }
+            }
+
+            // Rebuilding an index leaves the spill file of its clones intact
+            let mut spilled = ReachabilityIndex::with_spill(&graph, 0, &dir).unwrap();
+            let snapshot = spilled.clone();
+            let ids: Vec<VertexId> = graph.vertices().cloned().collect();
+
+            for pair in ids.windows(2) {
+                graph.add_edge(&pair[0], &pair[1]).ok();
+            }
+
+            spilled.rebuild(&graph);
+            let rebuilt = graph.reachability_index();
+
+            for a in ids.iter() {
+                for b in ids.iter() {
+                    assert_eq!(spilled.reaches(a, b), rebuilt.reaches(a, b));
+                    assert_eq!(snapshot.reaches(a, b), index.reaches(a, b));
+                }
+            }
+        }
+
+        // The spill files are removed along with the indices
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+        std::fs::remove_dir(&dir).unwrap();
+    }
+}
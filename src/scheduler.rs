@@ -0,0 +1,333 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::{DefaultHashBuilder, Graph, GraphErr};
+use crate::vertex_id::VertexId;
+
+use hashbrown::HashMap;
+
+#[cfg(not(feature = "no_std"))]
+use std::collections::VecDeque;
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::collections::vec_deque::VecDeque;
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// State of a vertex in a `Scheduler`.
+pub enum TaskState {
+    /// Waiting for some of its dependencies to complete
+    Pending,
+
+    /// All of its dependencies are done and it can be started
+    Ready,
+
+    /// Started but not yet completed or failed
+    Running,
+
+    /// Completed successfully
+    Completed,
+
+    /// Failed
+    Failed,
+
+    /// Will never run because a dependency failed
+    Cancelled,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// What a `Scheduler` does with a failed vertex.
+pub enum FailurePolicy {
+    /// Make the vertex ready again if it has failed at most the given
+    /// number of times, otherwise propagate the failure.
+    Retry(usize),
+
+    /// Cancel all vertices which depend on the failed vertex,
+    /// directly or transitively.
+    Propagate,
+
+    /// Treat the failed vertex as done so its dependents can run.
+    Continue,
+}
+
+/// Pull-based scheduler for driving the execution of a directed acyclic
+/// graph, where edges point from dependencies to their dependents.
+///
+/// Executors pull vertices whose dependencies are satisfied with
+/// `Scheduler::start()` whenever they have capacity to run them and
+/// report back with `Scheduler::complete()` or `Scheduler::fail()`.
+#[derive(Clone, Debug)]
+pub struct Scheduler<'a, T, S = DefaultHashBuilder> {
+    /// The graph being executed
+    graph: &'a Graph<T, S>,
+
+    /// Mapping between vertices and their states
+    states: HashMap<VertexId, TaskState>,
+
+    /// Number of dependencies of each pending vertex which are not done
+    waiting: HashMap<VertexId, usize>,
+
+    /// Number of times each vertex has failed
+    failures: HashMap<VertexId, usize>,
+
+    /// Ready vertices in the order they became ready
+    ready: VecDeque<VertexId>,
+
+    /// Number of running vertices
+    running: usize,
+}
+
+impl<'a, T, S: BuildHasher> Scheduler<'a, T, S> {
+    /// Creates a scheduler in which the roots of the graph are ready,
+    /// failing with `GraphErr::CycleError` if the graph contains cycles.
+    pub fn new(graph: &'a Graph<T, S>) -> Result<Scheduler<'a, T, S>, GraphErr> {
+        if graph.is_cyclic() {
+            return Err(GraphErr::CycleError);
+        }
+
+        let mut states = HashMap::with_capacity(graph.vertex_count());
+        let mut waiting = HashMap::new();
+
+        for id in graph.vertices() {
+            match graph.in_neighbors_count(id) {
+                0 => states.insert(*id, TaskState::Ready),
+                count => {
+                    waiting.insert(*id, count);
+                    states.insert(*id, TaskState::Pending)
+                }
+            };
+        }
+
+        let mut ready: Vec<VertexId> = graph.roots().cloned().collect();
+        ready.sort();
+
+        Ok(Scheduler {
+            graph,
+            states,
+            waiting,
+            failures: HashMap::new(),
+            ready: ready.into_iter().collect(),
+            running: 0,
+        })
+    }
+
+    /// Returns an iterator over the vertices which are ready
+    /// to be started, in the order they became ready.
+    pub fn ready(&self) -> impl Iterator<Item = &VertexId> {
+        self.ready.iter()
+    }
+
+    /// Marks the vertex which became ready first as running and
+    /// returns it, or `None` if no vertex is ready.
+    pub fn start(&mut self) -> Option<VertexId> {
+        let id = self.ready.pop_front()?;
+
+        self.states.insert(id, TaskState::Running);
+        self.running += 1;
+
+        Some(id)
+    }
+
+    /// Returns the state of the vertex with the given
+    /// id or `None` if there is no such vertex.
+    pub fn state(&self, id: &VertexId) -> Option<TaskState> {
+        self.states.get(id).cloned()
+    }
+
+    /// Returns the vertices which are in the given state, sorted by id.
+    pub fn with_state(&self, state: TaskState) -> Vec<VertexId> {
+        let mut result: Vec<VertexId> = self
+            .states
+            .iter()
+            .filter(|(_, s)| **s == state)
+            .map(|(id, _)| *id)
+            .collect();
+
+        result.sort();
+        result
+    }
+
+    /// Returns true if no vertex is ready or running, in which case
+    /// every vertex is either completed, failed or cancelled.
+    pub fn is_finished(&self) -> bool {
+        self.ready.is_empty() && self.running == 0
+    }
+
+    /// Marks the given ready or running vertex as completed, making
+    /// the dependents whose dependencies are now done ready.
+    ///
+    /// Fails with `GraphErr::NoSuchVertex` if there is no such vertex
+    /// or with `GraphErr::NotReady` if it is not ready or running.
+    pub fn complete(&mut self, id: &VertexId) -> Result<(), GraphErr> {
+        self.finish(id, TaskState::Completed)?;
+        self.release_dependents(id);
+
+        Ok(())
+    }
+
+    /// Marks the given ready or running vertex as failed and handles the
+    /// failure according to the given policy.
+    ///
+    /// Fails with `GraphErr::NoSuchVertex` if there is no such vertex
+    /// or with `GraphErr::NotReady` if it is not ready or running.
+    pub fn fail(&mut self, id: &VertexId, policy: FailurePolicy) -> Result<(), GraphErr> {
+        self.finish(id, TaskState::Failed)?;
+
+        let failures = self.failures.entry(*id).or_insert(0);
+        *failures += 1;
+
+        match policy {
+            FailurePolicy::Retry(retries) if *failures <= retries => {
+                self.states.insert(*id, TaskState::Ready);
+                self.ready.push_back(*id);
+            }
+            FailurePolicy::Retry(_) | FailurePolicy::Propagate => self.cancel_dependents(id),
+            FailurePolicy::Continue => self.release_dependents(id),
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of times the vertex with the given id has failed.
+    pub fn failures(&self, id: &VertexId) -> usize {
+        self.failures.get(id).cloned().unwrap_or(0)
+    }
+
+    fn finish(&mut self, id: &VertexId, state: TaskState) -> Result<(), GraphErr> {
+        match self.states.get(id) {
+            None => return Err(GraphErr::NoSuchVertex),
+            Some(TaskState::Running) => self.running -= 1,
+            Some(TaskState::Ready) => self.ready.retain(|v| v != id),
+            Some(_) => return Err(GraphErr::NotReady),
+        }
+
+        self.states.insert(*id, state);
+        Ok(())
+    }
+
+    fn release_dependents(&mut self, id: &VertexId) {
+        for n in self.graph.out_neighbors(id) {
+            let count = self.waiting.get_mut(n).unwrap();
+            *count -= 1;
+
+            if *count == 0 {
+                self.waiting.remove(n);
+                self.states.insert(*n, TaskState::Ready);
+                self.ready.push_back(*n);
+            }
+        }
+    }
+
+    fn cancel_dependents(&mut self, id: &VertexId) {
+        let mut stack: Vec<VertexId> = self.graph.out_neighbors(id).cloned().collect();
+
+        while let Some(current) = stack.pop() {
+            // Every dependent of a failed vertex is still pending
+            if self.states.get(&current) != Some(&TaskState::Pending) {
+                continue;
+            }
+
+            self.states.insert(current, TaskState::Cancelled);
+            self.waiting.remove(&current);
+            stack.extend(self.graph.out_neighbors(&current).cloned());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diamond() -> (Graph<usize>, Vec<VertexId>) {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+        let v4 = graph.add_vertex(4);
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v1, &v3).unwrap();
+        graph.add_edge(&v2, &v4).unwrap();
+        graph.add_edge(&v3, &v4).unwrap();
+
+        (graph, vec![v1, v2, v3, v4])
+    }
+
+    #[test]
+    fn test_runs_in_dependency_order() {
+        let (graph, ids) = diamond();
+        let mut scheduler = graph.scheduler().unwrap();
+
+        assert_eq!(scheduler.start(), Some(ids[0]));
+        assert_eq!(scheduler.start(), None);
+        assert!(!scheduler.is_finished());
+
+        scheduler.complete(&ids[0]).unwrap();
+        assert_eq!(scheduler.ready().count(), 2);
+
+        let a = scheduler.start().unwrap();
+        let b = scheduler.start().unwrap();
+
+        scheduler.complete(&a).unwrap();
+        assert_eq!(scheduler.state(&ids[3]), Some(TaskState::Pending));
+
+        scheduler.complete(&b).unwrap();
+        assert_eq!(scheduler.start(), Some(ids[3]));
+        assert_eq!(scheduler.complete(&ids[0]), Err(GraphErr::NotReady));
+
+        scheduler.complete(&ids[3]).unwrap();
+        assert!(scheduler.is_finished());
+        assert_eq!(scheduler.with_state(TaskState::Completed).len(), 4);
+    }
+
+    #[test]
+    fn test_retry_then_propagate() {
+        let (graph, ids) = diamond();
+        let mut scheduler = graph.scheduler().unwrap();
+
+        scheduler.start();
+        scheduler.fail(&ids[0], FailurePolicy::Retry(1)).unwrap();
+        assert_eq!(scheduler.state(&ids[0]), Some(TaskState::Ready));
+
+        scheduler.start();
+        scheduler.fail(&ids[0], FailurePolicy::Retry(1)).unwrap();
+
+        assert_eq!(scheduler.failures(&ids[0]), 2);
+        assert_eq!(scheduler.state(&ids[0]), Some(TaskState::Failed));
+        assert_eq!(scheduler.with_state(TaskState::Cancelled).len(), 3);
+        assert!(scheduler.is_finished());
+    }
+
+    #[test]
+    fn test_continue_after_failure() {
+        let (graph, ids) = diamond();
+        let mut scheduler = graph.scheduler().unwrap();
+
+        scheduler.complete(&ids[0]).unwrap();
+        scheduler.fail(&ids[1], FailurePolicy::Continue).unwrap();
+        scheduler.fail(&ids[2], FailurePolicy::Propagate).unwrap();
+
+        assert_eq!(scheduler.state(&ids[3]), Some(TaskState::Cancelled));
+
+        let (graph, ids) = diamond();
+        let mut scheduler = graph.scheduler().unwrap();
+
+        scheduler.complete(&ids[0]).unwrap();
+        scheduler.fail(&ids[1], FailurePolicy::Continue).unwrap();
+        scheduler.complete(&ids[2]).unwrap();
+
+        assert_eq!(scheduler.ready().collect::<Vec<_>>(), vec![&ids[3]]);
+        assert_eq!(
+            scheduler.complete(&VertexId::random()),
+            Err(GraphErr::NoSuchVertex)
+        );
+    }
+}
@@ -0,0 +1,264 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::{DefaultHashBuilder, Graph, GraphErr};
+use crate::vertex_id::VertexId;
+
+use hashbrown::HashMap;
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+/// Outcome of running a token simulation until no vertex can fire.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimulationReport {
+    /// Vertices fired in each round, sorted by id
+    rounds: Vec<Vec<VertexId>>,
+
+    /// Vertices left holding tokens without being able to
+    /// fire, along with their token counts, sorted by id
+    stuck: Vec<(VertexId, usize)>,
+}
+
+impl SimulationReport {
+    /// Returns the vertices fired in each round.
+    pub fn rounds(&self) -> &[Vec<VertexId>] {
+        &self.rounds
+    }
+
+    /// Returns an iterator over the fired vertices in firing order.
+    /// A vertex appears once for each time it fired.
+    pub fn firing_order(&self) -> impl Iterator<Item = &VertexId> {
+        self.rounds.iter().flatten()
+    }
+
+    /// Returns the vertices which still hold tokens but not enough
+    /// to fire, along with the number of tokens they hold.
+    pub fn stuck(&self) -> &[(VertexId, usize)] {
+        &self.stuck
+    }
+
+    /// Returns true if any vertex was left holding tokens.
+    pub fn is_stuck(&self) -> bool {
+        !self.stuck.is_empty()
+    }
+}
+
+/// Token propagation simulator over a directed acyclic graph.
+///
+/// Each vertex holds a number of tokens and requires a number of them
+/// to fire, which defaults to its number of inbound edges, or one for
+/// the roots. Firing a vertex consumes the required tokens and sends a
+/// token to each of its outbound neighbors. Vertices fire in rounds, each
+/// round firing all vertices which were able to fire when it started.
+#[derive(Clone, Debug)]
+pub struct TokenSimulation<'a, T, S = DefaultHashBuilder> {
+    /// The graph being simulated
+    graph: &'a Graph<T, S>,
+
+    /// Mapping between vertices and the tokens they hold
+    tokens: HashMap<VertexId, usize>,
+
+    /// Mapping between vertices and their custom requirements
+    requirements: HashMap<VertexId, usize>,
+}
+
+impl<'a, T, S: BuildHasher> TokenSimulation<'a, T, S> {
+    /// Creates a simulation without any tokens, failing with
+    /// `GraphErr::CycleError` if the graph contains cycles.
+    pub fn new(graph: &'a Graph<T, S>) -> Result<TokenSimulation<'a, T, S>, GraphErr> {
+        if graph.is_cyclic() {
+            return Err(GraphErr::CycleError);
+        }
+
+        Ok(TokenSimulation {
+            graph,
+            tokens: HashMap::new(),
+            requirements: HashMap::new(),
+        })
+    }
+
+    /// Gives the given number of tokens to the vertex with the given id.
+    pub fn add_tokens(&mut self, id: &VertexId, count: usize) -> Result<(), GraphErr> {
+        if self.graph.fetch(id).is_none() {
+            return Err(GraphErr::NoSuchVertex);
+        }
+
+        *self.tokens.entry(*id).or_insert(0) += count;
+        Ok(())
+    }
+
+    /// Returns the number of tokens held by the vertex with the given id.
+    pub fn tokens(&self, id: &VertexId) -> usize {
+        self.tokens.get(id).cloned().unwrap_or(0)
+    }
+
+    /// Sets the number of tokens the vertex with the given id requires
+    /// to fire. A requirement of zero is treated as one.
+    pub fn set_requirement(&mut self, id: &VertexId, count: usize) -> Result<(), GraphErr> {
+        if self.graph.fetch(id).is_none() {
+            return Err(GraphErr::NoSuchVertex);
+        }
+
+        self.requirements.insert(*id, count.max(1));
+        Ok(())
+    }
+
+    /// Returns the number of tokens the vertex with the given id requires to fire.
+    pub fn requirement(&self, id: &VertexId) -> usize {
+        match self.requirements.get(id) {
+            Some(count) => *count,
+            None => self.graph.in_neighbors_count(id).max(1),
+        }
+    }
+
+    /// Returns the vertices which can currently fire, sorted by id.
+    pub fn enabled(&self) -> Vec<VertexId> {
+        let mut enabled: Vec<VertexId> = self
+            .tokens
+            .iter()
+            .filter(|(id, count)| **count >= self.requirement(id))
+            .map(|(id, _)| *id)
+            .collect();
+
+        enabled.sort();
+        enabled
+    }
+
+    /// Fires every vertex which can currently fire once and
+    /// returns them sorted by id.
+    pub fn step(&mut self) -> Vec<VertexId> {
+        let fired = self.enabled();
+
+        for id in fired.iter() {
+            let requirement = self.requirement(id);
+            let count = self.tokens.get_mut(id).unwrap();
+
+            *count -= requirement;
+
+            if *count == 0 {
+                self.tokens.remove(id);
+            }
+        }
+
+        for id in fired.iter() {
+            for n in self.graph.out_neighbors(id) {
+                *self.tokens.entry(*n).or_insert(0) += 1;
+            }
+        }
+
+        fired
+    }
+
+    /// Fires vertices in rounds until none of them can fire.
+    pub fn run(&mut self) -> SimulationReport {
+        let mut rounds = Vec::new();
+
+        loop {
+            let fired = self.step();
+
+            if fired.is_empty() {
+                break;
+            }
+
+            rounds.push(fired);
+        }
+
+        let mut stuck: Vec<(VertexId, usize)> = self
+            .tokens
+            .iter()
+            .map(|(id, count)| (*id, *count))
+            .collect();
+
+        stuck.sort();
+
+        SimulationReport { rounds, stuck }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_join_waits_for_all_inbounds() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+        let v4 = graph.add_vertex(4);
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v1, &v3).unwrap();
+        graph.add_edge(&v2, &v4).unwrap();
+        graph.add_edge(&v3, &v4).unwrap();
+
+        let mut simulation = graph.token_simulation().unwrap();
+        simulation.add_tokens(&v1, 1).unwrap();
+
+        assert_eq!(simulation.step(), vec![v1]);
+        assert_eq!(simulation.tokens(&v2), 1);
+
+        let report = simulation.run();
+        let mut middle = vec![v2, v3];
+        middle.sort();
+
+        assert_eq!(report.rounds(), &[middle, vec![v4]][..]);
+        assert_eq!(report.firing_order().count(), 3);
+        assert!(!report.is_stuck());
+    }
+
+    #[test]
+    fn test_stuck_vertices() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+
+        graph.add_edge(&v1, &v3).unwrap();
+        graph.add_edge(&v2, &v3).unwrap();
+
+        let mut simulation = graph.token_simulation().unwrap();
+        simulation.add_tokens(&v1, 2).unwrap();
+
+        let report = simulation.run();
+
+        // v1 fires twice, but both tokens of v3 come from it
+        assert_eq!(report.firing_order().filter(|v| **v == v1).count(), 2);
+        assert_eq!(report.firing_order().filter(|v| **v == v3).count(), 1);
+        assert!(!report.is_stuck());
+
+        let mut simulation = graph.token_simulation().unwrap();
+        simulation.add_tokens(&v1, 1).unwrap();
+        simulation.set_requirement(&v3, 3).unwrap();
+
+        let report = simulation.run();
+
+        assert_eq!(report.stuck(), &[(v3, 1)][..]);
+        assert_eq!(
+            simulation.add_tokens(&VertexId::random(), 1),
+            Err(GraphErr::NoSuchVertex)
+        );
+    }
+
+    #[test]
+    fn test_cyclic_graph() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+
+        graph.add_edge(&v1, &v2).unwrap();
+        graph.add_edge(&v2, &v1).unwrap();
+
+        assert_eq!(graph.token_simulation().unwrap_err(), GraphErr::CycleError);
+    }
+}
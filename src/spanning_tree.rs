@@ -0,0 +1,82 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::disjoint_set::DisjointSet;
+use crate::graph::Graph;
+use crate::vertex_id::VertexId;
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+/// Returns the edges of a minimum spanning forest of the graph, found
+/// with Kruskal's algorithm. Edge directions are ignored, so of two edges
+/// going both ways between the same vertices only the lighter may be kept.
+pub(crate) fn kruskal<T, S: BuildHasher>(graph: &Graph<T, S>) -> Vec<(VertexId, VertexId)> {
+    let mut sets = DisjointSet::with_capacity(graph.vertex_count());
+
+    for v in graph.vertices() {
+        sets.make_set(*v);
+    }
+
+    let mut edges: Vec<(VertexId, VertexId, f32)> = graph
+        .edges_with_weights()
+        .map(|(a, b, w)| (*a, *b, w))
+        .collect();
+
+    // Sorting by ids as well makes the tree deterministic
+    edges.sort_by(|(a1, b1, w1), (a2, b2, w2)| {
+        w1.partial_cmp(w2)
+            .unwrap()
+            .then_with(|| (a1, b1).cmp(&(a2, b2)))
+    });
+
+    let mut tree = Vec::with_capacity(graph.vertex_count().saturating_sub(1));
+
+    for (a, b, _) in edges {
+        if sets.union(&a, &b) {
+            tree.push((a, b));
+        }
+    }
+
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kruskal() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+        let v4 = graph.add_vertex(4);
+        let v5 = graph.add_vertex(5);
+        let v6 = graph.add_vertex(6);
+
+        graph.add_edge_with_weight(&v1, &v2, 0.1).unwrap();
+        graph.add_edge_with_weight(&v2, &v3, 0.4).unwrap();
+        graph.add_edge_with_weight(&v3, &v1, 0.3).unwrap();
+        graph.add_edge_with_weight(&v3, &v4, -0.5).unwrap();
+        graph.add_edge_with_weight(&v4, &v3, 0.6).unwrap();
+
+        // Separate component
+        graph.add_edge_with_weight(&v5, &v6, 0.9).unwrap();
+
+        let mut tree = kruskal(&graph);
+        tree.sort();
+
+        let mut expected = vec![(v1, v2), (v3, v1), (v3, v4), (v5, v6)];
+        expected.sort();
+
+        assert_eq!(tree, expected);
+    }
+}
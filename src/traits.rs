@@ -0,0 +1,254 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::{Graph, GraphErr};
+use crate::iterators::VertexIter;
+use crate::vertex_id::VertexId;
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+/// Read-only access to a directed graph holding values of type `T`.
+///
+/// Implemented by `Graph` and meant to be implemented by wrappers
+/// such as views or frozen graphs, so they can be passed anywhere
+/// a read-only graph is expected.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::{Graph, GraphRead};
+///
+/// fn sum_of_roots<G: GraphRead<usize>>(graph: &G) -> usize {
+///     graph.roots().map(|id| graph.fetch(id).unwrap()).sum()
+/// }
+///
+/// let mut graph: Graph<usize> = Graph::new();
+///
+/// let v1 = graph.add_vertex(1);
+/// let _v2 = graph.add_vertex(2);
+/// let v3 = graph.add_vertex(3);
+///
+/// graph.add_edge(&v1, &v3).unwrap();
+///
+/// assert_eq!(sum_of_roots(&graph), 3);
+/// ```
+pub trait GraphRead<T> {
+    /// Returns the number of vertices in the graph.
+    fn vertex_count(&self) -> usize;
+
+    /// Returns the number of edges in the graph.
+    fn edge_count(&self) -> usize;
+
+    /// Returns the value of the vertex with the given id.
+    fn fetch(&self, id: &VertexId) -> Option<&T>;
+
+    /// Returns true if there is an edge from `a` to `b`.
+    fn has_edge(&self, a: &VertexId, b: &VertexId) -> bool;
+
+    /// Returns the weight of the edge from `a` to `b`.
+    fn weight(&self, a: &VertexId, b: &VertexId) -> Option<f32>;
+
+    /// Returns an iterator over all of the vertices.
+    fn vertices(&self) -> VertexIter<'_>;
+
+    /// Returns an iterator over the vertices without inbound edges.
+    fn roots(&self) -> VertexIter<'_>;
+
+    /// Returns an iterator over the vertices without outbound edges.
+    fn tips(&self) -> VertexIter<'_>;
+
+    /// Returns an iterator over the inbound neighbors of the vertex.
+    fn in_neighbors(&self, id: &VertexId) -> VertexIter<'_>;
+
+    /// Returns an iterator over the outbound neighbors of the vertex.
+    fn out_neighbors(&self, id: &VertexId) -> VertexIter<'_>;
+
+    /// Returns the number of inbound neighbors of the vertex.
+    fn in_neighbors_count(&self, id: &VertexId) -> usize {
+        self.in_neighbors(id).count()
+    }
+
+    /// Returns the number of outbound neighbors of the vertex.
+    fn out_neighbors_count(&self, id: &VertexId) -> usize {
+        self.out_neighbors(id).count()
+    }
+
+    /// Returns true if the graph has a vertex with the given id.
+    fn contains(&self, id: &VertexId) -> bool {
+        self.fetch(id).is_some()
+    }
+
+    /// Returns true if the graph has no vertices.
+    fn is_empty(&self) -> bool {
+        self.vertex_count() == 0
+    }
+}
+
+/// Mutating access to a directed graph holding values of type `T`.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::{Graph, GraphErr, GraphWrite, VertexId};
+///
+/// fn chain<G: GraphWrite<usize>>(graph: &mut G, n: usize) -> Result<Vec<VertexId>, GraphErr> {
+///     let ids: Vec<VertexId> = (0..n).map(|i| graph.add_vertex(i)).collect();
+///
+///     for pair in ids.windows(2) {
+///         graph.add_edge(&pair[0], &pair[1])?;
+///     }
+///
+///     Ok(ids)
+/// }
+///
+/// let mut graph: Graph<usize> = Graph::new();
+/// let ids = chain(&mut graph, 3).unwrap();
+///
+/// assert_eq!(graph.edge_count(), 2);
+/// assert!(graph.has_edge(&ids[1], &ids[2]));
+/// ```
+pub trait GraphWrite<T>: GraphRead<T> {
+    /// Adds a vertex holding the given value and returns its id.
+    fn add_vertex(&mut self, item: T) -> VertexId;
+
+    /// Returns a mutable reference to the value of the vertex.
+    fn fetch_mut(&mut self, id: &VertexId) -> Option<&mut T>;
+
+    /// Removes the vertex with the given id along with its edges.
+    fn remove(&mut self, id: &VertexId);
+
+    /// Adds an edge from `a` to `b`.
+    fn add_edge(&mut self, a: &VertexId, b: &VertexId) -> Result<(), GraphErr>;
+
+    /// Adds an edge from `a` to `b` with the given weight.
+    fn add_edge_with_weight(
+        &mut self,
+        a: &VertexId,
+        b: &VertexId,
+        weight: f32,
+    ) -> Result<(), GraphErr>;
+
+    /// Removes the edge from `a` to `b`, returning its weight
+    /// or `None` if there is no such edge.
+    fn remove_edge(&mut self, a: &VertexId, b: &VertexId) -> Option<f32>;
+
+    /// Sets the weight of the edge from `a` to `b`.
+    fn set_weight(&mut self, a: &VertexId, b: &VertexId, weight: f32) -> Result<(), GraphErr>;
+}
+
+impl<T, S: BuildHasher> GraphRead<T> for Graph<T, S> {
+    fn vertex_count(&self) -> usize {
+        Graph::vertex_count(self)
+    }
+
+    fn edge_count(&self) -> usize {
+        Graph::edge_count(self)
+    }
+
+    fn fetch(&self, id: &VertexId) -> Option<&T> {
+        Graph::fetch(self, id)
+    }
+
+    fn has_edge(&self, a: &VertexId, b: &VertexId) -> bool {
+        Graph::has_edge(self, a, b)
+    }
+
+    fn weight(&self, a: &VertexId, b: &VertexId) -> Option<f32> {
+        Graph::weight(self, a, b)
+    }
+
+    fn vertices(&self) -> VertexIter<'_> {
+        Graph::vertices(self)
+    }
+
+    fn roots(&self) -> VertexIter<'_> {
+        Graph::roots(self)
+    }
+
+    fn tips(&self) -> VertexIter<'_> {
+        Graph::tips(self)
+    }
+
+    fn in_neighbors(&self, id: &VertexId) -> VertexIter<'_> {
+        Graph::in_neighbors(self, id)
+    }
+
+    fn out_neighbors(&self, id: &VertexId) -> VertexIter<'_> {
+        Graph::out_neighbors(self, id)
+    }
+
+    fn in_neighbors_count(&self, id: &VertexId) -> usize {
+        Graph::in_neighbors_count(self, id)
+    }
+
+    fn out_neighbors_count(&self, id: &VertexId) -> usize {
+        Graph::out_neighbors_count(self, id)
+    }
+}
+
+impl<T, S: BuildHasher> GraphWrite<T> for Graph<T, S> {
+    fn add_vertex(&mut self, item: T) -> VertexId {
+        Graph::add_vertex(self, item)
+    }
+
+    fn fetch_mut(&mut self, id: &VertexId) -> Option<&mut T> {
+        Graph::fetch_mut(self, id)
+    }
+
+    fn remove(&mut self, id: &VertexId) {
+        Graph::remove(self, id)
+    }
+
+    fn add_edge(&mut self, a: &VertexId, b: &VertexId) -> Result<(), GraphErr> {
+        Graph::add_edge(self, a, b)
+    }
+
+    fn add_edge_with_weight(
+        &mut self,
+        a: &VertexId,
+        b: &VertexId,
+        weight: f32,
+    ) -> Result<(), GraphErr> {
+        Graph::add_edge_with_weight(self, a, b, weight)
+    }
+
+    fn remove_edge(&mut self, a: &VertexId, b: &VertexId) -> Option<f32> {
+        Graph::remove_edge(self, a, b)
+    }
+
+    fn set_weight(&mut self, a: &VertexId, b: &VertexId, weight: f32) -> Result<(), GraphErr> {
+        Graph::set_weight(self, a, b, weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts the edges of a graph through the read-only trait.
+    fn count_edges<G: GraphRead<usize>>(graph: &G) -> usize {
+        graph.vertices().map(|v| graph.out_neighbors_count(v)).sum()
+    }
+
+    #[test]
+    fn test_graph_through_traits() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = GraphWrite::add_vertex(&mut graph, 1);
+        let v2 = GraphWrite::add_vertex(&mut graph, 2);
+
+        GraphWrite::add_edge_with_weight(&mut graph, &v1, &v2, 0.5).unwrap();
+        *GraphWrite::fetch_mut(&mut graph, &v2).unwrap() += 1;
+
+        assert_eq!(count_edges(&graph), GraphRead::edge_count(&graph));
+        assert_eq!(GraphRead::fetch(&graph, &v2), Some(&3));
+        assert_eq!(GraphRead::weight(&graph, &v1, &v2), Some(0.5));
+        assert!(GraphRead::contains(&graph, &v1));
+
+        GraphWrite::remove(&mut graph, &v1);
+
+        assert_eq!(count_edges(&graph), 0);
+        assert!(!GraphRead::is_empty(&graph));
+        assert_eq!(GraphRead::roots(&graph).next(), Some(&v2));
+    }
+}
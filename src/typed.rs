@@ -0,0 +1,265 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::{Graph, GraphErr};
+use crate::vertex_id::VertexId;
+
+use hashbrown::{HashMap, HashSet};
+
+#[cfg(feature = "no_std")]
+use core::hash::Hash;
+#[cfg(not(feature = "no_std"))]
+use std::hash::Hash;
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
+
+/// Graph whose edges are tagged with one or more kinds, so that a
+/// single graph can hold several relations between the same vertices.
+///
+/// The underlying `Graph` contains an edge between two vertices if they
+/// are related by at least one kind, and the weight of that edge is
+/// shared by all of its kinds. `TypedGraph::relation()` projects the
+/// edges of a single kind into a plain `Graph` keeping the vertex ids,
+/// on which any of the graph algorithms can be run.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::TypedGraph;
+///
+/// #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// enum Relation {
+///     DependsOn,
+///     ConflictsWith,
+/// }
+///
+/// let mut graph: TypedGraph<&str, Relation> = TypedGraph::new();
+///
+/// let app = graph.add_vertex("app");
+/// let lib = graph.add_vertex("lib");
+/// let old = graph.add_vertex("old-lib");
+///
+/// graph.add_edge(&app, &lib, Relation::DependsOn).unwrap();
+/// graph.add_edge(&lib, &old, Relation::ConflictsWith).unwrap();
+///
+/// assert!(graph.has_edge(&app, &lib, &Relation::DependsOn));
+/// assert!(!graph.has_edge(&app, &lib, &Relation::ConflictsWith));
+/// assert_eq!(graph.graph().edge_count(), 2);
+///
+/// let dependencies = graph.relation(&Relation::DependsOn);
+///
+/// assert_eq!(dependencies.vertex_count(), 3);
+/// assert_eq!(dependencies.edge_count(), 1);
+/// assert_eq!(dependencies.bfs().count(), 3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TypedGraph<T, K: Hash + Eq> {
+    /// Graph holding the vertices and the edges of all kinds
+    graph: Graph<T>,
+
+    /// Mapping between edges and their kinds
+    kinds: HashMap<(VertexId, VertexId), HashSet<K>>,
+}
+
+impl<T, K: Hash + Eq> TypedGraph<T, K> {
+    /// Creates a new typed graph.
+    pub fn new() -> TypedGraph<T, K> {
+        TypedGraph {
+            graph: Graph::new(),
+            kinds: HashMap::new(),
+        }
+    }
+
+    /// Returns the underlying graph, which contains an edge between
+    /// two vertices if they are related by at least one kind.
+    pub fn graph(&self) -> &Graph<T> {
+        &self.graph
+    }
+
+    /// Adds a new vertex to the graph and returns its id.
+    pub fn add_vertex(&mut self, item: T) -> VertexId {
+        self.graph.add_vertex(item)
+    }
+
+    /// Returns a mutable reference to the value of the vertex
+    /// with the given id or `None` if there is no such vertex.
+    pub fn fetch_mut(&mut self, id: &VertexId) -> Option<&mut T> {
+        self.graph.fetch_mut(id)
+    }
+
+    /// Removes the vertex with the given id along with all of its edges.
+    pub fn remove(&mut self, id: &VertexId) {
+        self.kinds.retain(|(a, b), _| a != id && b != id);
+        self.graph.remove(id);
+    }
+
+    /// Relates the given vertices by the given kind, adding an edge to the
+    /// underlying graph if they are not related yet. Adding an edge of a
+    /// kind which is already listed is idempotent.
+    pub fn add_edge(&mut self, a: &VertexId, b: &VertexId, kind: K) -> Result<(), GraphErr> {
+        self.graph.add_edge(a, b)?;
+        self.kinds.entry((*a, *b)).or_default().insert(kind);
+
+        Ok(())
+    }
+
+    /// Removes the edge of the given kind between the given vertices. The
+    /// edge of the underlying graph is removed along with its last kind.
+    pub fn remove_edge(&mut self, a: &VertexId, b: &VertexId, kind: &K) {
+        if let Some(kinds) = self.kinds.get_mut(&(*a, *b)) {
+            kinds.remove(kind);
+
+            if kinds.is_empty() {
+                self.kinds.remove(&(*a, *b));
+                self.graph.remove_edge(a, b);
+            }
+        }
+    }
+
+    /// Sets the weight of the edge between the given vertices,
+    /// which is shared by all of its kinds.
+    pub fn set_weight(&mut self, a: &VertexId, b: &VertexId, weight: f32) -> Result<(), GraphErr> {
+        self.graph.set_weight(a, b, weight)
+    }
+
+    /// Returns true if the given vertices are related by the given kind.
+    pub fn has_edge(&self, a: &VertexId, b: &VertexId, kind: &K) -> bool {
+        match self.kinds.get(&(*a, *b)) {
+            Some(kinds) => kinds.contains(kind),
+            None => false,
+        }
+    }
+
+    /// Returns an iterator over the kinds of the edge between
+    /// the given vertices, in no particular order.
+    pub fn kinds(&self, a: &VertexId, b: &VertexId) -> impl Iterator<Item = &K> {
+        self.kinds.get(&(*a, *b)).into_iter().flat_map(|k| k.iter())
+    }
+
+    /// Returns an iterator over the edges of the given kind as pairs
+    /// of the outbound and the inbound vertex, in no particular order.
+    pub fn edges<'a>(&'a self, kind: &'a K) -> impl Iterator<Item = (&'a VertexId, &'a VertexId)> {
+        self.kinds
+            .iter()
+            .filter(move |(_, kinds)| kinds.contains(kind))
+            .map(|((a, b), _)| (a, b))
+    }
+
+    /// Returns an iterator over the vertices which the vertex
+    /// with the given id has outbound edges of the given kind to.
+    pub fn out_neighbors<'a>(
+        &'a self,
+        id: &'a VertexId,
+        kind: &'a K,
+    ) -> impl Iterator<Item = &'a VertexId> {
+        self.graph
+            .out_neighbors(id)
+            .filter(move |n| self.has_edge(id, n, kind))
+    }
+
+    /// Returns an iterator over the vertices which have outbound edges
+    /// of the given kind to the vertex with the given id.
+    pub fn in_neighbors<'a>(
+        &'a self,
+        id: &'a VertexId,
+        kind: &'a K,
+    ) -> impl Iterator<Item = &'a VertexId> {
+        self.graph
+            .in_neighbors(id)
+            .filter(move |n| self.has_edge(n, id, kind))
+    }
+
+    /// Returns a graph with all vertices, keeping their ids, and only the
+    /// edges of the given kind.
+    pub fn relation(&self, kind: &K) -> Graph<T>
+    where
+        T: Clone,
+    {
+        self.filter(|k| k == kind)
+    }
+
+    /// Returns a graph with all vertices, keeping their ids, and only the
+    /// edges which have at least one kind matching the given condition.
+    pub fn filter(&self, fun: impl Fn(&K) -> bool) -> Graph<T>
+    where
+        T: Clone,
+    {
+        let mut graph = self.graph.map(Clone::clone);
+
+        let removed: Vec<(VertexId, VertexId)> = self
+            .kinds
+            .iter()
+            .filter(|(_, kinds)| !kinds.iter().any(&fun))
+            .map(|(edge, _)| *edge)
+            .collect();
+
+        for (a, b) in removed {
+            graph.remove_edge(&a, &b);
+        }
+
+        graph
+    }
+}
+
+impl<T, K: Hash + Eq> Default for TypedGraph<T, K> {
+    fn default() -> TypedGraph<T, K> {
+        TypedGraph::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parallel_kinds() {
+        let mut graph: TypedGraph<usize, &str> = TypedGraph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+
+        graph.add_edge(&v1, &v2, "depends-on").unwrap();
+        graph.add_edge(&v1, &v2, "conflicts-with").unwrap();
+        graph.add_edge(&v1, &v3, "depends-on").unwrap();
+        graph.set_weight(&v1, &v2, 0.5).unwrap();
+
+        assert_eq!(graph.graph().edge_count(), 2);
+        assert_eq!(graph.kinds(&v1, &v2).count(), 2);
+        assert_eq!(graph.edges(&"depends-on").count(), 2);
+        assert_eq!(graph.out_neighbors(&v1, &"conflicts-with").count(), 1);
+        assert_eq!(graph.in_neighbors(&v3, &"depends-on").count(), 1);
+        assert_eq!(graph.in_neighbors(&v3, &"conflicts-with").count(), 0);
+
+        let conflicts = graph.relation(&"conflicts-with");
+
+        assert_eq!(conflicts.edge_count(), 1);
+        assert_eq!(conflicts.weight(&v1, &v2), Some(0.5));
+        assert_eq!(graph.filter(|_| true).edge_count(), 2);
+
+        // The edge stays while it has a kind
+        graph.remove_edge(&v1, &v2, &"depends-on");
+        assert!(graph.graph().has_edge(&v1, &v2));
+        assert!(!graph.has_edge(&v1, &v2, &"depends-on"));
+
+        graph.remove_edge(&v1, &v2, &"conflicts-with");
+        assert!(!graph.graph().has_edge(&v1, &v2));
+        assert_eq!(graph.kinds(&v1, &v2).count(), 0);
+    }
+
+    #[test]
+    fn test_remove_vertex_removes_kinds() {
+        let mut graph: TypedGraph<usize, u8> = TypedGraph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+
+        graph.add_edge(&v1, &v2, 0).unwrap();
+        graph.remove(&v2);
+
+        assert_eq!(graph.edges(&0).count(), 0);
+        assert_eq!(graph.add_edge(&v1, &v2, 0), Err(GraphErr::NoSuchVertex));
+        assert_eq!(graph.kinds.len(), 0);
+    }
+}
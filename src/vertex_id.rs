@@ -1,7 +1,17 @@
 // Copyright 2019 Octavian Oncescu
 
 #[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
-/// Id of a vertex
+/// Id of a vertex, which is 128 bits wide so ids never run out
+/// and can be converted from and to UUIDs or other `u128` keys.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::VertexId;
+///
+/// let id = VertexId::from(0x6ba7b810_9dad_11d1_80b4_00c04fd430c8u128);
+///
+/// assert_eq!(u128::from(id), 0x6ba7b810_9dad_11d1_80b4_00c04fd430c8);
+/// ```
 pub struct VertexId([u8; 16]); // 128bit
 
 impl core::fmt::Debug for VertexId {
@@ -13,6 +23,18 @@ impl core::fmt::Debug for VertexId {
     }
 }
 
+impl From<u128> for VertexId {
+    fn from(id: u128) -> VertexId {
+        VertexId(id.to_be_bytes())
+    }
+}
+
+impl From<VertexId> for u128 {
+    fn from(id: VertexId) -> u128 {
+        u128::from_be_bytes(id.0)
+    }
+}
+
 impl core::convert::AsRef<VertexId> for VertexId {
     fn as_ref(&self) -> &VertexId {
         &self
@@ -27,4 +49,34 @@ impl VertexId {
     pub fn bytes(&self) -> &[u8; 16] {
         &self.0
     }
+
+    /// Generates a random id which starts with the given tag.
+    pub(crate) fn random_tagged(tag: &[u8; 4]) -> VertexId {
+        let mut bytes = super::gen_bytes();
+        bytes[..4].copy_from_slice(tag);
+
+        VertexId(bytes)
+    }
+
+    /// Creates the id with the given sequence number, which
+    /// starts with the given tag if there is one.
+    pub(crate) fn sequential(tag: Option<&[u8; 4]>, number: u64) -> VertexId {
+        let mut bytes = [0; 16];
+        bytes[8..].copy_from_slice(&number.to_be_bytes());
+
+        if let Some(tag) = tag {
+            bytes[..4].copy_from_slice(tag);
+        }
+
+        VertexId(bytes)
+    }
+
+    pub(crate) fn has_tag(&self, tag: &[u8; 4]) -> bool {
+        self.0[..4] == tag[..]
+    }
+
+    /// Creates an id from the given bytes.
+    pub fn from_bytes(bytes: [u8; 16]) -> VertexId {
+        VertexId(bytes)
+    }
 }
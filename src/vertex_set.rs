@@ -0,0 +1,239 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::{DefaultHashBuilder, Graph, GraphErr};
+use crate::iterators::VertexIter;
+use crate::vertex_id::VertexId;
+
+use hashbrown::HashSet;
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::boxed::Box;
+
+#[cfg(feature = "no_std")]
+use core::fmt::{self, Debug};
+#[cfg(not(feature = "no_std"))]
+use std::fmt::{self, Debug};
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+/// Set of vertex ids which belong to a graph.
+///
+/// Every id in the set is guaranteed to belong to the graph the set
+/// is bound to and set operations fail with `GraphErr::GraphMismatch`
+/// when given sets of different graphs.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::{Graph, VertexSet};
+///
+/// let mut graph: Graph<usize> = Graph::new();
+///
+/// let v1 = graph.add_vertex(1);
+/// let v2 = graph.add_vertex(2);
+/// let v3 = graph.add_vertex(3);
+///
+/// let a = VertexSet::from_ids(&graph, &[v1, v2]).unwrap();
+/// let b = VertexSet::from_ids(&graph, &[v2, v3]).unwrap();
+///
+/// assert_eq!(a.union(&b).unwrap().len(), 3);
+/// assert_eq!(a.intersection(&b).unwrap().len(), 1);
+/// assert!(a.difference(&b).unwrap().contains(&v1));
+/// ```
+pub struct VertexSet<'a, T, S = DefaultHashBuilder> {
+    /// The graph owning the vertices.
+    graph: &'a Graph<T, S>,
+    /// Ids of the vertices in the set.
+    ids: HashSet<VertexId>,
+}
+
+impl<'a, T, S: BuildHasher> VertexSet<'a, T, S> {
+    /// Creates an empty set bound to the given graph.
+    pub fn new(graph: &'a Graph<T, S>) -> VertexSet<'a, T, S> {
+        VertexSet {
+            graph,
+            ids: HashSet::new(),
+        }
+    }
+
+    /// Creates a set of the given ids, failing with `GraphErr::NoSuchVertex`
+    /// if any of them does not belong to the graph.
+    pub fn from_ids<'b>(
+        graph: &'a Graph<T, S>,
+        ids: impl IntoIterator<Item = &'b VertexId>,
+    ) -> Result<VertexSet<'a, T, S>, GraphErr> {
+        let mut set = VertexSet::new(graph);
+
+        for id in ids {
+            set.insert(id)?;
+        }
+
+        Ok(set)
+    }
+
+    /// Creates a set of ids which are known to belong to the graph.
+    pub(crate) fn from_set(graph: &'a Graph<T, S>, ids: HashSet<VertexId>) -> VertexSet<'a, T, S> {
+        VertexSet { graph, ids }
+    }
+
+    /// Returns the graph the set is bound to.
+    pub fn graph(&self) -> &'a Graph<T, S> {
+        self.graph
+    }
+
+    /// Adds the given id to the set, returning `true` if it was not
+    /// already present. Fails with `GraphErr::NoSuchVertex` if the id
+    /// does not belong to the graph.
+    pub fn insert(&mut self, id: &VertexId) -> Result<bool, GraphErr> {
+        if self.graph.fetch(id).is_none() {
+            return Err(GraphErr::NoSuchVertex);
+        }
+
+        Ok(self.ids.insert(*id))
+    }
+
+    /// Removes the given id from the set, returning
+    /// `true` if it was present.
+    pub fn remove(&mut self, id: &VertexId) -> bool {
+        self.ids.remove(id)
+    }
+
+    /// Returns true if the set contains the given id.
+    pub fn contains(&self, id: &VertexId) -> bool {
+        self.ids.contains(id)
+    }
+
+    /// Returns the number of ids in the set.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Returns true if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Returns an iterator over the ids in the set.
+    pub fn iter(&self) -> VertexIter<'_> {
+        VertexIter(Box::new(self.ids.iter()))
+    }
+
+    /// Returns the set of ids which are in either set.
+    pub fn union(&self, other: &VertexSet<'a, T, S>) -> Result<VertexSet<'a, T, S>, GraphErr> {
+        self.check_graph(other)?;
+
+        Ok(self.with_ids(self.ids.union(&other.ids).cloned().collect()))
+    }
+
+    /// Returns the set of ids which are in both sets.
+    pub fn intersection(
+        &self,
+        other: &VertexSet<'a, T, S>,
+    ) -> Result<VertexSet<'a, T, S>, GraphErr> {
+        self.check_graph(other)?;
+
+        Ok(self.with_ids(self.ids.intersection(&other.ids).cloned().collect()))
+    }
+
+    /// Returns the set of ids which are in this set but not in the other.
+    pub fn difference(&self, other: &VertexSet<'a, T, S>) -> Result<VertexSet<'a, T, S>, GraphErr> {
+        self.check_graph(other)?;
+
+        Ok(self.with_ids(self.ids.difference(&other.ids).cloned().collect()))
+    }
+
+    /// Returns the set of the vertices of the graph
+    /// which are not in this set.
+    pub fn complement(&self) -> VertexSet<'a, T, S> {
+        self.with_ids(
+            self.graph
+                .vertices()
+                .filter(|v| !self.ids.contains(*v))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Consumes the set, returning the ids in it.
+    pub fn into_ids(self) -> HashSet<VertexId> {
+        self.ids
+    }
+
+    /// Fails with `GraphErr::GraphMismatch` if
+    /// the sets belong to different graphs.
+    pub(crate) fn check_graph(&self, other: &VertexSet<'_, T, S>) -> Result<(), GraphErr> {
+        if core::ptr::eq(self.graph, other.graph) {
+            Ok(())
+        } else {
+            Err(GraphErr::GraphMismatch)
+        }
+    }
+
+    fn with_ids(&self, ids: HashSet<VertexId>) -> VertexSet<'a, T, S> {
+        VertexSet::from_set(self.graph, ids)
+    }
+}
+
+impl<'a, T, S: BuildHasher> Clone for VertexSet<'a, T, S> {
+    fn clone(&self) -> Self {
+        self.with_ids(self.ids.clone())
+    }
+}
+
+impl<'a, T, S: BuildHasher> PartialEq for VertexSet<'a, T, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.check_graph(other).is_ok() && self.ids == other.ids
+    }
+}
+
+impl<'a, T, S: BuildHasher> Debug for VertexSet<'a, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.ids.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_foreign_ids_are_rejected() {
+        let mut graph1: Graph<usize> = Graph::new();
+        let mut graph2: Graph<usize> = Graph::new();
+
+        let v1 = graph1.add_vertex(1);
+        let v2 = graph2.add_vertex(2);
+
+        assert_eq!(
+            VertexSet::from_ids(&graph1, &[v2]).unwrap_err(),
+            GraphErr::NoSuchVertex
+        );
+
+        let a = VertexSet::from_ids(&graph1, &[v1]).unwrap();
+        let b = VertexSet::from_ids(&graph2, &[v2]).unwrap();
+
+        assert_eq!(a.union(&b).unwrap_err(), GraphErr::GraphMismatch);
+        assert_eq!(a.intersection(&b).unwrap_err(), GraphErr::GraphMismatch);
+        assert_eq!(a.difference(&b).unwrap_err(), GraphErr::GraphMismatch);
+    }
+
+    #[test]
+    fn test_complement() {
+        let mut graph: Graph<usize> = Graph::new();
+
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+
+        let set = VertexSet::from_ids(&graph, &[v1]).unwrap();
+        let complement = set.complement();
+
+        assert_eq!(complement, VertexSet::from_ids(&graph, &[v2, v3]).unwrap());
+        assert_eq!(complement.complement(), set);
+        assert_eq!(set.union(&complement).unwrap(), graph.vertex_set());
+    }
+}
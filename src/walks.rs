@@ -0,0 +1,165 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::{Graph, GraphErr};
+use crate::vertex_id::VertexId;
+
+#[cfg(not(feature = "no_std"))]
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::collections::binary_heap::BinaryHeap;
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
+#[cfg(feature = "no_std")]
+use core::cmp::Ordering;
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+#[derive(PartialEq, Debug)]
+struct WalkMeta {
+    walk: Vec<VertexId>,
+    score: f32,
+}
+
+impl Eq for WalkMeta {}
+
+impl PartialOrd for WalkMeta {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for WalkMeta {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Higher scores come first, followed by shorter walks.
+        self.score
+            .partial_cmp(&other.score)
+            .unwrap()
+            .then_with(|| other.walk.len().cmp(&self.walk.len()))
+    }
+}
+
+/// Returns the `k` walks starting from `src` with the highest scores,
+/// along with their scores. The score of a walk is the product of the
+/// weights of its edges, each multiplied by the decay factor.
+pub(crate) fn top_walks<T, S: BuildHasher>(
+    graph: &Graph<T, S>,
+    src: &VertexId,
+    k: usize,
+    max_len: usize,
+    decay: f32,
+) -> Result<Vec<(Vec<VertexId>, f32)>, GraphErr> {
+    if graph.fetch(src).is_none() {
+        return Err(GraphErr::NoSuchVertex);
+    }
+
+    if !(0.0..=1.0).contains(&decay) {
+        return Err(GraphErr::InvalidWeight);
+    }
+
+    if graph.edges_with_weights().any(|(_, _, w)| w < 0.0) {
+        return Err(GraphErr::InvalidWeight);
+    }
+
+    let mut result = Vec::with_capacity(k);
+    let mut frontier = BinaryHeap::new();
+
+    frontier.push(WalkMeta {
+        walk: vec![*src],
+        score: 1.0,
+    });
+
+    // Since weights and the decay factor are at most 1.0, extending
+    // a walk never increases its score, so walks are popped in
+    // nonincreasing order of their score.
+    while let Some(WalkMeta { walk, score }) = frontier.pop() {
+        if walk.len() > 1 {
+            if result.len() == k {
+                break;
+            }
+
+            result.push((walk.clone(), score));
+        }
+
+        if walk.len() > max_len {
+            continue;
+        }
+
+        let last = walk.last().unwrap();
+
+        for n in graph.out_neighbors(last) {
+            let mut next = walk.clone();
+            next.push(*n);
+
+            frontier.push(WalkMeta {
+                walk: next,
+                score: score * graph.weight(last, n).unwrap() * decay,
+            });
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_walks_with_invalid_input() {
+        let mut graph: Graph<usize> = Graph::new();
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+
+        graph.add_edge_with_weight(&v1, &v2, 0.5).unwrap();
+
+        assert_eq!(
+            top_walks(&graph, &VertexId::random(), 1, 1, 1.0),
+            Err(GraphErr::NoSuchVertex)
+        );
+        assert_eq!(
+            top_walks(&graph, &v1, 1, 1, 1.5),
+            Err(GraphErr::InvalidWeight)
+        );
+
+        graph.set_weight(&v1, &v2, -0.5).unwrap();
+
+        assert_eq!(
+            top_walks(&graph, &v1, 1, 1, 1.0),
+            Err(GraphErr::InvalidWeight)
+        );
+    }
+
+    #[test]
+    fn test_top_walks_with_revisits() {
+        let mut graph: Graph<usize> = Graph::new();
+        let v1 = graph.add_vertex(1);
+        let v2 = graph.add_vertex(2);
+        let v3 = graph.add_vertex(3);
+
+        graph.add_edge_with_weight(&v1, &v2, 1.0).unwrap();
+        graph.add_edge_with_weight(&v2, &v1, 1.0).unwrap();
+        graph.add_edge_with_weight(&v1, &v3, 0.25).unwrap();
+
+        let walks = top_walks(&graph, &v1, 4, 3, 0.5).unwrap();
+
+        assert_eq!(
+            walks,
+            vec![
+                (vec![v1, v2], 0.5),
+                (vec![v1, v2, v1], 0.25),
+                (vec![v1, v3], 0.125),
+                (vec![v1, v2, v1, v2], 0.125),
+            ]
+        );
+
+        // Walks are bounded by the maximum length
+        assert_eq!(top_walks(&graph, &v1, 10, 1, 0.5).unwrap().len(), 2);
+        assert_eq!(top_walks(&graph, &v1, 0, 1, 0.5).unwrap().len(), 0);
+    }
+}
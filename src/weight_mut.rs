@@ -0,0 +1,154 @@
+// Copyright 2019 Octavian Oncescu
+
+use crate::graph::{DefaultHashBuilder, Graph};
+use crate::vertex_id::VertexId;
+
+#[cfg(feature = "no_std")]
+extern crate alloc;
+#[cfg(feature = "no_std")]
+use alloc::boxed::Box;
+#[cfg(feature = "no_std")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "no_std")]
+use core::hash::BuildHasher;
+#[cfg(not(feature = "no_std"))]
+use std::hash::BuildHasher;
+
+#[cfg(feature = "no_std")]
+use core::ops::{Deref, DerefMut};
+#[cfg(not(feature = "no_std"))]
+use std::ops::{Deref, DerefMut};
+
+/// Mutable reference to the weight of an edge, as returned
+/// by `Graph::weight_mut()`. The new weight is set when the
+/// reference is dropped, which keeps the edges of the graph
+/// ordered by weight.
+///
+/// Weights out of the bounds of the graph are clamped to
+/// them and weights which are not numbers are ignored.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::Graph;
+///
+/// let mut graph: Graph<usize> = Graph::new();
+///
+/// let v1 = graph.add_vertex(1);
+/// let v2 = graph.add_vertex(2);
+///
+/// graph.add_edge_with_weight(&v1, &v2, 0.5).unwrap();
+///
+/// *graph.weight_mut(&v1, &v2).unwrap() /= 2.0;
+///
+/// assert_eq!(graph.weight(&v1, &v2), Some(0.25));
+/// ```
+#[derive(Debug)]
+pub struct WeightMut<'a, T, S: BuildHasher = DefaultHashBuilder> {
+    graph: &'a mut Graph<T, S>,
+    edge: (VertexId, VertexId, f32),
+}
+
+impl<'a, T, S: BuildHasher> WeightMut<'a, T, S> {
+    pub(crate) fn new(graph: &'a mut Graph<T, S>, a: VertexId, b: VertexId) -> Option<Self> {
+        let weight = graph.weight(&a, &b)?;
+
+        Some(WeightMut {
+            graph,
+            edge: (a, b, weight),
+        })
+    }
+}
+
+impl<'a, T, S: BuildHasher> Deref for WeightMut<'a, T, S> {
+    type Target = f32;
+
+    fn deref(&self) -> &f32 {
+        &self.edge.2
+    }
+}
+
+impl<'a, T, S: BuildHasher> DerefMut for WeightMut<'a, T, S> {
+    fn deref_mut(&mut self) -> &mut f32 {
+        &mut self.edge.2
+    }
+}
+
+impl<'a, T, S: BuildHasher> Drop for WeightMut<'a, T, S> {
+    fn drop(&mut self) {
+        self.graph.update_weights(Some(self.edge));
+    }
+}
+
+/// Mutable references to the weights of all edges of a graph, as
+/// returned by `Graph::weights_mut()`. The new weights are set when
+/// the references are dropped, in a single step of the history of
+/// the graph.
+///
+/// Weights out of the bounds of the graph are clamped to
+/// them and weights which are not numbers are ignored.
+///
+/// ## Example
+/// ```rust
+/// use graphlib::Graph;
+///
+/// let mut graph: Graph<usize> = Graph::new();
+///
+/// let v1 = graph.add_vertex(1);
+/// let v2 = graph.add_vertex(2);
+/// let v3 = graph.add_vertex(3);
+///
+/// graph.add_edge_with_weight(&v1, &v2, 0.2).unwrap();
+/// graph.add_edge_with_weight(&v2, &v3, 0.4).unwrap();
+///
+/// // Normalize the weights by the highest one
+/// let mut weights = graph.weights_mut();
+/// let max = weights.iter_mut().map(|(_, _, w)| *w).fold(0.0, f32::max);
+///
+/// for (_, _, weight) in weights.iter_mut() {
+///     *weight /= max;
+/// }
+///
+/// drop(weights);
+///
+/// assert_eq!(graph.weight(&v1, &v2), Some(0.5));
+/// assert_eq!(graph.weight(&v2, &v3), Some(1.0));
+/// ```
+#[derive(Debug)]
+pub struct WeightsMut<'a, T, S: BuildHasher = DefaultHashBuilder> {
+    graph: &'a mut Graph<T, S>,
+    edges: Vec<(VertexId, VertexId, f32)>,
+}
+
+impl<'a, T, S: BuildHasher> WeightsMut<'a, T, S> {
+    pub(crate) fn new(graph: &'a mut Graph<T, S>) -> WeightsMut<'a, T, S> {
+        let edges = graph
+            .edges_with_weights()
+            .map(|(a, b, w)| (*a, *b, w))
+            .collect();
+
+        WeightsMut { graph, edges }
+    }
+
+    /// Returns an iterator over the outbound vertex, the inbound
+    /// vertex and a mutable reference to the weight of each edge,
+    /// in no particular order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&VertexId, &VertexId, &mut f32)> {
+        self.edges.iter_mut().map(|(a, b, w)| (&*a, &*b, w))
+    }
+}
+
+impl<'a, 'b, T, S: BuildHasher> IntoIterator for &'b mut WeightsMut<'a, T, S> {
+    type Item = (&'b VertexId, &'b VertexId, &'b mut f32);
+    type IntoIter = Box<dyn 'b + Iterator<Item = (&'b VertexId, &'b VertexId, &'b mut f32)>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.iter_mut())
+    }
+}
+
+impl<'a, T, S: BuildHasher> Drop for WeightsMut<'a, T, S> {
+    fn drop(&mut self) {
+        self.graph.update_weights(self.edges.drain(..));
+    }
+}
@@ -0,0 +1,215 @@
+// Copyright 2019 Octavian Oncescu
+
+//! Differential tests which apply the same random operations to a graph
+//! and to a petgraph `StableDiGraph` and compare the results of the
+//! algorithms on both.
+//!
+//! The number of random cases can be raised with the
+//! `GRAPHLIB_DIFF_CASES` environment variable.
+
+use graphlib::{Graph, VertexId};
+use petgraph::algo;
+use petgraph::stable_graph::{NodeIndex, StableDiGraph};
+use rand::{Rng, SeedableRng};
+use rand_isaac::IsaacRng;
+use std::collections::HashMap;
+use std::env;
+
+const DEFAULT_CASES: u64 = 50;
+const OPERATIONS: usize = 60;
+
+/// Graph mirrored into petgraph along with the mapping between the ids.
+struct Mirror {
+    graph: Graph<usize>,
+    other: StableDiGraph<(), f32>,
+    ids: Vec<VertexId>,
+    nodes: HashMap<VertexId, NodeIndex>,
+}
+
+impl Mirror {
+    fn new() -> Mirror {
+        Mirror {
+            graph: Graph::new(),
+            other: StableDiGraph::new(),
+            ids: Vec::new(),
+            nodes: HashMap::new(),
+        }
+    }
+
+    fn add_vertex(&mut self) {
+        let id = self.graph.add_vertex(self.ids.len());
+        let node = self.other.add_node(());
+
+        self.ids.push(id);
+        self.nodes.insert(id, node);
+    }
+
+    fn remove_vertex(&mut self, i: usize) {
+        let id = self.ids.swap_remove(i);
+
+        self.graph.remove(&id);
+        self.other.remove_node(self.nodes.remove(&id).unwrap());
+    }
+
+    fn add_edge(&mut self, a: usize, b: usize, weight: f32) {
+        let (a, b) = (self.ids[a], self.ids[b]);
+
+        if a == b || self.graph.has_edge(&a, &b) {
+            return;
+        }
+
+        self.graph.add_edge_with_weight(&a, &b, weight).unwrap();
+        self.other.add_edge(self.nodes[&a], self.nodes[&b], weight);
+    }
+
+    fn remove_edge(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.ids[a], self.ids[b]);
+        self.graph.remove_edge(&a, &b);
+
+        if let Some(edge) = self.other.find_edge(self.nodes[&a], self.nodes[&b]) {
+            self.other.remove_edge(edge);
+        }
+    }
+
+    fn apply_random<R: Rng>(&mut self, rng: &mut R) {
+        if self.ids.len() < 2 {
+            self.add_vertex();
+            return;
+        }
+
+        let a = rng.gen_range(0, self.ids.len());
+        let b = rng.gen_range(0, self.ids.len());
+
+        match rng.gen_range(0, 10) {
+            0 | 1 => self.add_vertex(),
+            2 => self.remove_vertex(a),
+            3 => self.remove_edge(a, b),
+            _ => self.add_edge(a, b, rng.gen_range(0.0, 1.0)),
+        }
+    }
+
+    fn check_counts(&self) {
+        assert_eq!(self.graph.vertex_count(), self.other.node_count());
+        assert_eq!(self.graph.edge_count(), self.other.edge_count());
+    }
+
+    fn check_components(&self) {
+        let compact = petgraph::Graph::from(self.other.clone());
+
+        assert_eq!(
+            self.graph.components_above(-2.0).len(),
+            algo::connected_components(&compact)
+        );
+    }
+
+    fn check_shortest_paths(&self) {
+        for src in self.ids.iter() {
+            let distances = algo::dijkstra(&self.other, self.nodes[src], None, |e| *e.weight());
+
+            for dest in self.ids.iter().filter(|d| *d != src) {
+                let path: Vec<VertexId> = self.graph.dijkstra(src, dest).cloned().collect();
+
+                match distances.get(&self.nodes[dest]) {
+                    None => assert!(path.is_empty()),
+                    Some(distance) => {
+                        assert_eq!(path.first(), Some(src));
+                        assert_eq!(path.last(), Some(dest));
+
+                        let cost: f32 = path
+                            .windows(2)
+                            .map(|pair| self.graph.weight(&pair[0], &pair[1]).unwrap())
+                            .sum();
+
+                        assert!((cost - distance).abs() < 1e-4);
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_topo(&self) {
+        let cyclic = algo::is_cyclic_directed(&self.other);
+        assert_eq!(self.graph.is_cyclic(), cyclic);
+
+        if cyclic {
+            return;
+        }
+
+        let positions: HashMap<VertexId, usize> = self
+            .graph
+            .topo()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+
+        assert_eq!(positions.len(), self.graph.vertex_count());
+
+        for (b, a) in self.graph.edges() {
+            assert!(positions[a] < positions[b]);
+        }
+    }
+}
+
+fn cases() -> u64 {
+    env::var("GRAPHLIB_DIFF_CASES")
+        .ok()
+        .and_then(|cases| cases.parse().ok())
+        .unwrap_or(DEFAULT_CASES)
+}
+
+#[test]
+fn differential_against_petgraph() {
+    for seed in 0..cases() {
+        let mut rng = IsaacRng::seed_from_u64(seed);
+        let mut mirror = Mirror::new();
+
+        for _ in 0..OPERATIONS {
+            mirror.apply_random(&mut rng);
+            mirror.check_counts();
+        }
+
+        mirror.check_components();
+        mirror.check_shortest_paths();
+        mirror.check_topo();
+    }
+}
+
+#[test]
+fn isomorphism_against_petgraph() {
+    for seed in 0..cases() {
+        let mut rng = IsaacRng::seed_from_u64(seed);
+        let mut mirrors = (Mirror::new(), Mirror::new());
+
+        // Small graphs with similar sizes, so that both outcomes occur
+        for _ in 0..8 {
+            mirrors.0.apply_random(&mut rng);
+            mirrors.1.apply_random(&mut rng);
+        }
+
+        let compact = (
+            petgraph::Graph::from(mirrors.0.other.clone()),
+            petgraph::Graph::from(mirrors.1.other.clone()),
+        );
+
+        let isomorphic = algo::is_isomorphic(&compact.0, &compact.1);
+
+        assert_eq!(
+            mirrors
+                .0
+                .graph
+                .is_isomorphic_to(&mirrors.1.graph, |_, _| true),
+            isomorphic
+        );
+
+        if isomorphic {
+            assert_eq!(
+                mirrors.0.graph.structural_hash(),
+                mirrors.1.graph.structural_hash()
+            );
+        }
+        assert!(mirrors
+            .0
+            .graph
+            .is_isomorphic_to(&mirrors.0.graph, |_, _| true));
+    }
+}